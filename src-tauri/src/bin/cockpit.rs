@@ -0,0 +1,5 @@
+// 无窗口命令行入口：cockpit <platform> <command> [args]
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    std::process::exit(antigravity_cockpit_tools_lib::cli::run(args));
+}
@@ -0,0 +1,465 @@
+//! 命令行模式（无窗口）
+//!
+//! 供 `cockpit` 二进制使用，所有结果以 JSON 输出到 stdout，日志写入 stderr 与日志文件。
+//! `cockpit daemon` 进入无窗口常驻模式（见 `daemon` 模块）。
+
+use crate::modules::audit::{self, AuditSource};
+use crate::modules::{
+    self, atomic_file, codex_account, codex_quota, github_copilot_account, kiro_account, logger,
+    windsurf_account,
+};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::io::Read;
+use std::path::Path;
+
+const USAGE: &str = "用法: cockpit <platform> <command> [args]
+      cockpit daemon
+
+凭据库使用口令模式时，从环境变量 COCKPIT_VAULT_PASSPHRASE 读取口令，未设置时在终端中提示输入。
+
+daemon:
+  以无窗口模式常驻运行 WebSocket 服务、唤醒调度与配额刷新/预警循环
+
+平台 (platform):
+  antigravity | account       Antigravity 账号
+  codex                       Codex 账号
+  github-copilot | ghcp       GitHub Copilot 账号
+  windsurf                    Windsurf 账号
+  kiro                        Kiro 账号
+
+命令 (command):
+  list                                    列出账号
+  switch <account_id>                     切换账号
+  refresh-quota [account_id]              刷新配额（省略 account_id 时刷新全部）
+  export [--output <file>] [account_id...] 导出账号（省略 account_id 时导出全部）
+  import <file | ->                       从 JSON 文件导入账号（- 表示读取 stdin）
+  tag <account_id> [tag...]               设置账号标签（支持逗号分隔，省略时清空）
+";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Platform {
+    Antigravity,
+    Codex,
+    GitHubCopilot,
+    Windsurf,
+    Kiro,
+}
+
+impl Platform {
+    fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "antigravity" | "account" | "accounts" => Some(Self::Antigravity),
+            "codex" => Some(Self::Codex),
+            "github-copilot" | "github_copilot" | "copilot" | "ghcp" => Some(Self::GitHubCopilot),
+            "windsurf" => Some(Self::Windsurf),
+            "kiro" => Some(Self::Kiro),
+            _ => None,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Antigravity => "antigravity",
+            Self::Codex => "codex",
+            Self::GitHubCopilot => "github_copilot",
+            Self::Windsurf => "windsurf",
+            Self::Kiro => "kiro",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Action {
+    List,
+    Switch {
+        account_id: String,
+    },
+    RefreshQuota {
+        account_id: Option<String>,
+    },
+    Export {
+        account_ids: Vec<String>,
+        output: Option<String>,
+    },
+    Import {
+        input: String,
+    },
+    Tag {
+        account_id: String,
+        tags: Vec<String>,
+    },
+}
+
+/// 命令行入口，返回进程退出码
+pub fn run(args: Vec<String>) -> i32 {
+    if args.is_empty() || matches!(args[0].as_str(), "-h" | "--help" | "help") {
+        print!("{}", USAGE);
+        return if args.is_empty() { 2 } else { 0 };
+    }
+
+    if args[0] == "daemon" {
+        return crate::daemon::run();
+    }
+
+    let (platform, action) = match parse_args(&args) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            print_json(&json!({ "ok": false, "error": err }));
+            eprint!("{}", USAGE);
+            return 2;
+        }
+    };
+
+    logger::init_cli_logger();
+    if let Err(err) = modules::startup::init_headless() {
+        logger::log_error(&format!("[CLI] 初始化失败: {}", err));
+        print_json(&json!({
+            "ok": false,
+            "platform": platform.as_str(),
+            "error": err,
+        }));
+        return 1;
+    }
+
+    match tauri::async_runtime::block_on(audit::with_source(
+        AuditSource::Cli,
+        execute(platform, action),
+    )) {
+        Ok(data) => {
+            print_json(&json!({
+                "ok": true,
+                "platform": platform.as_str(),
+                "data": data,
+            }));
+            0
+        }
+        Err(err) => {
+            logger::log_warn(&format!("[CLI] 命令执行失败: {}", err));
+            print_json(&json!({
+                "ok": false,
+                "platform": platform.as_str(),
+                "error": err,
+            }));
+            1
+        }
+    }
+}
+
+fn print_json(value: &Value) {
+    match serde_json::to_string_pretty(value) {
+        Ok(text) => println!("{}", text),
+        Err(e) => println!("{{\"ok\":false,\"error\":\"序列化失败: {}\"}}", e),
+    }
+}
+
+fn parse_args(args: &[String]) -> Result<(Platform, Action), String> {
+    let platform_raw = args.first().ok_or("缺少平台参数")?;
+    let platform =
+        Platform::parse(platform_raw).ok_or_else(|| format!("未知平台: {}", platform_raw))?;
+    let command = args.get(1).ok_or("缺少命令参数")?;
+    let rest = &args[2..];
+
+    let action = match command.as_str() {
+        "list" => Action::List,
+        "switch" => Action::Switch {
+            account_id: required_arg(rest, "account_id")?,
+        },
+        "refresh-quota" | "refresh" => Action::RefreshQuota {
+            account_id: rest.first().cloned(),
+        },
+        "export" => {
+            let mut account_ids = Vec::new();
+            let mut output = None;
+            let mut iter = rest.iter();
+            while let Some(arg) = iter.next() {
+                match arg.as_str() {
+                    "-o" | "--output" => {
+                        output = Some(iter.next().cloned().ok_or("--output 缺少文件路径")?);
+                    }
+                    _ => account_ids.push(arg.clone()),
+                }
+            }
+            Action::Export {
+                account_ids,
+                output,
+            }
+        }
+        "import" => Action::Import {
+            input: required_arg(rest, "file")?,
+        },
+        "tag" => {
+            let account_id = required_arg(rest, "account_id")?;
+            let tags = rest[1..]
+                .iter()
+                .flat_map(|item| item.split(','))
+                .map(|item| item.trim().to_string())
+                .filter(|item| !item.is_empty())
+                .collect();
+            Action::Tag { account_id, tags }
+        }
+        other => return Err(format!("未知命令: {}", other)),
+    };
+
+    Ok((platform, action))
+}
+
+fn required_arg(rest: &[String], name: &str) -> Result<String, String> {
+    rest.first()
+        .cloned()
+        .ok_or_else(|| format!("缺少参数: {}", name))
+}
+
+fn to_value<T: Serialize>(value: &T) -> Result<Value, String> {
+    serde_json::to_value(value).map_err(|e| format!("序列化失败: {}", e))
+}
+
+fn summarize_results<T>(results: &[(String, Result<T, String>)]) -> Value {
+    let success = results.iter().filter(|(_, r)| r.is_ok()).count();
+    let details: Vec<Value> = results
+        .iter()
+        .map(|(account_id, result)| match result {
+            Ok(_) => json!({ "account_id": account_id, "ok": true }),
+            Err(err) => json!({ "account_id": account_id, "ok": false, "error": err }),
+        })
+        .collect();
+    json!({
+        "total": results.len(),
+        "success": success,
+        "failed": results.len() - success,
+        "details": details,
+    })
+}
+
+fn read_import_input(input: &str) -> Result<String, String> {
+    if input == "-" {
+        let mut content = String::new();
+        std::io::stdin()
+            .read_to_string(&mut content)
+            .map_err(|e| format!("读取 stdin 失败: {}", e))?;
+        return Ok(content);
+    }
+    std::fs::read_to_string(input).map_err(|e| format!("读取导入文件失败: {}", e))
+}
+
+fn all_account_ids(platform: Platform) -> Result<Vec<String>, String> {
+    let ids = match platform {
+        Platform::Antigravity => modules::list_accounts()?
+            .into_iter()
+            .map(|a| a.id)
+            .collect(),
+        Platform::Codex => codex_account::list_accounts()
+            .into_iter()
+            .map(|a| a.id)
+            .collect(),
+        Platform::GitHubCopilot => github_copilot_account::list_accounts()
+            .into_iter()
+            .map(|a| a.id)
+            .collect(),
+        Platform::Windsurf => windsurf_account::list_accounts()
+            .into_iter()
+            .map(|a| a.id)
+            .collect(),
+        Platform::Kiro => kiro_account::list_accounts()
+            .into_iter()
+            .map(|a| a.id)
+            .collect(),
+    };
+    Ok(ids)
+}
+
+async fn execute(platform: Platform, action: Action) -> Result<Value, String> {
+    match action {
+        Action::List => list(platform),
+        Action::Switch { account_id } => switch(platform, &account_id).await,
+        Action::RefreshQuota { account_id } => refresh_quota(platform, account_id).await,
+        Action::Export {
+            account_ids,
+            output,
+        } => export(platform, account_ids, output).await,
+        Action::Import { input } => {
+            let content = read_import_input(&input)?;
+            import(platform, content).await
+        }
+        Action::Tag { account_id, tags } => tag(platform, &account_id, tags),
+    }
+}
+
+fn list(platform: Platform) -> Result<Value, String> {
+    match platform {
+        Platform::Antigravity => to_value(&modules::list_accounts()?),
+        Platform::Codex => to_value(&codex_account::list_accounts()),
+        Platform::GitHubCopilot => to_value(&github_copilot_account::list_accounts()),
+        Platform::Windsurf => to_value(&windsurf_account::list_accounts()),
+        Platform::Kiro => to_value(&kiro_account::list_accounts()),
+    }
+}
+
+async fn switch(platform: Platform, account_id: &str) -> Result<Value, String> {
+    logger::log_info(&format!(
+        "[CLI] 切换账号: platform={}, account_id={}",
+        platform.as_str(),
+        account_id
+    ));
+    match platform {
+        Platform::Antigravity => {
+            to_value(&modules::account::switch_account_internal(account_id).await?)
+        }
+        Platform::Codex => {
+            to_value(&modules::codex_account::switch_account_internal(account_id).await?)
+        }
+        Platform::GitHubCopilot => {
+            let message = github_copilot_account::switch_account_internal(account_id).await?;
+            Ok(json!({ "account_id": account_id, "message": message }))
+        }
+        Platform::Windsurf => {
+            let message = windsurf_account::switch_account_internal(account_id).await?;
+            Ok(json!({ "account_id": account_id, "message": message }))
+        }
+        Platform::Kiro => {
+            let message = kiro_account::switch_account_internal(account_id).await?;
+            Ok(json!({ "account_id": account_id, "message": message }))
+        }
+    }
+}
+
+async fn refresh_quota(platform: Platform, account_id: Option<String>) -> Result<Value, String> {
+    match (platform, account_id) {
+        (Platform::Antigravity, Some(account_id)) => {
+            let mut account = modules::load_account(&account_id)?;
+            let quota = modules::fetch_quota_with_retry(&mut account, true)
+                .await
+                .map_err(|e| e.to_string())?;
+            modules::update_account_quota(&account_id, quota.clone())?;
+            to_value(&quota)
+        }
+        (Platform::Antigravity, None) => {
+            to_value(&modules::account::refresh_all_quotas_logic().await?)
+        }
+        (Platform::Codex, Some(account_id)) => {
+            to_value(&codex_quota::refresh_account_quota(&account_id).await?)
+        }
+        (Platform::Codex, None) => Ok(summarize_results(&codex_quota::refresh_all_quotas().await?)),
+        (Platform::GitHubCopilot, Some(account_id)) => {
+            to_value(&github_copilot_account::refresh_account_token(&account_id).await?)
+        }
+        (Platform::GitHubCopilot, None) => Ok(summarize_results(
+            &github_copilot_account::refresh_all_tokens().await?,
+        )),
+        (Platform::Windsurf, Some(account_id)) => {
+            to_value(&windsurf_account::refresh_account_token(&account_id).await?)
+        }
+        (Platform::Windsurf, None) => Ok(summarize_results(
+            &windsurf_account::refresh_all_tokens().await?,
+        )),
+        (Platform::Kiro, Some(account_id)) => {
+            to_value(&kiro_account::refresh_account_token(&account_id).await?)
+        }
+        (Platform::Kiro, None) => Ok(summarize_results(
+            &kiro_account::refresh_all_tokens().await?,
+        )),
+    }
+}
+
+async fn export(
+    platform: Platform,
+    account_ids: Vec<String>,
+    output: Option<String>,
+) -> Result<Value, String> {
+    let account_ids = if account_ids.is_empty() {
+        all_account_ids(platform)?
+    } else {
+        account_ids
+    };
+
+    let content = match platform {
+        Platform::Antigravity => modules::import::export_accounts_logic(&account_ids)?,
+        Platform::Codex => codex_account::export_accounts(&account_ids)?,
+        Platform::GitHubCopilot => github_copilot_account::export_accounts(&account_ids)?,
+        Platform::Windsurf => windsurf_account::export_accounts(&account_ids)?,
+        Platform::Kiro => kiro_account::export_accounts(&account_ids)?,
+    };
+    let exported: Value =
+        serde_json::from_str(&content).map_err(|e| format!("解析导出内容失败: {}", e))?;
+
+    match output {
+        Some(path) => {
+            atomic_file::replace(Path::new(&path), &content)
+                .map_err(|e| format!("写入导出文件失败: {}", e))?;
+            let count = exported.as_array().map(|items| items.len()).unwrap_or(0);
+            Ok(json!({ "path": path, "count": count }))
+        }
+        None => Ok(exported),
+    }
+}
+
+async fn import(platform: Platform, content: String) -> Result<Value, String> {
+    match platform {
+        Platform::Antigravity => to_value(&modules::import::import_from_json_logic(content).await?),
+        Platform::Codex => to_value(&codex_account::import_from_json(&content)?),
+        Platform::GitHubCopilot => to_value(&github_copilot_account::import_from_json(&content)?),
+        Platform::Windsurf => to_value(&windsurf_account::import_from_json(&content)?),
+        Platform::Kiro => to_value(&kiro_account::import_from_json(&content)?),
+    }
+}
+
+fn tag(platform: Platform, account_id: &str, tags: Vec<String>) -> Result<Value, String> {
+    match platform {
+        Platform::Antigravity => to_value(&modules::update_account_tags(account_id, tags)?),
+        Platform::Codex => to_value(&codex_account::update_account_tags(account_id, tags)?),
+        Platform::GitHubCopilot => to_value(&github_copilot_account::update_account_tags(
+            account_id, tags,
+        )?),
+        Platform::Windsurf => to_value(&windsurf_account::update_account_tags(account_id, tags)?),
+        Platform::Kiro => to_value(&kiro_account::update_account_tags(account_id, tags)?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(items: &[&str]) -> Vec<String> {
+        items.iter().map(|item| item.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_args_supports_platform_aliases() {
+        let (platform, action) = parse_args(&args(&["ghcp", "list"])).unwrap();
+        assert_eq!(platform, Platform::GitHubCopilot);
+        assert_eq!(action, Action::List);
+
+        let (platform, _) = parse_args(&args(&["account", "list"])).unwrap();
+        assert_eq!(platform, Platform::Antigravity);
+        assert!(parse_args(&args(&["unknown", "list"])).is_err());
+    }
+
+    #[test]
+    fn parse_args_export_collects_ids_and_output() {
+        let (_, action) = parse_args(&args(&[
+            "codex", "export", "a", "--output", "out.json", "b",
+        ]))
+        .unwrap();
+        assert_eq!(
+            action,
+            Action::Export {
+                account_ids: vec!["a".to_string(), "b".to_string()],
+                output: Some("out.json".to_string()),
+            }
+        );
+        assert!(parse_args(&args(&["codex", "export", "--output"])).is_err());
+    }
+
+    #[test]
+    fn parse_args_tag_splits_comma_separated_values() {
+        let (_, action) = parse_args(&args(&["kiro", "tag", "id-1", "work, ci", "team"])).unwrap();
+        assert_eq!(
+            action,
+            Action::Tag {
+                account_id: "id-1".to_string(),
+                tags: vec!["work".to_string(), "ci".to_string(), "team".to_string()],
+            }
+        );
+        assert!(parse_args(&args(&["kiro", "switch"])).is_err());
+    }
+}
@@ -0,0 +1,25 @@
+//! 审计日志相关命令
+
+use std::path::Path;
+
+use crate::modules::audit::{self, AuditEntry, AuditExportFormat, AuditQuery};
+
+/// 按条件查询审计日志（最新的在前）
+#[tauri::command]
+pub fn query_audit_log(query: Option<AuditQuery>) -> Result<Vec<AuditEntry>, String> {
+    audit::query(&query.unwrap_or_default())
+}
+
+/// 导出符合条件的审计日志为 JSON 或 CSV，返回导出条数
+#[tauri::command]
+pub fn export_audit_log(
+    target_path: String,
+    format: AuditExportFormat,
+    query: Option<AuditQuery>,
+) -> Result<usize, String> {
+    audit::export(
+        Path::new(target_path.trim()),
+        format,
+        &query.unwrap_or_default(),
+    )
+}
@@ -0,0 +1,40 @@
+//! 数据备份与恢复相关命令
+
+use std::path::Path;
+
+use crate::modules::backup::{self, BackupSummary, RestorePreview, RestoreResult};
+use crate::modules::websocket;
+
+/// 创建完整备份归档（口令为空时不加密）
+#[tauri::command]
+pub fn create_data_backup(
+    target_path: String,
+    passphrase: Option<String>,
+) -> Result<BackupSummary, String> {
+    backup::create_backup(Path::new(target_path.trim()), passphrase.as_deref())
+}
+
+/// 校验备份归档并预览与本机数据的差异
+#[tauri::command]
+pub fn preview_data_backup(
+    archive_path: String,
+    passphrase: Option<String>,
+) -> Result<RestorePreview, String> {
+    backup::preview_restore(Path::new(archive_path.trim()), passphrase.as_deref())
+}
+
+/// 从备份归档恢复选中的分区
+#[tauri::command]
+pub fn restore_data_backup(
+    archive_path: String,
+    passphrase: Option<String>,
+    sections: Vec<String>,
+) -> Result<RestoreResult, String> {
+    let result = backup::restore_backup(
+        Path::new(archive_path.trim()),
+        passphrase.as_deref(),
+        &sections,
+    )?;
+    websocket::broadcast_data_changed("backup_restored");
+    Ok(result)
+}
@@ -1,10 +1,7 @@
 use crate::models::codex::{CodexAccount, CodexQuota, CodexTokens};
-use crate::modules::{
-    codex_account, codex_oauth, codex_quota, config, logger, opencode_auth, process,
-};
+use crate::modules::import_plan::ImportOutcome;
+use crate::modules::{codex_account, codex_oauth, codex_quota, logger, process};
 use tauri::AppHandle;
-#[cfg(target_os = "macos")]
-use tauri::Emitter;
 
 /// 列出所有 Codex 账号
 #[tauri::command]
@@ -24,81 +21,7 @@ pub async fn switch_codex_account(
     app: AppHandle,
     account_id: String,
 ) -> Result<CodexAccount, String> {
-    let _ = codex_account::prepare_account_for_injection(&account_id).await?;
-
-    // 切换账号（写入 auth.json）
-    let account = codex_account::switch_account(&account_id)?;
-
-    // 同步更新 Codex 默认实例的绑定账号（不同步到 Antigravity，因为账号体系不同）
-    if let Err(e) = crate::modules::codex_instance::update_default_settings(
-        Some(Some(account_id.clone())),
-        None,
-        Some(false),
-    ) {
-        logger::log_warn(&format!("更新 Codex 默认实例绑定账号失败: {}", e));
-    } else {
-        logger::log_info(&format!(
-            "已同步更新 Codex 默认实例绑定账号: {}",
-            account_id
-        ));
-    }
-
-    let mut opencode_updated = false;
-    match opencode_auth::replace_openai_entry_from_codex(&account) {
-        Ok(()) => {
-            opencode_updated = true;
-        }
-        Err(e) => {
-            logger::log_warn(&format!("OpenCode auth.json 更新跳过: {}", e));
-        }
-    }
-
-    let user_config = config::get_user_config();
-    if user_config.opencode_sync_on_switch {
-        if opencode_updated {
-            if process::is_opencode_running() {
-                if let Err(e) = process::close_opencode(20) {
-                    logger::log_warn(&format!("OpenCode 关闭失败: {}", e));
-                }
-            } else {
-                logger::log_info("OpenCode 未在运行，准备启动");
-            }
-            if let Err(e) = process::start_opencode_with_path(Some(&user_config.opencode_app_path))
-            {
-                logger::log_warn(&format!("OpenCode 启动失败: {}", e));
-            }
-        } else {
-            logger::log_info("OpenCode 未更新 auth.json，跳过启动/重启");
-        }
-    } else {
-        logger::log_info("已关闭 OpenCode 自动重启");
-    }
-
-    #[cfg(target_os = "macos")]
-    {
-        if user_config.codex_launch_on_switch {
-            if process::is_codex_running() {
-                logger::log_info("检测到 Codex 正在运行，将按默认实例 PID 逻辑重启");
-            }
-            match crate::commands::codex_instance::codex_start_instance("__default__".to_string())
-                .await
-            {
-                Ok(_) => {}
-                Err(e) => {
-                    logger::log_warn(&format!("Codex 启动失败: {}", e));
-                    if e.starts_with("APP_PATH_NOT_FOUND:") {
-                        let _ = app.emit(
-                            "app:path_missing",
-                            serde_json::json!({ "app": "codex", "retry": { "kind": "default" } }),
-                        );
-                    }
-                }
-            }
-        } else {
-            logger::log_info("已关闭切换 Codex 时自动启动 Codex App");
-        }
-    }
-
+    let account = codex_account::switch_account_internal(&account_id).await?;
     let _ = crate::modules::tray::update_tray_menu(&app);
     Ok(account)
 }
@@ -123,7 +46,7 @@ pub fn import_codex_from_local() -> Result<CodexAccount, String> {
 
 /// 从 JSON 字符串导入账号
 #[tauri::command]
-pub fn import_codex_from_json(json_content: String) -> Result<Vec<CodexAccount>, String> {
+pub fn import_codex_from_json(json_content: String) -> Result<ImportOutcome<CodexAccount>, String> {
     codex_account::import_from_json(&json_content)
 }
 
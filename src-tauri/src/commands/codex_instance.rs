@@ -1,6 +1,6 @@
 use std::path::Path;
 
-use crate::models::{DefaultInstanceSettings, InstanceProfileView};
+use crate::models::InstanceProfileView;
 use crate::modules;
 
 const DEFAULT_INSTANCE_ID: &str = "__default__";
@@ -9,19 +9,6 @@ fn is_profile_initialized(user_data_dir: &str) -> bool {
     modules::instance::is_profile_initialized(Path::new(user_data_dir))
 }
 
-fn resolve_default_account_id(settings: &DefaultInstanceSettings) -> Option<String> {
-    if settings.follow_local_account {
-        resolve_local_account_id()
-    } else {
-        settings.bind_account_id.clone()
-    }
-}
-
-fn resolve_local_account_id() -> Option<String> {
-    let account = modules::codex_account::get_current_account()?;
-    Some(account.id)
-}
-
 #[tauri::command]
 pub async fn codex_get_instance_defaults() -> Result<modules::instance::InstanceDefaults, String> {
     modules::codex_instance::get_instance_defaults()
@@ -58,7 +45,8 @@ pub async fn codex_list_instances() -> Result<Vec<InstanceProfileView>, String>
         &process_entries,
     );
     let default_running = default_pid.is_some();
-    let default_bind_account_id = resolve_default_account_id(&default_settings);
+    let default_bind_account_id =
+        modules::codex_instance::resolve_default_account_id(&default_settings);
     result.push(InstanceProfileView {
         id: DEFAULT_INSTANCE_ID.to_string(),
         name: String::new(),
@@ -124,7 +112,7 @@ pub async fn codex_update_instance(
             .last_pid
             .map(modules::process::is_pid_running)
             .unwrap_or(false);
-        let default_bind_account_id = resolve_default_account_id(&updated);
+        let default_bind_account_id = modules::codex_instance::resolve_default_account_id(&updated);
         return Ok(InstanceProfileView {
             id: DEFAULT_INSTANCE_ID.to_string(),
             name: String::new(),
@@ -187,69 +175,7 @@ pub async fn codex_delete_instance(instance_id: String) -> Result<(), String> {
 
 #[tauri::command]
 pub async fn codex_start_instance(instance_id: String) -> Result<InstanceProfileView, String> {
-    if instance_id == DEFAULT_INSTANCE_ID {
-        let default_dir = modules::codex_instance::get_default_codex_home()?;
-        let default_dir_str = default_dir.to_string_lossy().to_string();
-        let default_settings = modules::codex_instance::load_default_settings()?;
-        let default_bind_account_id = resolve_default_account_id(&default_settings);
-        if let Some(pid) = modules::process::resolve_codex_pid(default_settings.last_pid, None) {
-            modules::process::close_pid(pid, 20)?;
-            let _ = modules::codex_instance::update_default_pid(None)?;
-        }
-        if let Some(ref account_id) = default_bind_account_id {
-            modules::codex_instance::inject_account_to_profile(&default_dir, account_id).await?;
-        }
-        let pid = modules::process::start_codex_default()?;
-        let _ = modules::codex_instance::update_default_pid(Some(pid))?;
-        let running = modules::process::is_pid_running(pid);
-        return Ok(InstanceProfileView {
-            id: DEFAULT_INSTANCE_ID.to_string(),
-            name: String::new(),
-            user_data_dir: default_dir_str,
-            extra_args: default_settings.extra_args,
-            bind_account_id: default_bind_account_id,
-            created_at: 0,
-            last_launched_at: None,
-            last_pid: Some(pid),
-            running,
-            initialized: modules::instance::is_profile_initialized(&default_dir),
-            is_default: true,
-            follow_local_account: default_settings.follow_local_account,
-        });
-    }
-
-    let store = modules::codex_instance::load_instance_store()?;
-    let instance = store
-        .instances
-        .into_iter()
-        .find(|item| item.id == instance_id)
-        .ok_or("实例不存在")?;
-
-    if let Some(pid) =
-        modules::process::resolve_codex_pid(instance.last_pid, Some(&instance.user_data_dir))
-    {
-        modules::process::close_pid(pid, 20)?;
-        let _ = modules::codex_instance::update_instance_pid(&instance.id, None)?;
-    }
-
-    if let Some(ref account_id) = instance.bind_account_id {
-        modules::codex_instance::inject_account_to_profile(
-            Path::new(&instance.user_data_dir),
-            account_id,
-        )
-        .await?;
-    }
-
-    let extra_args = modules::process::parse_extra_args(&instance.extra_args);
-    let pid = modules::process::start_codex_with_args(&instance.user_data_dir, &extra_args)?;
-    let updated = modules::codex_instance::update_instance_after_start(&instance.id, pid)?;
-    let running = modules::process::is_pid_running(pid);
-    let initialized = is_profile_initialized(&updated.user_data_dir);
-    Ok(InstanceProfileView::from_profile(
-        updated,
-        running,
-        initialized,
-    ))
+    modules::codex_instance::start_instance(&instance_id).await
 }
 
 #[tauri::command]
@@ -263,7 +189,8 @@ pub async fn codex_stop_instance(instance_id: String) -> Result<InstanceProfileV
         }
         let _ = modules::codex_instance::update_default_pid(None)?;
         let running = false;
-        let default_bind_account_id = resolve_default_account_id(&default_settings);
+        let default_bind_account_id =
+            modules::codex_instance::resolve_default_account_id(&default_settings);
         return Ok(InstanceProfileView {
             id: DEFAULT_INSTANCE_ID.to_string(),
             name: String::new(),
@@ -1,6 +1,7 @@
-use tauri::{AppHandle, Emitter};
+use tauri::AppHandle;
 
 use crate::models::github_copilot::{GitHubCopilotAccount, GitHubCopilotOAuthStartResponse};
+use crate::modules::import_plan::ImportOutcome;
 use crate::modules::{github_copilot_account, github_copilot_oauth, logger};
 
 /// 列出所有 GitHub Copilot 账号
@@ -25,7 +26,7 @@ pub fn delete_github_copilot_accounts(account_ids: Vec<String>) -> Result<(), St
 #[tauri::command]
 pub fn import_github_copilot_from_json(
     json_content: String,
-) -> Result<Vec<GitHubCopilotAccount>, String> {
+) -> Result<ImportOutcome<GitHubCopilotAccount>, String> {
     github_copilot_account::import_from_json(&json_content)
 }
 
@@ -144,59 +145,9 @@ pub async fn inject_github_copilot_to_vscode(
     app: AppHandle,
     account_id: String,
 ) -> Result<String, String> {
-    logger::log_info(&format!("开始切换 GitHub Copilot 账号: {}", account_id));
-    let account = github_copilot_account::load_account(&account_id)
-        .ok_or_else(|| format!("GitHub Copilot account not found: {}", account_id))?;
-    logger::log_info(&format!(
-        "正在切换到 GitHub Copilot 账号: {} (ID: {})",
-        account.github_login, account.id
-    ));
-
-    // 同步更新 VS Code 默认实例绑定账号，确保后续走默认实例启动链路时注入目标明确。
-    if let Err(e) = crate::modules::github_copilot_instance::update_default_settings(
-        Some(Some(account_id.clone())),
-        None,
-        Some(false),
-    ) {
-        logger::log_warn(&format!("更新 GitHub Copilot 默认实例绑定账号失败: {}", e));
-    } else {
-        logger::log_info(&format!(
-            "已同步更新 GitHub Copilot 默认实例绑定账号: {}",
-            account_id
-        ));
-    }
-
-    let launch_warning =
-        match crate::commands::github_copilot_instance::github_copilot_start_instance(
-            "__default__".to_string(),
-        )
-        .await
-        {
-            Ok(_) => None,
-            Err(e) => {
-                if e.starts_with("APP_PATH_NOT_FOUND:") || e.contains("启动 VS Code 失败") {
-                    logger::log_warn(&format!("GitHub Copilot 默认实例启动失败: {}", e));
-                    if e.starts_with("APP_PATH_NOT_FOUND:") || e.contains("APP_PATH_NOT_FOUND:") {
-                        let _ = app.emit(
-                            "app:path_missing",
-                            serde_json::json!({ "app": "vscode", "retry": { "kind": "default" } }),
-                        );
-                    }
-                    Some(e)
-                } else {
-                    return Err(e);
-                }
-            }
-        };
-
-    logger::log_info(&format!(
-        "GitHub Copilot 账号切换完成: {}",
-        account.github_login
-    ));
-    let _ = crate::modules::tray::update_tray_menu(&app);
-    if let Some(err) = launch_warning {
-        Ok(format!("切换完成，但 VS Code 启动失败: {}", err))
-    } else {
-        Ok("切换完成".to_string())
+    let result = github_copilot_account::switch_account_internal(&account_id).await;
+    if result.is_ok() {
+        let _ = crate::modules::tray::update_tray_menu(&app);
     }
+    result
 }
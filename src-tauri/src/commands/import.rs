@@ -22,41 +22,48 @@ pub async fn import_from_local() -> Result<models::Account, String> {
 }
 
 #[tauri::command]
-pub async fn import_from_json(json_content: String) -> Result<Vec<models::Account>, String> {
+pub async fn import_from_json(
+    json_content: String,
+) -> Result<modules::import_plan::ImportOutcome<models::Account>, String> {
     modules::import::import_from_json_logic(json_content).await
 }
 
 #[tauri::command]
 pub async fn export_accounts(account_ids: Vec<String>) -> Result<String, String> {
-    let mut accounts_to_export = Vec::new();
-
-    if account_ids.is_empty() {
-        // 导出全部
-        accounts_to_export = modules::list_accounts()?;
-    } else {
-        for id in &account_ids {
-            if let Ok(account) = modules::load_account(id) {
-                accounts_to_export.push(account);
-            }
-        }
-    }
-
-    #[derive(serde::Serialize)]
-    struct SimpleAccount {
-        email: String,
-        refresh_token: String,
-    }
+    modules::import::export_accounts_logic(&account_ids)
+}
 
-    let simplified: Vec<SimpleAccount> = accounts_to_export
-        .into_iter()
-        .map(|account| SimpleAccount {
-            email: account.email,
-            refresh_token: account.token.refresh_token,
+/// 导出统一交换格式（platforms 为空时导出全部平台）
+#[tauri::command]
+pub async fn export_interchange_accounts(platforms: Vec<String>) -> Result<String, String> {
+    let platforms = platforms
+        .iter()
+        .map(|value| {
+            modules::data_store::AccountPlatform::parse(value)
+                .ok_or_else(|| format!("未知的平台: {}", value))
         })
-        .collect();
+        .collect::<Result<Vec<_>, String>>()?;
+    modules::interchange::export_document(&platforms)
+}
 
-    let json =
-        serde_json::to_string_pretty(&simplified).map_err(|e| format!("序列化失败: {}", e))?;
+/// 预览交换文件的导入计划（不写入数据）
+#[tauri::command]
+pub async fn preview_interchange_import(
+    json_content: String,
+) -> Result<modules::import_plan::ImportPlan, String> {
+    modules::import_plan::preview_import(&json_content)
+}
 
-    Ok(json)
+/// 按策略导入统一交换格式中的全部平台账号
+#[tauri::command]
+pub async fn import_interchange_accounts(
+    json_content: String,
+    strategy: Option<modules::import_plan::ImportStrategy>,
+) -> Result<modules::interchange::InterchangeImportResult, String> {
+    let result =
+        modules::interchange::import_document(&json_content, strategy.unwrap_or_default())?;
+    if !result.imported.is_empty() {
+        modules::websocket::broadcast_data_changed("import_interchange");
+    }
+    Ok(result)
 }
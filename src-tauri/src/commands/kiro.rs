@@ -1,8 +1,10 @@
 use std::time::Instant;
-use tauri::{AppHandle, Emitter};
+use tauri::AppHandle;
 
 use crate::models::kiro::{KiroAccount, KiroOAuthStartResponse};
-use crate::modules::{kiro_account, kiro_oauth, logger};
+use crate::modules::data_store::AccountPlatform;
+use crate::modules::import_plan::ImportOutcome;
+use crate::modules::{audit, kiro_account, kiro_oauth, logger};
 
 #[tauri::command]
 pub fn list_kiro_accounts() -> Result<Vec<KiroAccount>, String> {
@@ -20,16 +22,20 @@ pub fn delete_kiro_accounts(account_ids: Vec<String>) -> Result<(), String> {
 }
 
 #[tauri::command]
-pub fn import_kiro_from_json(json_content: String) -> Result<Vec<KiroAccount>, String> {
+pub fn import_kiro_from_json(json_content: String) -> Result<ImportOutcome<KiroAccount>, String> {
     kiro_account::import_from_json(&json_content)
 }
 
 #[tauri::command]
 pub async fn import_kiro_from_local() -> Result<Vec<KiroAccount>, String> {
-    let payload = kiro_oauth::build_payload_from_local_files()?;
-    let payload = kiro_oauth::enrich_payload_with_runtime_usage(payload).await;
-    let account = kiro_account::upsert_account(payload)?;
-    Ok(vec![account])
+    let result = async {
+        let payload = kiro_oauth::build_payload_from_local_files()?;
+        let payload = kiro_oauth::enrich_payload_with_runtime_usage(payload).await;
+        kiro_account::upsert_account(payload)
+    }
+    .await;
+    audit::record_import(AccountPlatform::Kiro, "local", result.as_ref().map(|_| 1));
+    Ok(vec![result?])
 }
 
 #[tauri::command]
@@ -157,63 +163,9 @@ pub fn get_kiro_accounts_index_path() -> Result<String, String> {
 
 #[tauri::command]
 pub async fn inject_kiro_to_vscode(app: AppHandle, account_id: String) -> Result<String, String> {
-    let started_at = Instant::now();
-    logger::log_info(&format!(
-        "[Kiro Switch] 开始切换账号: account_id={}",
-        account_id
-    ));
-
-    let account = kiro_account::load_account(&account_id)
-        .ok_or_else(|| format!("Kiro account not found: {}", account_id))?;
-
-    if let Err(err) = crate::modules::kiro_instance::update_default_settings(
-        Some(Some(account_id.clone())),
-        None,
-        Some(false),
-    ) {
-        logger::log_warn(&format!("更新 Kiro 默认实例绑定账号失败: {}", err));
-    }
-
-    let launch_warning = match crate::commands::kiro_instance::kiro_start_instance(
-        "__default__".to_string(),
-    )
-    .await
-    {
-        Ok(_) => None,
-        Err(err) => {
-            if err.starts_with("APP_PATH_NOT_FOUND:") || err.contains("启动 Kiro 失败") {
-                logger::log_warn(&format!("Kiro 默认实例启动失败: {}", err));
-                if err.starts_with("APP_PATH_NOT_FOUND:") {
-                    let _ = app.emit(
-                        "app:path_missing",
-                        serde_json::json!({ "app": "kiro", "retry": { "kind": "default" } }),
-                    );
-                }
-                Some(err)
-            } else {
-                return Err(err);
-            }
-        }
-    };
-
-    let _ = crate::modules::tray::update_tray_menu(&app);
-
-    if let Some(err) = launch_warning {
-        logger::log_warn(&format!(
-            "[Kiro Switch] 切号完成但启动失败: account_id={}, email={}, elapsed={}ms, error={}",
-            account.id,
-            account.email,
-            started_at.elapsed().as_millis(),
-            err
-        ));
-        Ok(format!("切换完成，但 Kiro 启动失败: {}", err))
-    } else {
-        logger::log_info(&format!(
-            "[Kiro Switch] 切号成功: account_id={}, email={}, elapsed={}ms",
-            account.id,
-            account.email,
-            started_at.elapsed().as_millis()
-        ));
-        Ok(format!("切换完成: {}", account.email))
+    let result = kiro_account::switch_account_internal(&account_id).await;
+    if result.is_ok() {
+        let _ = crate::modules::tray::update_tray_menu(&app);
     }
+    result
 }
@@ -1,45 +1,10 @@
-use std::path::Path;
-
 use crate::models::{DefaultInstanceSettings, InstanceProfileView};
 use crate::modules;
 
 const DEFAULT_INSTANCE_ID: &str = "__default__";
 
 fn is_profile_initialized(user_data_dir: &str) -> bool {
-    let path = Path::new(user_data_dir);
-    if !path.exists() {
-        return false;
-    }
-    match std::fs::read_dir(path) {
-        Ok(mut iter) => iter.next().is_some(),
-        Err(_) => false,
-    }
-}
-
-fn inject_bound_account_for_instance_start(
-    user_data_dir: &str,
-    bind_account_id: Option<&str>,
-) -> Result<(), String> {
-    let bind_id = bind_account_id
-        .map(str::trim)
-        .filter(|value| !value.is_empty());
-    let Some(bind_id) = bind_id else {
-        return Ok(());
-    };
-
-    let account = modules::kiro_account::load_account(bind_id)
-        .ok_or_else(|| format!("绑定账号不存在: {}", bind_id))?;
-
-    modules::logger::log_info(&format!(
-        "实例启动检测到绑定 Kiro 账号，准备注入: bind_account_id={}, email={}, user_data_dir={}",
-        bind_id, account.email, user_data_dir
-    ));
-
-    modules::kiro_instance::close_kiro(&[user_data_dir.to_string()], 20)?;
-    modules::kiro_instance::inject_account_to_profile(Path::new(user_data_dir), bind_id)?;
-
-    modules::logger::log_info(&format!("Kiro 账号注入完成: {}", account.email));
-    Ok(())
+    modules::kiro_instance::is_profile_initialized(user_data_dir)
 }
 
 #[tauri::command]
@@ -210,85 +175,7 @@ pub async fn kiro_delete_instance(instance_id: String) -> Result<(), String> {
 
 #[tauri::command]
 pub async fn kiro_start_instance(instance_id: String) -> Result<InstanceProfileView, String> {
-    modules::logger::log_info(&format!("开始启动 Kiro 实例: {}", instance_id));
-
-    if instance_id == DEFAULT_INSTANCE_ID {
-        let default_dir = modules::kiro_instance::get_default_kiro_user_data_dir()?;
-        let default_dir_str = default_dir.to_string_lossy().to_string();
-        let default_settings = modules::kiro_instance::load_default_settings()?;
-
-        if let Some(pid) = modules::kiro_instance::resolve_kiro_pid(default_settings.last_pid, None)
-        {
-            modules::process::close_pid(pid, 20)?;
-            let _ = modules::kiro_instance::update_default_pid(None)?;
-        }
-
-        modules::kiro_instance::close_kiro(&[default_dir_str.clone()], 20)?;
-        inject_bound_account_for_instance_start(
-            &default_dir_str,
-            default_settings.bind_account_id.as_deref(),
-        )?;
-
-        let extra_args = modules::process::parse_extra_args(&default_settings.extra_args);
-        let pid = modules::kiro_instance::start_kiro_default_with_args_with_new_window(
-            &extra_args,
-            true,
-        )?;
-        let _ = modules::kiro_instance::update_default_pid(Some(pid))?;
-
-        let running = modules::kiro_instance::resolve_kiro_pid(Some(pid), None).is_some();
-        return Ok(InstanceProfileView {
-            id: DEFAULT_INSTANCE_ID.to_string(),
-            name: String::new(),
-            user_data_dir: default_dir_str,
-            extra_args: default_settings.extra_args,
-            bind_account_id: default_settings.bind_account_id,
-            created_at: 0,
-            last_launched_at: None,
-            last_pid: Some(pid),
-            running,
-            initialized: is_profile_initialized(&default_dir.to_string_lossy()),
-            is_default: true,
-            follow_local_account: false,
-        });
-    }
-
-    let store = modules::kiro_instance::load_instance_store()?;
-    let instance = store
-        .instances
-        .into_iter()
-        .find(|item| item.id == instance_id)
-        .ok_or("实例不存在")?;
-
-    if let Some(pid) =
-        modules::kiro_instance::resolve_kiro_pid(instance.last_pid, Some(&instance.user_data_dir))
-    {
-        modules::process::close_pid(pid, 20)?;
-        let _ = modules::kiro_instance::update_instance_pid(&instance.id, None)?;
-    }
-
-    modules::kiro_instance::close_kiro(&[instance.user_data_dir.clone()], 20)?;
-    inject_bound_account_for_instance_start(
-        &instance.user_data_dir,
-        instance.bind_account_id.as_deref(),
-    )?;
-
-    let extra_args = modules::process::parse_extra_args(&instance.extra_args);
-    let pid = modules::kiro_instance::start_kiro_with_args_with_new_window(
-        &instance.user_data_dir,
-        &extra_args,
-        true,
-    )?;
-    let updated = modules::kiro_instance::update_instance_after_start(&instance.id, pid)?;
-
-    let running =
-        modules::kiro_instance::resolve_kiro_pid(Some(pid), Some(&updated.user_data_dir)).is_some();
-    let initialized = is_profile_initialized(&updated.user_data_dir);
-    Ok(InstanceProfileView::from_profile(
-        updated,
-        running,
-        initialized,
-    ))
+    modules::kiro_instance::start_instance(&instance_id).await
 }
 
 #[tauri::command]
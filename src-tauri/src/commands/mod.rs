@@ -1,4 +1,6 @@
 pub mod account;
+pub mod audit;
+pub mod backup;
 pub mod codex;
 pub mod codex_instance;
 pub mod device;
@@ -10,8 +12,11 @@ pub mod instance;
 pub mod kiro;
 pub mod kiro_instance;
 pub mod oauth;
+pub mod quota_history;
 pub mod system;
+pub mod trash;
 pub mod update;
+pub mod vault;
 pub mod wakeup;
 pub mod windsurf;
 pub mod windsurf_instance;
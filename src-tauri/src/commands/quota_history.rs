@@ -0,0 +1,47 @@
+//! 配额历史、耗尽预测与重置日历相关命令
+
+use std::path::Path;
+
+use crate::modules::data_store::AccountPlatform;
+use crate::modules::quota_history::{self, QuotaForecast, QuotaPoint};
+use crate::modules::quota_reset::{self, UpcomingReset};
+
+fn parse_platform(platform: &str) -> Result<AccountPlatform, String> {
+    AccountPlatform::parse(platform.trim()).ok_or_else(|| format!("未知平台: {}", platform))
+}
+
+/// 查询账号的配额历史（按时间先后）
+#[tauri::command]
+pub fn get_quota_history(
+    platform: String,
+    account_id: String,
+    meter: Option<String>,
+    since: Option<i64>,
+) -> Result<Vec<QuotaPoint>, String> {
+    quota_history::series(
+        parse_platform(&platform)?,
+        &account_id,
+        meter.as_deref(),
+        since,
+    )
+}
+
+/// 计算各账号配额项的消耗速率与预计耗尽时间（不传平台时计算全部平台）
+#[tauri::command]
+pub fn get_quota_forecasts(platform: Option<String>) -> Result<Vec<QuotaForecast>, String> {
+    let platform = platform.as_deref().map(parse_platform).transpose()?;
+    quota_history::forecast(platform)
+}
+
+/// 列出各账号即将到来的配额重置（不传平台时列出全部平台）
+#[tauri::command]
+pub fn get_upcoming_quota_resets(platform: Option<String>) -> Result<Vec<UpcomingReset>, String> {
+    let platform = platform.as_deref().map(parse_platform).transpose()?;
+    quota_reset::upcoming(platform)
+}
+
+/// 导出全部账号即将到来的配额重置为 iCalendar (.ics) 文件，返回事件数
+#[tauri::command]
+pub fn export_quota_reset_calendar(target_path: String) -> Result<usize, String> {
+    quota_reset::export_calendar(Path::new(target_path.trim()))
+}
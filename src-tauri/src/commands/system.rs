@@ -3,7 +3,8 @@ use tauri::Manager;
 
 use crate::modules;
 use crate::modules::config::{
-    self, CloseWindowBehavior, MinimizeWindowBehavior, UserConfig, DEFAULT_WS_PORT,
+    self, CloseWindowBehavior, MinimizeWindowBehavior, UserConfig, WatchedGroup,
+    DEFAULT_PROXY_PORT, DEFAULT_WS_PORT,
 };
 use crate::modules::websocket;
 
@@ -18,6 +19,16 @@ pub struct NetworkConfig {
     pub actual_port: Option<u16>,
     /// 默认端口
     pub default_port: u16,
+    /// 本地 API 代理是否启用
+    pub proxy_enabled: bool,
+    /// 本地 API 代理端口
+    pub proxy_port: u16,
+    /// 本地 API 代理访问密钥
+    pub proxy_api_key: String,
+    /// 本地 API 代理实际运行端口（未运行为 None）
+    pub proxy_actual_port: Option<u16>,
+    /// 本地 API 代理默认端口
+    pub proxy_default_port: u16,
 }
 
 /// 通用设置配置（前端使用）
@@ -63,6 +74,48 @@ pub struct GeneralConfig {
     pub auto_switch_enabled: bool,
     /// 自动切号阈值（百分比）
     pub auto_switch_threshold: i32,
+    /// 自动切号选号策略
+    pub auto_switch_strategy: String,
+    /// 自动切号选号策略参数
+    pub auto_switch_strategy_param: String,
+    /// 关注的模型分组及分组阈值
+    pub watched_groups: Vec<WatchedGroup>,
+    /// 是否启用配额保留
+    pub quota_protection_enabled: bool,
+    /// 配额保留阈值（百分比）
+    pub quota_protection_reserve: i32,
+    /// 是否启用 Codex 自动切号
+    pub codex_auto_switch_enabled: bool,
+    /// Codex 自动切号阈值（百分比）
+    pub codex_auto_switch_threshold: i32,
+    /// Codex 自动切号选号策略
+    pub codex_auto_switch_strategy: String,
+    /// Codex 选号策略参数
+    pub codex_auto_switch_strategy_param: String,
+    /// 是否启用 GitHub Copilot 自动切号
+    pub ghcp_auto_switch_enabled: bool,
+    /// GitHub Copilot 自动切号阈值（百分比）
+    pub ghcp_auto_switch_threshold: i32,
+    /// GitHub Copilot 自动切号选号策略
+    pub ghcp_auto_switch_strategy: String,
+    /// GitHub Copilot 选号策略参数
+    pub ghcp_auto_switch_strategy_param: String,
+    /// 是否启用 Windsurf 自动切号
+    pub windsurf_auto_switch_enabled: bool,
+    /// Windsurf 自动切号阈值（百分比）
+    pub windsurf_auto_switch_threshold: i32,
+    /// Windsurf 自动切号选号策略
+    pub windsurf_auto_switch_strategy: String,
+    /// Windsurf 选号策略参数
+    pub windsurf_auto_switch_strategy_param: String,
+    /// 是否启用 Kiro 自动切号
+    pub kiro_auto_switch_enabled: bool,
+    /// Kiro 自动切号阈值（百分比）
+    pub kiro_auto_switch_threshold: i32,
+    /// Kiro 自动切号选号策略
+    pub kiro_auto_switch_strategy: String,
+    /// Kiro 选号策略参数
+    pub kiro_auto_switch_strategy_param: String,
     /// 是否启用配额预警通知
     pub quota_alert_enabled: bool,
     /// 配额预警阈值（百分比）
@@ -83,6 +136,8 @@ pub struct GeneralConfig {
     pub kiro_quota_alert_enabled: bool,
     /// Kiro 配额预警阈值（百分比）
     pub kiro_quota_alert_threshold: i32,
+    /// 回收站保留天数，0 表示永久保留
+    pub trash_retention_days: i32,
 }
 
 #[tauri::command]
@@ -134,6 +189,15 @@ pub fn get_downloads_dir() -> Result<String, String> {
     Err("无法获取下载目录".to_string())
 }
 
+/// 启动时的数据迁移结果，迁移失败时返回迁移错误（error_type 为 migration_failed）
+#[tauri::command]
+pub fn get_startup_migration_error() -> Result<(), crate::error::AppError> {
+    match modules::startup::migration_error() {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
 /// 获取网络服务配置
 #[tauri::command]
 pub fn get_network_config() -> Result<NetworkConfig, String> {
@@ -145,14 +209,37 @@ pub fn get_network_config() -> Result<NetworkConfig, String> {
         ws_port: user_config.ws_port,
         actual_port,
         default_port: DEFAULT_WS_PORT,
+        proxy_enabled: user_config.proxy_enabled,
+        proxy_port: user_config.proxy_port,
+        proxy_api_key: user_config.proxy_api_key,
+        proxy_actual_port: modules::api_proxy::get_running_port(),
+        proxy_default_port: DEFAULT_PROXY_PORT,
     })
 }
 
 /// 保存网络服务配置
 #[tauri::command]
-pub fn save_network_config(ws_enabled: bool, ws_port: u16) -> Result<bool, String> {
+pub fn save_network_config(
+    ws_enabled: bool,
+    ws_port: u16,
+    proxy_enabled: Option<bool>,
+    proxy_port: Option<u16>,
+    proxy_api_key: Option<String>,
+) -> Result<bool, String> {
     let current = config::get_user_config();
-    let needs_restart = current.ws_port != ws_port || current.ws_enabled != ws_enabled;
+    let proxy_enabled = proxy_enabled.unwrap_or(current.proxy_enabled);
+    let proxy_port = proxy_port.unwrap_or(current.proxy_port);
+    let mut proxy_api_key = proxy_api_key
+        .map(|value| value.trim().to_string())
+        .unwrap_or_else(|| current.proxy_api_key.clone());
+    // 代理不接受无密钥访问，启用时未填写则自动生成
+    if proxy_enabled && proxy_api_key.is_empty() {
+        proxy_api_key = modules::api_proxy::generate_api_key();
+    }
+    let needs_restart = current.ws_port != ws_port
+        || current.ws_enabled != ws_enabled
+        || current.proxy_enabled != proxy_enabled
+        || current.proxy_port != proxy_port;
 
     let new_config = UserConfig {
         ws_enabled,
@@ -178,6 +265,27 @@ pub fn save_network_config(ws_enabled: bool, ws_port: u16) -> Result<bool, Strin
         codex_launch_on_switch: current.codex_launch_on_switch,
         auto_switch_enabled: current.auto_switch_enabled,
         auto_switch_threshold: current.auto_switch_threshold,
+        auto_switch_strategy: current.auto_switch_strategy,
+        auto_switch_strategy_param: current.auto_switch_strategy_param,
+        watched_groups: current.watched_groups,
+        quota_protection_enabled: current.quota_protection_enabled,
+        quota_protection_reserve: current.quota_protection_reserve,
+        codex_auto_switch_enabled: current.codex_auto_switch_enabled,
+        codex_auto_switch_threshold: current.codex_auto_switch_threshold,
+        codex_auto_switch_strategy: current.codex_auto_switch_strategy,
+        codex_auto_switch_strategy_param: current.codex_auto_switch_strategy_param,
+        ghcp_auto_switch_enabled: current.ghcp_auto_switch_enabled,
+        ghcp_auto_switch_threshold: current.ghcp_auto_switch_threshold,
+        ghcp_auto_switch_strategy: current.ghcp_auto_switch_strategy,
+        ghcp_auto_switch_strategy_param: current.ghcp_auto_switch_strategy_param,
+        windsurf_auto_switch_enabled: current.windsurf_auto_switch_enabled,
+        windsurf_auto_switch_threshold: current.windsurf_auto_switch_threshold,
+        windsurf_auto_switch_strategy: current.windsurf_auto_switch_strategy,
+        windsurf_auto_switch_strategy_param: current.windsurf_auto_switch_strategy_param,
+        kiro_auto_switch_enabled: current.kiro_auto_switch_enabled,
+        kiro_auto_switch_threshold: current.kiro_auto_switch_threshold,
+        kiro_auto_switch_strategy: current.kiro_auto_switch_strategy,
+        kiro_auto_switch_strategy_param: current.kiro_auto_switch_strategy_param,
         quota_alert_enabled: current.quota_alert_enabled,
         quota_alert_threshold: current.quota_alert_threshold,
         codex_quota_alert_enabled: current.codex_quota_alert_enabled,
@@ -188,6 +296,10 @@ pub fn save_network_config(ws_enabled: bool, ws_port: u16) -> Result<bool, Strin
         windsurf_quota_alert_threshold: current.windsurf_quota_alert_threshold,
         kiro_quota_alert_enabled: current.kiro_quota_alert_enabled,
         kiro_quota_alert_threshold: current.kiro_quota_alert_threshold,
+        proxy_enabled,
+        proxy_port,
+        proxy_api_key,
+        trash_retention_days: current.trash_retention_days,
     };
 
     config::save_user_config(&new_config)?;
@@ -231,6 +343,27 @@ pub fn get_general_config() -> Result<GeneralConfig, String> {
         codex_launch_on_switch: user_config.codex_launch_on_switch,
         auto_switch_enabled: user_config.auto_switch_enabled,
         auto_switch_threshold: user_config.auto_switch_threshold,
+        auto_switch_strategy: user_config.auto_switch_strategy,
+        auto_switch_strategy_param: user_config.auto_switch_strategy_param,
+        watched_groups: user_config.watched_groups,
+        quota_protection_enabled: user_config.quota_protection_enabled,
+        quota_protection_reserve: user_config.quota_protection_reserve,
+        codex_auto_switch_enabled: user_config.codex_auto_switch_enabled,
+        codex_auto_switch_threshold: user_config.codex_auto_switch_threshold,
+        codex_auto_switch_strategy: user_config.codex_auto_switch_strategy,
+        codex_auto_switch_strategy_param: user_config.codex_auto_switch_strategy_param,
+        ghcp_auto_switch_enabled: user_config.ghcp_auto_switch_enabled,
+        ghcp_auto_switch_threshold: user_config.ghcp_auto_switch_threshold,
+        ghcp_auto_switch_strategy: user_config.ghcp_auto_switch_strategy,
+        ghcp_auto_switch_strategy_param: user_config.ghcp_auto_switch_strategy_param,
+        windsurf_auto_switch_enabled: user_config.windsurf_auto_switch_enabled,
+        windsurf_auto_switch_threshold: user_config.windsurf_auto_switch_threshold,
+        windsurf_auto_switch_strategy: user_config.windsurf_auto_switch_strategy,
+        windsurf_auto_switch_strategy_param: user_config.windsurf_auto_switch_strategy_param,
+        kiro_auto_switch_enabled: user_config.kiro_auto_switch_enabled,
+        kiro_auto_switch_threshold: user_config.kiro_auto_switch_threshold,
+        kiro_auto_switch_strategy: user_config.kiro_auto_switch_strategy,
+        kiro_auto_switch_strategy_param: user_config.kiro_auto_switch_strategy_param,
         quota_alert_enabled: user_config.quota_alert_enabled,
         quota_alert_threshold: user_config.quota_alert_threshold,
         codex_quota_alert_enabled: user_config.codex_quota_alert_enabled,
@@ -241,6 +374,7 @@ pub fn get_general_config() -> Result<GeneralConfig, String> {
         windsurf_quota_alert_threshold: user_config.windsurf_quota_alert_threshold,
         kiro_quota_alert_enabled: user_config.kiro_quota_alert_enabled,
         kiro_quota_alert_threshold: user_config.kiro_quota_alert_threshold,
+        trash_retention_days: user_config.trash_retention_days,
     })
 }
 
@@ -268,6 +402,9 @@ pub fn save_general_config(
     codex_launch_on_switch: bool,
     auto_switch_enabled: Option<bool>,
     auto_switch_threshold: Option<i32>,
+    auto_switch_strategy: Option<String>,
+    auto_switch_strategy_param: Option<String>,
+    watched_groups: Option<Vec<WatchedGroup>>,
     quota_alert_enabled: Option<bool>,
     quota_alert_threshold: Option<i32>,
     codex_quota_alert_enabled: Option<bool>,
@@ -278,6 +415,25 @@ pub fn save_general_config(
     windsurf_quota_alert_threshold: Option<i32>,
     kiro_quota_alert_enabled: Option<bool>,
     kiro_quota_alert_threshold: Option<i32>,
+    quota_protection_enabled: Option<bool>,
+    quota_protection_reserve: Option<i32>,
+    codex_auto_switch_enabled: Option<bool>,
+    codex_auto_switch_threshold: Option<i32>,
+    codex_auto_switch_strategy: Option<String>,
+    codex_auto_switch_strategy_param: Option<String>,
+    ghcp_auto_switch_enabled: Option<bool>,
+    ghcp_auto_switch_threshold: Option<i32>,
+    ghcp_auto_switch_strategy: Option<String>,
+    ghcp_auto_switch_strategy_param: Option<String>,
+    windsurf_auto_switch_enabled: Option<bool>,
+    windsurf_auto_switch_threshold: Option<i32>,
+    windsurf_auto_switch_strategy: Option<String>,
+    windsurf_auto_switch_strategy_param: Option<String>,
+    kiro_auto_switch_enabled: Option<bool>,
+    kiro_auto_switch_threshold: Option<i32>,
+    kiro_auto_switch_strategy: Option<String>,
+    kiro_auto_switch_strategy_param: Option<String>,
+    trash_retention_days: Option<i32>,
 ) -> Result<(), String> {
     let current = config::get_user_config();
     let normalized_opencode_path = opencode_app_path.trim().to_string();
@@ -337,6 +493,46 @@ pub fn save_general_config(
         codex_launch_on_switch,
         auto_switch_enabled: auto_switch_enabled.unwrap_or(current.auto_switch_enabled),
         auto_switch_threshold: auto_switch_threshold.unwrap_or(current.auto_switch_threshold),
+        auto_switch_strategy: auto_switch_strategy.unwrap_or(current.auto_switch_strategy),
+        auto_switch_strategy_param: auto_switch_strategy_param
+            .unwrap_or(current.auto_switch_strategy_param),
+        watched_groups: watched_groups.unwrap_or(current.watched_groups),
+        quota_protection_enabled: quota_protection_enabled
+            .unwrap_or(current.quota_protection_enabled),
+        quota_protection_reserve: quota_protection_reserve
+            .unwrap_or(current.quota_protection_reserve),
+        codex_auto_switch_enabled: codex_auto_switch_enabled
+            .unwrap_or(current.codex_auto_switch_enabled),
+        codex_auto_switch_threshold: codex_auto_switch_threshold
+            .unwrap_or(current.codex_auto_switch_threshold),
+        codex_auto_switch_strategy: codex_auto_switch_strategy
+            .unwrap_or(current.codex_auto_switch_strategy),
+        codex_auto_switch_strategy_param: codex_auto_switch_strategy_param
+            .unwrap_or(current.codex_auto_switch_strategy_param),
+        ghcp_auto_switch_enabled: ghcp_auto_switch_enabled
+            .unwrap_or(current.ghcp_auto_switch_enabled),
+        ghcp_auto_switch_threshold: ghcp_auto_switch_threshold
+            .unwrap_or(current.ghcp_auto_switch_threshold),
+        ghcp_auto_switch_strategy: ghcp_auto_switch_strategy
+            .unwrap_or(current.ghcp_auto_switch_strategy),
+        ghcp_auto_switch_strategy_param: ghcp_auto_switch_strategy_param
+            .unwrap_or(current.ghcp_auto_switch_strategy_param),
+        windsurf_auto_switch_enabled: windsurf_auto_switch_enabled
+            .unwrap_or(current.windsurf_auto_switch_enabled),
+        windsurf_auto_switch_threshold: windsurf_auto_switch_threshold
+            .unwrap_or(current.windsurf_auto_switch_threshold),
+        windsurf_auto_switch_strategy: windsurf_auto_switch_strategy
+            .unwrap_or(current.windsurf_auto_switch_strategy),
+        windsurf_auto_switch_strategy_param: windsurf_auto_switch_strategy_param
+            .unwrap_or(current.windsurf_auto_switch_strategy_param),
+        kiro_auto_switch_enabled: kiro_auto_switch_enabled
+            .unwrap_or(current.kiro_auto_switch_enabled),
+        kiro_auto_switch_threshold: kiro_auto_switch_threshold
+            .unwrap_or(current.kiro_auto_switch_threshold),
+        kiro_auto_switch_strategy: kiro_auto_switch_strategy
+            .unwrap_or(current.kiro_auto_switch_strategy),
+        kiro_auto_switch_strategy_param: kiro_auto_switch_strategy_param
+            .unwrap_or(current.kiro_auto_switch_strategy_param),
         quota_alert_enabled: quota_alert_enabled.unwrap_or(current.quota_alert_enabled),
         quota_alert_threshold: quota_alert_threshold.unwrap_or(current.quota_alert_threshold),
         codex_quota_alert_enabled: codex_quota_alert_enabled
@@ -355,6 +551,11 @@ pub fn save_general_config(
             .unwrap_or(current.kiro_quota_alert_enabled),
         kiro_quota_alert_threshold: kiro_quota_alert_threshold
             .unwrap_or(current.kiro_quota_alert_threshold),
+        // 保留本地 API 代理设置不变
+        proxy_enabled: current.proxy_enabled,
+        proxy_port: current.proxy_port,
+        proxy_api_key: current.proxy_api_key,
+        trash_retention_days: trash_retention_days.unwrap_or(current.trash_retention_days),
     };
 
     config::save_user_config(&new_config)?;
@@ -434,6 +635,13 @@ pub fn set_wakeup_override(enabled: bool) -> Result<(), String> {
     Ok(())
 }
 
+/// 获取后台配额刷新调度状态（各平台下一次刷新时间）
+#[tauri::command]
+pub fn get_refresh_scheduler_status(
+) -> Result<Vec<modules::refresh_scheduler::RefreshPlatformStatus>, String> {
+    Ok(modules::refresh_scheduler::get_status())
+}
+
 /// 执行窗口关闭操作
 /// action: "minimize" | "quit"
 /// remember: 是否记住选择
@@ -523,6 +731,11 @@ pub async fn open_folder(path: String) -> Result<(), String> {
 /// 删除损坏的文件（会先备份）
 #[tauri::command]
 pub async fn delete_corrupted_file(path: String) -> Result<(), String> {
+    // 数据库中的文档（账号索引、实例配置）在库内改名隔离，不能移走整个数据库文件
+    if modules::data_store::quarantine_document(&path)?.is_some() {
+        return Ok(());
+    }
+
     let file_path = std::path::Path::new(&path);
 
     if !file_path.exists() {
@@ -544,3 +757,21 @@ pub async fn delete_corrupted_file(path: String) -> Result<(), String> {
 
     Ok(())
 }
+
+/// 从快照或损坏内容中恢复文件（损坏内容会先备份）
+#[tauri::command]
+pub async fn recover_corrupted_file(
+    path: String,
+) -> Result<modules::recovery::RecoveryResult, String> {
+    modules::recovery::recover(&path)
+}
+
+/// 将数据库中的账号与实例数据按旧版 JSON 目录结构导出，供外部工具读取
+#[tauri::command]
+pub async fn export_data_store_json(target_dir: String) -> Result<usize, String> {
+    let target = target_dir.trim();
+    if target.is_empty() {
+        return Err("导出目录不能为空".to_string());
+    }
+    modules::data_store::export_json_layout(std::path::Path::new(target))
+}
@@ -0,0 +1,30 @@
+//! 账号回收站相关命令
+
+use crate::modules::data_store::AccountPlatform;
+use crate::modules::trash::{self, TrashEntry};
+
+fn parse_platform(platform: &str) -> Result<AccountPlatform, String> {
+    AccountPlatform::parse(platform.trim()).ok_or_else(|| format!("未知平台: {}", platform))
+}
+
+/// 列出回收站中的账号（不传平台时列出全部平台）
+#[tauri::command]
+pub fn list_account_trash(platform: Option<String>) -> Result<Vec<TrashEntry>, String> {
+    let platform = platform.as_deref().map(parse_platform).transpose()?;
+    trash::list(platform)
+}
+
+/// 从回收站恢复账号，返回恢复的条数
+#[tauri::command]
+pub fn restore_trashed_accounts(
+    platform: String,
+    account_ids: Vec<String>,
+) -> Result<usize, String> {
+    trash::restore(parse_platform(&platform)?, &account_ids)
+}
+
+/// 彻底删除回收站中的账号，返回删除的条数
+#[tauri::command]
+pub fn purge_account_trash(platform: String, account_ids: Vec<String>) -> Result<usize, String> {
+    trash::purge(parse_platform(&platform)?, &account_ids)
+}
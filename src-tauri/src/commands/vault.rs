@@ -0,0 +1,45 @@
+//! 账号凭据加密存储相关命令
+
+use crate::modules::vault::{self, KeyProviderKind, VaultStatus};
+use crate::modules::websocket;
+
+/// 获取凭据库状态
+#[tauri::command]
+pub fn get_vault_status() -> Result<VaultStatus, String> {
+    Ok(vault::get_status())
+}
+
+/// 启用凭据库并加密已有账号文件
+#[tauri::command]
+pub fn enable_vault(
+    provider: String,
+    passphrase: Option<String>,
+    key_file_path: Option<String>,
+) -> Result<VaultStatus, String> {
+    let provider = KeyProviderKind::parse(&provider)?;
+    let status = vault::enable(provider, passphrase.as_deref(), key_file_path.as_deref())?;
+    websocket::broadcast_data_changed("vault_enabled");
+    Ok(status)
+}
+
+/// 解锁凭据库（口令模式需传入口令）
+#[tauri::command]
+pub fn unlock_vault(passphrase: Option<String>) -> Result<VaultStatus, String> {
+    let status = vault::unlock(passphrase.as_deref())?;
+    websocket::broadcast_data_changed("vault_unlocked");
+    Ok(status)
+}
+
+/// 锁定凭据库
+#[tauri::command]
+pub fn lock_vault() -> Result<VaultStatus, String> {
+    Ok(vault::lock())
+}
+
+/// 停用凭据库并解密全部账号文件
+#[tauri::command]
+pub fn disable_vault() -> Result<VaultStatus, String> {
+    let status = vault::disable()?;
+    websocket::broadcast_data_changed("vault_disabled");
+    Ok(status)
+}
@@ -1,5 +1,4 @@
 use crate::modules;
-use tauri::AppHandle;
 
 #[tauri::command]
 pub async fn trigger_wakeup(
@@ -20,12 +19,11 @@ pub async fn fetch_available_models() -> Result<Vec<modules::wakeup::AvailableMo
 
 #[tauri::command]
 pub async fn wakeup_sync_state(
-    app: AppHandle,
     enabled: bool,
     tasks: Vec<modules::wakeup_scheduler::WakeupTaskInput>,
 ) -> Result<(), String> {
     modules::wakeup_scheduler::sync_state(enabled, tasks);
-    modules::wakeup_scheduler::ensure_started(app);
+    modules::wakeup_scheduler::ensure_started();
     Ok(())
 }
 
@@ -1,8 +1,10 @@
 use std::time::Instant;
-use tauri::{AppHandle, Emitter};
+use tauri::AppHandle;
 
 use crate::models::windsurf::{WindsurfAccount, WindsurfOAuthStartResponse};
-use crate::modules::{logger, windsurf_account, windsurf_oauth};
+use crate::modules::data_store::AccountPlatform;
+use crate::modules::import_plan::ImportOutcome;
+use crate::modules::{audit, logger, windsurf_account, windsurf_oauth};
 
 #[tauri::command]
 pub fn list_windsurf_accounts() -> Result<Vec<WindsurfAccount>, String> {
@@ -20,23 +22,33 @@ pub fn delete_windsurf_accounts(account_ids: Vec<String>) -> Result<(), String>
 }
 
 #[tauri::command]
-pub fn import_windsurf_from_json(json_content: String) -> Result<Vec<WindsurfAccount>, String> {
+pub fn import_windsurf_from_json(
+    json_content: String,
+) -> Result<ImportOutcome<WindsurfAccount>, String> {
     windsurf_account::import_from_json(&json_content)
 }
 
 #[tauri::command]
 pub async fn import_windsurf_from_local() -> Result<Vec<WindsurfAccount>, String> {
-    let auth_status = windsurf_account::read_local_auth_status()?.ok_or_else(|| {
-        "未在本机 Windsurf 客户端中找到登录信息（windsurfAuthStatus）".to_string()
-    })?;
-    let mut payload = windsurf_oauth::build_payload_from_local_auth_status(auth_status).await?;
-    if payload.github_login.trim().is_empty() {
-        if let Some(hint) = windsurf_account::read_local_login_hint() {
-            payload.github_login = hint;
+    let result = async {
+        let auth_status = windsurf_account::read_local_auth_status()?.ok_or_else(|| {
+            "未在本机 Windsurf 客户端中找到登录信息（windsurfAuthStatus）".to_string()
+        })?;
+        let mut payload = windsurf_oauth::build_payload_from_local_auth_status(auth_status).await?;
+        if payload.github_login.trim().is_empty() {
+            if let Some(hint) = windsurf_account::read_local_login_hint() {
+                payload.github_login = hint;
+            }
         }
+        windsurf_account::upsert_account(payload)
     }
-    let account = windsurf_account::upsert_account(payload)?;
-    Ok(vec![account])
+    .await;
+    audit::record_import(
+        AccountPlatform::Windsurf,
+        "local",
+        result.as_ref().map(|_| 1),
+    );
+    Ok(vec![result?])
 }
 
 #[tauri::command]
@@ -197,67 +209,9 @@ pub async fn inject_windsurf_to_vscode(
     app: AppHandle,
     account_id: String,
 ) -> Result<String, String> {
-    let started_at = Instant::now();
-    logger::log_info(&format!(
-        "[Windsurf Switch] 开始切换账号: account_id={}",
-        account_id
-    ));
-    let account = windsurf_account::load_account(&account_id)
-        .ok_or_else(|| format!("Windsurf account not found: {}", account_id))?;
-    logger::log_info(&format!(
-        "[Windsurf Switch] 目标账号信息: login={}, email={}",
-        account.github_login,
-        account.github_email.as_deref().unwrap_or("-")
-    ));
-
-    if let Err(e) = crate::modules::windsurf_instance::update_default_settings(
-        Some(Some(account_id.clone())),
-        None,
-        Some(false),
-    ) {
-        logger::log_warn(&format!("更新 Windsurf 默认实例绑定账号失败: {}", e));
-    }
-
-    let launch_warning = match crate::commands::windsurf_instance::windsurf_start_instance(
-        "__default__".to_string(),
-    )
-    .await
-    {
-        Ok(_) => None,
-        Err(e) => {
-            if e.starts_with("APP_PATH_NOT_FOUND:") || e.contains("启动 Windsurf 失败") {
-                logger::log_warn(&format!("Windsurf 默认实例启动失败: {}", e));
-                if e.starts_with("APP_PATH_NOT_FOUND:") || e.contains("APP_PATH_NOT_FOUND:") {
-                    let _ = app.emit(
-                        "app:path_missing",
-                        serde_json::json!({ "app": "windsurf", "retry": { "kind": "default" } }),
-                    );
-                }
-                Some(e)
-            } else {
-                return Err(e);
-            }
-        }
-    };
-
-    if let Some(err) = launch_warning {
+    let result = windsurf_account::switch_account_internal(&account_id).await;
+    if result.is_ok() {
         let _ = crate::modules::tray::update_tray_menu(&app);
-        logger::log_warn(&format!(
-            "[Windsurf Switch] 切号完成但启动失败: account_id={}, login={}, elapsed={}ms, error={}",
-            account.id,
-            account.github_login,
-            started_at.elapsed().as_millis(),
-            err
-        ));
-        Ok(format!("切换完成，但 Windsurf 启动失败: {}", err))
-    } else {
-        let _ = crate::modules::tray::update_tray_menu(&app);
-        logger::log_info(&format!(
-            "[Windsurf Switch] 切号成功: account_id={}, login={}, elapsed={}ms",
-            account.id,
-            account.github_login,
-            started_at.elapsed().as_millis()
-        ));
-        Ok(format!("切换完成: {}", account.github_login))
     }
+    result
 }
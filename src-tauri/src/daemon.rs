@@ -0,0 +1,81 @@
+//! 无窗口常驻模式（daemon）
+//!
+//! 不创建 Tauri 窗口与托盘，仅运行 WebSocket 服务、本地 API 代理、唤醒调度和配额刷新/预警循环，
+//! 适合作为 systemd 用户服务在无桌面环境的机器上运行。
+
+use crate::modules::{
+    api_proxy, config, logger, refresh_scheduler, startup, wakeup_scheduler, websocket,
+};
+use std::time::Duration;
+
+/// 重新读取配置文件的间隔（桌面端或命令行可能修改配置）
+const CONFIG_RELOAD_SECS: u64 = 60;
+
+/// daemon 入口，阻塞直到收到退出信号，返回进程退出码
+pub fn run() -> i32 {
+    logger::init_logger();
+    logger::log_info("[Daemon] 以无窗口模式启动");
+    if let Err(e) = startup::init_headless() {
+        logger::log_error(&format!("[Daemon] 初始化失败: {}", e));
+        return 1;
+    }
+
+    tauri::async_runtime::block_on(async {
+        match wakeup_scheduler::restore_persisted_state() {
+            Ok(count) => {
+                logger::log_info(&format!("[Daemon] 已恢复唤醒任务: {} 个", count));
+            }
+            Err(e) => {
+                logger::log_warn(&format!("[Daemon] 恢复唤醒任务失败: {}", e));
+            }
+        }
+
+        tauri::async_runtime::spawn(async {
+            websocket::start_server().await;
+        });
+        tauri::async_runtime::spawn(async {
+            api_proxy::start_server().await;
+        });
+        wakeup_scheduler::ensure_started();
+        refresh_scheduler::ensure_started();
+        tauri::async_runtime::spawn(run_config_reload_loop());
+
+        wait_for_shutdown_signal().await;
+    });
+
+    logger::log_info("[Daemon] 已退出");
+    0
+}
+
+async fn wait_for_shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = terminate.recv() => {}
+                }
+                logger::log_info("[Daemon] 收到退出信号");
+                return;
+            }
+            Err(e) => {
+                logger::log_warn(&format!("[Daemon] 注册 SIGTERM 失败: {}", e));
+            }
+        }
+    }
+
+    let _ = tokio::signal::ctrl_c().await;
+    logger::log_info("[Daemon] 收到退出信号");
+}
+
+/// 定期从磁盘重新加载配置，使刷新间隔、预警与自动切号设置在 daemon 中实时生效
+async fn run_config_reload_loop() {
+    loop {
+        tokio::time::sleep(Duration::from_secs(CONFIG_RELOAD_SECS)).await;
+        if let Err(e) = config::reload_user_config() {
+            logger::log_warn(&format!("[Daemon] 重新加载配置失败，沿用内存配置: {}", e));
+        }
+    }
+}
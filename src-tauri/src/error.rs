@@ -28,6 +28,13 @@ pub enum AppError {
         original_error: String,
     },
 
+    #[error("Migration {version} ({name}) failed: {message}")]
+    Migration {
+        version: u32,
+        name: String,
+        message: String,
+    },
+
     #[error("Unknown error: {0}")]
     Unknown(String),
 }
@@ -52,6 +59,18 @@ impl Serialize for AppError {
                 map.serialize_entry("original_error", original_error)?;
                 map.end()
             }
+            AppError::Migration {
+                version,
+                name,
+                message,
+            } => {
+                let mut map = serializer.serialize_map(Some(4))?;
+                map.serialize_entry("error_type", "migration_failed")?;
+                map.serialize_entry("version", version)?;
+                map.serialize_entry("name", name)?;
+                map.serialize_entry("message", message)?;
+                map.end()
+            }
             _ => serializer.serialize_str(self.to_string().as_str()),
         }
     }
@@ -1,4 +1,6 @@
+pub mod cli;
 mod commands;
+mod daemon;
 pub mod error;
 mod models;
 mod modules;
@@ -95,11 +97,23 @@ pub fn run() {
                 }
             }
 
+            // 打开本地数据库（首次启动时导入旧版 JSON 数据）、自动解锁凭据库并执行数据迁移，
+            // 需在读取账号数据的后台服务启动前完成
+            modules::startup::init_desktop();
+
             // 启动 WebSocket 服务（使用 Tauri 的 async runtime）
             tauri::async_runtime::spawn(async {
                 modules::websocket::start_server().await;
             });
 
+            // 启动本地 API 代理（未启用时直接返回）
+            tauri::async_runtime::spawn(async {
+                modules::api_proxy::start_server().await;
+            });
+
+            // 启动后台配额刷新调度（不依赖前端定时器）
+            modules::refresh_scheduler::ensure_started();
+
             #[cfg(target_os = "macos")]
             apply_macos_activation_policy(&app.handle());
 
@@ -186,10 +200,14 @@ pub fn run() {
             commands::import::import_from_local,
             commands::import::import_from_json,
             commands::import::export_accounts,
+            commands::import::export_interchange_accounts,
+            commands::import::preview_interchange_import,
+            commands::import::import_interchange_accounts,
             // System Commands
             commands::system::open_data_folder,
             commands::system::save_text_file,
             commands::system::get_downloads_dir,
+            commands::system::get_startup_migration_error,
             commands::system::get_network_config,
             commands::system::save_network_config,
             commands::system::get_general_config,
@@ -198,9 +216,12 @@ pub fn run() {
             commands::system::set_app_path,
             commands::system::detect_app_path,
             commands::system::set_wakeup_override,
+            commands::system::get_refresh_scheduler_status,
             commands::system::handle_window_close,
             commands::system::open_folder,
             commands::system::delete_corrupted_file,
+            commands::system::recover_corrupted_file,
+            commands::system::export_data_store_json,
             // Wakeup Commands
             commands::wakeup::trigger_wakeup,
             commands::wakeup::fetch_available_models,
@@ -213,6 +234,28 @@ pub fn run() {
             commands::update::update_last_check_time,
             commands::update::get_update_settings,
             commands::update::save_update_settings,
+            // Vault Commands
+            commands::vault::get_vault_status,
+            commands::vault::enable_vault,
+            commands::vault::unlock_vault,
+            commands::vault::lock_vault,
+            commands::vault::disable_vault,
+            // Backup Commands
+            commands::backup::create_data_backup,
+            commands::backup::preview_data_backup,
+            commands::backup::restore_data_backup,
+            // Audit Commands
+            commands::audit::query_audit_log,
+            commands::audit::export_audit_log,
+            // Trash Commands
+            commands::trash::list_account_trash,
+            commands::trash::restore_trashed_accounts,
+            commands::trash::purge_account_trash,
+            // Quota History Commands
+            commands::quota_history::get_quota_history,
+            commands::quota_history::get_quota_forecasts,
+            commands::quota_history::get_upcoming_quota_resets,
+            commands::quota_history::export_quota_reset_calendar,
             // Group Commands
             commands::group::get_group_settings,
             commands::group::save_group_settings,
@@ -27,6 +27,9 @@ pub struct Account {
     /// 受配额保护禁用的模型列表
     #[serde(default, skip_serializing_if = "HashSet::is_empty")]
     pub protected_models: HashSet<String>,
+    /// 由配额保留策略自动加入 `protected_models` 的模型，关闭策略或配额恢复时只移除这些
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    pub reserve_protected_models: HashSet<String>,
     /// 最近一次配额错误信息
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub quota_error: Option<QuotaErrorInfo>,
@@ -53,6 +56,7 @@ impl Account {
             disabled_reason: None,
             disabled_at: None,
             protected_models: HashSet::new(),
+            reserve_protected_models: HashSet::new(),
             quota_error: None,
             created_at: now,
             last_used: now,
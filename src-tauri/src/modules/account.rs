@@ -11,6 +11,12 @@ use crate::models::{
     QuotaErrorInfo, TokenData,
 };
 use crate::modules;
+use crate::modules::audit::{self, AuditAction, AuditEvent, AuditSource};
+use crate::modules::data_store;
+use crate::modules::quota_history;
+use crate::modules::quota_meter::PlatformQuota;
+use crate::modules::quota_watch;
+use crate::modules::refresh_plan;
 
 static ACCOUNT_INDEX_LOCK: std::sync::LazyLock<Mutex<()>> =
     std::sync::LazyLock::new(|| Mutex::new(()));
@@ -22,8 +28,7 @@ const QUOTA_ALERT_COOLDOWN_SECONDS: i64 = 300;
 
 // 使用与 AntigravityCockpit 插件相同的数据目录
 const DATA_DIR: &str = ".antigravity_cockpit";
-const ACCOUNTS_INDEX: &str = "accounts.json";
-const ACCOUNTS_DIR: &str = "accounts";
+const PLATFORM: data_store::AccountPlatform = data_store::AccountPlatform::Antigravity;
 
 /// 获取数据目录路径
 pub fn get_data_dir() -> Result<PathBuf, String> {
@@ -37,29 +42,12 @@ pub fn get_data_dir() -> Result<PathBuf, String> {
     Ok(data_dir)
 }
 
-/// 获取账号目录路径
-pub fn get_accounts_dir() -> Result<PathBuf, String> {
-    let data_dir = get_data_dir()?;
-    let accounts_dir = data_dir.join(ACCOUNTS_DIR);
-
-    if !accounts_dir.exists() {
-        fs::create_dir_all(&accounts_dir).map_err(|e| format!("创建账号目录失败: {}", e))?;
-    }
-
-    Ok(accounts_dir)
-}
-
 /// 加载账号索引
 pub fn load_account_index() -> Result<AccountIndex, String> {
-    let data_dir = get_data_dir()?;
-    let index_path = data_dir.join(ACCOUNTS_INDEX);
-
-    if !index_path.exists() {
+    let index_name = PLATFORM.index_name();
+    let Some(content) = data_store::load_document(index_name)? else {
         return Ok(AccountIndex::new());
-    }
-
-    let content =
-        fs::read_to_string(&index_path).map_err(|e| format!("读取账号索引失败: {}", e))?;
+    };
 
     if content.trim().is_empty() {
         return Ok(AccountIndex::new());
@@ -67,8 +55,8 @@ pub fn load_account_index() -> Result<AccountIndex, String> {
 
     serde_json::from_str(&content).map_err(|e| {
         crate::error::file_corrupted_error(
-            ACCOUNTS_INDEX,
-            &index_path.to_string_lossy(),
+            index_name,
+            &data_store::document_location(index_name),
             &e.to_string(),
         )
     })
@@ -76,42 +64,27 @@ pub fn load_account_index() -> Result<AccountIndex, String> {
 
 /// 保存账号索引
 pub fn save_account_index(index: &AccountIndex) -> Result<(), String> {
-    let data_dir = get_data_dir()?;
-    let index_path = data_dir.join(ACCOUNTS_INDEX);
-    let temp_path = data_dir.join(format!("{}.tmp", ACCOUNTS_INDEX));
-
     let content =
         serde_json::to_string_pretty(index).map_err(|e| format!("序列化账号索引失败: {}", e))?;
 
-    fs::write(&temp_path, content).map_err(|e| format!("写入临时索引文件失败: {}", e))?;
-
-    fs::rename(temp_path, index_path).map_err(|e| format!("替换索引文件失败: {}", e))
+    data_store::save_document(PLATFORM.index_name(), &content)
+        .map_err(|e| format!("保存账号索引失败: {}", e))
 }
 
 /// 加载账号数据
 pub fn load_account(account_id: &str) -> Result<Account, String> {
-    let accounts_dir = get_accounts_dir()?;
-    let account_path = accounts_dir.join(format!("{}.json", account_id));
-
-    if !account_path.exists() {
-        return Err(format!("账号不存在: {}", account_id));
-    }
-
-    let content =
-        fs::read_to_string(&account_path).map_err(|e| format!("读取账号数据失败: {}", e))?;
+    let content = data_store::load_account(PLATFORM, account_id)?
+        .ok_or_else(|| format!("账号不存在: {}", account_id))?;
 
     serde_json::from_str(&content).map_err(|e| format!("解析账号数据失败: {}", e))
 }
 
 /// 保存账号数据
 pub fn save_account(account: &Account) -> Result<(), String> {
-    let accounts_dir = get_accounts_dir()?;
-    let account_path = accounts_dir.join(format!("{}.json", account.id));
-
     let content =
         serde_json::to_string_pretty(account).map_err(|e| format!("序列化账号数据失败: {}", e))?;
 
-    fs::write(&account_path, content).map_err(|e| format!("保存账号数据失败: {}", e))
+    data_store::save_account(PLATFORM, &account.id, &content)
 }
 
 fn normalize_tags(tags: Vec<String>) -> Result<Vec<String>, String> {
@@ -141,29 +114,32 @@ fn normalize_tags(tags: Vec<String>) -> Result<Vec<String>, String> {
 
 /// 更新账号标签
 pub fn update_account_tags(account_id: &str, tags: Vec<String>) -> Result<Account, String> {
-    let mut account = load_account(account_id)?;
-    let normalized = normalize_tags(tags)?;
-    account.tags = normalized;
-    save_account(&account)?;
-    Ok(account)
+    let result = (|| -> Result<Account, String> {
+        let mut account = load_account(account_id)?;
+        account.tags = normalize_tags(tags)?;
+        save_account(&account)?;
+        Ok(account)
+    })();
+    let event = AuditEvent::new(PLATFORM, AuditAction::Tag).account(account_id);
+    match &result {
+        Ok(account) => event.detail(account.tags.join(", ")),
+        Err(_) => event,
+    }
+    .result(&result)
+    .record();
+    result
 }
 
 /// 列出所有账号
 pub fn list_accounts() -> Result<Vec<Account>, String> {
     modules::logger::log_info("开始列出账号...");
     let index = load_account_index()?;
-    let mut accounts = Vec::new();
-
-    for summary in &index.accounts {
-        match load_account(&summary.id) {
-            Ok(mut account) => {
-                let _ = modules::quota_cache::apply_cached_quota(&mut account, "authorized");
-                accounts.push(account);
-            }
-            Err(e) => {
-                modules::logger::log_error(&format!("加载账号失败: {}", e));
-            }
-        }
+    let mut accounts: Vec<Account> = data_store::load_indexed_accounts(
+        PLATFORM,
+        index.accounts.iter().map(|summary| summary.id.as_str()),
+    );
+    for account in &mut accounts {
+        let _ = modules::quota_cache::apply_cached_quota(account, "authorized");
     }
 
     Ok(accounts)
@@ -197,7 +173,7 @@ fn is_strict_account_identity_match(existing: &Account, email: &str, token: &Tok
     false
 }
 
-fn find_matching_account_id(
+pub(crate) fn find_matching_account_id(
     index: &AccountIndex,
     email: &str,
     token: &TokenData,
@@ -335,6 +311,13 @@ pub fn upsert_account(
 
 /// 删除账号
 pub fn delete_account(account_id: &str) -> Result<(), String> {
+    let event = AuditEvent::new(PLATFORM, AuditAction::Delete).account(account_id);
+    let result = delete_account_inner(account_id);
+    event.result(&result).record();
+    result
+}
+
+fn delete_account_inner(account_id: &str) -> Result<(), String> {
     let _lock = ACCOUNT_INDEX_LOCK
         .lock()
         .map_err(|e| format!("获取锁失败: {}", e))?;
@@ -351,45 +334,78 @@ pub fn delete_account(account_id: &str) -> Result<(), String> {
         index.current_account_id = index.accounts.first().map(|s| s.id.clone());
     }
 
-    save_account_index(&index)?;
-
-    let accounts_dir = get_accounts_dir()?;
-    let account_path = accounts_dir.join(format!("{}.json", account_id));
-
-    if account_path.exists() {
-        fs::remove_file(&account_path).map_err(|e| format!("删除账号文件失败: {}", e))?;
-    }
-
-    Ok(())
+    let content =
+        serde_json::to_string_pretty(&index).map_err(|e| format!("序列化账号索引失败: {}", e))?;
+    data_store::transaction(|tx| {
+        tx.save_document(PLATFORM.index_name(), &content)?;
+        tx.trash_account(PLATFORM, account_id).map(|_| ())
+    })
 }
 
 /// 批量删除账号
 pub fn delete_accounts(account_ids: &[String]) -> Result<(), String> {
+    let events: Vec<AuditEvent> = account_ids
+        .iter()
+        .map(|id| AuditEvent::new(PLATFORM, AuditAction::Delete).account(id))
+        .collect();
+    let result = delete_accounts_inner(account_ids);
+    for event in events {
+        event.result(&result).record();
+    }
+    result
+}
+
+fn delete_accounts_inner(account_ids: &[String]) -> Result<(), String> {
     let _lock = ACCOUNT_INDEX_LOCK
         .lock()
         .map_err(|e| format!("获取锁失败: {}", e))?;
     let mut index = load_account_index()?;
 
-    let accounts_dir = get_accounts_dir()?;
-
     for account_id in account_ids {
         index.accounts.retain(|s| &s.id != account_id);
 
         if index.current_account_id.as_deref() == Some(account_id) {
             index.current_account_id = None;
         }
-
-        let account_path = accounts_dir.join(format!("{}.json", account_id));
-        if account_path.exists() {
-            let _ = fs::remove_file(&account_path);
-        }
     }
 
     if index.current_account_id.is_none() {
         index.current_account_id = index.accounts.first().map(|s| s.id.clone());
     }
 
-    save_account_index(&index)
+    let content =
+        serde_json::to_string_pretty(&index).map_err(|e| format!("序列化账号索引失败: {}", e))?;
+    data_store::transaction(|tx| {
+        tx.save_document(PLATFORM.index_name(), &content)?;
+        for account_id in account_ids {
+            tx.trash_account(PLATFORM, account_id)?;
+        }
+        Ok(())
+    })
+}
+
+/// 从回收站恢复账号并重新加入账号列表
+pub fn restore_account(account_id: &str) -> Result<Account, String> {
+    let _lock = ACCOUNT_INDEX_LOCK
+        .lock()
+        .map_err(|e| format!("获取锁失败: {}", e))?;
+    let mut index = load_account_index()?;
+    data_store::restore_trashed_account(PLATFORM, account_id, |tx, content| {
+        let account: Account =
+            serde_json::from_str(content).map_err(|e| format!("解析账号数据失败: {}", e))?;
+        index.accounts.retain(|s| s.id != account.id);
+        index.accounts.push(AccountSummary {
+            id: account.id.clone(),
+            email: account.email.clone(),
+            name: account.name.clone(),
+            created_at: account.created_at,
+            last_used: account.last_used,
+        });
+        let content = serde_json::to_string_pretty(&index)
+            .map_err(|e| format!("序列化账号索引失败: {}", e))?;
+        tx.save_document(PLATFORM.index_name(), &content)?;
+        Ok(account)
+    })
 }
 
 /// 重新排序账号列表
@@ -480,6 +496,71 @@ fn save_current_account_file(email: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// 从本地客户端同步当前账号状态
+/// 读取本地 state.vscdb 中的 refresh_token，与 Tools 账号列表对比
+/// 如匹配账号与当前账号不同，则静默更新 current_account_id
+pub fn sync_current_from_client() -> Result<Option<String>, String> {
+    use base64::{engine::general_purpose, Engine as _};
+
+    // 读取本地数据库中的 refresh_token
+    let db_path = modules::db::get_db_path()?;
+    let conn =
+        rusqlite::Connection::open(&db_path).map_err(|e| format!("打开数据库失败: {}", e))?;
+
+    let state_data: Result<String, _> = conn.query_row(
+        "SELECT value FROM ItemTable WHERE key = ?",
+        ["jetskiStateSync.agentManagerInitState"],
+        |row| row.get(0),
+    );
+
+    let state_data = match state_data {
+        Ok(data) => data,
+        Err(_) => {
+            // 未找到登录状态，可能客户端未登录
+            return Ok(None);
+        }
+    };
+
+    // Base64 解码
+    let blob = general_purpose::STANDARD
+        .decode(&state_data)
+        .map_err(|e| format!("Base64 解码失败: {}", e))?;
+
+    // 提取 refresh_token
+    let local_refresh_token = match crate::utils::protobuf::extract_refresh_token(&blob) {
+        Some(token) if !token.is_empty() => token,
+        _ => return Ok(None),
+    };
+
+    // 获取当前 Tools 记录的账号 ID
+    let current_account_id = get_current_account_id().ok().flatten();
+
+    // 遍历账号列表，查找匹配的 refresh_token
+    let accounts = list_accounts()?;
+
+    for account in &accounts {
+        if account.token.refresh_token == local_refresh_token {
+            // 找到匹配账号
+            if current_account_id.as_ref() != Some(&account.id) {
+                // 当前账号不一致，静默更新
+                modules::logger::log_info(&format!(
+                    "[SyncClient] 检测到客户端账号变更，同步至: {}",
+                    account.email
+                ));
+                set_current_account_id(&account.id)?;
+                return Ok(Some(account.id.clone()));
+            } else {
+                // 已经是当前账号，无需操作
+                return Ok(None);
+            }
+        }
+    }
+
+    // 未找到匹配账号（可能是新账号，未导入到 Tools）
+    modules::logger::log_info("[SyncClient] 本地客户端账号未在 Tools 中找到");
+    Ok(None)
+}
+
 /// 更新账号配额
 pub fn update_account_quota(account_id: &str, quota: QuotaData) -> Result<(), String> {
     let mut account = load_account(account_id)?;
@@ -505,8 +586,18 @@ pub fn update_account_quota(account_id: &str, quota: QuotaData) -> Result<(), St
     }
 
     account.update_quota(quota);
+    let reserve = modules::quota_protection::configured_reserve();
+    if modules::quota_protection::apply_policy(&mut account, reserve, chrono::Utc::now()) {
+        let mut protected: Vec<&String> = account.protected_models.iter().collect();
+        protected.sort();
+        modules::logger::log_info(&format!(
+            "[QuotaProtection] 账号 {} 受保护模型更新: {:?}",
+            account.email, protected
+        ));
+    }
     save_account(&account)?;
     if let Some(ref quota) = account.quota {
+        quota_history::record(PLATFORM, &account.id, &quota.quota_meters());
         let _ = modules::quota_cache::write_quota_cache("authorized", &account.email, quota);
     }
     Ok(())
@@ -623,9 +714,20 @@ pub struct RefreshStats {
     pub details: Vec<String>,
 }
 
+/// 配额通知类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuotaAlertKind {
+    /// 当前账号配额低于预警阈值
+    Low,
+    /// 之前低于阈值的账号已重置恢复（见 `quota_reset`）
+    Replenished,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct QuotaAlertPayload {
     pub platform: String,
+    pub kind: QuotaAlertKind,
     pub current_account_id: String,
     pub current_email: String,
     pub threshold: i32,
@@ -644,7 +746,11 @@ fn normalize_quota_alert_threshold(raw: i32) -> i32 {
     raw.clamp(0, 100)
 }
 
-fn should_trigger_auto_switch(account: &Account, threshold: i32) -> bool {
+fn should_trigger_auto_switch(
+    account: &Account,
+    threshold: i32,
+    groups: Option<&[quota_watch::WatchGroup]>,
+) -> bool {
     if account.disabled {
         return true;
     }
@@ -657,10 +763,20 @@ fn should_trigger_auto_switch(account: &Account, threshold: i32) -> bool {
         return true;
     }
 
-    quota.models.iter().any(|m| m.percentage <= threshold)
+    match groups {
+        // 配置了关注分组时，未关注模型的配额耗尽不触发切号
+        Some(groups) => !quota_watch::low_groups(account, groups).is_empty(),
+        None => quota.models.iter().any(|m| m.percentage <= threshold),
+    }
 }
 
-fn can_be_auto_switch_candidate(account: &Account, current_id: &str, threshold: i32) -> bool {
+fn can_be_auto_switch_candidate(
+    account: &Account,
+    current_id: &str,
+    threshold: i32,
+    groups: Option<&[quota_watch::WatchGroup]>,
+    reserve_enabled: bool,
+) -> bool {
     if account.id == current_id || account.disabled {
         return false;
     }
@@ -673,7 +789,61 @@ fn can_be_auto_switch_candidate(account: &Account, current_id: &str, threshold:
         return false;
     }
 
-    quota.models.iter().all(|m| m.percentage >= threshold)
+    if let Some(groups) = groups {
+        // 每个关注分组都需有可用配额且不低于分组阈值
+        let quotas = quota_watch::group_quotas(account, groups, |name| {
+            !modules::quota_protection::is_model_protected(account, name, reserve_enabled)
+        });
+        return quotas.len() == groups.len()
+            && quotas.iter().all(|q| q.percentage >= q.group.threshold);
+    }
+
+    let mut usable = quota
+        .models
+        .iter()
+        .filter(|m| {
+            !modules::quota_protection::is_model_protected(account, &m.name, reserve_enabled)
+        })
+        .peekable();
+    // 全部模型都处于保留状态的账号不作为候选
+    if usable.peek().is_none() {
+        return false;
+    }
+    usable.all(|m| m.percentage >= threshold)
+}
+
+/// 自动切号选号使用的配额指标（跳过处于保留状态的模型；配置了关注分组时按分组计算）
+fn usable_quota_metrics(
+    account: &Account,
+    groups: Option<&[quota_watch::WatchGroup]>,
+    reserve_enabled: bool,
+) -> Vec<(String, i32)> {
+    if let Some(groups) = groups {
+        return quota_watch::group_quotas(account, groups, |name| {
+            !modules::quota_protection::is_model_protected(account, name, reserve_enabled)
+        })
+        .into_iter()
+        .map(|q| (q.group.id.clone(), q.percentage))
+        .collect();
+    }
+    account
+        .quota
+        .as_ref()
+        .map(|quota| {
+            quota
+                .models
+                .iter()
+                .filter(|m| {
+                    !modules::quota_protection::is_model_protected(
+                        account,
+                        &m.name,
+                        reserve_enabled,
+                    )
+                })
+                .map(|m| (m.name.clone(), m.percentage))
+                .collect()
+        })
+        .unwrap_or_default()
 }
 
 fn can_be_quota_alert_candidate(account: &Account, current_id: &str) -> bool {
@@ -692,15 +862,21 @@ fn can_be_quota_alert_candidate(account: &Account, current_id: &str) -> bool {
     true
 }
 
-fn average_quota_percentage(account: &Account) -> f64 {
-    let Some(quota) = account.quota.as_ref() else {
-        return 0.0;
-    };
-    if quota.models.is_empty() {
+fn average_quota_percentage(
+    account: &Account,
+    groups: Option<&[quota_watch::WatchGroup]>,
+    reserve_enabled: bool,
+) -> f64 {
+    // 受保护模型的剩余配额保留给手动使用，不参与候选评分
+    let usable: Vec<i32> = usable_quota_metrics(account, groups, reserve_enabled)
+        .into_iter()
+        .map(|(_, pct)| pct)
+        .collect();
+    if usable.is_empty() {
         return 0.0;
     }
-    let sum: i32 = quota.models.iter().map(|m| m.percentage).sum();
-    sum as f64 / quota.models.len() as f64
+    let sum: i32 = usable.iter().sum();
+    sum as f64 / usable.len() as f64
 }
 
 fn build_quota_alert_cooldown_key(account_id: &str, threshold: i32) -> String {
@@ -728,7 +904,12 @@ fn clear_quota_alert_cooldown(account_id: &str, threshold: i32) {
     }
 }
 
-fn pick_quota_alert_recommendation(accounts: &[Account], current_id: &str) -> Option<Account> {
+fn pick_quota_alert_recommendation(
+    accounts: &[Account],
+    current_id: &str,
+    groups: Option<&[quota_watch::WatchGroup]>,
+    reserve_enabled: bool,
+) -> Option<Account> {
     let mut candidates: Vec<Account> = accounts
         .iter()
         .filter(|a| can_be_quota_alert_candidate(a, current_id))
@@ -740,8 +921,8 @@ fn pick_quota_alert_recommendation(accounts: &[Account], current_id: &str) -> Op
     }
 
     candidates.sort_by(|a, b| {
-        let avg_a = average_quota_percentage(a);
-        let avg_b = average_quota_percentage(b);
+        let avg_a = average_quota_percentage(a, groups, reserve_enabled);
+        let avg_b = average_quota_percentage(b, groups, reserve_enabled);
         avg_b
             .partial_cmp(&avg_a)
             .unwrap_or(std::cmp::Ordering::Equal)
@@ -751,21 +932,34 @@ fn pick_quota_alert_recommendation(accounts: &[Account], current_id: &str) -> Op
     candidates.into_iter().next()
 }
 
+/// 通知中展示的平台名称
+pub(crate) fn platform_display_name(platform: &str) -> &'static str {
+    match platform {
+        "codex" => "Codex",
+        "github_copilot" => "GitHub Copilot",
+        "windsurf" => "Windsurf",
+        "kiro" => "Kiro",
+        _ => "Antigravity",
+    }
+}
+
 fn build_quota_alert_notification_text(payload: &QuotaAlertPayload) -> (String, String) {
-    let title = format!(
-        "{} 配额预警",
-        match payload.platform.as_str() {
-            "codex" => "Codex",
-            "github_copilot" => "GitHub Copilot",
-            "windsurf" => "Windsurf",
-            _ => "Antigravity",
-        }
-    );
+    let platform = platform_display_name(&payload.platform);
     let model_text = if payload.low_models.is_empty() {
         "未知模型".to_string()
     } else {
         payload.low_models.join(", ")
     };
+    if payload.kind == QuotaAlertKind::Replenished {
+        let title = format!("{} 配额已恢复", platform);
+        let body = format!(
+            "{} 已重置，可以继续使用（模型：{}，当前最低 {}%）",
+            payload.current_email, model_text, payload.lowest_percentage
+        );
+        return (title, body);
+    }
+
+    let title = format!("{} 配额预警", platform);
     let mut body = format!(
         "{} 低于 {}%（最低 {}%，模型：{}）",
         payload.current_email, payload.threshold, payload.lowest_percentage, model_text
@@ -850,10 +1044,21 @@ pub fn send_quota_alert_native_notification(payload: &QuotaAlertPayload) {
 }
 
 pub fn dispatch_quota_alert(payload: &QuotaAlertPayload) {
-    modules::logger::log_warn(&format!(
-        "[QuotaAlert] 触发配额预警: platform={}, current_id={}, threshold={}%, lowest={}%",
-        payload.platform, payload.current_account_id, payload.threshold, payload.lowest_percentage
-    ));
+    match payload.kind {
+        QuotaAlertKind::Low => modules::logger::log_warn(&format!(
+            "[QuotaAlert] 触发配额预警: platform={}, current_id={}, threshold={}%, lowest={}%",
+            payload.platform,
+            payload.current_account_id,
+            payload.threshold,
+            payload.lowest_percentage
+        )),
+        QuotaAlertKind::Replenished => modules::logger::log_info(&format!(
+            "[QuotaAlert] 配额已恢复: platform={}, account_id={}, models={}",
+            payload.platform,
+            payload.current_account_id,
+            payload.low_models.join(", ")
+        )),
+    }
 
     if let Some(app_handle) = crate::get_app_handle() {
         emit_quota_alert(app_handle, payload);
@@ -889,14 +1094,21 @@ pub fn run_quota_alert_if_needed() -> Result<Option<QuotaAlertPayload>, String>
         return Ok(None);
     };
 
-    let low_models: Vec<(String, i32)> = if quota.is_forbidden {
-        vec![("all".to_string(), 0)]
+    let groups = quota_watch::load_watch_groups(quota_watch::WatchPurpose::QuotaAlert, threshold);
+    // (名称, 剩余百分比, 触发阈值)；配置了关注分组时按分组汇总，名称为分组名
+    let low_models: Vec<(String, i32, i32)> = if quota.is_forbidden {
+        vec![("all".to_string(), 0, threshold)]
+    } else if let Some(groups) = groups.as_deref() {
+        quota_watch::low_groups(current, groups)
+            .into_iter()
+            .map(|q| (q.group.name.clone(), q.percentage, q.group.threshold))
+            .collect()
     } else {
         quota
             .models
             .iter()
             .filter(|model| model.percentage <= threshold)
-            .map(|model| (model.name.clone(), model.percentage))
+            .map(|model| (model.name.clone(), model.percentage, threshold))
             .collect()
     };
 
@@ -911,15 +1123,25 @@ pub fn run_quota_alert_if_needed() -> Result<Option<QuotaAlertPayload>, String>
         return Ok(None);
     }
 
-    let recommendation = pick_quota_alert_recommendation(&accounts, &current_id);
-    let lowest_percentage = low_models.iter().map(|(_, pct)| *pct).min().unwrap_or(0);
+    let recommendation = pick_quota_alert_recommendation(
+        &accounts,
+        &current_id,
+        groups.as_deref(),
+        modules::quota_protection::reserve_enabled(),
+    );
+    let (lowest_percentage, alert_threshold) = low_models
+        .iter()
+        .map(|(_, pct, limit)| (*pct, *limit))
+        .min()
+        .unwrap_or((0, threshold));
     let payload = QuotaAlertPayload {
         platform: "antigravity".to_string(),
+        kind: QuotaAlertKind::Low,
         current_account_id: current_id.clone(),
         current_email: current.email.clone(),
-        threshold,
+        threshold: alert_threshold,
         lowest_percentage,
-        low_models: low_models.into_iter().map(|(name, _)| name).collect(),
+        low_models: low_models.into_iter().map(|(name, _, _)| name).collect(),
         recommended_account_id: recommendation.as_ref().map(|acc| acc.id.clone()),
         recommended_email: recommendation.as_ref().map(|acc| acc.email.clone()),
         triggered_at: now,
@@ -946,39 +1168,61 @@ async fn run_auto_switch_if_needed_inner() -> Result<Option<Account>, String> {
         None => return Ok(None),
     };
 
-    if !should_trigger_auto_switch(current, threshold) {
+    let groups = quota_watch::load_watch_groups(quota_watch::WatchPurpose::AutoSwitch, threshold);
+    if !should_trigger_auto_switch(current, threshold, groups.as_deref()) {
         return Ok(None);
     }
 
-    let mut candidates: Vec<Account> = accounts
-        .into_iter()
-        .filter(|a| can_be_auto_switch_candidate(a, &current_id, threshold))
+    let strategy = modules::auto_switch::SelectionStrategy::from_config(
+        &cfg.auto_switch_strategy,
+        &cfg.auto_switch_strategy_param,
+    );
+    let reserve_enabled = modules::quota_protection::reserve_enabled();
+    let candidates: Vec<modules::auto_switch::SwitchCandidate> = accounts
+        .iter()
+        .map(|a| modules::auto_switch::SwitchCandidate {
+            id: a.id.clone(),
+            metrics: usable_quota_metrics(a, groups.as_deref(), reserve_enabled),
+            last_used: a.last_used,
+            tags: a.tags.clone(),
+            eligible: can_be_auto_switch_candidate(
+                a,
+                &current_id,
+                threshold,
+                groups.as_deref(),
+                reserve_enabled,
+            ),
+        })
         .collect();
 
-    if candidates.is_empty() {
+    let Some(target) = modules::auto_switch::pick_candidate(&candidates, &current_id, &strategy)
+    else {
         modules::logger::log_warn(&format!(
-            "[AutoSwitch] 当前账号低于阈值 {}%，但没有可切换候选账号",
-            threshold
+            "[AutoSwitch] 当前账号低于阈值 {}%，但没有可切换候选账号: strategy={}",
+            threshold,
+            strategy.as_str()
         ));
+        audit::auto_switch_event(PLATFORM, &current_id, threshold, strategy.as_str())
+            .account(&current_id)
+            .skipped()
+            .record();
         return Ok(None);
-    }
-
-    candidates.sort_by(|a, b| {
-        let avg_a = average_quota_percentage(a);
-        let avg_b = average_quota_percentage(b);
-        avg_b
-            .partial_cmp(&avg_a)
-            .unwrap_or(std::cmp::Ordering::Equal)
-            .then_with(|| a.last_used.cmp(&b.last_used))
-    });
+    };
 
-    let target = &candidates[0];
     modules::logger::log_info(&format!(
-        "[AutoSwitch] 触发自动切号: current_id={}, target_id={}, threshold={}%",
-        current_id, target.id, threshold
+        "[AutoSwitch] 触发自动切号: current_id={}, target_id={}, threshold={}%, strategy={}",
+        current_id,
+        target.id,
+        threshold,
+        strategy.as_str()
     ));
 
-    let switched = switch_account_internal(&target.id).await?;
+    let result = switch_account_internal(&target.id).await;
+    audit::auto_switch_event(PLATFORM, &current_id, threshold, strategy.as_str())
+        .account(&target.id)
+        .result(&result)
+        .record();
+    let switched = result?;
     modules::websocket::broadcast_account_switched(&switched.id, &switched.email);
     modules::websocket::broadcast_data_changed("auto_switch");
     Ok(Some(switched))
@@ -990,13 +1234,23 @@ pub async fn run_auto_switch_if_needed() -> Result<Option<Account>, String> {
         return Ok(None);
     }
 
-    let result = run_auto_switch_if_needed_inner().await;
+    let result =
+        audit::with_source(AuditSource::AutoSwitch, run_auto_switch_if_needed_inner()).await;
     AUTO_SWITCH_IN_PROGRESS.store(false, Ordering::SeqCst);
     result
 }
 
 /// 批量刷新所有账号配额
 pub async fn refresh_all_quotas_logic() -> Result<RefreshStats, String> {
+    refresh_quotas_logic(false).await
+}
+
+/// 定时刷新：只刷新按刷新计划已到期的账号（见 `refresh_plan`）
+pub async fn refresh_due_quotas_logic() -> Result<RefreshStats, String> {
+    refresh_quotas_logic(true).await
+}
+
+async fn refresh_quotas_logic(only_due: bool) -> Result<RefreshStats, String> {
     use futures::future::join_all;
     use std::sync::Arc;
     use tokio::sync::Semaphore;
@@ -1009,6 +1263,9 @@ pub async fn refresh_all_quotas_logic() -> Result<RefreshStats, String> {
         MAX_CONCURRENT
     ));
     let accounts = list_accounts()?;
+    refresh_plan::retain(PLATFORM, |id| {
+        accounts.iter().any(|account| account.id == id)
+    });
 
     let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT));
 
@@ -1017,15 +1274,17 @@ pub async fn refresh_all_quotas_logic() -> Result<RefreshStats, String> {
         .filter(|account| {
             if account.disabled {
                 modules::logger::log_info("  - Skipping Disabled account");
+                refresh_plan::remove(PLATFORM, &account.id);
                 return false;
             }
             if let Some(ref q) = account.quota {
                 if q.is_forbidden {
                     modules::logger::log_info("  - Skipping Forbidden account");
+                    refresh_plan::remove(PLATFORM, &account.id);
                     return false;
                 }
             }
-            true
+            !only_due || refresh_plan::is_due(PLATFORM, &account.id)
         })
         .map(|mut account| {
             let email = account.email.clone();
@@ -1035,14 +1294,18 @@ pub async fn refresh_all_quotas_logic() -> Result<RefreshStats, String> {
                 let _guard = permit.acquire().await.unwrap();
                 match fetch_quota_with_retry(&mut account, false).await {
                     Ok(quota) => {
+                        let meters = quota.quota_meters();
                         if let Err(e) = update_account_quota(&account_id, quota) {
+                            refresh_plan::record_failure(PLATFORM, &account_id);
                             let msg = format!("Account {}: Save quota failed - {}", email, e);
                             Err(msg)
                         } else {
+                            refresh_plan::record_success(PLATFORM, &account_id, &meters);
                             Ok(())
                         }
                     }
                     Err(e) => {
+                        refresh_plan::record_failure(PLATFORM, &account_id);
                         let msg = format!("Account {}: Fetch quota failed - {}", email, e);
                         Err(msg)
                     }
@@ -1144,6 +1407,15 @@ pub async fn fetch_quota_with_retry(
 /// 内部切换账号函数（供 WebSocket 调用）
 /// 完整流程：Token刷新 + 关闭程序 + 注入 + 指纹同步 + 重启
 pub async fn switch_account_internal(account_id: &str) -> Result<Account, String> {
+    let result = switch_account_steps(account_id).await;
+    AuditEvent::new(PLATFORM, AuditAction::Switch)
+        .account(account_id)
+        .result(&result)
+        .record();
+    result
+}
+
+async fn switch_account_steps(account_id: &str) -> Result<Account, String> {
     modules::logger::log_info("[Switch] 开始切换账号");
 
     // 1. 加载并验证账号存在
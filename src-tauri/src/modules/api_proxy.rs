@@ -0,0 +1,645 @@
+//! 本地 API 代理模块
+//! 在 127.0.0.1 上提供 OpenAI / Anthropic 兼容接口，将请求转发到 Cloud Code（与唤醒共用 streamGenerateContent），
+//! 并按 `QuotaData` 中的剩余配额（同分组模型共享配额）从账号池中选号
+
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::models::Account;
+use crate::modules::{
+    self, api_proxy_anthropic, api_proxy_openai, config, group_settings, logger, quota_protection,
+    wakeup,
+};
+
+/// 请求头最大长度
+const MAX_HEADER_BYTES: usize = 64 * 1024;
+/// 请求体最大长度
+const MAX_BODY_BYTES: usize = 32 * 1024 * 1024;
+/// 单个请求最多尝试的账号数（账号级错误时换号）
+const MAX_ACCOUNT_ATTEMPTS: usize = 3;
+/// 上游请求超时（秒），需覆盖完整的流式输出
+const UPSTREAM_TIMEOUT_SECS: u64 = 600;
+
+static RUNNING_PORT: OnceLock<Mutex<Option<u16>>> = OnceLock::new();
+/// 账号最近一次被代理选中的时间，用于配额相同的账号之间轮换
+static LAST_PICKED: OnceLock<Mutex<HashMap<String, i64>>> = OnceLock::new();
+
+/// 解析后的 HTTP 请求
+#[derive(Debug)]
+pub(crate) struct HttpRequest {
+    pub method: String,
+    pub path: String,
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl HttpRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).map(|value| value.as_str())
+    }
+}
+
+/// 上游请求失败（已映射为返回给客户端的 HTTP 状态码）
+#[derive(Debug)]
+pub(crate) struct UpstreamError {
+    pub status: u16,
+    pub message: String,
+}
+
+/// 获取代理实际运行的端口（未运行为 None）
+pub fn get_running_port() -> Option<u16> {
+    RUNNING_PORT
+        .get_or_init(|| Mutex::new(None))
+        .lock()
+        .ok()
+        .and_then(|port| *port)
+}
+
+fn set_running_port(port: Option<u16>) {
+    if let Ok(mut current) = RUNNING_PORT.get_or_init(|| Mutex::new(None)).lock() {
+        *current = port;
+    }
+}
+
+/// 生成新的代理访问密钥
+pub fn generate_api_key() -> String {
+    random_id("sk-cockpit-")
+}
+
+/// 启用代理但未配置访问密钥时（如旧版本配置）生成一个并保存
+fn ensure_api_key(cfg: &config::UserConfig) -> Result<(), String> {
+    if !cfg.proxy_api_key.trim().is_empty() {
+        return Ok(());
+    }
+    let mut updated = cfg.clone();
+    updated.proxy_api_key = generate_api_key();
+    config::save_user_config(&updated)?;
+    logger::log_info("[Proxy] 未配置访问密钥，已自动生成（可在设置 - 网络中查看）");
+    Ok(())
+}
+
+/// 启动本地 API 代理（未启用时直接返回）
+pub async fn start_server() {
+    let cfg = config::get_user_config();
+    if !cfg.proxy_enabled {
+        logger::log_info("[Proxy] 本地 API 代理未启用");
+        return;
+    }
+    if let Err(e) = ensure_api_key(&cfg) {
+        logger::log_error(&format!("[Proxy] 生成访问密钥失败，代理未启动: {}", e));
+        return;
+    }
+
+    let addr = format!("127.0.0.1:{}", cfg.proxy_port);
+    let listener = match TcpListener::bind(&addr).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            logger::log_error(&format!("[Proxy] 无法绑定端口 {}: {}", cfg.proxy_port, e));
+            return;
+        }
+    };
+
+    set_running_port(Some(cfg.proxy_port));
+    logger::log_info(&format!("[Proxy] 本地 API 代理已启动: http://{}/v1", addr));
+
+    while let Ok((stream, _)) = listener.accept().await {
+        tokio::spawn(handle_connection(stream));
+    }
+
+    set_running_port(None);
+}
+
+async fn handle_connection(stream: TcpStream) {
+    let mut reader = BufReader::new(stream);
+    let request = match read_request(&mut reader).await {
+        Ok(Some(request)) => request,
+        Ok(None) => return,
+        Err(e) => {
+            let mut stream = reader.into_inner();
+            let _ = write_json(
+                &mut stream,
+                400,
+                &api_proxy_openai::error_body(&e, "invalid_request_error"),
+            )
+            .await;
+            return;
+        }
+    };
+    let mut stream = reader.into_inner();
+
+    if let Err(e) = dispatch(&mut stream, request).await {
+        logger::log_warn(&format!("[Proxy] 写入响应失败: {}", e));
+    }
+    let _ = stream.shutdown().await;
+}
+
+async fn dispatch<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    request: HttpRequest,
+) -> std::io::Result<()> {
+    let path = request.path.trim_end_matches('/');
+    let is_anthropic = path.starts_with("/v1/messages");
+    let api_key = config::get_user_config().proxy_api_key;
+    if !is_authorized(&request, &api_key) {
+        let body = if is_anthropic {
+            api_proxy_anthropic::error_body("Invalid API key", "authentication_error")
+        } else {
+            api_proxy_openai::error_body("Invalid API key", "authentication_error")
+        };
+        return write_json(writer, 401, &body).await;
+    }
+
+    match (request.method.as_str(), path) {
+        ("GET", "/v1/models") | ("GET", "/models") => api_proxy_openai::handle_models(writer).await,
+        ("POST", "/v1/chat/completions") | ("POST", "/chat/completions") => {
+            api_proxy_openai::handle_chat_completions(writer, &request.body).await
+        }
+        ("POST", "/v1/messages") => {
+            api_proxy_anthropic::handle_messages(writer, &request.body).await
+        }
+        _ => {
+            write_json(
+                writer,
+                404,
+                &api_proxy_openai::error_body(
+                    &format!("Unknown endpoint: {} {}", request.method, request.path),
+                    "invalid_request_error",
+                ),
+            )
+            .await
+        }
+    }
+}
+
+/// 校验访问密钥（支持 `Authorization: Bearer` 与 `x-api-key`），未配置密钥时一律拒绝
+fn is_authorized(request: &HttpRequest, api_key: &str) -> bool {
+    let expected = api_key.trim();
+    if expected.is_empty() {
+        return false;
+    }
+    let bearer = request
+        .header("authorization")
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|value| value.trim());
+    bearer == Some(expected)
+        || request.header("x-api-key").map(|value| value.trim()) == Some(expected)
+}
+
+/// 读取一个 HTTP/1.1 请求（连接在响应后关闭，不支持分块请求体）
+pub(crate) async fn read_request<R: AsyncRead + Unpin>(
+    reader: &mut BufReader<R>,
+) -> Result<Option<HttpRequest>, String> {
+    let mut request_line = String::new();
+    let read = reader
+        .read_line(&mut request_line)
+        .await
+        .map_err(|e| format!("读取请求失败: {}", e))?;
+    if read == 0 {
+        return Ok(None);
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_uppercase();
+    let target = parts.next().unwrap_or_default();
+    if method.is_empty() || target.is_empty() {
+        return Err("Malformed request line".to_string());
+    }
+    let path = target.split('?').next().unwrap_or(target).to_string();
+
+    let mut headers = HashMap::new();
+    let mut header_bytes = 0usize;
+    loop {
+        let mut line = String::new();
+        let read = reader
+            .read_line(&mut line)
+            .await
+            .map_err(|e| format!("读取请求头失败: {}", e))?;
+        header_bytes += read;
+        if read == 0 || header_bytes > MAX_HEADER_BYTES {
+            return Err("Request headers too large or truncated".to_string());
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_lowercase(), value.trim().to_string());
+        }
+    }
+
+    if headers
+        .get("transfer-encoding")
+        .map(|value| value.to_lowercase().contains("chunked"))
+        .unwrap_or(false)
+    {
+        return Err("Chunked request body is not supported".to_string());
+    }
+
+    let length = headers
+        .get("content-length")
+        .map(|value| value.parse::<usize>())
+        .transpose()
+        .map_err(|_| "Invalid Content-Length".to_string())?
+        .unwrap_or(0);
+    if length > MAX_BODY_BYTES {
+        return Err("Request body too large".to_string());
+    }
+
+    let mut body = vec![0u8; length];
+    reader
+        .read_exact(&mut body)
+        .await
+        .map_err(|e| format!("读取请求体失败: {}", e))?;
+
+    Ok(Some(HttpRequest {
+        method,
+        path,
+        headers,
+        body,
+    }))
+}
+
+fn status_text(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        204 => "No Content",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        429 => "Too Many Requests",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        _ => "Error",
+    }
+}
+
+pub(crate) async fn write_response<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    status: u16,
+    content_type: &str,
+    body: &[u8],
+) -> std::io::Result<()> {
+    let head = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        status,
+        status_text(status),
+        content_type,
+        body.len()
+    );
+    writer.write_all(head.as_bytes()).await?;
+    writer.write_all(body).await?;
+    writer.flush().await
+}
+
+pub(crate) async fn write_json<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    status: u16,
+    body: &serde_json::Value,
+) -> std::io::Result<()> {
+    let bytes = serde_json::to_vec(body).unwrap_or_default();
+    write_response(writer, status, "application/json", &bytes).await
+}
+
+/// 写入 SSE 响应头（响应体以连接关闭结束）
+pub(crate) async fn start_sse<W: AsyncWrite + Unpin>(writer: &mut W) -> std::io::Result<()> {
+    let head = "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n";
+    writer.write_all(head.as_bytes()).await?;
+    writer.flush().await
+}
+
+/// 写入一条 SSE 事件
+pub(crate) async fn write_sse<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    event: Option<&str>,
+    data: &str,
+) -> std::io::Result<()> {
+    let mut frame = String::new();
+    if let Some(event) = event {
+        frame.push_str("event: ");
+        frame.push_str(event);
+        frame.push('\n');
+    }
+    frame.push_str("data: ");
+    frame.push_str(data);
+    frame.push_str("\n\n");
+    writer.write_all(frame.as_bytes()).await?;
+    writer.flush().await
+}
+
+/// 与指定模型共享配额的模型列表（同一分组内的模型；未分组时仅包含自身）
+pub(crate) fn quota_group_models(model: &str) -> Vec<String> {
+    let settings = group_settings::load_group_settings();
+    match settings.get_group_for_model(model) {
+        Some(group_id) => settings.get_models_in_group(group_id),
+        None => vec![model.to_string()],
+    }
+}
+
+/// 账号在该配额分组下的剩余配额（取分组内最低值）；未知（尚未刷新或不含这些模型）返回 None
+fn group_percentage(account: &Account, group_models: &[String]) -> Option<i32> {
+    account
+        .quota
+        .as_ref()?
+        .models
+        .iter()
+        .filter(|item| group_models.iter().any(|name| name == &item.name))
+        .map(|item| item.percentage)
+        .min()
+}
+
+/// 按剩余配额对账号池排序：跳过禁用、403 与该分组配额耗尽的账号，
+/// 配额未知的账号排在最后；配额相同时优先最久未被代理使用的账号
+pub(crate) fn rank_accounts(
+    accounts: Vec<Account>,
+    group_models: &[String],
+    last_picked: &HashMap<String, i64>,
+) -> Vec<Account> {
+    let mut ranked: Vec<(i32, i64, Account)> = accounts
+        .into_iter()
+        .filter(|account| !account.disabled)
+        .filter(|account| {
+            account
+                .quota
+                .as_ref()
+                .map(|quota| !quota.is_forbidden)
+                .unwrap_or(true)
+        })
+        .filter_map(|account| {
+            let score = match group_percentage(&account, group_models) {
+                Some(percentage) if percentage <= 0 => return None,
+                Some(percentage) => percentage,
+                None => -1,
+            };
+            let picked_at = last_picked.get(&account.id).copied().unwrap_or(0);
+            Some((score, picked_at, account))
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+    ranked.into_iter().map(|(_, _, account)| account).collect()
+}
+
+fn mark_picked(account_id: &str) {
+    if let Ok(mut map) = LAST_PICKED
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+    {
+        map.insert(
+            account_id.to_string(),
+            chrono::Utc::now().timestamp_millis(),
+        );
+    }
+}
+
+fn last_picked_snapshot() -> HashMap<String, i64> {
+    LAST_PICKED
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .map(|map| map.clone())
+        .unwrap_or_default()
+}
+
+/// 代理可用的模型列表（账号池中所有可用账号配额里出现过的模型）
+pub(crate) fn list_pool_models() -> Result<Vec<String>, String> {
+    let mut models: Vec<String> = modules::list_accounts()?
+        .into_iter()
+        .filter(|account| !account.disabled)
+        .filter_map(|account| account.quota)
+        .filter(|quota| !quota.is_forbidden)
+        .flat_map(|quota| quota.models.into_iter().map(|item| item.name))
+        .collect();
+    models.sort();
+    models.dedup();
+    Ok(models)
+}
+
+/// 按配额顺序选号并打开上游流；授权失效、无权限或限流时自动换下一个账号
+///
+/// `build_body` 接收账号的项目 ID，返回 streamGenerateContent 请求体。
+pub(crate) async fn open_upstream<F>(
+    model: &str,
+    build_body: F,
+) -> Result<(Account, wakeup::CloudCodeStream), UpstreamError>
+where
+    F: Fn(&str) -> serde_json::Value,
+{
+    let accounts = modules::list_accounts().map_err(|e| UpstreamError {
+        status: 503,
+        message: e,
+    })?;
+    let group_models = quota_group_models(model);
+    let reserve_enabled = quota_protection::reserve_enabled();
+    // 分组内任一模型处于配额保留状态的账号不参与代理选号
+    let accounts: Vec<Account> = accounts
+        .into_iter()
+        .filter(|account| {
+            !group_models
+                .iter()
+                .any(|name| quota_protection::is_model_protected(account, name, reserve_enabled))
+        })
+        .collect();
+    let candidates = rank_accounts(accounts, &group_models, &last_picked_snapshot());
+    if candidates.is_empty() {
+        return Err(UpstreamError {
+            status: 503,
+            message: format!("No available account for model {}", model),
+        });
+    }
+
+    let client = crate::utils::http::create_client(UPSTREAM_TIMEOUT_SECS);
+    let mut last_error = UpstreamError {
+        status: 503,
+        message: "No available account".to_string(),
+    };
+    for mut account in candidates.into_iter().take(MAX_ACCOUNT_ATTEMPTS) {
+        mark_picked(&account.id);
+        let (access_token, project_id) = match wakeup::prepare_account_request(&mut account).await {
+            Ok(context) => context,
+            Err(e) => {
+                logger::log_warn(&format!(
+                    "[Proxy] 账号准备失败，尝试下一个: email={}, error={}",
+                    account.email, e
+                ));
+                last_error = UpstreamError {
+                    status: 502,
+                    message: e,
+                };
+                continue;
+            }
+        };
+
+        let body = build_body(&project_id);
+        match wakeup::open_stream(&client, &access_token, &body).await {
+            Ok(stream) => {
+                logger::log_info(&format!(
+                    "[Proxy] 转发请求: model={}, email={}",
+                    model, account.email
+                ));
+                return Ok((account, stream));
+            }
+            Err(e) if e.is_account_error() => {
+                logger::log_warn(&format!(
+                    "[Proxy] 账号不可用，尝试下一个: email={}, error={}",
+                    account.email, e
+                ));
+                last_error = UpstreamError {
+                    status: e.status.unwrap_or(502),
+                    message: e.message,
+                };
+            }
+            Err(e) => {
+                return Err(UpstreamError {
+                    status: e.status.filter(|status| *status < 500).unwrap_or(502),
+                    message: e.message,
+                });
+            }
+        }
+    }
+    Err(last_error)
+}
+
+/// 生成随机 ID 后缀
+pub(crate) fn random_id(prefix: &str) -> String {
+    format!("{}{}", prefix, uuid::Uuid::new_v4().simple())
+}
+
+/// 当前 Unix 时间戳（秒）
+pub(crate) fn now_secs() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+/// 去掉 Gemini 函数声明不支持的 JSON Schema 字段
+pub(crate) fn sanitize_schema(schema: &serde_json::Value) -> serde_json::Value {
+    match schema {
+        serde_json::Value::Object(map) => {
+            let mut cleaned = serde_json::Map::new();
+            for (key, value) in map {
+                if matches!(
+                    key.as_str(),
+                    "$schema" | "additionalProperties" | "$id" | "$ref" | "definitions" | "$defs"
+                ) {
+                    continue;
+                }
+                cleaned.insert(key.clone(), sanitize_schema(value));
+            }
+            serde_json::Value::Object(cleaned)
+        }
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.iter().map(sanitize_schema).collect())
+        }
+        other => other.clone(),
+    }
+}
+
+/// 空参数时补一个合法的 object schema
+pub(crate) fn function_parameters(schema: Option<&serde_json::Value>) -> serde_json::Value {
+    match schema {
+        Some(value) if value.is_object() => sanitize_schema(value),
+        _ => json!({ "type": "object", "properties": {} }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{QuotaData, TokenData};
+
+    fn account(id: &str, models: &[(&str, i32)]) -> Account {
+        let token = TokenData::new(
+            "access".to_string(),
+            "refresh".to_string(),
+            3600,
+            Some(format!("{}@example.com", id)),
+            None,
+            None,
+        );
+        let mut account = Account::new(id.to_string(), format!("{}@example.com", id), token);
+        if !models.is_empty() {
+            let mut quota = QuotaData::new();
+            for (name, percentage) in models {
+                quota.add_model(name.to_string(), None, *percentage, String::new());
+            }
+            account.quota = Some(quota);
+        }
+        account
+    }
+
+    #[test]
+    fn rank_accounts_orders_by_remaining_quota_and_skips_unusable() {
+        let mut disabled = account("disabled", &[("gemini-3-pro", 100)]);
+        disabled.disabled = true;
+        let mut forbidden = account("forbidden", &[("gemini-3-pro", 100)]);
+        forbidden.quota.as_mut().unwrap().is_forbidden = true;
+
+        let accounts = vec![
+            account("low", &[("gemini-3-pro", 20)]),
+            account("empty", &[("gemini-3-pro", 0)]),
+            account("unknown", &[]),
+            account("high", &[("gemini-3-pro", 80)]),
+            disabled,
+            forbidden,
+        ];
+
+        let group = vec!["gemini-3-pro".to_string()];
+        let ranked: Vec<String> = rank_accounts(accounts, &group, &HashMap::new())
+            .into_iter()
+            .map(|account| account.id)
+            .collect();
+        assert_eq!(ranked, vec!["high", "low", "unknown"]);
+    }
+
+    #[test]
+    fn rank_accounts_rotates_between_equal_quota() {
+        let accounts = vec![
+            account("a", &[("gemini-3-pro", 50)]),
+            account("b", &[("gemini-3-pro", 50)]),
+        ];
+        let mut last_picked = HashMap::new();
+        last_picked.insert("a".to_string(), 10);
+
+        let group = vec!["gemini-3-pro".to_string()];
+        let ranked = rank_accounts(accounts, &group, &last_picked);
+        assert_eq!(ranked[0].id, "b");
+    }
+
+    #[test]
+    fn rank_accounts_uses_lowest_quota_in_group() {
+        let accounts = vec![
+            account(
+                "drained",
+                &[("claude-sonnet-4-5", 90), ("claude-opus-4-5-thinking", 0)],
+            ),
+            account(
+                "partial",
+                &[("claude-sonnet-4-5", 30), ("claude-opus-4-5-thinking", 40)],
+            ),
+        ];
+        let group = vec![
+            "claude-sonnet-4-5".to_string(),
+            "claude-opus-4-5-thinking".to_string(),
+        ];
+
+        let ranked: Vec<String> = rank_accounts(accounts, &group, &HashMap::new())
+            .into_iter()
+            .map(|account| account.id)
+            .collect();
+        assert_eq!(ranked, vec!["partial"]);
+    }
+
+    #[tokio::test]
+    async fn read_request_parses_headers_and_body() {
+        let raw = b"POST /v1/chat/completions?x=1 HTTP/1.1\r\nHost: localhost\r\nContent-Length: 2\r\nAuthorization: Bearer k\r\n\r\n{}";
+        let mut reader = BufReader::new(&raw[..]);
+        let request = read_request(&mut reader).await.unwrap().unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/v1/chat/completions");
+        assert_eq!(request.body, b"{}");
+        assert!(is_authorized(&request, "k"));
+        assert!(!is_authorized(&request, "other"));
+        assert!(!is_authorized(&request, ""));
+    }
+}
@@ -0,0 +1,761 @@
+//! 本地 API 代理：Anthropic Messages 兼容接口
+//! `/v1/messages`（支持 SSE 流式、tool_use 与 thinking 块），面向 Antigravity 提供的 Claude 模型，
+//! 按模型所在分组（如 `claude_45`）的剩余配额在账号间轮换
+
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use tokio::io::AsyncWrite;
+
+use crate::modules::api_proxy::{self, UpstreamError};
+use crate::modules::wakeup::{self, CloudCodeStream, StreamPart, StreamUsage};
+
+#[derive(Debug, Deserialize)]
+struct MessagesRequest {
+    model: String,
+    messages: Vec<InputMessage>,
+    #[serde(default)]
+    system: Option<serde_json::Value>,
+    #[serde(default)]
+    max_tokens: Option<u32>,
+    #[serde(default)]
+    stream: bool,
+    #[serde(default)]
+    temperature: Option<f64>,
+    #[serde(default)]
+    top_p: Option<f64>,
+    #[serde(default)]
+    top_k: Option<u32>,
+    #[serde(default)]
+    stop_sequences: Option<Vec<String>>,
+    #[serde(default)]
+    tools: Option<Vec<ToolDefinition>>,
+    #[serde(default)]
+    tool_choice: Option<serde_json::Value>,
+    #[serde(default)]
+    thinking: Option<ThinkingConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+struct InputMessage {
+    role: String,
+    content: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct ToolDefinition {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    input_schema: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ThinkingConfig {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    budget_tokens: Option<u32>,
+}
+
+impl MessagesRequest {
+    fn thinking_enabled(&self) -> bool {
+        self.thinking
+            .as_ref()
+            .map(|thinking| thinking.kind == "enabled")
+            .unwrap_or(false)
+    }
+}
+
+/// 转换后的 Cloud Code 请求参数
+#[derive(Debug, Clone)]
+struct TranslatedRequest {
+    contents: serde_json::Value,
+    system_parts: Vec<serde_json::Value>,
+    generation_config: serde_json::Value,
+    tools: Option<serde_json::Value>,
+    tool_config: Option<serde_json::Value>,
+}
+
+/// Anthropic 格式的错误响应体
+pub(crate) fn error_body(message: &str, kind: &str) -> serde_json::Value {
+    json!({ "type": "error", "error": { "type": kind, "message": message } })
+}
+
+fn error_type_for_status(status: u16) -> &'static str {
+    match status {
+        400 => "invalid_request_error",
+        401 => "authentication_error",
+        403 => "permission_error",
+        429 => "rate_limit_error",
+        503 => "overloaded_error",
+        _ => "api_error",
+    }
+}
+
+/// `POST /v1/messages`
+pub(crate) async fn handle_messages<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    body: &[u8],
+) -> std::io::Result<()> {
+    let request: MessagesRequest = match serde_json::from_slice(body) {
+        Ok(request) => request,
+        Err(e) => {
+            return api_proxy::write_json(
+                writer,
+                400,
+                &error_body(
+                    &format!("Invalid request body: {}", e),
+                    "invalid_request_error",
+                ),
+            )
+            .await;
+        }
+    };
+    let translated = match translate_request(&request) {
+        Ok(translated) => translated,
+        Err(e) => {
+            return api_proxy::write_json(writer, 400, &error_body(&e, "invalid_request_error"))
+                .await;
+        }
+    };
+
+    let known_models = api_proxy::list_pool_models().unwrap_or_default();
+    let model = resolve_model(&request.model, request.thinking_enabled(), &known_models);
+    let opened = api_proxy::open_upstream(&model, |project_id| {
+        let mut body = wakeup::build_generate_body(
+            project_id,
+            &model,
+            translated.contents.clone(),
+            translated.system_parts.clone(),
+            translated.generation_config.clone(),
+            translated.tools.clone(),
+        );
+        if let Some(tool_config) = translated.tool_config.clone() {
+            body["request"]["toolConfig"] = tool_config;
+        }
+        body
+    })
+    .await;
+    let mut stream = match opened {
+        Ok((_, stream)) => stream,
+        Err(UpstreamError { status, message }) => {
+            return api_proxy::write_json(
+                writer,
+                status,
+                &error_body(&message, error_type_for_status(status)),
+            )
+            .await;
+        }
+    };
+
+    // 响应中回显客户端请求的模型名，避免 SDK 校验失败
+    if request.stream {
+        stream_message(writer, &mut stream, &request.model).await
+    } else {
+        match collect_message(&mut stream, &request.model).await {
+            Ok(response) => api_proxy::write_json(writer, 200, &response).await,
+            Err(e) => api_proxy::write_json(writer, 502, &error_body(&e, "api_error")).await,
+        }
+    }
+}
+
+/// 将 Anthropic 模型名映射为 Antigravity 模型 ID
+///
+/// 去掉日期或 `-latest` 后缀；开启 thinking 时优先使用 `-thinking` 变体。
+fn resolve_model(requested: &str, thinking: bool, known_models: &[String]) -> String {
+    let trimmed = requested.trim();
+    let base = match trimmed.rsplit_once('-') {
+        Some((head, tail))
+            if tail == "latest"
+                || (tail.len() == 8 && tail.chars().all(|c| c.is_ascii_digit())) =>
+        {
+            head
+        }
+        _ => trimmed,
+    };
+    let thinking_variant = if base.ends_with("-thinking") {
+        base.to_string()
+    } else {
+        format!("{}-thinking", base)
+    };
+
+    let candidates = if thinking {
+        [thinking_variant, base.to_string()]
+    } else {
+        [base.to_string(), thinking_variant]
+    };
+    candidates
+        .iter()
+        .find(|candidate| known_models.contains(candidate))
+        .cloned()
+        .unwrap_or_else(|| base.to_string())
+}
+
+fn content_blocks(content: &serde_json::Value) -> Vec<serde_json::Value> {
+    match content {
+        serde_json::Value::String(text) => vec![json!({ "type": "text", "text": text })],
+        serde_json::Value::Array(items) => items.clone(),
+        _ => Vec::new(),
+    }
+}
+
+fn blocks_to_text(content: &serde_json::Value) -> String {
+    content_blocks(content)
+        .iter()
+        .filter_map(|block| block.get("text").and_then(|value| value.as_str()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 将单个 Anthropic 内容块转换为 Gemini part
+fn block_to_part(
+    block: &serde_json::Value,
+    tool_names: &mut HashMap<String, String>,
+) -> Option<serde_json::Value> {
+    let kind = block.get("type").and_then(|value| value.as_str())?;
+    match kind {
+        "text" => block
+            .get("text")
+            .and_then(|value| value.as_str())
+            .filter(|text| !text.is_empty())
+            .map(|text| json!({ "text": text })),
+        "image" | "document" => {
+            let source = block.get("source")?;
+            if source.get("type").and_then(|value| value.as_str()) != Some("base64") {
+                return None;
+            }
+            Some(json!({
+                "inlineData": {
+                    "mimeType": source.get("media_type").cloned().unwrap_or_default(),
+                    "data": source.get("data").cloned().unwrap_or_default(),
+                }
+            }))
+        }
+        "tool_use" => {
+            let id = block.get("id").and_then(|value| value.as_str())?;
+            let name = block.get("name").and_then(|value| value.as_str())?;
+            tool_names.insert(id.to_string(), name.to_string());
+            Some(json!({
+                "functionCall": {
+                    "id": id,
+                    "name": name,
+                    "args": block.get("input").cloned().unwrap_or_else(|| json!({})),
+                }
+            }))
+        }
+        "tool_result" => {
+            let id = block
+                .get("tool_use_id")
+                .and_then(|value| value.as_str())
+                .unwrap_or_default();
+            let name = tool_names
+                .get(id)
+                .cloned()
+                .unwrap_or_else(|| "tool".to_string());
+            let text = block.get("content").map(blocks_to_text).unwrap_or_default();
+            let is_error = block
+                .get("is_error")
+                .and_then(|value| value.as_bool())
+                .unwrap_or(false);
+            let response = if is_error {
+                json!({ "error": text })
+            } else {
+                json!({ "result": text })
+            };
+            Some(json!({
+                "functionResponse": { "id": id, "name": name, "response": response }
+            }))
+        }
+        // 历史 thinking 块只有带签名时才能回传给上游
+        "thinking" => {
+            let signature = block.get("signature").and_then(|value| value.as_str())?;
+            Some(json!({
+                "text": block.get("thinking").and_then(|value| value.as_str()).unwrap_or_default(),
+                "thought": true,
+                "thoughtSignature": signature,
+            }))
+        }
+        _ => None,
+    }
+}
+
+fn translate_request(request: &MessagesRequest) -> Result<TranslatedRequest, String> {
+    if request.model.trim().is_empty() {
+        return Err("model is required".to_string());
+    }
+
+    let system_parts: Vec<serde_json::Value> = request
+        .system
+        .as_ref()
+        .map(|system| {
+            content_blocks(system)
+                .iter()
+                .filter_map(|block| block.get("text").and_then(|value| value.as_str()))
+                .filter(|text| !text.is_empty())
+                .map(|text| json!({ "text": text }))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut contents: Vec<serde_json::Value> = Vec::new();
+    let mut tool_names: HashMap<String, String> = HashMap::new();
+    for message in &request.messages {
+        let role = match message.role.as_str() {
+            "user" => "user",
+            "assistant" => "model",
+            other => return Err(format!("Unsupported message role: {}", other)),
+        };
+        let parts: Vec<serde_json::Value> = content_blocks(&message.content)
+            .iter()
+            .filter_map(|block| block_to_part(block, &mut tool_names))
+            .collect();
+        if parts.is_empty() {
+            continue;
+        }
+        match contents.last_mut() {
+            Some(last) if last.get("role").and_then(|value| value.as_str()) == Some(role) => {
+                if let Some(existing) = last.get_mut("parts").and_then(|value| value.as_array_mut())
+                {
+                    existing.extend(parts);
+                }
+            }
+            _ => contents.push(json!({ "role": role, "parts": parts })),
+        }
+    }
+    if contents.is_empty() {
+        return Err("messages must contain at least one non-empty message".to_string());
+    }
+
+    let mut generation_config = serde_json::Map::new();
+    if let Some(max_tokens) = request.max_tokens {
+        generation_config.insert("maxOutputTokens".to_string(), json!(max_tokens));
+    }
+    if let Some(temperature) = request.temperature {
+        generation_config.insert("temperature".to_string(), json!(temperature));
+    }
+    if let Some(top_p) = request.top_p {
+        generation_config.insert("topP".to_string(), json!(top_p));
+    }
+    if let Some(top_k) = request.top_k {
+        generation_config.insert("topK".to_string(), json!(top_k));
+    }
+    if let Some(stops) = request
+        .stop_sequences
+        .as_ref()
+        .filter(|stops| !stops.is_empty())
+    {
+        generation_config.insert("stopSequences".to_string(), json!(stops));
+    }
+    if request.thinking_enabled() {
+        let mut thinking = json!({ "includeThoughts": true });
+        if let Some(budget) = request
+            .thinking
+            .as_ref()
+            .and_then(|thinking| thinking.budget_tokens)
+        {
+            thinking["thinkingBudget"] = json!(budget);
+        }
+        generation_config.insert("thinkingConfig".to_string(), thinking);
+    }
+
+    let declarations: Vec<serde_json::Value> = request
+        .tools
+        .iter()
+        .flatten()
+        .map(|tool| {
+            json!({
+                "name": tool.name,
+                "description": tool.description.clone().unwrap_or_default(),
+                "parameters": api_proxy::function_parameters(tool.input_schema.as_ref()),
+            })
+        })
+        .collect();
+    let tools = if declarations.is_empty() {
+        None
+    } else {
+        Some(json!([{ "functionDeclarations": declarations }]))
+    };
+
+    let tool_config = request.tool_choice.as_ref().and_then(|choice| {
+        let mode = match choice.get("type").and_then(|value| value.as_str())? {
+            "auto" => json!({ "mode": "AUTO" }),
+            "any" => json!({ "mode": "ANY" }),
+            "none" => json!({ "mode": "NONE" }),
+            "tool" => json!({
+                "mode": "ANY",
+                "allowedFunctionNames": [choice.get("name").cloned().unwrap_or_default()],
+            }),
+            _ => return None,
+        };
+        Some(json!({ "functionCallingConfig": mode }))
+    });
+
+    Ok(TranslatedRequest {
+        contents: serde_json::Value::Array(contents),
+        system_parts,
+        generation_config: serde_json::Value::Object(generation_config),
+        tools,
+        tool_config,
+    })
+}
+
+fn map_stop_reason(reason: Option<&str>, has_tool_use: bool) -> &'static str {
+    if has_tool_use {
+        return "tool_use";
+    }
+    match reason {
+        Some("MAX_TOKENS") => "max_tokens",
+        _ => "end_turn",
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BlockKind {
+    Text,
+    Thinking,
+}
+
+/// 将上游片段组装为 Anthropic 内容块，并生成对应的流式事件
+///
+/// 相邻的文本/思考片段合并为同一个块；每个函数调用生成一个独立的 tool_use 块。
+#[derive(Default)]
+struct MessageAssembler {
+    blocks: Vec<serde_json::Value>,
+    open: Option<BlockKind>,
+    has_tool_use: bool,
+}
+
+impl MessageAssembler {
+    fn close_block(&mut self, events: &mut Vec<(&'static str, serde_json::Value)>) {
+        if self.open.take().is_some() {
+            events.push((
+                "content_block_stop",
+                json!({ "type": "content_block_stop", "index": self.blocks.len() - 1 }),
+            ));
+        }
+    }
+
+    fn open_block(&mut self, kind: BlockKind, events: &mut Vec<(&'static str, serde_json::Value)>) {
+        if self.open == Some(kind) {
+            return;
+        }
+        self.close_block(events);
+        let block = match kind {
+            BlockKind::Text => json!({ "type": "text", "text": "" }),
+            BlockKind::Thinking => json!({ "type": "thinking", "thinking": "", "signature": "" }),
+        };
+        self.blocks.push(block.clone());
+        self.open = Some(kind);
+        events.push((
+            "content_block_start",
+            json!({ "type": "content_block_start", "index": self.blocks.len() - 1, "content_block": block }),
+        ));
+    }
+
+    fn append(&mut self, field: &str, value: &str) {
+        if let Some(block) = self.blocks.last_mut() {
+            let current = block
+                .get(field)
+                .and_then(|existing| existing.as_str())
+                .unwrap_or_default()
+                .to_string();
+            block[field] = json!(current + value);
+        }
+    }
+
+    fn delta(&self, delta: serde_json::Value) -> (&'static str, serde_json::Value) {
+        (
+            "content_block_delta",
+            json!({ "type": "content_block_delta", "index": self.blocks.len() - 1, "delta": delta }),
+        )
+    }
+
+    fn push_part(&mut self, part: StreamPart) -> Vec<(&'static str, serde_json::Value)> {
+        let mut events = Vec::new();
+        match part {
+            StreamPart::Text(text) => {
+                self.open_block(BlockKind::Text, &mut events);
+                self.append("text", &text);
+                events.push(self.delta(json!({ "type": "text_delta", "text": text })));
+            }
+            StreamPart::Thought { text, signature } => {
+                self.open_block(BlockKind::Thinking, &mut events);
+                if !text.is_empty() {
+                    self.append("thinking", &text);
+                    events.push(self.delta(json!({ "type": "thinking_delta", "thinking": text })));
+                }
+                if let Some(signature) = signature {
+                    self.append("signature", &signature);
+                    events.push(
+                        self.delta(json!({ "type": "signature_delta", "signature": signature })),
+                    );
+                }
+            }
+            StreamPart::FunctionCall { id, name, args } => {
+                self.close_block(&mut events);
+                self.has_tool_use = true;
+                let id = id.unwrap_or_else(|| api_proxy::random_id("toolu_"));
+                self.blocks
+                    .push(json!({ "type": "tool_use", "id": id, "name": name, "input": args }));
+                let index = self.blocks.len() - 1;
+                events.push((
+                    "content_block_start",
+                    json!({
+                        "type": "content_block_start",
+                        "index": index,
+                        "content_block": { "type": "tool_use", "id": id, "name": name, "input": {} },
+                    }),
+                ));
+                events.push(self.delta(json!({
+                    "type": "input_json_delta",
+                    "partial_json": serde_json::to_string(&args).unwrap_or_else(|_| "{}".to_string()),
+                })));
+                events.push((
+                    "content_block_stop",
+                    json!({ "type": "content_block_stop", "index": index }),
+                ));
+            }
+        }
+        events
+    }
+
+    fn finish(&mut self) -> Vec<(&'static str, serde_json::Value)> {
+        let mut events = Vec::new();
+        self.close_block(&mut events);
+        events
+    }
+}
+
+fn usage_json(usage: &StreamUsage) -> serde_json::Value {
+    json!({ "input_tokens": usage.prompt_tokens, "output_tokens": usage.completion_tokens })
+}
+
+/// 读取完整上游响应并组装为 Anthropic `message`
+async fn collect_message(
+    stream: &mut CloudCodeStream,
+    model: &str,
+) -> Result<serde_json::Value, String> {
+    let mut assembler = MessageAssembler::default();
+    let mut stop_reason: Option<String> = None;
+    let mut usage = StreamUsage::default();
+
+    while let Some(event) = stream.next_event().await? {
+        for part in event.parts {
+            assembler.push_part(part);
+        }
+        if event.finish_reason.is_some() {
+            stop_reason = event.finish_reason;
+        }
+        if let Some(value) = event.usage {
+            usage = value;
+        }
+    }
+    assembler.finish();
+
+    Ok(json!({
+        "id": api_proxy::random_id("msg_"),
+        "type": "message",
+        "role": "assistant",
+        "model": model,
+        "content": assembler.blocks,
+        "stop_reason": map_stop_reason(stop_reason.as_deref(), assembler.has_tool_use),
+        "stop_sequence": serde_json::Value::Null,
+        "usage": usage_json(&usage),
+    }))
+}
+
+/// 以 Anthropic SSE 事件逐段转发上游输出
+async fn stream_message<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    stream: &mut CloudCodeStream,
+    model: &str,
+) -> std::io::Result<()> {
+    api_proxy::start_sse(writer).await?;
+    let message_start = json!({
+        "type": "message_start",
+        "message": {
+            "id": api_proxy::random_id("msg_"),
+            "type": "message",
+            "role": "assistant",
+            "model": model,
+            "content": [],
+            "stop_reason": serde_json::Value::Null,
+            "stop_sequence": serde_json::Value::Null,
+            "usage": { "input_tokens": 0, "output_tokens": 0 },
+        }
+    });
+    api_proxy::write_sse(writer, Some("message_start"), &message_start.to_string()).await?;
+
+    let mut assembler = MessageAssembler::default();
+    let mut stop_reason: Option<String> = None;
+    let mut usage = StreamUsage::default();
+    loop {
+        let event = match stream.next_event().await {
+            Ok(Some(event)) => event,
+            Ok(None) => break,
+            Err(e) => {
+                let body = error_body(&e, "api_error").to_string();
+                return api_proxy::write_sse(writer, Some("error"), &body).await;
+            }
+        };
+        for part in event.parts {
+            for (name, payload) in assembler.push_part(part) {
+                api_proxy::write_sse(writer, Some(name), &payload.to_string()).await?;
+            }
+        }
+        if event.finish_reason.is_some() {
+            stop_reason = event.finish_reason;
+        }
+        if let Some(value) = event.usage {
+            usage = value;
+        }
+    }
+
+    for (name, payload) in assembler.finish() {
+        api_proxy::write_sse(writer, Some(name), &payload.to_string()).await?;
+    }
+    let message_delta = json!({
+        "type": "message_delta",
+        "delta": {
+            "stop_reason": map_stop_reason(stop_reason.as_deref(), assembler.has_tool_use),
+            "stop_sequence": serde_json::Value::Null,
+        },
+        "usage": usage_json(&usage),
+    });
+    api_proxy::write_sse(writer, Some("message_delta"), &message_delta.to_string()).await?;
+    api_proxy::write_sse(
+        writer,
+        Some("message_stop"),
+        &json!({ "type": "message_stop" }).to_string(),
+    )
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(value: serde_json::Value) -> MessagesRequest {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn resolve_model_strips_suffix_and_prefers_thinking_variant() {
+        let known = vec![
+            "claude-sonnet-4-5".to_string(),
+            "claude-sonnet-4-5-thinking".to_string(),
+            "claude-opus-4-5-thinking".to_string(),
+        ];
+        assert_eq!(
+            resolve_model("claude-sonnet-4-5-20250929", false, &known),
+            "claude-sonnet-4-5"
+        );
+        assert_eq!(
+            resolve_model("claude-sonnet-4-5", true, &known),
+            "claude-sonnet-4-5-thinking"
+        );
+        assert_eq!(
+            resolve_model("claude-opus-4-5", false, &known),
+            "claude-opus-4-5-thinking"
+        );
+    }
+
+    #[test]
+    fn translate_request_maps_tool_use_and_thinking() {
+        let request = parse(json!({
+            "model": "claude-sonnet-4-5",
+            "max_tokens": 1024,
+            "system": [{ "type": "text", "text": "be brief" }],
+            "thinking": { "type": "enabled", "budget_tokens": 2048 },
+            "tool_choice": { "type": "tool", "name": "get_weather" },
+            "tools": [{ "name": "get_weather", "input_schema": { "type": "object", "properties": {} } }],
+            "messages": [
+                { "role": "user", "content": "weather?" },
+                { "role": "assistant", "content": [
+                    { "type": "thinking", "thinking": "need tool", "signature": "sig" },
+                    { "type": "tool_use", "id": "toolu_1", "name": "get_weather", "input": { "city": "Paris" } }
+                ]},
+                { "role": "user", "content": [
+                    { "type": "tool_result", "tool_use_id": "toolu_1", "content": [{ "type": "text", "text": "sunny" }] }
+                ]}
+            ]
+        }));
+
+        let translated = translate_request(&request).unwrap();
+        assert_eq!(translated.system_parts, vec![json!({ "text": "be brief" })]);
+        let contents = translated.contents.as_array().unwrap();
+        assert_eq!(contents.len(), 3);
+        assert_eq!(contents[1]["parts"][0]["thoughtSignature"], "sig");
+        assert_eq!(
+            contents[1]["parts"][1]["functionCall"]["args"]["city"],
+            "Paris"
+        );
+        assert_eq!(
+            contents[2]["parts"][0]["functionResponse"]["name"],
+            "get_weather"
+        );
+        assert_eq!(
+            contents[2]["parts"][0]["functionResponse"]["response"]["result"],
+            "sunny"
+        );
+        assert_eq!(
+            translated.generation_config["thinkingConfig"]["thinkingBudget"],
+            2048
+        );
+        assert_eq!(
+            translated.tool_config.unwrap()["functionCallingConfig"]["allowedFunctionNames"],
+            json!(["get_weather"])
+        );
+    }
+
+    #[test]
+    fn assembler_emits_anthropic_block_events() {
+        let mut assembler = MessageAssembler::default();
+        let mut names: Vec<&str> = Vec::new();
+        let parts = vec![
+            StreamPart::Thought {
+                text: "hmm".to_string(),
+                signature: None,
+            },
+            StreamPart::Thought {
+                text: String::new(),
+                signature: Some("sig".to_string()),
+            },
+            StreamPart::Text("Hel".to_string()),
+            StreamPart::Text("lo".to_string()),
+            StreamPart::FunctionCall {
+                id: None,
+                name: "get_weather".to_string(),
+                args: json!({ "city": "Paris" }),
+            },
+        ];
+        for part in parts {
+            names.extend(assembler.push_part(part).into_iter().map(|(name, _)| name));
+        }
+        names.extend(assembler.finish().into_iter().map(|(name, _)| name));
+
+        assert_eq!(
+            names,
+            vec![
+                "content_block_start",
+                "content_block_delta",
+                "content_block_delta",
+                "content_block_stop",
+                "content_block_start",
+                "content_block_delta",
+                "content_block_delta",
+                "content_block_stop",
+                "content_block_start",
+                "content_block_delta",
+                "content_block_stop",
+            ]
+        );
+        assert_eq!(assembler.blocks[0]["thinking"], "hmm");
+        assert_eq!(assembler.blocks[0]["signature"], "sig");
+        assert_eq!(assembler.blocks[1]["text"], "Hello");
+        assert_eq!(assembler.blocks[2]["input"]["city"], "Paris");
+        assert!(assembler.has_tool_use);
+    }
+}
@@ -0,0 +1,669 @@
+//! 本地 API 代理：OpenAI 兼容接口
+//! `/v1/chat/completions`（支持 SSE 流式）与 `/v1/models`，请求转换为 Cloud Code 的 Gemini 格式
+
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use tokio::io::AsyncWrite;
+
+use crate::modules::api_proxy::{self, UpstreamError};
+use crate::modules::wakeup::{self, CloudCodeStream, StreamPart, StreamUsage};
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionRequest {
+    model: String,
+    messages: Vec<ChatMessage>,
+    #[serde(default)]
+    stream: bool,
+    #[serde(default)]
+    stream_options: Option<StreamOptions>,
+    #[serde(default)]
+    max_tokens: Option<u32>,
+    #[serde(default)]
+    max_completion_tokens: Option<u32>,
+    #[serde(default)]
+    temperature: Option<f64>,
+    #[serde(default)]
+    top_p: Option<f64>,
+    #[serde(default)]
+    stop: Option<serde_json::Value>,
+    #[serde(default)]
+    tools: Option<Vec<ChatTool>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamOptions {
+    #[serde(default)]
+    include_usage: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatMessage {
+    role: String,
+    #[serde(default)]
+    content: Option<serde_json::Value>,
+    #[serde(default)]
+    tool_calls: Option<Vec<ToolCall>>,
+    #[serde(default)]
+    tool_call_id: Option<String>,
+    #[serde(default)]
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ToolCall {
+    #[serde(default)]
+    id: Option<String>,
+    function: ToolCallFunction,
+}
+
+#[derive(Debug, Deserialize)]
+struct ToolCallFunction {
+    name: String,
+    #[serde(default)]
+    arguments: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatTool {
+    #[serde(default)]
+    function: Option<FunctionDefinition>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FunctionDefinition {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    parameters: Option<serde_json::Value>,
+}
+
+/// 转换后的 Cloud Code 请求参数
+#[derive(Debug, Clone)]
+struct TranslatedRequest {
+    contents: serde_json::Value,
+    system_parts: Vec<serde_json::Value>,
+    generation_config: serde_json::Value,
+    tools: Option<serde_json::Value>,
+}
+
+/// OpenAI 格式的错误响应体
+pub(crate) fn error_body(message: &str, kind: &str) -> serde_json::Value {
+    json!({ "error": { "message": message, "type": kind, "code": serde_json::Value::Null } })
+}
+
+fn error_type_for_status(status: u16) -> &'static str {
+    match status {
+        400 => "invalid_request_error",
+        401 | 403 => "authentication_error",
+        429 => "rate_limit_error",
+        _ => "api_error",
+    }
+}
+
+/// `GET /v1/models`
+pub(crate) async fn handle_models<W: AsyncWrite + Unpin>(writer: &mut W) -> std::io::Result<()> {
+    match api_proxy::list_pool_models() {
+        Ok(models) => {
+            let data: Vec<serde_json::Value> = models
+                .into_iter()
+                .map(|id| json!({ "id": id, "object": "model", "created": 0, "owned_by": "antigravity" }))
+                .collect();
+            api_proxy::write_json(writer, 200, &json!({ "object": "list", "data": data })).await
+        }
+        Err(e) => api_proxy::write_json(writer, 500, &error_body(&e, "api_error")).await,
+    }
+}
+
+/// `POST /v1/chat/completions`
+pub(crate) async fn handle_chat_completions<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    body: &[u8],
+) -> std::io::Result<()> {
+    let request: ChatCompletionRequest = match serde_json::from_slice(body) {
+        Ok(request) => request,
+        Err(e) => {
+            return api_proxy::write_json(
+                writer,
+                400,
+                &error_body(
+                    &format!("Invalid request body: {}", e),
+                    "invalid_request_error",
+                ),
+            )
+            .await;
+        }
+    };
+    let translated = match translate_request(&request) {
+        Ok(translated) => translated,
+        Err(e) => {
+            return api_proxy::write_json(writer, 400, &error_body(&e, "invalid_request_error"))
+                .await;
+        }
+    };
+
+    let model = request.model.clone();
+    let opened = api_proxy::open_upstream(&model, |project_id| {
+        wakeup::build_generate_body(
+            project_id,
+            &model,
+            translated.contents.clone(),
+            translated.system_parts.clone(),
+            translated.generation_config.clone(),
+            translated.tools.clone(),
+        )
+    })
+    .await;
+    let mut stream = match opened {
+        Ok((_, stream)) => stream,
+        Err(UpstreamError { status, message }) => {
+            return api_proxy::write_json(
+                writer,
+                status,
+                &error_body(&message, error_type_for_status(status)),
+            )
+            .await;
+        }
+    };
+
+    if request.stream {
+        let include_usage = request
+            .stream_options
+            .as_ref()
+            .map(|options| options.include_usage)
+            .unwrap_or(false);
+        stream_completion(writer, &mut stream, &model, include_usage).await
+    } else {
+        match collect_completion(&mut stream, &model).await {
+            Ok(response) => api_proxy::write_json(writer, 200, &response).await,
+            Err(e) => api_proxy::write_json(writer, 502, &error_body(&e, "api_error")).await,
+        }
+    }
+}
+
+/// 将消息内容（字符串或分段数组）转换为 Gemini parts
+fn content_to_parts(content: Option<&serde_json::Value>) -> Vec<serde_json::Value> {
+    match content {
+        Some(serde_json::Value::String(text)) if !text.is_empty() => vec![json!({ "text": text })],
+        Some(serde_json::Value::Array(items)) => items
+            .iter()
+            .filter_map(
+                |item| match item.get("type").and_then(|value| value.as_str()) {
+                    Some("text") => item
+                        .get("text")
+                        .and_then(|value| value.as_str())
+                        .filter(|text| !text.is_empty())
+                        .map(|text| json!({ "text": text })),
+                    Some("image_url") => {
+                        let url = item
+                            .get("image_url")
+                            .and_then(|value| value.get("url").or(Some(value)))
+                            .and_then(|value| value.as_str())?;
+                        Some(image_url_to_part(url))
+                    }
+                    _ => None,
+                },
+            )
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// data URL 转为 inlineData；远程图片无法转发，以文本形式保留链接
+fn image_url_to_part(url: &str) -> serde_json::Value {
+    if let Some(rest) = url.strip_prefix("data:") {
+        if let Some((meta, data)) = rest.split_once(',') {
+            if let Some(mime) = meta.strip_suffix(";base64") {
+                return json!({ "inlineData": { "mimeType": mime, "data": data } });
+            }
+        }
+    }
+    json!({ "text": format!("[image] {}", url) })
+}
+
+fn content_to_text(content: Option<&serde_json::Value>) -> String {
+    content_to_parts(content)
+        .iter()
+        .filter_map(|part| part.get("text").and_then(|value| value.as_str()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// 追加一轮对话；与上一轮角色相同时合并 parts
+fn push_content(contents: &mut Vec<serde_json::Value>, role: &str, parts: Vec<serde_json::Value>) {
+    if parts.is_empty() {
+        return;
+    }
+    if let Some(last) = contents.last_mut() {
+        if last.get("role").and_then(|value| value.as_str()) == Some(role) {
+            if let Some(existing) = last.get_mut("parts").and_then(|value| value.as_array_mut()) {
+                existing.extend(parts);
+                return;
+            }
+        }
+    }
+    contents.push(json!({ "role": role, "parts": parts }));
+}
+
+fn translate_request(request: &ChatCompletionRequest) -> Result<TranslatedRequest, String> {
+    if request.model.trim().is_empty() {
+        return Err("model is required".to_string());
+    }
+
+    let mut contents: Vec<serde_json::Value> = Vec::new();
+    let mut system_parts: Vec<serde_json::Value> = Vec::new();
+    let mut tool_names: HashMap<String, String> = HashMap::new();
+
+    for message in &request.messages {
+        match message.role.as_str() {
+            "system" | "developer" => {
+                system_parts.extend(content_to_parts(message.content.as_ref()));
+            }
+            "user" => {
+                push_content(
+                    &mut contents,
+                    "user",
+                    content_to_parts(message.content.as_ref()),
+                );
+            }
+            "assistant" => {
+                let mut parts = content_to_parts(message.content.as_ref());
+                for call in message.tool_calls.iter().flatten() {
+                    let args: serde_json::Value = if call.function.arguments.trim().is_empty() {
+                        json!({})
+                    } else {
+                        serde_json::from_str(&call.function.arguments)
+                            .unwrap_or_else(|_| json!({ "input": call.function.arguments }))
+                    };
+                    let mut function_call = json!({ "name": call.function.name, "args": args });
+                    if let Some(id) = call.id.as_ref() {
+                        tool_names.insert(id.clone(), call.function.name.clone());
+                        function_call["id"] = json!(id);
+                    }
+                    parts.push(json!({ "functionCall": function_call }));
+                }
+                push_content(&mut contents, "model", parts);
+            }
+            "tool" | "function" => {
+                let name = message
+                    .name
+                    .clone()
+                    .or_else(|| {
+                        message
+                            .tool_call_id
+                            .as_ref()
+                            .and_then(|id| tool_names.get(id).cloned())
+                    })
+                    .unwrap_or_else(|| "tool".to_string());
+                let mut function_response = json!({
+                    "name": name,
+                    "response": { "result": content_to_text(message.content.as_ref()) }
+                });
+                if let Some(id) = message.tool_call_id.as_ref() {
+                    function_response["id"] = json!(id);
+                }
+                push_content(
+                    &mut contents,
+                    "user",
+                    vec![json!({ "functionResponse": function_response })],
+                );
+            }
+            other => return Err(format!("Unsupported message role: {}", other)),
+        }
+    }
+
+    if contents.is_empty() {
+        return Err("messages must contain at least one user message".to_string());
+    }
+
+    let mut generation_config = serde_json::Map::new();
+    if let Some(temperature) = request.temperature {
+        generation_config.insert("temperature".to_string(), json!(temperature));
+    }
+    if let Some(top_p) = request.top_p {
+        generation_config.insert("topP".to_string(), json!(top_p));
+    }
+    if let Some(max_tokens) = request.max_completion_tokens.or(request.max_tokens) {
+        generation_config.insert("maxOutputTokens".to_string(), json!(max_tokens));
+    }
+    match request.stop.as_ref() {
+        Some(serde_json::Value::String(stop)) => {
+            generation_config.insert("stopSequences".to_string(), json!([stop]));
+        }
+        Some(serde_json::Value::Array(stops)) if !stops.is_empty() => {
+            generation_config.insert("stopSequences".to_string(), json!(stops));
+        }
+        _ => {}
+    }
+
+    let declarations: Vec<serde_json::Value> = request
+        .tools
+        .iter()
+        .flatten()
+        .filter_map(|tool| tool.function.as_ref())
+        .map(|function| {
+            json!({
+                "name": function.name,
+                "description": function.description.clone().unwrap_or_default(),
+                "parameters": api_proxy::function_parameters(function.parameters.as_ref()),
+            })
+        })
+        .collect();
+    let tools = if declarations.is_empty() {
+        None
+    } else {
+        Some(json!([{ "functionDeclarations": declarations }]))
+    };
+
+    Ok(TranslatedRequest {
+        contents: serde_json::Value::Array(contents),
+        system_parts,
+        generation_config: serde_json::Value::Object(generation_config),
+        tools,
+    })
+}
+
+fn map_finish_reason(reason: Option<&str>, has_tool_calls: bool) -> &'static str {
+    if has_tool_calls {
+        return "tool_calls";
+    }
+    match reason {
+        Some("MAX_TOKENS") => "length",
+        Some("SAFETY") | Some("RECITATION") | Some("BLOCKLIST") | Some("PROHIBITED_CONTENT") => {
+            "content_filter"
+        }
+        _ => "stop",
+    }
+}
+
+fn usage_json(usage: &StreamUsage) -> serde_json::Value {
+    json!({
+        "prompt_tokens": usage.prompt_tokens,
+        "completion_tokens": usage.completion_tokens,
+        "total_tokens": usage.total_tokens,
+    })
+}
+
+fn tool_call_json(id: Option<String>, name: &str, args: &serde_json::Value) -> serde_json::Value {
+    json!({
+        "id": id.unwrap_or_else(|| api_proxy::random_id("call_")),
+        "type": "function",
+        "function": {
+            "name": name,
+            "arguments": serde_json::to_string(args).unwrap_or_else(|_| "{}".to_string()),
+        }
+    })
+}
+
+/// 读取完整上游响应并组装为 `chat.completion`
+async fn collect_completion(
+    stream: &mut CloudCodeStream,
+    model: &str,
+) -> Result<serde_json::Value, String> {
+    let mut text = String::new();
+    let mut reasoning = String::new();
+    let mut tool_calls: Vec<serde_json::Value> = Vec::new();
+    let mut finish_reason: Option<String> = None;
+    let mut usage = StreamUsage::default();
+
+    while let Some(event) = stream.next_event().await? {
+        for part in event.parts {
+            match part {
+                StreamPart::Text(value) => text.push_str(&value),
+                StreamPart::Thought { text: value, .. } => reasoning.push_str(&value),
+                StreamPart::FunctionCall { id, name, args } => {
+                    tool_calls.push(tool_call_json(id, &name, &args));
+                }
+            }
+        }
+        if event.finish_reason.is_some() {
+            finish_reason = event.finish_reason;
+        }
+        if let Some(value) = event.usage {
+            usage = value;
+        }
+    }
+
+    let mut message = json!({ "role": "assistant", "content": text });
+    if !reasoning.is_empty() {
+        message["reasoning_content"] = json!(reasoning);
+    }
+    if !tool_calls.is_empty() {
+        message["tool_calls"] = json!(tool_calls);
+    }
+
+    Ok(json!({
+        "id": api_proxy::random_id("chatcmpl-"),
+        "object": "chat.completion",
+        "created": api_proxy::now_secs(),
+        "model": model,
+        "choices": [{
+            "index": 0,
+            "message": message,
+            "finish_reason": map_finish_reason(finish_reason.as_deref(), !tool_calls.is_empty()),
+        }],
+        "usage": usage_json(&usage),
+    }))
+}
+
+/// 以 `chat.completion.chunk` SSE 事件逐段转发上游输出
+async fn stream_completion<W: AsyncWrite + Unpin>(
+    writer: &mut W,
+    stream: &mut CloudCodeStream,
+    model: &str,
+    include_usage: bool,
+) -> std::io::Result<()> {
+    let id = api_proxy::random_id("chatcmpl-");
+    let created = api_proxy::now_secs();
+    let chunk = |delta: serde_json::Value, finish_reason: Option<&str>| {
+        json!({
+            "id": id,
+            "object": "chat.completion.chunk",
+            "created": created,
+            "model": model,
+            "choices": [{ "index": 0, "delta": delta, "finish_reason": finish_reason }],
+        })
+        .to_string()
+    };
+
+    api_proxy::start_sse(writer).await?;
+    api_proxy::write_sse(
+        writer,
+        None,
+        &chunk(json!({ "role": "assistant", "content": "" }), None),
+    )
+    .await?;
+
+    let mut tool_call_index = 0usize;
+    let mut finish_reason: Option<String> = None;
+    let mut usage = StreamUsage::default();
+    loop {
+        let event = match stream.next_event().await {
+            Ok(Some(event)) => event,
+            Ok(None) => break,
+            Err(e) => {
+                api_proxy::write_sse(writer, None, &error_body(&e, "api_error").to_string())
+                    .await?;
+                break;
+            }
+        };
+        for part in event.parts {
+            let delta = match part {
+                StreamPart::Text(text) => json!({ "content": text }),
+                StreamPart::Thought { text, .. } if !text.is_empty() => {
+                    json!({ "reasoning_content": text })
+                }
+                StreamPart::Thought { .. } => continue,
+                StreamPart::FunctionCall { id, name, args } => {
+                    let mut call = tool_call_json(id, &name, &args);
+                    call["index"] = json!(tool_call_index);
+                    tool_call_index += 1;
+                    json!({ "tool_calls": [call] })
+                }
+            };
+            api_proxy::write_sse(writer, None, &chunk(delta, None)).await?;
+        }
+        if event.finish_reason.is_some() {
+            finish_reason = event.finish_reason;
+        }
+        if let Some(value) = event.usage {
+            usage = value;
+        }
+    }
+
+    let reason = map_finish_reason(finish_reason.as_deref(), tool_call_index > 0);
+    api_proxy::write_sse(writer, None, &chunk(json!({}), Some(reason))).await?;
+    if include_usage {
+        let usage_chunk = json!({
+            "id": id,
+            "object": "chat.completion.chunk",
+            "created": created,
+            "model": model,
+            "choices": [],
+            "usage": usage_json(&usage),
+        });
+        api_proxy::write_sse(writer, None, &usage_chunk.to_string()).await?;
+    }
+    api_proxy::write_sse(writer, None, "[DONE]").await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncWriteExt, BufReader};
+    use tokio::net::TcpListener;
+
+    fn parse(value: serde_json::Value) -> ChatCompletionRequest {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn translate_request_maps_roles_and_tool_calls() {
+        let request = parse(json!({
+            "model": "gemini-3-pro",
+            "max_tokens": 256,
+            "stop": "END",
+            "messages": [
+                { "role": "system", "content": "be brief" },
+                { "role": "user", "content": [{ "type": "text", "text": "weather?" }] },
+                { "role": "assistant", "content": null, "tool_calls": [
+                    { "id": "call_1", "type": "function", "function": { "name": "get_weather", "arguments": "{\"city\":\"Paris\"}" } }
+                ]},
+                { "role": "tool", "tool_call_id": "call_1", "content": "sunny" }
+            ],
+            "tools": [{ "type": "function", "function": {
+                "name": "get_weather",
+                "parameters": { "$schema": "x", "type": "object", "additionalProperties": false, "properties": { "city": { "type": "string" } } }
+            }}]
+        }));
+
+        let translated = translate_request(&request).unwrap();
+        assert_eq!(translated.system_parts, vec![json!({ "text": "be brief" })]);
+        let contents = translated.contents.as_array().unwrap();
+        assert_eq!(contents.len(), 3);
+        assert_eq!(contents[1]["role"], "model");
+        assert_eq!(
+            contents[1]["parts"][0]["functionCall"]["args"]["city"],
+            "Paris"
+        );
+        assert_eq!(
+            contents[2]["parts"][0]["functionResponse"]["name"],
+            "get_weather"
+        );
+        assert_eq!(translated.generation_config["maxOutputTokens"], 256);
+        assert_eq!(
+            translated.generation_config["stopSequences"],
+            json!(["END"])
+        );
+        let parameters = &translated.tools.unwrap()[0]["functionDeclarations"][0]["parameters"];
+        assert!(parameters.get("$schema").is_none());
+        assert!(parameters.get("additionalProperties").is_none());
+    }
+
+    #[test]
+    fn translate_request_requires_user_content() {
+        let request = parse(json!({
+            "model": "gemini-3-pro",
+            "messages": [{ "role": "system", "content": "only system" }]
+        }));
+        assert!(translate_request(&request).is_err());
+    }
+
+    /// 启动一个本地假 Cloud Code 服务，每个连接都返回同一段 SSE
+    async fn start_fake_cloud_code() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let mut reader = BufReader::new(stream);
+                let request = api_proxy::read_request(&mut reader).await.unwrap().unwrap();
+                assert!(request.path.ends_with(":streamGenerateContent"));
+                assert_eq!(request.header("authorization"), Some("Bearer test-token"));
+                let mut stream = reader.into_inner();
+                let events = [
+                    json!({ "response": { "candidates": [{ "content": { "parts": [{ "text": "thinking", "thought": true }] } }] } }),
+                    json!({ "response": { "candidates": [{ "content": { "parts": [{ "text": "Hel" }] } }] } }),
+                    json!({ "response": {
+                        "candidates": [{ "content": { "parts": [{ "text": "lo" }] }, "finishReason": "STOP" }],
+                        "usageMetadata": { "promptTokenCount": 3, "candidatesTokenCount": 2, "totalTokenCount": 5 }
+                    } }),
+                ];
+                let mut body = String::new();
+                for event in events {
+                    body.push_str(&format!("data: {}\r\n\r\n", event));
+                }
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n{}",
+                    body
+                );
+                stream.write_all(response.as_bytes()).await.unwrap();
+                let _ = stream.shutdown().await;
+            }
+        });
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn relays_fake_cloud_code_stream_as_openai_responses() {
+        let base_urls = vec![start_fake_cloud_code().await];
+
+        let client = reqwest::Client::new();
+        let body = wakeup::build_generate_body(
+            "test-project",
+            "gemini-3-pro",
+            json!([{ "role": "user", "parts": [{ "text": "hi" }] }]),
+            Vec::new(),
+            json!({}),
+            None,
+        );
+
+        let mut stream = wakeup::open_stream_at(&client, &base_urls, "test-token", &body)
+            .await
+            .unwrap();
+        let response = collect_completion(&mut stream, "gemini-3-pro")
+            .await
+            .unwrap();
+        assert_eq!(response["choices"][0]["message"]["content"], "Hello");
+        assert_eq!(
+            response["choices"][0]["message"]["reasoning_content"],
+            "thinking"
+        );
+        assert_eq!(response["choices"][0]["finish_reason"], "stop");
+        assert_eq!(response["usage"]["total_tokens"], 5);
+
+        let mut stream = wakeup::open_stream_at(&client, &base_urls, "test-token", &body)
+            .await
+            .unwrap();
+        let mut output: Vec<u8> = Vec::new();
+        stream_completion(&mut output, &mut stream, "gemini-3-pro", true)
+            .await
+            .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.starts_with("HTTP/1.1 200 OK\r\nContent-Type: text/event-stream"));
+        assert!(output.contains("\"content\":\"Hel\""));
+        assert!(output.contains("\"finish_reason\":\"stop\""));
+        assert!(output.contains("\"total_tokens\":5"));
+        assert!(output.trim_end().ends_with("data: [DONE]"));
+    }
+}
@@ -0,0 +1,111 @@
+//! 数据文件原子写入
+//!
+//! 写入流程：同目录临时文件 → fsync → rename 覆盖目标文件，崩溃或断电时目标文件只会是
+//! 旧内容或新内容，不会出现写了一半的 JSON。`write` 额外持有 `<文件名>.lock` 咨询锁，
+//! 与同时写入共享数据文件的 VS Code 扩展等其他进程互斥；写入其他应用的文件（如
+//! `~/.codex/auth.json`）时使用 `replace`，不在对方目录留下锁文件。
+
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// 临时文件序号，避免同一进程内并发写入同一文件时临时文件互相覆盖
+static TEMP_SEQ: AtomicU64 = AtomicU64::new(0);
+
+fn file_name(path: &Path) -> Result<String, String> {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| format!("无效的文件路径: {}", path.display()))
+}
+
+/// 目标文件对应的锁文件路径
+fn lock_path(path: &Path) -> Result<PathBuf, String> {
+    Ok(path.with_file_name(format!("{}.lock", file_name(path)?)))
+}
+
+/// 持有目标文件的跨进程写锁，锁在返回值释放时自动解除
+pub fn lock(path: &Path) -> Result<File, String> {
+    let lock_path = lock_path(path)?;
+    if let Some(parent) = lock_path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
+    }
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .read(true)
+        .write(true)
+        .open(&lock_path)
+        .map_err(|e| format!("打开锁文件失败({}): {}", lock_path.display(), e))?;
+    file.lock()
+        .map_err(|e| format!("获取文件锁失败({}): {}", lock_path.display(), e))?;
+    Ok(file)
+}
+
+/// 原子替换文件内容（不加锁）
+pub fn replace(path: &Path, content: impl AsRef<[u8]>) -> Result<(), String> {
+    let parent = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
+
+    let temp_path = parent.join(format!(
+        ".{}.{}.{}.tmp",
+        file_name(path)?,
+        std::process::id(),
+        TEMP_SEQ.fetch_add(1, Ordering::Relaxed)
+    ));
+    let result = write_and_sync(&temp_path, content.as_ref())
+        .and_then(|_| fs::rename(&temp_path, path).map_err(|e| format!("替换文件失败: {}", e)));
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+        return result;
+    }
+
+    // 目录项同步到磁盘后 rename 才算持久化（Windows 不支持对目录 fsync）
+    #[cfg(unix)]
+    if let Ok(dir) = File::open(parent) {
+        let _ = dir.sync_all();
+    }
+    Ok(())
+}
+
+/// 加锁后原子替换文件内容
+pub fn write(path: &Path, content: impl AsRef<[u8]>) -> Result<(), String> {
+    let _lock = lock(path)?;
+    replace(path, content)
+}
+
+fn write_and_sync(path: &Path, content: &[u8]) -> Result<(), String> {
+    let mut file = File::create(path).map_err(|e| format!("写入临时文件失败: {}", e))?;
+    file.write_all(content)
+        .map_err(|e| format!("写入临时文件失败: {}", e))?;
+    file.sync_all()
+        .map_err(|e| format!("同步临时文件失败: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replaces_content_without_leaving_temp_files() {
+        let dir = std::env::temp_dir().join(format!("cockpit-atomic-file-{}", std::process::id()));
+        let path = dir.join("store.json");
+
+        write(&path, "{\"v\":1}").unwrap();
+        write(&path, "{\"v\":2}").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"v\":2}");
+
+        let mut names: Vec<String> = fs::read_dir(&dir)
+            .unwrap()
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["store.json", "store.json.lock"]);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,487 @@
+//! 账号操作审计日志
+//!
+//! 切号、注入、删除、改标签、导入与自动切号决策写入数据库 `audit_log` 表（只允许追加，
+//! 由触发器禁止修改和删除），记录操作来源、执行者（系统用户@主机名）与结果。
+//! 操作来源通过 `with_source` 在异步任务内传递，未指定时视为界面操作。
+
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{params, params_from_iter};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use crate::modules::data_store::{self, AccountPlatform};
+use crate::modules::{atomic_file, logger};
+
+/// 单次查询最多返回的条数
+const MAX_QUERY_LIMIT: usize = 5000;
+
+tokio::task_local! {
+    static CURRENT_SOURCE: AuditSource;
+}
+
+/// 执行者：系统用户名@主机名
+static ACTOR: LazyLock<String> = LazyLock::new(|| {
+    let user = std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string());
+    let host = sysinfo::System::host_name().unwrap_or_else(|| "unknown".to_string());
+    format!("{}@{}", user, host)
+});
+
+/// 操作来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditSource {
+    Ui,
+    WebSocket,
+    Scheduler,
+    AutoSwitch,
+    Cli,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Switch,
+    Inject,
+    Delete,
+    Tag,
+    Import,
+    /// 从回收站恢复
+    Restore,
+    /// 从回收站彻底删除
+    Purge,
+    /// 自动切号决策（是否切换、切到哪个账号）
+    AutoSwitch,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOutcome {
+    Success,
+    Failure,
+    /// 未执行（如自动切号没有可用候选账号）
+    Skipped,
+}
+
+macro_rules! impl_as_str {
+    ($ty:ty { $($variant:ident => $name:literal),+ $(,)? }) => {
+        impl $ty {
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $name),+
+                }
+            }
+
+            fn parse(value: &str) -> Option<Self> {
+                match value {
+                    $($name => Some(Self::$variant),)+
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+impl_as_str!(AuditSource {
+    Ui => "ui",
+    WebSocket => "web_socket",
+    Scheduler => "scheduler",
+    AutoSwitch => "auto_switch",
+    Cli => "cli",
+});
+
+impl_as_str!(AuditAction {
+    Switch => "switch",
+    Inject => "inject",
+    Delete => "delete",
+    Tag => "tag",
+    Import => "import",
+    Restore => "restore",
+    Purge => "purge",
+    AutoSwitch => "auto_switch",
+});
+
+impl_as_str!(AuditOutcome {
+    Success => "success",
+    Failure => "failure",
+    Skipped => "skipped",
+});
+
+/// 审计日志条目
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    pub id: i64,
+    /// 记录时间（秒）
+    pub created_at: i64,
+    pub platform: String,
+    pub action: AuditAction,
+    pub account_id: Option<String>,
+    /// 账号邮箱或登录名（记录时解析，账号删除后仍可辨认）
+    pub account: Option<String>,
+    pub source: AuditSource,
+    pub actor: String,
+    pub outcome: AuditOutcome,
+    pub error: Option<String>,
+    pub detail: Option<String>,
+}
+
+/// 查询条件，均为可选
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditQuery {
+    pub platform: Option<String>,
+    pub action: Option<AuditAction>,
+    pub source: Option<AuditSource>,
+    pub outcome: Option<AuditOutcome>,
+    /// 匹配账号 ID 或邮箱（包含即可）
+    pub account: Option<String>,
+    /// 起始时间（秒，含）
+    pub since: Option<i64>,
+    /// 结束时间（秒，含）
+    pub until: Option<i64>,
+    pub limit: Option<usize>,
+    pub offset: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditExportFormat {
+    Json,
+    Csv,
+}
+
+/// 待写入的审计事件
+pub struct AuditEvent {
+    platform: AccountPlatform,
+    action: AuditAction,
+    account_id: Option<String>,
+    account: Option<String>,
+    outcome: AuditOutcome,
+    error: Option<String>,
+    detail: Option<String>,
+}
+
+impl AuditEvent {
+    pub fn new(platform: AccountPlatform, action: AuditAction) -> Self {
+        Self {
+            platform,
+            action,
+            account_id: None,
+            account: None,
+            outcome: AuditOutcome::Success,
+            error: None,
+            detail: None,
+        }
+    }
+
+    /// 关联账号，并立即从本地数据解析邮箱（删除前调用才能保留邮箱）
+    pub fn account(mut self, account_id: &str) -> Self {
+        self.account = data_store::account_label(self.platform, account_id);
+        self.account_id = Some(account_id.to_string());
+        self
+    }
+
+    /// 关联回收站中的账号，邮箱使用移入回收站时记录的标识
+    pub fn trashed_account(mut self, account_id: &str, label: Option<String>) -> Self {
+        self.account = label;
+        self.account_id = Some(account_id.to_string());
+        self
+    }
+
+    pub fn detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    pub fn result<T>(mut self, result: &Result<T, String>) -> Self {
+        match result {
+            Ok(_) => self.outcome = AuditOutcome::Success,
+            Err(e) => {
+                self.outcome = AuditOutcome::Failure;
+                self.error = Some(e.clone());
+            }
+        }
+        self
+    }
+
+    pub fn skipped(mut self) -> Self {
+        self.outcome = AuditOutcome::Skipped;
+        self
+    }
+
+    /// 写入审计日志（失败只记录日志，不影响操作本身）
+    pub fn record(self) {
+        let source = current_source();
+        let result = data_store::with_connection(|conn| {
+            conn.execute(
+                "INSERT INTO audit_log (created_at, platform, action, account_id, account, source, actor, outcome, error, detail)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    chrono::Utc::now().timestamp(),
+                    self.platform.as_str(),
+                    self.action.as_str(),
+                    self.account_id,
+                    self.account,
+                    source.as_str(),
+                    ACTOR.as_str(),
+                    self.outcome.as_str(),
+                    self.error,
+                    self.detail,
+                ],
+            )
+            .map_err(data_store::db_error)
+        });
+        if let Err(e) = result {
+            logger::log_warn(&format!("[Audit] 写入审计日志失败: {}", e));
+        }
+    }
+}
+
+/// 在指定来源下执行异步操作，期间写入的审计日志都记为该来源
+pub async fn with_source<F: Future>(source: AuditSource, future: F) -> F::Output {
+    CURRENT_SOURCE.scope(source, future).await
+}
+
+/// 当前操作来源，未指定时为界面操作
+pub fn current_source() -> AuditSource {
+    CURRENT_SOURCE
+        .try_with(|source| *source)
+        .unwrap_or(AuditSource::Ui)
+}
+
+/// 记录一次导入，`method` 为导入方式（json / local / extension 等）
+pub fn record_import(platform: AccountPlatform, method: &str, result: Result<usize, &String>) {
+    let event = AuditEvent::new(platform, AuditAction::Import);
+    match result {
+        Ok(count) => event.detail(format!("{}: {} 个账号", method, count)),
+        Err(e) => event.detail(method).result::<()>(&Err(e.clone())),
+    }
+    .record();
+}
+
+/// 记录一次向实例目录注入账号
+pub fn record_inject<T>(
+    platform: AccountPlatform,
+    account_id: &str,
+    profile_dir: &Path,
+    result: &Result<T, String>,
+) {
+    AuditEvent::new(platform, AuditAction::Inject)
+        .account(account_id)
+        .detail(profile_dir.to_string_lossy())
+        .result(result)
+        .record();
+}
+
+/// 自动切号决策事件，调用方补充目标账号与结果（无候选账号时记为 skipped）
+pub fn auto_switch_event(
+    platform: AccountPlatform,
+    current_id: &str,
+    threshold: i32,
+    strategy: &str,
+) -> AuditEvent {
+    AuditEvent::new(platform, AuditAction::AutoSwitch).detail(format!(
+        "from={}, threshold={}%, strategy={}",
+        current_id, threshold, strategy
+    ))
+}
+
+/// 按条件查询审计日志，最新的在前（每次最多 `MAX_QUERY_LIMIT` 条）
+pub fn query(query: &AuditQuery) -> Result<Vec<AuditEntry>, String> {
+    let limit = query.limit.unwrap_or(MAX_QUERY_LIMIT).min(MAX_QUERY_LIMIT);
+    select(query, Some(limit))
+}
+
+/// 按条件读取审计日志，`limit` 为 None 时不限条数（导出使用）
+fn select(query: &AuditQuery, limit: Option<usize>) -> Result<Vec<AuditEntry>, String> {
+    let mut conditions: Vec<&str> = Vec::new();
+    let mut values: Vec<SqlValue> = Vec::new();
+    let mut push = |condition: &'static str, value: SqlValue| {
+        conditions.push(condition);
+        values.push(value);
+    };
+    if let Some(platform) = query.platform.as_deref().filter(|p| !p.is_empty()) {
+        push("platform = ?", SqlValue::Text(platform.to_string()));
+    }
+    if let Some(action) = query.action {
+        push("action = ?", SqlValue::Text(action.as_str().to_string()));
+    }
+    if let Some(source) = query.source {
+        push("source = ?", SqlValue::Text(source.as_str().to_string()));
+    }
+    if let Some(outcome) = query.outcome {
+        push("outcome = ?", SqlValue::Text(outcome.as_str().to_string()));
+    }
+    if let Some(account) = query
+        .account
+        .as_deref()
+        .map(str::trim)
+        .filter(|a| !a.is_empty())
+    {
+        push(
+            "instr(lower(coalesce(account_id, '') || char(10) || coalesce(account, '')), lower(?)) > 0",
+            SqlValue::Text(account.to_string()),
+        );
+    }
+    if let Some(since) = query.since {
+        push("created_at >= ?", SqlValue::Integer(since));
+    }
+    if let Some(until) = query.until {
+        push("created_at <= ?", SqlValue::Integer(until));
+    }
+
+    let mut sql = "SELECT id, created_at, platform, action, account_id, account, source, actor, outcome, error, detail FROM audit_log".to_string();
+    if !conditions.is_empty() {
+        sql.push_str(" WHERE ");
+        sql.push_str(&conditions.join(" AND "));
+    }
+    sql.push_str(" ORDER BY id DESC LIMIT ? OFFSET ?");
+    // SQLite 中 LIMIT -1 表示不限条数
+    values.push(SqlValue::Integer(limit.map_or(-1, |limit| limit as i64)));
+    values.push(SqlValue::Integer(query.offset.unwrap_or(0) as i64));
+
+    data_store::with_connection(|conn| {
+        let mut stmt = conn.prepare(&sql).map_err(data_store::db_error)?;
+        let rows = stmt
+            .query_map(params_from_iter(values), |row| {
+                let text = |index: usize| row.get::<_, String>(index);
+                let id: i64 = row.get(0)?;
+                let (action, source, outcome) = (text(3)?, text(6)?, text(8)?);
+                // 无法识别的记录（如较新版本写入的操作类型）跳过，不按其他类型展示
+                let (Some(action), Some(source), Some(outcome)) = (
+                    AuditAction::parse(&action),
+                    AuditSource::parse(&source),
+                    AuditOutcome::parse(&outcome),
+                ) else {
+                    logger::log_warn(&format!(
+                        "[Audit] 跳过无法识别的审计记录: id={}, action={}, source={}, outcome={}",
+                        id, action, source, outcome
+                    ));
+                    return Ok(None);
+                };
+                Ok(Some(AuditEntry {
+                    id,
+                    created_at: row.get(1)?,
+                    platform: text(2)?,
+                    action,
+                    account_id: row.get(4)?,
+                    account: row.get(5)?,
+                    source,
+                    actor: text(7)?,
+                    outcome,
+                    error: row.get(9)?,
+                    detail: row.get(10)?,
+                }))
+            })
+            .map_err(data_store::db_error)?;
+        rows.filter_map(Result::transpose)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(data_store::db_error)
+    })
+}
+
+/// 导出符合条件的全部审计日志（不受单次查询条数上限限制），返回导出条数
+pub fn export(
+    target: &Path,
+    format: AuditExportFormat,
+    query: &AuditQuery,
+) -> Result<usize, String> {
+    let entries = select(
+        &AuditQuery {
+            offset: None,
+            ..query.clone()
+        },
+        None,
+    )?;
+    let content = match format {
+        AuditExportFormat::Json => serde_json::to_string_pretty(&entries)
+            .map_err(|e| format!("序列化审计日志失败: {}", e))?,
+        AuditExportFormat::Csv => to_csv(&entries),
+    };
+    atomic_file::replace(target, content)?;
+    logger::log_info(&format!(
+        "[Audit] 已导出 {} 条审计日志: {}",
+        entries.len(),
+        target.display()
+    ));
+    Ok(entries.len())
+}
+
+fn to_csv(entries: &[AuditEntry]) -> String {
+    fn escape(value: &str) -> String {
+        if value.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_string()
+        }
+    }
+
+    let mut csv =
+        String::from("time,platform,action,account_id,account,source,actor,outcome,error,detail\n");
+    for entry in entries {
+        let time = chrono::DateTime::from_timestamp(entry.created_at, 0)
+            .map(|time| time.to_rfc3339())
+            .unwrap_or_default();
+        let fields = [
+            time.as_str(),
+            entry.platform.as_str(),
+            entry.action.as_str(),
+            entry.account_id.as_deref().unwrap_or(""),
+            entry.account.as_deref().unwrap_or(""),
+            entry.source.as_str(),
+            entry.actor.as_str(),
+            entry.outcome.as_str(),
+            entry.error.as_deref().unwrap_or(""),
+            entry.detail.as_deref().unwrap_or(""),
+        ];
+        let line: Vec<String> = fields.iter().map(|field| escape(field)).collect();
+        csv.push_str(&line.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn source_defaults_to_ui_and_follows_scope() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        assert_eq!(current_source(), AuditSource::Ui);
+        let nested = runtime.block_on(with_source(AuditSource::Scheduler, async {
+            let outer = current_source();
+            let inner = with_source(AuditSource::AutoSwitch, async { current_source() }).await;
+            (outer, inner)
+        }));
+        assert_eq!(nested, (AuditSource::Scheduler, AuditSource::AutoSwitch));
+    }
+
+    #[test]
+    fn csv_export_escapes_fields() {
+        let entry = AuditEntry {
+            id: 1,
+            created_at: 0,
+            platform: "kiro".to_string(),
+            action: AuditAction::Tag,
+            account_id: Some("kiro_1".to_string()),
+            account: Some("a@example.com".to_string()),
+            source: AuditSource::Ui,
+            actor: "me@host".to_string(),
+            outcome: AuditOutcome::Success,
+            error: None,
+            detail: Some("tags: \"a\", b".to_string()),
+        };
+        let csv = to_csv(&[entry]);
+        assert_eq!(
+            csv.lines().nth(1).unwrap(),
+            "1970-01-01T00:00:00+00:00,kiro,tag,kiro_1,a@example.com,ui,me@host,success,,\"tags: \"\"a\"\", b\""
+        );
+    }
+}
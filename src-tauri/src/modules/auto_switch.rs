@@ -0,0 +1,288 @@
+//! 自动切号通用逻辑
+//!
+//! 各平台负责提取配额指标、判断候选资格并执行切号，这里只负责判断是否需要切号，
+//! 以及按配置的选号策略从候选账号中挑选切换目标。
+
+use std::cmp::Ordering;
+
+use crate::modules::{group_settings, logger};
+
+/// 参与自动切号评估的账号视图（按平台账号列表顺序传入）
+#[derive(Debug, Clone)]
+pub struct SwitchCandidate {
+    pub id: String,
+    /// 配额指标：(名称, 剩余百分比)
+    pub metrics: Vec<(String, i32)>,
+    pub last_used: i64,
+    pub tags: Vec<String>,
+    /// 是否满足平台的候选条件（配额高于阈值、未禁用等）
+    pub eligible: bool,
+}
+
+/// 自动切号选号策略
+#[derive(Debug, Clone, PartialEq)]
+pub enum SelectionStrategy {
+    /// 平均剩余配额最高者优先（默认）
+    HighestQuota,
+    /// 按账号列表顺序轮换到当前账号之后的下一个可用账号
+    RoundRobin,
+    /// 最久未使用的账号优先
+    LeastRecentlyUsed,
+    /// 指定模型分组内剩余配额最高者优先（分组内取最低值）；
+    /// 指标名可以是分组内的模型名，也可以是已按分组汇总的分组 ID
+    GroupQuota {
+        group_id: String,
+        models: Vec<String>,
+    },
+    /// 仅在带指定标签的账号中按剩余配额挑选
+    Tagged(String),
+    /// 按账号列表顺序（拖拽排序）选择排在最前的可用账号
+    Priority,
+}
+
+impl SelectionStrategy {
+    /// 从配置解析选号策略，`param` 为分组 ID 或标签名；无法识别时回退为默认策略
+    pub fn from_config(kind: &str, param: &str) -> Self {
+        let param = param.trim();
+        match kind.trim() {
+            "round_robin" => SelectionStrategy::RoundRobin,
+            "least_recently_used" => SelectionStrategy::LeastRecentlyUsed,
+            "priority" => SelectionStrategy::Priority,
+            "group_quota" if !param.is_empty() => {
+                let models = group_settings::load_group_settings().get_models_in_group(param);
+                if models.is_empty() {
+                    logger::log_warn(&format!(
+                        "[AutoSwitch] 分组 {} 不存在或不含模型，回退为默认选号策略",
+                        param
+                    ));
+                    return SelectionStrategy::HighestQuota;
+                }
+                SelectionStrategy::GroupQuota {
+                    group_id: param.to_string(),
+                    models,
+                }
+            }
+            "tag" if !param.is_empty() => SelectionStrategy::Tagged(param.to_lowercase()),
+            _ => SelectionStrategy::HighestQuota,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SelectionStrategy::HighestQuota => "highest_quota",
+            SelectionStrategy::RoundRobin => "round_robin",
+            SelectionStrategy::LeastRecentlyUsed => "least_recently_used",
+            SelectionStrategy::GroupQuota { .. } => "group_quota",
+            SelectionStrategy::Tagged(_) => "tag",
+            SelectionStrategy::Priority => "priority",
+        }
+    }
+}
+
+pub fn normalize_threshold(raw: i32) -> i32 {
+    raw.clamp(0, 100)
+}
+
+/// 任一配额指标低于或等于阈值时触发切号
+pub fn should_trigger(metrics: &[(String, i32)], threshold: i32) -> bool {
+    metrics.iter().any(|(_, pct)| *pct <= threshold)
+}
+
+/// 所有配额指标都高于阈值（避免切过去后立即再次触发）
+pub fn is_above_threshold(metrics: &[(String, i32)], threshold: i32) -> bool {
+    !metrics.is_empty() && metrics.iter().all(|(_, pct)| *pct > threshold)
+}
+
+fn average_percentage(metrics: &[(String, i32)]) -> f64 {
+    if metrics.is_empty() {
+        return 0.0;
+    }
+    let sum: i32 = metrics.iter().map(|(_, pct)| *pct).sum();
+    sum as f64 / metrics.len() as f64
+}
+
+fn group_percentage(metrics: &[(String, i32)], group_id: &str, models: &[String]) -> Option<i32> {
+    metrics
+        .iter()
+        .filter(|(name, _)| name == group_id || models.iter().any(|model| model == name))
+        .map(|(_, pct)| *pct)
+        .min()
+}
+
+fn by_quota(a: &SwitchCandidate, b: &SwitchCandidate) -> Ordering {
+    average_percentage(&b.metrics)
+        .partial_cmp(&average_percentage(&a.metrics))
+        .unwrap_or(Ordering::Equal)
+        .then_with(|| a.last_used.cmp(&b.last_used))
+}
+
+/// 按选号策略挑选切换目标
+///
+/// `candidates` 需包含当前账号并保持平台账号列表顺序，轮换与优先级策略依赖该顺序。
+pub fn pick_candidate<'a>(
+    candidates: &'a [SwitchCandidate],
+    current_id: &str,
+    strategy: &SelectionStrategy,
+) -> Option<&'a SwitchCandidate> {
+    let current_pos = candidates
+        .iter()
+        .position(|candidate| candidate.id == current_id);
+    let mut eligible: Vec<(usize, &SwitchCandidate)> = candidates
+        .iter()
+        .enumerate()
+        .filter(|(_, candidate)| candidate.eligible && candidate.id != current_id)
+        .collect();
+
+    match strategy {
+        SelectionStrategy::HighestQuota => eligible.sort_by(|a, b| by_quota(a.1, b.1)),
+        SelectionStrategy::RoundRobin => {
+            // 从当前账号之后开始循环，当前账号不在列表中时从头开始
+            let start = current_pos.map(|pos| pos + 1).unwrap_or(0);
+            let len = candidates.len();
+            eligible.sort_by_key(|(index, _)| (index + len - start) % len);
+        }
+        SelectionStrategy::LeastRecentlyUsed => eligible.sort_by(|a, b| {
+            a.1.last_used
+                .cmp(&b.1.last_used)
+                .then_with(|| by_quota(a.1, b.1))
+        }),
+        SelectionStrategy::GroupQuota { group_id, models } => {
+            eligible.retain(|(_, candidate)| {
+                group_percentage(&candidate.metrics, group_id, models).is_some()
+            });
+            eligible.sort_by(|a, b| {
+                group_percentage(&b.1.metrics, group_id, models)
+                    .cmp(&group_percentage(&a.1.metrics, group_id, models))
+                    .then_with(|| a.1.last_used.cmp(&b.1.last_used))
+            });
+        }
+        SelectionStrategy::Tagged(tag) => {
+            eligible.retain(|(_, candidate)| {
+                candidate
+                    .tags
+                    .iter()
+                    .any(|item| item.trim().eq_ignore_ascii_case(tag))
+            });
+            eligible.sort_by(|a, b| by_quota(a.1, b.1));
+        }
+        SelectionStrategy::Priority => {}
+    }
+
+    eligible.into_iter().next().map(|(_, candidate)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(id: &str, metrics: &[i32], last_used: i64) -> SwitchCandidate {
+        let metrics: Vec<(String, i32)> = metrics
+            .iter()
+            .enumerate()
+            .map(|(i, pct)| (format!("m{}", i), *pct))
+            .collect();
+        SwitchCandidate {
+            id: id.to_string(),
+            eligible: is_above_threshold(&metrics, 5),
+            metrics,
+            last_used,
+            tags: Vec::new(),
+        }
+    }
+
+    fn pick(candidates: &[SwitchCandidate], current: &str, strategy: SelectionStrategy) -> String {
+        pick_candidate(candidates, current, &strategy)
+            .map(|candidate| candidate.id.clone())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn picks_highest_average_above_threshold() {
+        let candidates = vec![
+            candidate("current", &[100, 100], 0),
+            candidate("low", &[90, 3], 0),
+            candidate("mid", &[50, 50], 0),
+            candidate("high", &[80, 70], 0),
+            candidate("unknown", &[], 0),
+        ];
+        let target = pick_candidate(&candidates, "current", &SelectionStrategy::HighestQuota);
+        assert_eq!(target.unwrap().id, "high");
+    }
+
+    #[test]
+    fn prefers_least_recently_used_on_tie() {
+        let candidates = vec![candidate("a", &[60], 20), candidate("b", &[60], 10)];
+        assert_eq!(pick(&candidates, "x", SelectionStrategy::HighestQuota), "b");
+        assert!(should_trigger(&[("m".to_string(), 5)], 5));
+        assert!(!should_trigger(&[("m".to_string(), 6)], 5));
+    }
+
+    #[test]
+    fn order_based_strategies_follow_list_order() {
+        let candidates = vec![
+            candidate("a", &[30], 0),
+            candidate("b", &[90], 0),
+            candidate("current", &[3], 0),
+            candidate("c", &[1], 0),
+            candidate("d", &[40], 0),
+        ];
+        assert_eq!(
+            pick(&candidates, "current", SelectionStrategy::RoundRobin),
+            "d"
+        );
+        assert_eq!(pick(&candidates, "d", SelectionStrategy::RoundRobin), "a");
+        assert_eq!(
+            pick(&candidates, "current", SelectionStrategy::Priority),
+            "a"
+        );
+    }
+
+    #[test]
+    fn filtering_strategies_respect_tags_groups_and_usage() {
+        let mut tagged = candidate("tagged", &[40, 40], 30);
+        tagged.tags = vec!["Team-A".to_string()];
+        let mut group = candidate("group", &[20, 95], 20);
+        group.metrics[1].0 = "claude-sonnet-4-5".to_string();
+        let claude_group = SelectionStrategy::GroupQuota {
+            group_id: "claude_45".to_string(),
+            models: vec!["claude-sonnet-4-5".to_string()],
+        };
+        let candidates = vec![
+            candidate("current", &[1], 0),
+            candidate("untagged", &[90, 90], 10),
+            tagged,
+            group,
+        ];
+
+        assert_eq!(
+            pick(
+                &candidates,
+                "current",
+                SelectionStrategy::Tagged("team-a".to_string())
+            ),
+            "tagged"
+        );
+        assert_eq!(pick(&candidates, "current", claude_group.clone()), "group");
+        let mut grouped = candidates.clone();
+        grouped.push(SwitchCandidate {
+            id: "grouped".to_string(),
+            metrics: vec![("claude_45".to_string(), 98)],
+            last_used: 40,
+            tags: Vec::new(),
+            eligible: true,
+        });
+        assert_eq!(pick(&grouped, "current", claude_group), "grouped");
+        assert_eq!(
+            pick(&candidates, "current", SelectionStrategy::LeastRecentlyUsed),
+            "untagged"
+        );
+        assert_eq!(
+            pick(
+                &candidates,
+                "current",
+                SelectionStrategy::Tagged("none".to_string())
+            ),
+            ""
+        );
+    }
+}
@@ -0,0 +1,623 @@
+//! 数据目录完整备份与恢复
+//!
+//! 备份归档是单个 JSON 文件：头部记录归档格式版本、应用版本与数据格式版本，正文包含
+//! 清单（路径、所属分区、大小、SHA-256）与全部文件内容。可选用口令加密正文。
+//! 条目路径：
+//! - `accounts/<平台>/<账号 ID>.json`：账号详情（解密后的明文，恢复时按本机凭据库重新加密）
+//! - `documents/<文档名>`：账号索引与实例配置
+//! - `files/<文件名>`：数据目录下的设置文件
+//!
+//! 恢复按分区（五个平台 + 设置）选择性执行：平台分区整体替换为归档内容，
+//! 设置分区只覆盖归档中包含的文件。恢复前会先快照当前数据。
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::modules::data_store::{self, AccountPlatform};
+use crate::modules::{account, atomic_file, config, group_settings, logger, migration, vault};
+
+/// 归档格式标识
+const ARCHIVE_FORMAT: &str = "cockpit-backup";
+/// 当前归档格式版本
+const ARCHIVE_VERSION: u32 = 1;
+/// 设置分区名
+const SETTINGS_SECTION: &str = "settings";
+/// 快照目录
+const SNAPSHOT_DIR: &str = "backups";
+
+/// 设置分区包含的数据目录文件
+const SETTINGS_FILES: &[&str] = &[
+    "config.json",
+    group_settings::GROUP_SETTINGS_FILE,
+    "tray_layout.json",
+    "fingerprints.json",
+    "device_original.json",
+    "wakeup_history.json",
+];
+
+/// 归档文件
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BackupArchive {
+    format: String,
+    version: u32,
+    app_version: String,
+    data_version: u32,
+    created_at: i64,
+    /// 口令加密时的盐（Base64），未加密时为 None
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    salt: Option<String>,
+    /// 未加密的正文
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    body: Option<ArchiveBody>,
+    /// 加密后的正文
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    sealed: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ArchiveBody {
+    manifest: Vec<ManifestEntry>,
+    files: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ManifestEntry {
+    path: String,
+    section: String,
+    size: usize,
+    sha256: String,
+}
+
+/// 创建备份的结果
+#[derive(Debug, Clone, Serialize)]
+pub struct BackupSummary {
+    pub path: String,
+    pub entries: usize,
+    pub encrypted: bool,
+}
+
+/// 单个分区的差异
+#[derive(Debug, Clone, Serialize)]
+pub struct SectionDiff {
+    pub section: String,
+    /// 归档中有、本机没有
+    pub added: Vec<String>,
+    /// 两边都有但内容不同
+    pub changed: Vec<String>,
+    /// 本机有、归档中没有（恢复平台分区时会被删除）
+    pub removed: Vec<String>,
+    pub unchanged: usize,
+}
+
+/// 恢复预览
+#[derive(Debug, Clone, Serialize)]
+pub struct RestorePreview {
+    pub app_version: String,
+    pub data_version: u32,
+    pub created_at: i64,
+    pub encrypted: bool,
+    pub sections: Vec<SectionDiff>,
+}
+
+/// 恢复结果
+#[derive(Debug, Clone, Serialize)]
+pub struct RestoreResult {
+    pub sections: Vec<String>,
+    pub written: usize,
+    pub removed: usize,
+    pub snapshot_path: String,
+}
+
+/// 当前数据中的一个条目
+struct Entry {
+    section: String,
+    content: String,
+}
+
+fn sha256_hex(content: &str) -> String {
+    Sha256::digest(content.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn account_path(platform: AccountPlatform, id: &str) -> String {
+    format!("accounts/{}/{}.json", platform.as_str(), id)
+}
+
+fn document_path(name: &str) -> String {
+    format!("documents/{}", name)
+}
+
+fn file_path(name: &str) -> String {
+    format!("files/{}", name)
+}
+
+fn all_sections() -> Vec<&'static str> {
+    AccountPlatform::ALL
+        .iter()
+        .map(|platform| platform.as_str())
+        .chain(std::iter::once(SETTINGS_SECTION))
+        .collect()
+}
+
+/// 条目路径对应的分区，路径不合法时返回 None
+fn section_of(path: &str) -> Option<String> {
+    let valid_name =
+        |name: &str| !name.is_empty() && !name.starts_with('.') && !name.contains(['/', '\\']);
+    if let Some(rest) = path.strip_prefix("accounts/") {
+        let (platform, file) = rest.split_once('/')?;
+        let id = file.strip_suffix(".json")?;
+        if !valid_name(id) {
+            return None;
+        }
+        return AccountPlatform::parse(platform).map(|platform| platform.as_str().to_string());
+    }
+    if let Some(name) = path.strip_prefix("documents/") {
+        return AccountPlatform::ALL
+            .into_iter()
+            .find(|platform| {
+                platform.index_name() == name || platform.instance_store_name() == name
+            })
+            .map(|platform| platform.as_str().to_string());
+    }
+    if let Some(name) = path.strip_prefix("files/") {
+        return SETTINGS_FILES
+            .contains(&name)
+            .then(|| SETTINGS_SECTION.to_string());
+    }
+    None
+}
+
+/// 按归档路径收集本机当前数据（账号详情为解密后的内容，任一账号无法解密时返回错误）
+fn collect_current(sections: &[&str]) -> Result<BTreeMap<String, Entry>, String> {
+    let mut entries = BTreeMap::new();
+    for platform in AccountPlatform::ALL {
+        let section = platform.as_str();
+        if !sections.contains(&section) {
+            continue;
+        }
+        for name in [platform.index_name(), platform.instance_store_name()] {
+            if let Some(content) = data_store::load_document(name)? {
+                entries.insert(
+                    document_path(name),
+                    Entry {
+                        section: section.to_string(),
+                        content,
+                    },
+                );
+            }
+        }
+        for (id, content) in data_store::load_accounts_strict(platform)? {
+            entries.insert(
+                account_path(platform, &id),
+                Entry {
+                    section: section.to_string(),
+                    content,
+                },
+            );
+        }
+    }
+    if sections.contains(&SETTINGS_SECTION) {
+        let data_dir = account::get_data_dir()?;
+        for name in SETTINGS_FILES {
+            let path = data_dir.join(name);
+            if !path.is_file() {
+                continue;
+            }
+            let content =
+                fs::read_to_string(&path).map_err(|e| format!("读取 {} 失败: {}", name, e))?;
+            entries.insert(
+                file_path(name),
+                Entry {
+                    section: SETTINGS_SECTION.to_string(),
+                    content,
+                },
+            );
+        }
+    }
+    Ok(entries)
+}
+
+/// 把整个数据目录（数据库快照 + 顶层 JSON 文件）复制到 `backups/<label>-<时间>/`，
+/// 用于迁移、恢复等破坏性操作之前
+pub fn snapshot_data_dir(label: &str) -> Result<PathBuf, String> {
+    let data_dir = account::get_data_dir()?;
+    let target = data_dir.join(SNAPSHOT_DIR).join(format!(
+        "{}-{}",
+        label,
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    fs::create_dir_all(&target).map_err(|e| format!("创建备份目录失败: {}", e))?;
+
+    data_store::backup_to(&target.join(data_store::DB_FILE))?;
+    let entries = fs::read_dir(&data_dir).map_err(|e| format!("读取数据目录失败: {}", e))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "json") {
+            fs::copy(&path, target.join(entry.file_name()))
+                .map_err(|e| format!("备份文件失败({}): {}", path.display(), e))?;
+        }
+    }
+
+    logger::log_info(&format!("[Backup] 已快照数据目录: {}", target.display()));
+    Ok(target)
+}
+
+/// 在 `before`（秒）之前创建的数据目录快照，快照中的数据库保留当时的账号数据
+pub fn snapshots_before(before: i64) -> Vec<PathBuf> {
+    let Ok(root) = account::get_data_dir().map(|dir| dir.join(SNAPSHOT_DIR)) else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(root) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            fs::metadata(path.join(data_store::DB_FILE))
+                .and_then(|meta| meta.modified())
+                .ok()
+                .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+                .is_some_and(|modified| (modified.as_secs() as i64) < before)
+        })
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+/// 创建备份归档，`passphrase` 非空时加密正文
+pub fn create_backup(target: &Path, passphrase: Option<&str>) -> Result<BackupSummary, String> {
+    vault::ensure_unlocked()?;
+    let entries = collect_current(&all_sections())?;
+    let mut body = ArchiveBody::default();
+    for (path, entry) in entries {
+        body.manifest.push(ManifestEntry {
+            path: path.clone(),
+            section: entry.section,
+            size: entry.content.len(),
+            sha256: sha256_hex(&entry.content),
+        });
+        body.files.insert(path, entry.content);
+    }
+    let entry_count = body.manifest.len();
+
+    let passphrase = passphrase.filter(|value| !value.is_empty());
+    let mut archive = BackupArchive {
+        format: ARCHIVE_FORMAT.to_string(),
+        version: ARCHIVE_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        data_version: migration::latest_version(),
+        created_at: chrono::Utc::now().timestamp(),
+        salt: None,
+        body: None,
+        sealed: None,
+    };
+    match passphrase {
+        Some(passphrase) => {
+            let plain = serde_json::to_string(&body).map_err(|e| format!("序列化失败: {}", e))?;
+            let (salt, sealed) = vault::seal_with_passphrase(passphrase, &plain)?;
+            archive.salt = Some(salt);
+            archive.sealed = Some(sealed);
+        }
+        None => archive.body = Some(body),
+    }
+
+    let content =
+        serde_json::to_string_pretty(&archive).map_err(|e| format!("序列化失败: {}", e))?;
+    atomic_file::replace(target, content)?;
+    logger::log_info(&format!(
+        "[Backup] 已创建备份: {} ({} 个条目, 加密={})",
+        target.display(),
+        entry_count,
+        passphrase.is_some()
+    ));
+    Ok(BackupSummary {
+        path: target.to_string_lossy().to_string(),
+        entries: entry_count,
+        encrypted: passphrase.is_some(),
+    })
+}
+
+/// 读取并校验归档：格式、版本、解密、清单与内容逐项核对
+fn read_archive(
+    path: &Path,
+    passphrase: Option<&str>,
+) -> Result<(BackupArchive, ArchiveBody), String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("读取备份文件失败: {}", e))?;
+    let mut archive: BackupArchive =
+        serde_json::from_str(&content).map_err(|e| format!("备份文件格式无效: {}", e))?;
+    if archive.format != ARCHIVE_FORMAT {
+        return Err("不是 Cockpit Tools 备份文件".to_string());
+    }
+    if archive.version > ARCHIVE_VERSION {
+        return Err(format!(
+            "备份文件格式版本 {} 高于当前应用支持的版本 {}，请先升级应用",
+            archive.version, ARCHIVE_VERSION
+        ));
+    }
+    if archive.data_version > migration::latest_version() {
+        return Err(format!(
+            "备份数据格式版本 {} 高于当前应用支持的版本 {}，请先升级应用",
+            archive.data_version,
+            migration::latest_version()
+        ));
+    }
+
+    let body = match (archive.body.take(), &archive.sealed, &archive.salt) {
+        (Some(body), None, _) => body,
+        (None, Some(sealed), Some(salt)) => {
+            let passphrase = passphrase
+                .filter(|value| !value.is_empty())
+                .ok_or("备份文件已加密，请输入口令")?;
+            let plain = vault::open_with_passphrase(passphrase, salt, sealed)?;
+            serde_json::from_str(&plain).map_err(|e| format!("备份内容格式无效: {}", e))?
+        }
+        _ => return Err("备份文件格式无效: 缺少正文".to_string()),
+    };
+
+    let mut seen = BTreeSet::new();
+    for entry in &body.manifest {
+        if section_of(&entry.path).as_deref() != Some(entry.section.as_str()) {
+            return Err(format!("备份条目路径无效: {}", entry.path));
+        }
+        let content = body
+            .files
+            .get(&entry.path)
+            .ok_or_else(|| format!("备份缺少文件: {}", entry.path))?;
+        if content.len() != entry.size || sha256_hex(content) != entry.sha256 {
+            return Err(format!("备份文件校验失败: {}", entry.path));
+        }
+        seen.insert(entry.path.as_str());
+    }
+    if let Some(extra) = body.files.keys().find(|path| !seen.contains(path.as_str())) {
+        return Err(format!("备份包含清单外的文件: {}", extra));
+    }
+    Ok((archive, body))
+}
+
+/// 账号条目在预览中展示邮箱（没有时用账号 ID），其他条目展示文件名
+fn entry_label(path: &str, content: &str) -> String {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    if !path.starts_with("accounts/") {
+        return name.to_string();
+    }
+    let id = name.trim_end_matches(".json");
+    serde_json::from_str::<serde_json::Value>(content)
+        .ok()
+        .and_then(|value| {
+            value
+                .get("email")
+                .and_then(|email| email.as_str())
+                .filter(|email| !email.is_empty())
+                .map(|email| format!("{} ({})", email, id))
+        })
+        .unwrap_or_else(|| id.to_string())
+}
+
+fn diff_sections(
+    archived: &BTreeMap<String, (String, String)>,
+    current: &BTreeMap<String, Entry>,
+) -> Vec<SectionDiff> {
+    all_sections()
+        .into_iter()
+        .map(|section| {
+            let mut diff = SectionDiff {
+                section: section.to_string(),
+                added: Vec::new(),
+                changed: Vec::new(),
+                removed: Vec::new(),
+                unchanged: 0,
+            };
+            for (path, (entry_section, content)) in archived {
+                if entry_section != section {
+                    continue;
+                }
+                match current.get(path) {
+                    None => diff.added.push(entry_label(path, content)),
+                    Some(entry) if entry.content != *content => {
+                        diff.changed.push(entry_label(path, content))
+                    }
+                    Some(_) => diff.unchanged += 1,
+                }
+            }
+            // 设置分区只覆盖归档中包含的文件，不会删除本机文件
+            if section != SETTINGS_SECTION {
+                for (path, entry) in current {
+                    if entry.section == section && !archived.contains_key(path) {
+                        diff.removed.push(entry_label(path, &entry.content));
+                    }
+                }
+            }
+            diff
+        })
+        .collect()
+}
+
+fn archived_entries(body: ArchiveBody) -> BTreeMap<String, (String, String)> {
+    let mut files = body.files;
+    body.manifest
+        .into_iter()
+        .filter_map(|entry| {
+            let content = files.remove(&entry.path)?;
+            Some((entry.path, (entry.section, content)))
+        })
+        .collect()
+}
+
+/// 校验归档并与本机数据对比
+pub fn preview_restore(path: &Path, passphrase: Option<&str>) -> Result<RestorePreview, String> {
+    vault::ensure_unlocked()?;
+    let (archive, body) = read_archive(path, passphrase)?;
+    let current = collect_current(&all_sections())?;
+    Ok(RestorePreview {
+        app_version: archive.app_version,
+        data_version: archive.data_version,
+        created_at: archive.created_at,
+        encrypted: archive.sealed.is_some(),
+        sections: diff_sections(&archived_entries(body), &current),
+    })
+}
+
+/// 恢复选中的分区
+pub fn restore_backup(
+    path: &Path,
+    passphrase: Option<&str>,
+    sections: &[String],
+) -> Result<RestoreResult, String> {
+    let known = all_sections();
+    let selected: Vec<&str> = known
+        .iter()
+        .copied()
+        .filter(|section| sections.iter().any(|item| item == section))
+        .collect();
+    if let Some(unknown) = sections.iter().find(|item| !known.contains(&item.as_str())) {
+        return Err(format!("未知的恢复分区: {}", unknown));
+    }
+    if selected.is_empty() {
+        return Err("请选择要恢复的内容".to_string());
+    }
+    // 锁定时无法读出本机账号，会把归档外的账号误判为多余而删除，恢复的账号也无法加密写入
+    vault::ensure_unlocked()?;
+
+    let (_, body) = read_archive(path, passphrase)?;
+    let archived = archived_entries(body);
+    let current = collect_current(&selected)?;
+    let snapshot = snapshot_data_dir("restore")?;
+
+    let mut written = 0usize;
+    let mut removed = 0usize;
+    data_store::transaction(|tx| {
+        for platform in AccountPlatform::ALL {
+            let section = platform.as_str();
+            if !selected.contains(&section) {
+                continue;
+            }
+            for name in [platform.index_name(), platform.instance_store_name()] {
+                match archived.get(&document_path(name)) {
+                    Some((_, content)) => tx.save_document(name, content)?,
+                    None => tx.delete_document(name)?,
+                }
+            }
+            let prefix = format!("accounts/{}/", section);
+            for (path, entry) in &current {
+                if entry.section == section
+                    && path.starts_with(&prefix)
+                    && !archived.contains_key(path)
+                {
+                    let id = path[prefix.len()..].trim_end_matches(".json");
+                    tx.delete_account(platform, id)?;
+                    removed += 1;
+                }
+            }
+            for (path, (_, content)) in archived.range(prefix.clone()..) {
+                let Some(file) = path.strip_prefix(&prefix) else {
+                    break;
+                };
+                tx.save_account(platform, file.trim_end_matches(".json"), content)?;
+                written += 1;
+            }
+        }
+        Ok(())
+    })?;
+
+    if selected.contains(&SETTINGS_SECTION) {
+        let data_dir = account::get_data_dir()?;
+        for name in SETTINGS_FILES {
+            if let Some((_, content)) = archived.get(&file_path(name)) {
+                atomic_file::write(&data_dir.join(name), content)?;
+                written += 1;
+            }
+        }
+        // 刷新内存中的用户配置
+        config::save_user_config(&config::load_user_config()?)?;
+    }
+
+    logger::log_info(&format!(
+        "[Backup] 已从 {} 恢复 {:?}: 写入 {} 项, 删除 {} 项",
+        path.display(),
+        selected,
+        written,
+        removed
+    ));
+    Ok(RestoreResult {
+        sections: selected.iter().map(|section| section.to_string()).collect(),
+        written,
+        removed,
+        snapshot_path: snapshot.to_string_lossy().to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn section_of_rejects_unknown_or_unsafe_paths() {
+        assert_eq!(
+            section_of("accounts/codex/abc.json").as_deref(),
+            Some("codex")
+        );
+        assert_eq!(
+            section_of("documents/kiro_instances.json").as_deref(),
+            Some("kiro")
+        );
+        assert_eq!(
+            section_of("files/config.json").as_deref(),
+            Some(SETTINGS_SECTION)
+        );
+        assert_eq!(section_of("accounts/codex/../x.json"), None);
+        assert_eq!(section_of("accounts/unknown/abc.json"), None);
+        assert_eq!(section_of("files/vault.json"), None);
+    }
+
+    #[test]
+    fn diff_reports_added_changed_and_removed_per_section() {
+        let archived: BTreeMap<String, (String, String)> = [
+            ("accounts/codex/a.json", r#"{"email":"a@x.com"}"#),
+            ("accounts/codex/b.json", r#"{"email":"b@x.com","v":2}"#),
+            ("files/config.json", "{}"),
+        ]
+        .into_iter()
+        .map(|(path, content)| {
+            (
+                path.to_string(),
+                (section_of(path).unwrap(), content.to_string()),
+            )
+        })
+        .collect();
+        let current: BTreeMap<String, Entry> = [
+            ("accounts/codex/b.json", r#"{"email":"b@x.com","v":1}"#),
+            ("accounts/codex/c.json", r#"{"email":"c@x.com"}"#),
+            ("files/tray_layout.json", "{}"),
+        ]
+        .into_iter()
+        .map(|(path, content)| {
+            (
+                path.to_string(),
+                Entry {
+                    section: section_of(path).unwrap(),
+                    content: content.to_string(),
+                },
+            )
+        })
+        .collect();
+
+        let diffs = diff_sections(&archived, &current);
+        let codex = diffs.iter().find(|diff| diff.section == "codex").unwrap();
+        assert_eq!(codex.added, vec!["a@x.com (a)"]);
+        assert_eq!(codex.changed, vec!["b@x.com (b)"]);
+        assert_eq!(codex.removed, vec!["c@x.com (c)"]);
+        let settings = diffs
+            .iter()
+            .find(|diff| diff.section == SETTINGS_SECTION)
+            .unwrap();
+        assert_eq!(settings.added, vec!["config.json"]);
+        assert!(settings.removed.is_empty());
+    }
+}
@@ -2,16 +2,25 @@ use crate::models::codex::{
     CodexAccount, CodexAccountIndex, CodexAccountSummary, CodexAuthFile, CodexAuthTokens,
     CodexJwtPayload, CodexTokens,
 };
-use crate::modules::{codex_oauth, logger};
+use crate::modules::audit::{self, AuditAction, AuditEvent, AuditSource};
+use crate::modules::import_plan::ImportOutcome;
+use crate::modules::interchange::{self, InterchangeAccount};
+use crate::modules::{
+    atomic_file, auto_switch, codex_oauth, config, data_store, import_plan, logger, opencode_auth,
+    process, quota_meter,
+};
 use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
 use std::collections::HashMap;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
 
+static CODEX_AUTO_SWITCH_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
 static CODEX_QUOTA_ALERT_LAST_SENT: std::sync::LazyLock<Mutex<HashMap<String, i64>>> =
     std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
 const CODEX_QUOTA_ALERT_COOLDOWN_SECONDS: i64 = 300;
+const PLATFORM: data_store::AccountPlatform = data_store::AccountPlatform::Codex;
 
 /// 获取 Codex 数据目录
 pub fn get_codex_home() -> PathBuf {
@@ -23,25 +32,6 @@ pub fn get_auth_json_path() -> PathBuf {
     get_codex_home().join("auth.json")
 }
 
-/// 获取我们的多账号存储路径
-fn get_accounts_storage_path() -> PathBuf {
-    let data_dir = dirs::data_local_dir()
-        .unwrap_or_else(|| dirs::home_dir().expect("无法获取用户目录"))
-        .join("com.antigravity.cockpit-tools");
-    fs::create_dir_all(&data_dir).ok();
-    data_dir.join("codex_accounts.json")
-}
-
-/// 获取账号详情存储目录
-fn get_accounts_dir() -> PathBuf {
-    let data_dir = dirs::data_local_dir()
-        .unwrap_or_else(|| dirs::home_dir().expect("无法获取用户目录"))
-        .join("com.antigravity.cockpit-tools")
-        .join("codex_accounts");
-    fs::create_dir_all(&data_dir).ok();
-    data_dir
-}
-
 /// 解析 JWT Token 的 payload
 pub fn decode_jwt_payload(token: &str) -> Result<CodexJwtPayload, String> {
     let parts: Vec<&str> = token.split('.').collect();
@@ -234,64 +224,45 @@ pub fn extract_user_info(
 
 /// 读取账号索引
 pub fn load_account_index() -> CodexAccountIndex {
-    let path = get_accounts_storage_path();
-    if !path.exists() {
-        return CodexAccountIndex::new();
-    }
-
-    match fs::read_to_string(&path) {
-        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|_| CodexAccountIndex::new()),
-        Err(_) => CodexAccountIndex::new(),
+    match data_store::load_document(PLATFORM.index_name()) {
+        Ok(Some(content)) => {
+            serde_json::from_str(&content).unwrap_or_else(|_| CodexAccountIndex::new())
+        }
+        _ => CodexAccountIndex::new(),
     }
 }
 
 /// 保存账号索引
 pub fn save_account_index(index: &CodexAccountIndex) -> Result<(), String> {
-    let path = get_accounts_storage_path();
     let content = serde_json::to_string_pretty(index).map_err(|e| format!("序列化失败: {}", e))?;
-    fs::write(&path, content).map_err(|e| format!("写入文件失败: {}", e))?;
-    Ok(())
+    data_store::save_document(PLATFORM.index_name(), &content)
 }
 
 /// 读取单个账号详情
 pub fn load_account(account_id: &str) -> Option<CodexAccount> {
-    let path = get_accounts_dir().join(format!("{}.json", account_id));
-    if !path.exists() {
-        return None;
-    }
-
-    match fs::read_to_string(&path) {
-        Ok(content) => serde_json::from_str(&content).ok(),
-        Err(_) => None,
-    }
+    let content = data_store::load_account(PLATFORM, account_id).ok()??;
+    serde_json::from_str(&content).ok()
 }
 
 /// 保存单个账号详情
 pub fn save_account(account: &CodexAccount) -> Result<(), String> {
-    let path = get_accounts_dir().join(format!("{}.json", &account.id));
     let content =
         serde_json::to_string_pretty(account).map_err(|e| format!("序列化失败: {}", e))?;
-    fs::write(&path, content).map_err(|e| format!("写入文件失败: {}", e))?;
-    Ok(())
+    data_store::save_account(PLATFORM, &account.id, &content)
 }
 
 /// 删除单个账号
 pub fn delete_account_file(account_id: &str) -> Result<(), String> {
-    let path = get_accounts_dir().join(format!("{}.json", account_id));
-    if path.exists() {
-        fs::remove_file(&path).map_err(|e| format!("删除文件失败: {}", e))?;
-    }
-    Ok(())
+    data_store::delete_account(PLATFORM, account_id)
 }
 
 /// 列出所有账号
 pub fn list_accounts() -> Vec<CodexAccount> {
     let index = load_account_index();
-    index
-        .accounts
-        .iter()
-        .filter_map(|summary| load_account(&summary.id))
-        .collect()
+    data_store::load_indexed_accounts(
+        PLATFORM,
+        index.accounts.iter().map(|summary| summary.id.as_str()),
+    )
 }
 
 /// 添加或更新账号
@@ -392,6 +363,13 @@ fn upsert_account_with_hints(
 
 /// 删除账号
 pub fn remove_account(account_id: &str) -> Result<(), String> {
+    let event = AuditEvent::new(PLATFORM, AuditAction::Delete).account(account_id);
+    let result = remove_account_inner(account_id);
+    event.result(&result).record();
+    result
+}
+
+fn remove_account_inner(account_id: &str) -> Result<(), String> {
     let mut index = load_account_index();
 
     // 从索引中移除
@@ -403,7 +381,8 @@ pub fn remove_account(account_id: &str) -> Result<(), String> {
     }
 
     save_account_index(&index)?;
-    delete_account_file(account_id)?;
+    // 移入回收站，保留期内可恢复
+    data_store::trash_account(PLATFORM, account_id)?;
 
     Ok(())
 }
@@ -416,6 +395,27 @@ pub fn remove_accounts(account_ids: &[String]) -> Result<(), String> {
     Ok(())
 }
 
+/// 从回收站恢复账号并重新加入索引
+pub fn restore_account(account_id: &str) -> Result<CodexAccount, String> {
+    let mut index = load_account_index();
+    data_store::restore_trashed_account(PLATFORM, account_id, |tx, content| {
+        let account: CodexAccount =
+            serde_json::from_str(content).map_err(|e| format!("解析账号数据失败: {}", e))?;
+        index.accounts.retain(|a| a.id != account.id);
+        index.accounts.push(CodexAccountSummary {
+            id: account.id.clone(),
+            email: account.email.clone(),
+            plan_type: account.plan_type.clone(),
+            created_at: account.created_at,
+            last_used: account.last_used,
+        });
+        let index_content =
+            serde_json::to_string_pretty(&index).map_err(|e| format!("序列化失败: {}", e))?;
+        tx.save_document(PLATFORM.index_name(), &index_content)?;
+        Ok(account)
+    })
+}
+
 /// 获取当前激活的账号（基于 auth.json）
 pub fn get_current_account() -> Option<CodexAccount> {
     let auth_path = get_auth_json_path();
@@ -499,7 +499,7 @@ pub fn write_auth_file_to_dir(base_dir: &Path, account: &CodexAccount) -> Result
     let auth_file = build_auth_file(account);
     let content =
         serde_json::to_string_pretty(&auth_file).map_err(|e| format!("序列化失败: {}", e))?;
-    fs::write(&auth_path, content).map_err(|e| format!("写入 auth.json 失败: {}", e))?;
+    atomic_file::replace(&auth_path, content).map_err(|e| format!("写入 auth.json 失败: {}", e))?;
     Ok(())
 }
 
@@ -530,6 +530,15 @@ pub async fn prepare_account_for_injection(account_id: &str) -> Result<CodexAcco
 
 /// 切换账号（写入 auth.json）
 pub fn switch_account(account_id: &str) -> Result<CodexAccount, String> {
+    let result = switch_account_inner(account_id);
+    AuditEvent::new(PLATFORM, AuditAction::Switch)
+        .account(account_id)
+        .result(&result)
+        .record();
+    result
+}
+
+fn switch_account_inner(account_id: &str) -> Result<CodexAccount, String> {
     let account = load_account(account_id).ok_or_else(|| format!("账号不存在: {}", account_id))?;
     write_auth_file_to_dir(&get_codex_home(), &account)?;
 
@@ -550,6 +559,12 @@ pub fn switch_account(account_id: &str) -> Result<CodexAccount, String> {
 
 /// 从本地 auth.json 导入账号
 pub fn import_from_local() -> Result<CodexAccount, String> {
+    let result = import_from_local_inner();
+    audit::record_import(PLATFORM, "local", result.as_ref().map(|_| 1));
+    result
+}
+
+fn import_from_local_inner() -> Result<CodexAccount, String> {
     let auth_path = get_auth_json_path();
     if !auth_path.exists() {
         return Err("未找到 ~/.codex/auth.json 文件".to_string());
@@ -573,7 +588,27 @@ pub fn import_from_local() -> Result<CodexAccount, String> {
 }
 
 /// 从 JSON 字符串导入账号
-pub fn import_from_json(json_content: &str) -> Result<Vec<CodexAccount>, String> {
+pub fn import_from_json(json_content: &str) -> Result<ImportOutcome<CodexAccount>, String> {
+    let result = import_from_json_inner(json_content);
+    audit::record_import(
+        PLATFORM,
+        "json",
+        result.as_ref().map(|outcome| outcome.imported.len()),
+    );
+    result
+}
+
+fn import_from_json_inner(json_content: &str) -> Result<ImportOutcome<CodexAccount>, String> {
+    // 统一交换格式：只导入其中的 Codex 账号
+    if let Some(document) = interchange::parse_if_interchange(json_content)? {
+        return Ok(import_plan::import_entries(
+            document.entries_for(PLATFORM),
+            import_interchange_entry,
+            update_account_tags,
+        ));
+    }
+
+    // 以下格式只导入 Token：本机已有该账号且 Token 更新（或内容一致）时跳过
     // 尝试解析为 auth.json 格式
     if let Ok(auth_file) = serde_json::from_str::<CodexAuthFile>(json_content) {
         let account_id_hint = auth_file.tokens.account_id.clone();
@@ -582,18 +617,36 @@ pub fn import_from_json(json_content: &str) -> Result<Vec<CodexAccount>, String>
             access_token: auth_file.tokens.access_token,
             refresh_token: auth_file.tokens.refresh_token,
         };
+        let mut candidate = CodexAccount::new(String::new(), String::new(), tokens.clone());
+        candidate.account_id = account_id_hint.clone();
+        if !import_plan::should_import_legacy(&to_interchange_entry(&candidate)?)? {
+            return Ok(ImportOutcome::default());
+        }
         let account = upsert_account_with_hints(tokens, account_id_hint, None)?;
-        return Ok(vec![account]);
+        return Ok(ImportOutcome {
+            imported: vec![account],
+            failed: Vec::new(),
+        });
     }
 
     // 尝试解析为账号数组
     if let Ok(accounts) = serde_json::from_str::<Vec<CodexAccount>>(json_content) {
-        let mut result = Vec::new();
+        let mut outcome = ImportOutcome::default();
         for acc in accounts {
-            let imported = upsert_account(acc.tokens)?;
-            result.push(imported);
+            let imported = to_interchange_entry(&acc)
+                .and_then(|entry| import_plan::should_import_legacy(&entry))
+                .and_then(|should_import| {
+                    should_import
+                        .then(|| upsert_account(acc.tokens))
+                        .transpose()
+                });
+            match imported {
+                Ok(Some(account)) => outcome.imported.push(account),
+                Ok(None) => {}
+                Err(e) => outcome.fail(&acc.email, e),
+            }
         }
-        return Ok(result);
+        return Ok(outcome);
     }
 
     Err("无法解析 JSON 内容".to_string())
@@ -609,90 +662,281 @@ pub fn export_accounts(account_ids: &[String]) -> Result<String, String> {
     serde_json::to_string_pretty(&accounts).map_err(|e| format!("序列化失败: {}", e))
 }
 
-pub fn update_account_tags(account_id: &str, tags: Vec<String>) -> Result<CodexAccount, String> {
-    let mut account =
-        load_account(account_id).ok_or_else(|| format!("账号不存在: {}", account_id))?;
+/// 交换格式中 Codex 账号的配额字段
+const INTERCHANGE_QUOTA_FIELDS: &[&str] = &["quota"];
 
-    account.tags = Some(tags);
-    save_account(&account)?;
+/// 导出账号为交换格式条目（ID 为空时导出全部）
+pub fn export_interchange(account_ids: &[String]) -> Result<Vec<InterchangeAccount>, String> {
+    let accounts = if account_ids.is_empty() {
+        list_accounts()
+    } else {
+        account_ids
+            .iter()
+            .filter_map(|id| load_account(id))
+            .collect()
+    };
+    accounts.iter().map(to_interchange_entry).collect()
+}
+
+fn to_interchange_entry(account: &CodexAccount) -> Result<InterchangeAccount, String> {
+    interchange::to_entry(PLATFORM, account, &account.email, INTERCHANGE_QUOTA_FIELDS)
+}
+
+/// 查找交换格式条目对应的本机账号（与导入时的匹配规则一致）
+pub fn find_interchange_match(
+    entry: &InterchangeAccount,
+) -> Result<Option<InterchangeAccount>, String> {
+    let record: CodexAccount = interchange::into_record(entry, INTERCHANGE_QUOTA_FIELDS)?;
+    let (email, _, _, id_token_account_id, id_token_org_id) =
+        extract_user_info(&record.tokens.id_token)?;
+    let account_id = normalize_optional_value(
+        extract_chatgpt_account_id_from_access_token(&record.tokens.access_token)
+            .or(id_token_account_id)
+            .or(record.account_id),
+    );
+    let organization_id = normalize_optional_value(
+        extract_chatgpt_organization_id_from_access_token(&record.tokens.access_token)
+            .or(id_token_org_id)
+            .or(record.organization_id),
+    );
+    let index = load_account_index();
+    find_existing_account_id(
+        &index,
+        &email,
+        account_id.as_deref(),
+        organization_id.as_deref(),
+    )
+    .and_then(|id| load_account(&id))
+    .map(|account| to_interchange_entry(&account))
+    .transpose()
+}
 
+/// 导入单个交换格式条目：按 Token 重新识别账号，并写入标签与配额
+pub fn import_interchange_entry(entry: &InterchangeAccount) -> Result<CodexAccount, String> {
+    let record: CodexAccount = interchange::into_record(entry, INTERCHANGE_QUOTA_FIELDS)?;
+    let mut account =
+        upsert_account_with_hints(record.tokens, record.account_id, record.organization_id)?;
+    account.tags = record.tags;
+    if record.quota.is_some() {
+        account.quota = record.quota;
+    }
+    save_account(&account)?;
     Ok(account)
 }
 
-fn normalize_quota_alert_threshold(raw: i32) -> i32 {
-    raw.clamp(0, 100)
+pub fn update_account_tags(account_id: &str, tags: Vec<String>) -> Result<CodexAccount, String> {
+    let event = AuditEvent::new(PLATFORM, AuditAction::Tag)
+        .account(account_id)
+        .detail(tags.join(", "));
+    let result = (|| -> Result<CodexAccount, String> {
+        let mut account =
+            load_account(account_id).ok_or_else(|| format!("账号不存在: {}", account_id))?;
+
+        account.tags = Some(tags);
+        save_account(&account)?;
+
+        Ok(account)
+    })();
+    event.result(&result).record();
+    result
 }
 
-fn format_codex_quota_metric_label(window_minutes: Option<i64>, fallback: &str) -> String {
-    const HOUR_MINUTES: i64 = 60;
-    const DAY_MINUTES: i64 = 24 * HOUR_MINUTES;
-    const WEEK_MINUTES: i64 = 7 * DAY_MINUTES;
+/// 切换 Codex 账号的完整流程（不依赖窗口，供命令行/后台调用）
+pub async fn switch_account_internal(account_id: &str) -> Result<CodexAccount, String> {
+    let account_id = account_id.to_string();
+    let _ = prepare_account_for_injection(&account_id).await?;
+
+    // 切换账号（写入 auth.json）
+    let account = switch_account(&account_id)?;
+
+    // 同步更新 Codex 默认实例的绑定账号（不同步到 Antigravity，因为账号体系不同）
+    if let Err(e) = crate::modules::codex_instance::update_default_settings(
+        Some(Some(account_id.clone())),
+        None,
+        Some(false),
+    ) {
+        logger::log_warn(&format!("更新 Codex 默认实例绑定账号失败: {}", e));
+    } else {
+        logger::log_info(&format!(
+            "已同步更新 Codex 默认实例绑定账号: {}",
+            account_id
+        ));
+    }
 
-    let Some(minutes) = window_minutes.filter(|value| *value > 0) else {
-        return fallback.to_string();
-    };
+    let mut opencode_updated = false;
+    match opencode_auth::replace_openai_entry_from_codex(&account) {
+        Ok(()) => {
+            opencode_updated = true;
+        }
+        Err(e) => {
+            logger::log_warn(&format!("OpenCode auth.json 更新跳过: {}", e));
+        }
+    }
 
-    if minutes >= WEEK_MINUTES - 1 {
-        let weeks = (minutes + WEEK_MINUTES - 1) / WEEK_MINUTES;
-        return if weeks <= 1 {
-            "Weekly".to_string()
+    let user_config = config::get_user_config();
+    if user_config.opencode_sync_on_switch {
+        if opencode_updated {
+            if process::is_opencode_running() {
+                if let Err(e) = process::close_opencode(20) {
+                    logger::log_warn(&format!("OpenCode 关闭失败: {}", e));
+                }
+            } else {
+                logger::log_info("OpenCode 未在运行，准备启动");
+            }
+            if let Err(e) = process::start_opencode_with_path(Some(&user_config.opencode_app_path))
+            {
+                logger::log_warn(&format!("OpenCode 启动失败: {}", e));
+            }
         } else {
-            format!("{} Week", weeks)
-        };
+            logger::log_info("OpenCode 未更新 auth.json，跳过启动/重启");
+        }
+    } else {
+        logger::log_info("已关闭 OpenCode 自动重启");
     }
 
-    if minutes >= DAY_MINUTES - 1 {
-        let days = (minutes + DAY_MINUTES - 1) / DAY_MINUTES;
-        return format!("{}d", days);
+    #[cfg(target_os = "macos")]
+    {
+        if user_config.codex_launch_on_switch {
+            if process::is_codex_running() {
+                logger::log_info("检测到 Codex 正在运行，将按默认实例 PID 逻辑重启");
+            }
+            match crate::modules::codex_instance::start_instance("__default__").await {
+                Ok(_) => {}
+                Err(e) => {
+                    logger::log_warn(&format!("Codex 启动失败: {}", e));
+                    if e.starts_with("APP_PATH_NOT_FOUND:") {
+                        if let Some(app) = crate::get_app_handle() {
+                            use tauri::Emitter;
+                            let _ = app.emit(
+                                "app:path_missing",
+                                serde_json::json!({ "app": "codex", "retry": { "kind": "default" } }),
+                            );
+                        }
+                    }
+                }
+            }
+        } else {
+            logger::log_info("已关闭切换 Codex 时自动启动 Codex App");
+        }
     }
 
-    if minutes >= HOUR_MINUTES {
-        let hours = (minutes + HOUR_MINUTES - 1) / HOUR_MINUTES;
-        return format!("{}h", hours);
-    }
+    Ok(account)
+}
 
-    format!("{}m", minutes)
+fn normalize_quota_alert_threshold(raw: i32) -> i32 {
+    raw.clamp(0, 100)
 }
 
-fn extract_quota_metrics(account: &CodexAccount) -> Vec<(String, i32)> {
-    let Some(quota) = account.quota.as_ref() else {
-        return Vec::new();
-    };
+fn should_trigger_auto_switch(account: &CodexAccount, threshold: i32, now: i64) -> bool {
+    auto_switch::should_trigger(&quota_meter::effective_metrics(account, now), threshold)
+}
 
-    let has_presence =
-        quota.hourly_window_present.is_some() || quota.weekly_window_present.is_some();
-    let mut metrics = Vec::new();
+fn can_be_auto_switch_candidate(
+    account: &CodexAccount,
+    current_id: &str,
+    threshold: i32,
+    now: i64,
+) -> bool {
+    if account.id == current_id || account.quota_error.is_some() {
+        return false;
+    }
+    // 5 小时与周配额都需高于阈值，避免切过去后立即再次触发
+    auto_switch::is_above_threshold(&quota_meter::effective_metrics(account, now), threshold)
+}
 
-    if !has_presence || quota.hourly_window_present.unwrap_or(false) {
-        metrics.push((
-            format_codex_quota_metric_label(quota.hourly_window_minutes, "5h"),
-            quota.hourly_percentage.clamp(0, 100),
-        ));
+/// 按选号策略挑选切换目标，指标为两个窗口按重置时间折算后的剩余百分比
+fn pick_auto_switch_target(
+    accounts: &[CodexAccount],
+    current_id: &str,
+    threshold: i32,
+    now: i64,
+    strategy: &auto_switch::SelectionStrategy,
+) -> Option<CodexAccount> {
+    let candidates: Vec<auto_switch::SwitchCandidate> = accounts
+        .iter()
+        .map(|account| auto_switch::SwitchCandidate {
+            id: account.id.clone(),
+            metrics: quota_meter::effective_metrics(account, now),
+            last_used: account.last_used,
+            tags: account.tags.clone().unwrap_or_default(),
+            eligible: can_be_auto_switch_candidate(account, current_id, threshold, now),
+        })
+        .collect();
+    let target = auto_switch::pick_candidate(&candidates, current_id, strategy)?;
+    accounts
+        .iter()
+        .find(|account| account.id == target.id)
+        .cloned()
+}
+
+async fn run_auto_switch_if_needed_inner() -> Result<Option<CodexAccount>, String> {
+    let cfg = crate::modules::config::get_user_config();
+    if !cfg.codex_auto_switch_enabled {
+        return Ok(None);
     }
 
-    if !has_presence || quota.weekly_window_present.unwrap_or(false) {
-        metrics.push((
-            format_codex_quota_metric_label(quota.weekly_window_minutes, "Weekly"),
-            quota.weekly_percentage.clamp(0, 100),
-        ));
+    let threshold = auto_switch::normalize_threshold(cfg.codex_auto_switch_threshold);
+    let accounts = list_accounts();
+    let current_id = match resolve_current_account_id(&accounts) {
+        Some(id) => id,
+        None => return Ok(None),
+    };
+    let current = match accounts.iter().find(|account| account.id == current_id) {
+        Some(account) => account,
+        None => return Ok(None),
+    };
+
+    let now = chrono::Utc::now().timestamp();
+    if !should_trigger_auto_switch(current, threshold, now) {
+        return Ok(None);
     }
 
-    if metrics.is_empty() {
-        metrics.push((
-            format_codex_quota_metric_label(quota.hourly_window_minutes, "5h"),
-            quota.hourly_percentage.clamp(0, 100),
+    let strategy = auto_switch::SelectionStrategy::from_config(
+        &cfg.codex_auto_switch_strategy,
+        &cfg.codex_auto_switch_strategy_param,
+    );
+    let Some(target) = pick_auto_switch_target(&accounts, &current_id, threshold, now, &strategy)
+    else {
+        logger::log_warn(&format!(
+            "[CodexAutoSwitch] 当前账号低于阈值 {}%，但没有可切换候选账号: strategy={}",
+            threshold,
+            strategy.as_str()
         ));
-    }
+        audit::auto_switch_event(PLATFORM, &current_id, threshold, strategy.as_str())
+            .account(&current_id)
+            .skipped()
+            .record();
+        return Ok(None);
+    };
+
+    logger::log_info(&format!(
+        "[CodexAutoSwitch] 触发自动切号: current_id={}, target_id={}, threshold={}%, strategy={}",
+        current_id,
+        target.id,
+        threshold,
+        strategy.as_str()
+    ));
 
-    metrics
+    // 走完整切号流程：写入 auth.json，并按配置重启 OpenCode / Codex App
+    let result = switch_account_internal(&target.id).await;
+    audit::auto_switch_event(PLATFORM, &current_id, threshold, strategy.as_str())
+        .account(&target.id)
+        .result(&result)
+        .record();
+    Ok(Some(result?))
 }
 
-fn average_quota_percentage(metrics: &[(String, i32)]) -> f64 {
-    if metrics.is_empty() {
-        return 0.0;
+/// 当前 Codex 账号 5 小时或周配额低于阈值时自动切换到配额最充足的账号
+pub async fn run_auto_switch_if_needed() -> Result<Option<CodexAccount>, String> {
+    if CODEX_AUTO_SWITCH_IN_PROGRESS.swap(true, Ordering::SeqCst) {
+        logger::log_info("[CodexAutoSwitch] 自动切号进行中，跳过本次检查");
+        return Ok(None);
     }
-    let sum: i32 = metrics.iter().map(|(_, pct)| *pct).sum();
-    sum as f64 / metrics.len() as f64
+
+    let result =
+        audit::with_source(AuditSource::AutoSwitch, run_auto_switch_if_needed_inner()).await;
+    CODEX_AUTO_SWITCH_IN_PROGRESS.store(false, Ordering::SeqCst);
+    result
 }
 
 fn build_quota_alert_cooldown_key(account_id: &str, threshold: i32) -> String {
@@ -747,7 +991,7 @@ fn pick_quota_alert_recommendation(
     let mut candidates: Vec<CodexAccount> = accounts
         .iter()
         .filter(|account| account.id != current_id)
-        .filter(|account| !extract_quota_metrics(account).is_empty())
+        .filter(|account| !quota_meter::metrics(account).is_empty())
         .cloned()
         .collect();
 
@@ -756,8 +1000,8 @@ fn pick_quota_alert_recommendation(
     }
 
     candidates.sort_by(|a, b| {
-        let avg_a = average_quota_percentage(&extract_quota_metrics(a));
-        let avg_b = average_quota_percentage(&extract_quota_metrics(b));
+        let avg_a = quota_meter::average_percentage(&quota_meter::metrics(a));
+        let avg_b = quota_meter::average_percentage(&quota_meter::metrics(b));
         avg_b
             .partial_cmp(&avg_a)
             .unwrap_or(std::cmp::Ordering::Equal)
@@ -786,11 +1030,7 @@ pub fn run_quota_alert_if_needed(
         None => return Ok(None),
     };
 
-    let metrics = extract_quota_metrics(current);
-    let low_models: Vec<(String, i32)> = metrics
-        .into_iter()
-        .filter(|(_, pct)| *pct <= threshold)
-        .collect();
+    let low_models = quota_meter::low_metrics(current, threshold);
 
     if low_models.is_empty() {
         clear_quota_alert_cooldown(&current_id, threshold);
@@ -807,6 +1047,7 @@ pub fn run_quota_alert_if_needed(
     let lowest_percentage = low_models.iter().map(|(_, pct)| *pct).min().unwrap_or(0);
     let payload = crate::modules::account::QuotaAlertPayload {
         platform: "codex".to_string(),
+        kind: crate::modules::account::QuotaAlertKind::Low,
         current_account_id: current_id,
         current_email: current.email.clone(),
         threshold,
@@ -820,3 +1061,86 @@ pub fn run_quota_alert_if_needed(
     crate::modules::account::dispatch_quota_alert(&payload);
     Ok(Some(payload))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::codex::CodexQuota;
+
+    fn account(id: &str, hourly: (i32, Option<i64>), weekly: (i32, Option<i64>)) -> CodexAccount {
+        CodexAccount {
+            id: id.to_string(),
+            email: format!("{}@example.com", id),
+            user_id: None,
+            plan_type: None,
+            account_id: None,
+            organization_id: None,
+            tokens: CodexTokens {
+                id_token: String::new(),
+                access_token: String::new(),
+                refresh_token: None,
+            },
+            quota: Some(CodexQuota {
+                hourly_percentage: hourly.0,
+                hourly_reset_time: hourly.1,
+                hourly_window_minutes: Some(300),
+                hourly_window_present: Some(true),
+                weekly_percentage: weekly.0,
+                weekly_reset_time: weekly.1,
+                weekly_window_minutes: Some(10080),
+                weekly_window_present: Some(true),
+                raw_data: None,
+            }),
+            quota_error: None,
+            tags: None,
+            created_at: 0,
+            last_used: 0,
+        }
+    }
+
+    #[test]
+    fn auto_switch_considers_both_windows_and_reset_times() {
+        let now = 1_000_000;
+        let current = account("current", (3, Some(now + 600)), (80, Some(now + 86_400)));
+        assert!(should_trigger_auto_switch(&current, 5, now));
+        // 5 小时窗口已过重置时间，视为已恢复
+        let reset = account("reset", (3, Some(now - 1)), (80, Some(now + 86_400)));
+        assert!(!should_trigger_auto_switch(&reset, 5, now));
+
+        let accounts = vec![
+            current,
+            account("weekly_low", (100, None), (4, Some(now + 86_400))),
+            account("balanced", (60, None), (50, None)),
+            account("fresh_hourly", (90, None), (80, None)),
+        ];
+        let target = pick_auto_switch_target(
+            &accounts,
+            "current",
+            5,
+            now,
+            &auto_switch::SelectionStrategy::HighestQuota,
+        )
+        .unwrap();
+        assert_eq!(target.id, "fresh_hourly");
+    }
+
+    #[test]
+    fn auto_switch_skips_accounts_with_quota_errors() {
+        let now = 1_000_000;
+        let mut broken = account("broken", (100, None), (100, None));
+        broken.quota_error = Some(crate::models::codex::CodexQuotaErrorInfo {
+            code: None,
+            message: "401".to_string(),
+            timestamp: now,
+        });
+        let accounts = vec![account("current", (0, None), (0, None)), broken];
+        assert!(pick_auto_switch_target(
+            &accounts,
+            "current",
+            5,
+            now,
+            &auto_switch::SelectionStrategy::HighestQuota
+        )
+        .is_none());
+    }
+}
@@ -5,7 +5,7 @@ use std::sync::Mutex;
 use chrono::Utc;
 use uuid::Uuid;
 
-use crate::models::{DefaultInstanceSettings, InstanceProfile, InstanceStore};
+use crate::models::{DefaultInstanceSettings, InstanceProfile, InstanceProfileView, InstanceStore};
 use crate::modules;
 use crate::modules::instance::InstanceDefaults;
 use crate::modules::instance_store;
@@ -16,20 +16,14 @@ static CODEX_INSTANCE_STORE_LOCK: std::sync::LazyLock<Mutex<()>> =
     std::sync::LazyLock::new(|| Mutex::new(()));
 
 const CODEX_INSTANCES_FILE: &str = "codex_instances.json";
-
-fn instances_path() -> Result<PathBuf, String> {
-    let data_dir = modules::account::get_data_dir()?;
-    Ok(data_dir.join(CODEX_INSTANCES_FILE))
-}
+const DEFAULT_INSTANCE_ID: &str = "__default__";
 
 pub fn load_instance_store() -> Result<InstanceStore, String> {
-    let path = instances_path()?;
-    instance_store::load_instance_store(&path, CODEX_INSTANCES_FILE)
+    instance_store::load_instance_store(CODEX_INSTANCES_FILE)
 }
 
 pub fn save_instance_store(store: &InstanceStore) -> Result<(), String> {
-    let path = instances_path()?;
-    instance_store::save_instance_store(&path, CODEX_INSTANCES_FILE, store)
+    instance_store::save_instance_store(CODEX_INSTANCES_FILE, store)
 }
 
 pub fn load_default_settings() -> Result<DefaultInstanceSettings, String> {
@@ -336,6 +330,89 @@ pub fn clear_all_pids() -> Result<(), String> {
 }
 
 pub async fn inject_account_to_profile(profile_dir: &Path, account_id: &str) -> Result<(), String> {
+    let result = write_account_to_profile(profile_dir, account_id).await;
+    modules::audit::record_inject(
+        modules::data_store::AccountPlatform::Codex,
+        account_id,
+        profile_dir,
+        &result,
+    );
+    result
+}
+
+async fn write_account_to_profile(profile_dir: &Path, account_id: &str) -> Result<(), String> {
     let account = modules::codex_account::prepare_account_for_injection(account_id).await?;
     modules::codex_account::write_auth_file_to_dir(profile_dir, &account)
 }
+
+/// 默认实例实际使用的账号：跟随本地账号时取当前 auth.json 对应的账号，否则取绑定账号
+pub fn resolve_default_account_id(settings: &DefaultInstanceSettings) -> Option<String> {
+    if settings.follow_local_account {
+        modules::codex_account::get_current_account().map(|account| account.id)
+    } else {
+        settings.bind_account_id.clone()
+    }
+}
+
+/// 启动实例（`__default__` 为默认实例）：先关闭仍在运行的进程，注入绑定账号后重新启动
+pub async fn start_instance(instance_id: &str) -> Result<InstanceProfileView, String> {
+    if instance_id == DEFAULT_INSTANCE_ID {
+        let default_dir = get_default_codex_home()?;
+        let default_dir_str = default_dir.to_string_lossy().to_string();
+        let default_settings = load_default_settings()?;
+        let default_bind_account_id = resolve_default_account_id(&default_settings);
+        if let Some(pid) = modules::process::resolve_codex_pid(default_settings.last_pid, None) {
+            modules::process::close_pid(pid, 20)?;
+            let _ = update_default_pid(None)?;
+        }
+        if let Some(ref account_id) = default_bind_account_id {
+            inject_account_to_profile(&default_dir, account_id).await?;
+        }
+        let pid = modules::process::start_codex_default()?;
+        let _ = update_default_pid(Some(pid))?;
+        let running = modules::process::is_pid_running(pid);
+        return Ok(InstanceProfileView {
+            id: DEFAULT_INSTANCE_ID.to_string(),
+            name: String::new(),
+            user_data_dir: default_dir_str,
+            extra_args: default_settings.extra_args,
+            bind_account_id: default_bind_account_id,
+            created_at: 0,
+            last_launched_at: None,
+            last_pid: Some(pid),
+            running,
+            initialized: modules::instance::is_profile_initialized(&default_dir),
+            is_default: true,
+            follow_local_account: default_settings.follow_local_account,
+        });
+    }
+
+    let store = load_instance_store()?;
+    let instance = store
+        .instances
+        .into_iter()
+        .find(|item| item.id == instance_id)
+        .ok_or("实例不存在")?;
+
+    if let Some(pid) =
+        modules::process::resolve_codex_pid(instance.last_pid, Some(&instance.user_data_dir))
+    {
+        modules::process::close_pid(pid, 20)?;
+        let _ = update_instance_pid(&instance.id, None)?;
+    }
+
+    if let Some(ref account_id) = instance.bind_account_id {
+        inject_account_to_profile(Path::new(&instance.user_data_dir), account_id).await?;
+    }
+
+    let extra_args = modules::process::parse_extra_args(&instance.extra_args);
+    let pid = modules::process::start_codex_with_args(&instance.user_data_dir, &extra_args)?;
+    let updated = update_instance_after_start(&instance.id, pid)?;
+    let running = modules::process::is_pid_running(pid);
+    let initialized = modules::instance::is_profile_initialized(Path::new(&updated.user_data_dir));
+    Ok(InstanceProfileView::from_profile(
+        updated,
+        running,
+        initialized,
+    ))
+}
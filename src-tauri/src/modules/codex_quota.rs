@@ -1,10 +1,13 @@
 use crate::models::codex::{CodexAccount, CodexQuota, CodexQuotaErrorInfo};
-use crate::modules::{codex_account, logger};
+use crate::modules::quota_history;
+use crate::modules::quota_meter::PlatformQuota;
+use crate::modules::{codex_account, data_store, logger, refresh_plan};
 use reqwest::header::{HeaderMap, HeaderValue, ACCEPT, AUTHORIZATION};
 use serde::{Deserialize, Serialize};
 
 // 使用 wham/usage 端点（Quotio 使用的）
 const USAGE_URL: &str = "https://chatgpt.com/backend-api/wham/usage";
+const PLATFORM: data_store::AccountPlatform = data_store::AccountPlatform::Codex;
 
 fn get_header_value(headers: &HeaderMap, name: &str) -> String {
     headers
@@ -290,22 +293,38 @@ pub async fn refresh_account_quota(account_id: &str) -> Result<CodexQuota, Strin
     account.quota = Some(quota.clone());
     account.quota_error = None;
     codex_account::save_account(&account)?;
+    quota_history::record(PLATFORM, &account.id, &quota.quota_meters());
 
     Ok(quota)
 }
 
 /// 刷新所有账号配额
 pub async fn refresh_all_quotas() -> Result<Vec<(String, Result<CodexQuota, String>)>, String> {
+    refresh_quotas(false).await
+}
+
+/// 定时刷新：只刷新按刷新计划已到期的账号（见 `refresh_plan`）
+pub async fn refresh_due_quotas() -> Result<Vec<(String, Result<CodexQuota, String>)>, String> {
+    refresh_quotas(true).await
+}
+
+async fn refresh_quotas(
+    only_due: bool,
+) -> Result<Vec<(String, Result<CodexQuota, String>)>, String> {
     use futures::future::join_all;
     use std::sync::Arc;
     use tokio::sync::Semaphore;
 
     const MAX_CONCURRENT: usize = 5;
     let accounts = codex_account::list_accounts();
+    refresh_plan::retain(PLATFORM, |id| {
+        accounts.iter().any(|account| account.id == id)
+    });
 
     let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT));
     let tasks: Vec<_> = accounts
         .into_iter()
+        .filter(|account| !only_due || refresh_plan::is_due(PLATFORM, &account.id))
         .map(|account| {
             let account_id = account.id;
             let semaphore = semaphore.clone();
@@ -315,6 +334,12 @@ pub async fn refresh_all_quotas() -> Result<Vec<(String, Result<CodexQuota, Stri
                     .await
                     .map_err(|e| format!("获取 Codex 刷新并发许可失败: {}", e))?;
                 let result = refresh_account_quota(&account_id).await;
+                match &result {
+                    Ok(quota) => {
+                        refresh_plan::record_success(PLATFORM, &account_id, &quota.quota_meters())
+                    }
+                    Err(_) => refresh_plan::record_failure(PLATFORM, &account_id),
+                }
                 Ok::<(String, Result<CodexQuota, String>), String>((account_id, result))
             }
         })
@@ -7,9 +7,14 @@ use std::fs;
 use std::path::PathBuf;
 use std::sync::{OnceLock, RwLock};
 
+use crate::modules::atomic_file;
+
 /// 默认 WebSocket 端口
 pub const DEFAULT_WS_PORT: u16 = 19528;
 
+/// 默认本地 API 代理端口
+pub const DEFAULT_PROXY_PORT: u16 = 19530;
+
 /// 端口尝试范围（从配置端口开始，最多尝试 100 个）
 pub const PORT_RANGE: u16 = 100;
 
@@ -101,9 +106,73 @@ pub struct UserConfig {
     /// 是否启用自动切号
     #[serde(default = "default_auto_switch_enabled")]
     pub auto_switch_enabled: bool,
-    /// 自动切号阈值（百分比），任意模型配额低于此值触发
+    /// 自动切号阈值（百分比）：未配置关注分组时任意模型配额低于此值触发；
+    /// 配置了关注分组（`watched_groups`）时按分组最低配额与分组阈值判断，分组未设置阈值时使用此值
     #[serde(default = "default_auto_switch_threshold")]
     pub auto_switch_threshold: i32,
+    /// 自动切号选号策略
+    #[serde(default = "default_auto_switch_strategy")]
+    pub auto_switch_strategy: String,
+    /// 自动切号选号策略参数（分组 ID 或标签）
+    #[serde(default = "default_auto_switch_strategy_param")]
+    pub auto_switch_strategy_param: String,
+    /// 关注的模型分组及分组阈值（为空时按单个模型评估）
+    #[serde(default = "default_watched_groups")]
+    pub watched_groups: Vec<WatchedGroup>,
+    /// 是否启用配额保留（模型配额低于保留值时加入受保护列表）
+    #[serde(default = "default_quota_protection_enabled")]
+    pub quota_protection_enabled: bool,
+    /// 配额保留阈值（百分比），模型配额低于此值后不再被自动任务消耗
+    #[serde(default = "default_quota_protection_reserve")]
+    pub quota_protection_reserve: i32,
+    /// 是否启用 Codex 自动切号
+    #[serde(default = "default_codex_auto_switch_enabled")]
+    pub codex_auto_switch_enabled: bool,
+    /// Codex 自动切号阈值（百分比），5 小时或周配额低于此值触发
+    #[serde(default = "default_codex_auto_switch_threshold")]
+    pub codex_auto_switch_threshold: i32,
+    /// Codex 自动切号选号策略
+    #[serde(default = "default_codex_auto_switch_strategy")]
+    pub codex_auto_switch_strategy: String,
+    /// Codex 选号策略参数
+    #[serde(default = "default_codex_auto_switch_strategy_param")]
+    pub codex_auto_switch_strategy_param: String,
+    /// 是否启用 GitHub Copilot 自动切号
+    #[serde(default = "default_ghcp_auto_switch_enabled")]
+    pub ghcp_auto_switch_enabled: bool,
+    /// GitHub Copilot 自动切号阈值（百分比）
+    #[serde(default = "default_ghcp_auto_switch_threshold")]
+    pub ghcp_auto_switch_threshold: i32,
+    /// GitHub Copilot 自动切号选号策略
+    #[serde(default = "default_ghcp_auto_switch_strategy")]
+    pub ghcp_auto_switch_strategy: String,
+    /// GitHub Copilot 选号策略参数
+    #[serde(default = "default_ghcp_auto_switch_strategy_param")]
+    pub ghcp_auto_switch_strategy_param: String,
+    /// 是否启用 Windsurf 自动切号
+    #[serde(default = "default_windsurf_auto_switch_enabled")]
+    pub windsurf_auto_switch_enabled: bool,
+    /// Windsurf 自动切号阈值（百分比）
+    #[serde(default = "default_windsurf_auto_switch_threshold")]
+    pub windsurf_auto_switch_threshold: i32,
+    /// Windsurf 自动切号选号策略
+    #[serde(default = "default_windsurf_auto_switch_strategy")]
+    pub windsurf_auto_switch_strategy: String,
+    /// Windsurf 选号策略参数
+    #[serde(default = "default_windsurf_auto_switch_strategy_param")]
+    pub windsurf_auto_switch_strategy_param: String,
+    /// 是否启用 Kiro 自动切号
+    #[serde(default = "default_kiro_auto_switch_enabled")]
+    pub kiro_auto_switch_enabled: bool,
+    /// Kiro 自动切号阈值（百分比）
+    #[serde(default = "default_kiro_auto_switch_threshold")]
+    pub kiro_auto_switch_threshold: i32,
+    /// Kiro 自动切号选号策略
+    #[serde(default = "default_kiro_auto_switch_strategy")]
+    pub kiro_auto_switch_strategy: String,
+    /// Kiro 选号策略参数
+    #[serde(default = "default_kiro_auto_switch_strategy_param")]
+    pub kiro_auto_switch_strategy_param: String,
     /// 是否启用配额预警通知
     #[serde(default = "default_quota_alert_enabled")]
     pub quota_alert_enabled: bool,
@@ -134,6 +203,18 @@ pub struct UserConfig {
     /// Kiro 配额预警阈值（百分比）
     #[serde(default = "default_kiro_quota_alert_threshold")]
     pub kiro_quota_alert_threshold: i32,
+    /// 是否启用本地 API 代理（OpenAI 兼容接口）
+    #[serde(default = "default_proxy_enabled")]
+    pub proxy_enabled: bool,
+    /// 本地 API 代理端口
+    #[serde(default = "default_proxy_port")]
+    pub proxy_port: u16,
+    /// 本地 API 代理访问密钥（必填，为空时拒绝所有请求；开启代理时自动生成）
+    #[serde(default = "default_proxy_api_key")]
+    pub proxy_api_key: String,
+    /// 回收站保留天数，0 表示永久保留
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: i32,
 }
 
 /// 窗口关闭行为
@@ -170,6 +251,18 @@ impl Default for MinimizeWindowBehavior {
     }
 }
 
+/// 关注的模型分组（配置后自动切号与配额预警只评估这些分组）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WatchedGroup {
+    pub group_id: String,
+    /// 分组自动切号阈值，未设置时使用全局阈值
+    #[serde(default)]
+    pub auto_switch_threshold: Option<i32>,
+    /// 分组配额预警阈值，未设置时使用全局阈值
+    #[serde(default)]
+    pub quota_alert_threshold: Option<i32>,
+}
+
 fn default_ws_enabled() -> bool {
     true
 }
@@ -236,6 +329,69 @@ fn default_auto_switch_enabled() -> bool {
 fn default_auto_switch_threshold() -> i32 {
     5
 }
+fn default_auto_switch_strategy() -> String {
+    "highest_quota".to_string()
+}
+fn default_auto_switch_strategy_param() -> String {
+    String::new()
+}
+fn default_watched_groups() -> Vec<WatchedGroup> {
+    Vec::new()
+}
+fn default_quota_protection_enabled() -> bool {
+    false
+}
+fn default_quota_protection_reserve() -> i32 {
+    5
+}
+fn default_codex_auto_switch_enabled() -> bool {
+    false
+}
+fn default_codex_auto_switch_threshold() -> i32 {
+    5
+}
+fn default_codex_auto_switch_strategy() -> String {
+    "highest_quota".to_string()
+}
+fn default_codex_auto_switch_strategy_param() -> String {
+    String::new()
+}
+fn default_ghcp_auto_switch_enabled() -> bool {
+    false
+}
+fn default_ghcp_auto_switch_threshold() -> i32 {
+    5
+}
+fn default_ghcp_auto_switch_strategy() -> String {
+    "highest_quota".to_string()
+}
+fn default_ghcp_auto_switch_strategy_param() -> String {
+    String::new()
+}
+fn default_windsurf_auto_switch_enabled() -> bool {
+    false
+}
+fn default_windsurf_auto_switch_threshold() -> i32 {
+    5
+}
+fn default_windsurf_auto_switch_strategy() -> String {
+    "highest_quota".to_string()
+}
+fn default_windsurf_auto_switch_strategy_param() -> String {
+    String::new()
+}
+fn default_kiro_auto_switch_enabled() -> bool {
+    false
+}
+fn default_kiro_auto_switch_threshold() -> i32 {
+    5
+}
+fn default_kiro_auto_switch_strategy() -> String {
+    "highest_quota".to_string()
+}
+fn default_kiro_auto_switch_strategy_param() -> String {
+    String::new()
+}
 fn default_quota_alert_enabled() -> bool {
     false
 }
@@ -266,6 +422,18 @@ fn default_kiro_quota_alert_enabled() -> bool {
 fn default_kiro_quota_alert_threshold() -> i32 {
     20
 }
+fn default_proxy_enabled() -> bool {
+    false
+}
+fn default_proxy_port() -> u16 {
+    DEFAULT_PROXY_PORT
+}
+fn default_proxy_api_key() -> String {
+    String::new()
+}
+fn default_trash_retention_days() -> i32 {
+    30
+}
 
 impl Default for UserConfig {
     fn default() -> Self {
@@ -292,6 +460,27 @@ impl Default for UserConfig {
             codex_launch_on_switch: default_codex_launch_on_switch(),
             auto_switch_enabled: default_auto_switch_enabled(),
             auto_switch_threshold: default_auto_switch_threshold(),
+            auto_switch_strategy: default_auto_switch_strategy(),
+            auto_switch_strategy_param: default_auto_switch_strategy_param(),
+            watched_groups: default_watched_groups(),
+            quota_protection_enabled: default_quota_protection_enabled(),
+            quota_protection_reserve: default_quota_protection_reserve(),
+            codex_auto_switch_enabled: default_codex_auto_switch_enabled(),
+            codex_auto_switch_threshold: default_codex_auto_switch_threshold(),
+            codex_auto_switch_strategy: default_codex_auto_switch_strategy(),
+            codex_auto_switch_strategy_param: default_codex_auto_switch_strategy_param(),
+            ghcp_auto_switch_enabled: default_ghcp_auto_switch_enabled(),
+            ghcp_auto_switch_threshold: default_ghcp_auto_switch_threshold(),
+            ghcp_auto_switch_strategy: default_ghcp_auto_switch_strategy(),
+            ghcp_auto_switch_strategy_param: default_ghcp_auto_switch_strategy_param(),
+            windsurf_auto_switch_enabled: default_windsurf_auto_switch_enabled(),
+            windsurf_auto_switch_threshold: default_windsurf_auto_switch_threshold(),
+            windsurf_auto_switch_strategy: default_windsurf_auto_switch_strategy(),
+            windsurf_auto_switch_strategy_param: default_windsurf_auto_switch_strategy_param(),
+            kiro_auto_switch_enabled: default_kiro_auto_switch_enabled(),
+            kiro_auto_switch_threshold: default_kiro_auto_switch_threshold(),
+            kiro_auto_switch_strategy: default_kiro_auto_switch_strategy(),
+            kiro_auto_switch_strategy_param: default_kiro_auto_switch_strategy_param(),
             quota_alert_enabled: default_quota_alert_enabled(),
             quota_alert_threshold: default_quota_alert_threshold(),
             codex_quota_alert_enabled: default_codex_quota_alert_enabled(),
@@ -302,6 +491,10 @@ impl Default for UserConfig {
             windsurf_quota_alert_threshold: default_windsurf_quota_alert_threshold(),
             kiro_quota_alert_enabled: default_kiro_quota_alert_enabled(),
             kiro_quota_alert_threshold: default_kiro_quota_alert_threshold(),
+            proxy_enabled: default_proxy_enabled(),
+            proxy_port: default_proxy_port(),
+            proxy_api_key: default_proxy_api_key(),
+            trash_retention_days: default_trash_retention_days(),
         }
     }
 }
@@ -468,7 +661,7 @@ pub fn save_user_config(config: &UserConfig) -> Result<(), String> {
     let json =
         serde_json::to_string_pretty(config).map_err(|e| format!("序列化配置失败: {}", e))?;
 
-    fs::write(&config_path, json).map_err(|e| format!("写入配置文件失败: {}", e))?;
+    atomic_file::write(&config_path, json).map_err(|e| format!("写入配置文件失败: {}", e))?;
 
     // 更新运行时状态
     if let Ok(mut state) = get_runtime_state().write() {
@@ -483,6 +676,15 @@ pub fn save_user_config(config: &UserConfig) -> Result<(), String> {
     Ok(())
 }
 
+/// 重新从磁盘加载用户配置并刷新内存（其它进程修改配置文件后使用）
+pub fn reload_user_config() -> Result<UserConfig, String> {
+    let config = load_user_config()?;
+    if let Ok(mut state) = get_runtime_state().write() {
+        state.user_config = config.clone();
+    }
+    Ok(config)
+}
+
 /// 获取用户配置（从内存）
 pub fn get_user_config() -> UserConfig {
     get_runtime_state()
@@ -518,7 +720,7 @@ pub fn save_server_status(status: &ServerStatus) -> Result<(), String> {
     let json =
         serde_json::to_string_pretty(status).map_err(|e| format!("序列化状态失败: {}", e))?;
 
-    fs::write(&status_path, json).map_err(|e| format!("写入状态文件失败: {}", e))?;
+    atomic_file::write(&status_path, json).map_err(|e| format!("写入状态文件失败: {}", e))?;
 
     crate::modules::logger::log_info(&format!(
         "[Config] 服务状态已保存: ws_port={}, pid={}",
@@ -0,0 +1,1295 @@
+//! 本地数据库存储
+//!
+//! 五个平台的账号索引、账号详情与实例配置统一保存在数据目录下的 `cockpit.db`（SQLite）。
+//! 账号索引与实例配置以原文件名为键存入 `documents` 表，账号详情按 (平台, 账号 ID)
+//! 存入 `accounts` 表，内容经由凭据库加密（启用时）。首次打开时会把旧版 JSON 文件导入数据库，
+//! 并将旧文件移入 `legacy_json/` 目录备份；`export_json_layout` 可导出回旧版目录结构。
+//! Antigravity 的 `accounts.json` 与 `accounts/` 同时由 VS Code 扩展读写，导入后保留在原处：
+//! 数据库写入提交后同步写回这些文件，读取前导入扩展修改过的文件。扩展无法解密，因此共享文件
+//! 始终是明文，是凭据库加密的例外；导入时先校验 JSON，再按凭据库加密后写入数据库。
+
+use rusqlite::{params, Connection, OptionalExtension, Transaction};
+use serde::de::DeserializeOwned;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::modules::{account, atomic_file, logger, vault};
+
+/// 数据库文件名
+pub(crate) const DB_FILE: &str = "cockpit.db";
+/// 旧版 JSON 文件备份目录
+const LEGACY_BACKUP_DIR: &str = "legacy_json";
+/// 旧版 JSON 已导入标记
+const META_LEGACY_IMPORTED: &str = "legacy_json_imported_at";
+/// 每个文档保留的快照数
+pub(crate) const SNAPSHOT_LIMIT: usize = 5;
+
+/// 数据库结构迁移，按顺序执行，版本号记录在 `PRAGMA user_version`
+const SCHEMA_MIGRATIONS: &[&str] = &[
+    // v1: 文档表、账号表、元数据表
+    "CREATE TABLE documents (
+        name TEXT PRIMARY KEY,
+        content TEXT NOT NULL,
+        updated_at INTEGER NOT NULL
+    );
+    CREATE TABLE accounts (
+        platform TEXT NOT NULL,
+        id TEXT NOT NULL,
+        content TEXT NOT NULL,
+        updated_at INTEGER NOT NULL,
+        PRIMARY KEY (platform, id)
+    );
+    CREATE INDEX idx_accounts_platform_updated ON accounts (platform, updated_at);
+    CREATE TABLE meta (
+        key TEXT PRIMARY KEY,
+        value TEXT NOT NULL
+    );",
+    // v2: 文档快照，每次写入有效内容时保留，用于损坏后恢复
+    "CREATE TABLE document_snapshots (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        name TEXT NOT NULL,
+        content TEXT NOT NULL,
+        created_at INTEGER NOT NULL
+    );
+    CREATE INDEX idx_document_snapshots_name ON document_snapshots (name, id);",
+    // v3: 账号操作审计日志（只允许追加）
+    "CREATE TABLE audit_log (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        created_at INTEGER NOT NULL,
+        platform TEXT NOT NULL,
+        action TEXT NOT NULL,
+        account_id TEXT,
+        account TEXT,
+        source TEXT NOT NULL,
+        actor TEXT NOT NULL,
+        outcome TEXT NOT NULL,
+        error TEXT,
+        detail TEXT
+    );
+    CREATE INDEX idx_audit_log_created ON audit_log (created_at);
+    CREATE TRIGGER audit_log_no_update BEFORE UPDATE ON audit_log
+    BEGIN SELECT RAISE(ABORT, 'audit_log is append-only'); END;
+    CREATE TRIGGER audit_log_no_delete BEFORE DELETE ON audit_log
+    BEGIN SELECT RAISE(ABORT, 'audit_log is append-only'); END;",
+    // v4: 账号回收站，删除的账号保留原始（加密）内容，到期后清理
+    "CREATE TABLE account_trash (
+        platform TEXT NOT NULL,
+        id TEXT NOT NULL,
+        label TEXT,
+        content TEXT NOT NULL,
+        deleted_at INTEGER NOT NULL,
+        PRIMARY KEY (platform, id)
+    );
+    CREATE INDEX idx_account_trash_deleted ON account_trash (deleted_at);",
+    // v5: 配额历史，每次刷新按账号、配额项记录一条
+    "CREATE TABLE quota_history (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        platform TEXT NOT NULL,
+        account_id TEXT NOT NULL,
+        meter TEXT NOT NULL,
+        remaining_percent REAL NOT NULL,
+        used REAL,
+        quota_limit REAL,
+        reset_at INTEGER,
+        recorded_at INTEGER NOT NULL
+    );
+    CREATE INDEX idx_quota_history_account ON quota_history (platform, account_id, meter, recorded_at);
+    CREATE INDEX idx_quota_history_recorded ON quota_history (recorded_at);",
+];
+
+static CONNECTION: LazyLock<Mutex<Option<Connection>>> = LazyLock::new(|| Mutex::new(None));
+
+/// 账号所属平台
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccountPlatform {
+    Antigravity,
+    Codex,
+    GitHubCopilot,
+    Windsurf,
+    Kiro,
+}
+
+impl AccountPlatform {
+    pub const ALL: [AccountPlatform; 5] = [
+        AccountPlatform::Antigravity,
+        AccountPlatform::Codex,
+        AccountPlatform::GitHubCopilot,
+        AccountPlatform::Windsurf,
+        AccountPlatform::Kiro,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AccountPlatform::Antigravity => "antigravity",
+            AccountPlatform::Codex => "codex",
+            AccountPlatform::GitHubCopilot => "github_copilot",
+            AccountPlatform::Windsurf => "windsurf",
+            AccountPlatform::Kiro => "kiro",
+        }
+    }
+
+    /// 账号索引的文档名（即旧版索引文件名）
+    pub fn index_name(&self) -> &'static str {
+        match self {
+            AccountPlatform::Antigravity => "accounts.json",
+            AccountPlatform::Codex => "codex_accounts.json",
+            AccountPlatform::GitHubCopilot => "github_copilot_accounts.json",
+            AccountPlatform::Windsurf => "windsurf_accounts.json",
+            AccountPlatform::Kiro => "kiro_accounts.json",
+        }
+    }
+
+    /// 实例配置的文档名（即旧版实例配置文件名）
+    pub fn instance_store_name(&self) -> &'static str {
+        match self {
+            AccountPlatform::Antigravity => "instances.json",
+            AccountPlatform::Codex => "codex_instances.json",
+            AccountPlatform::GitHubCopilot => "github_copilot_instances.json",
+            AccountPlatform::Windsurf => "windsurf_instances.json",
+            AccountPlatform::Kiro => "kiro_instances.json",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        AccountPlatform::ALL
+            .into_iter()
+            .find(|platform| platform.as_str() == value)
+    }
+
+    /// 旧版账号详情目录名
+    fn accounts_dir_name(&self) -> &'static str {
+        match self {
+            AccountPlatform::Antigravity => "accounts",
+            AccountPlatform::Codex => "codex_accounts",
+            AccountPlatform::GitHubCopilot => "github_copilot_accounts",
+            AccountPlatform::Windsurf => "windsurf_accounts",
+            AccountPlatform::Kiro => "kiro_accounts",
+        }
+    }
+
+    /// 旧版索引文件与账号目录所在的根目录（Codex 使用系统本地数据目录）
+    fn legacy_root(&self) -> Result<PathBuf, String> {
+        match self {
+            AccountPlatform::Codex => Ok(dirs::data_local_dir()
+                .or_else(dirs::home_dir)
+                .ok_or("无法获取用户目录")?
+                .join("com.antigravity.cockpit-tools")),
+            _ => account::get_data_dir(),
+        }
+    }
+}
+
+pub fn db_path() -> Result<PathBuf, String> {
+    Ok(account::get_data_dir()?.join(DB_FILE))
+}
+
+fn now_ts() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+pub(crate) fn db_error(e: rusqlite::Error) -> String {
+    format!("数据库操作失败: {}", e)
+}
+
+fn open_connection() -> Result<Connection, String> {
+    let path = db_path()?;
+    let mut conn = Connection::open(&path).map_err(|e| format!("打开数据库失败: {}", e))?;
+    conn.busy_timeout(std::time::Duration::from_secs(5))
+        .map_err(db_error)?;
+    conn.pragma_update(None, "journal_mode", "WAL")
+        .map_err(db_error)?;
+    apply_schema(&mut conn)?;
+    import_legacy_json(&mut conn)?;
+    Ok(conn)
+}
+
+fn apply_schema(conn: &mut Connection) -> Result<(), String> {
+    let current: usize = conn
+        .pragma_query_value(None, "user_version", |row| row.get::<_, i64>(0))
+        .map_err(db_error)?
+        .max(0) as usize;
+    if current > SCHEMA_MIGRATIONS.len() {
+        return Err(format!(
+            "数据库版本 ({}) 高于当前应用支持的版本 ({})，请升级应用",
+            current,
+            SCHEMA_MIGRATIONS.len()
+        ));
+    }
+    for (index, sql) in SCHEMA_MIGRATIONS.iter().enumerate().skip(current) {
+        let version = index + 1;
+        let tx = conn.transaction().map_err(db_error)?;
+        tx.execute_batch(sql)
+            .map_err(|e| format!("数据库结构升级到 v{} 失败: {}", version, e))?;
+        tx.pragma_update(None, "user_version", version as i64)
+            .map_err(db_error)?;
+        tx.commit().map_err(db_error)?;
+        logger::log_info(&format!("[DataStore] 数据库结构已升级到 v{}", version));
+    }
+    Ok(())
+}
+
+/// 在数据库连接上执行操作（首次调用时打开数据库并完成结构升级与旧数据导入）
+pub(crate) fn with_connection<T>(
+    f: impl FnOnce(&mut Connection) -> Result<T, String>,
+) -> Result<T, String> {
+    let mut guard = CONNECTION
+        .lock()
+        .map_err(|e| format!("获取数据库锁失败: {}", e))?;
+    if guard.is_none() {
+        *guard = Some(open_connection()?);
+    }
+    f(guard.as_mut().expect("数据库连接已初始化"))
+}
+
+/// 在单个事务中执行多项写入
+pub fn transaction<T>(f: impl FnOnce(&StoreTx) -> Result<T, String>) -> Result<T, String> {
+    with_connection(|conn| {
+        let tx = conn.transaction().map_err(db_error)?;
+        let store = StoreTx {
+            tx: &tx,
+            shared: RefCell::new(Vec::new()),
+        };
+        let result = f(&store)?;
+        let shared = store.shared.into_inner();
+        tx.commit().map_err(db_error)?;
+        write_shared(shared);
+        Ok(result)
+    })
+}
+
+/// 事务内的写入操作
+pub struct StoreTx<'a> {
+    tx: &'a Transaction<'a>,
+    /// 提交后需要同步到共享文件的写入
+    shared: RefCell<Vec<SharedWrite>>,
+}
+
+impl StoreTx<'_> {
+    pub fn save_document(&self, name: &str, content: &str) -> Result<(), String> {
+        upsert_document(self.tx, name, content)?;
+        if is_shared_document(name) {
+            self.shared
+                .borrow_mut()
+                .push(SharedWrite::Index(content.to_string()));
+        }
+        Ok(())
+    }
+
+    pub fn delete_document(&self, name: &str) -> Result<(), String> {
+        self.tx
+            .execute("DELETE FROM documents WHERE name = ?1", params![name])
+            .map_err(db_error)?;
+        Ok(())
+    }
+
+    pub fn save_account(
+        &self,
+        platform: AccountPlatform,
+        id: &str,
+        content: &str,
+    ) -> Result<(), String> {
+        let sealed = vault::seal(content)?;
+        upsert_account(self.tx, platform, id, &sealed)?;
+        self.share_account(platform, id, Some(content.to_string()));
+        Ok(())
+    }
+
+    pub fn delete_account(&self, platform: AccountPlatform, id: &str) -> Result<(), String> {
+        self.tx
+            .execute(
+                "DELETE FROM accounts WHERE platform = ?1 AND id = ?2",
+                params![platform.as_str(), id],
+            )
+            .map_err(db_error)?;
+        self.share_account(platform, id, None);
+        Ok(())
+    }
+
+    /// 把账号详情移入回收站（同 ID 的旧回收记录会被覆盖），账号不存在时返回 false
+    pub fn trash_account(&self, platform: AccountPlatform, id: &str) -> Result<bool, String> {
+        let trashed = trash_account_row(self.tx, platform, id)?;
+        if trashed {
+            self.share_account(platform, id, None);
+        }
+        Ok(trashed)
+    }
+
+    fn share_account(&self, platform: AccountPlatform, id: &str, content: Option<String>) {
+        if platform == AccountPlatform::Antigravity {
+            self.shared
+                .borrow_mut()
+                .push(SharedWrite::Account(id.to_string(), content));
+        }
+    }
+}
+
+fn trash_account_row(
+    conn: &Connection,
+    platform: AccountPlatform,
+    id: &str,
+) -> Result<bool, String> {
+    let content: Option<String> = conn
+        .query_row(
+            "SELECT content FROM accounts WHERE platform = ?1 AND id = ?2",
+            params![platform.as_str(), id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(db_error)?;
+    let Some(content) = content else {
+        return Ok(false);
+    };
+    let label = vault::open(&content)
+        .ok()
+        .and_then(|content| content_label(&content));
+    conn.execute(
+        "INSERT OR REPLACE INTO account_trash (platform, id, label, content, deleted_at)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![platform.as_str(), id, label, content, now_ts()],
+    )
+    .map_err(db_error)?;
+    conn.execute(
+        "DELETE FROM accounts WHERE platform = ?1 AND id = ?2",
+        params![platform.as_str(), id],
+    )
+    .map_err(db_error)?;
+    Ok(true)
+}
+
+/// 从账号详情中取出用于展示的标识（邮箱或 GitHub 登录名）
+fn content_label(content: &str) -> Option<String> {
+    let record: serde_json::Value = serde_json::from_str(content).ok()?;
+    ["email", "github_login"]
+        .iter()
+        .find_map(|field| record.get(*field)?.as_str())
+        .filter(|label| !label.is_empty())
+        .map(str::to_string)
+}
+
+fn upsert_document(conn: &Connection, name: &str, content: &str) -> Result<(), String> {
+    conn.execute(
+        "INSERT INTO documents (name, content, updated_at) VALUES (?1, ?2, ?3)
+         ON CONFLICT(name) DO UPDATE SET content = excluded.content, updated_at = excluded.updated_at",
+        params![name, content, now_ts()],
+    )
+    .map_err(db_error)?;
+    record_snapshot(conn, name, content)
+}
+
+/// 记录文档快照：跳过无法解析的内容与和最新快照相同的内容，只保留最近 `SNAPSHOT_LIMIT` 份
+fn record_snapshot(conn: &Connection, name: &str, content: &str) -> Result<(), String> {
+    if serde_json::from_str::<serde::de::IgnoredAny>(content).is_err() {
+        return Ok(());
+    }
+    let latest: Option<String> = conn
+        .query_row(
+            "SELECT content FROM document_snapshots WHERE name = ?1 ORDER BY id DESC LIMIT 1",
+            params![name],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(db_error)?;
+    if latest.as_deref() == Some(content) {
+        return Ok(());
+    }
+    conn.execute(
+        "INSERT INTO document_snapshots (name, content, created_at) VALUES (?1, ?2, ?3)",
+        params![name, content, now_ts()],
+    )
+    .map_err(db_error)?;
+    conn.execute(
+        "DELETE FROM document_snapshots WHERE name = ?1 AND id NOT IN (
+            SELECT id FROM document_snapshots WHERE name = ?1 ORDER BY id DESC LIMIT ?2
+         )",
+        params![name, SNAPSHOT_LIMIT as i64],
+    )
+    .map_err(db_error)?;
+    Ok(())
+}
+
+fn upsert_account(
+    conn: &Connection,
+    platform: AccountPlatform,
+    id: &str,
+    content: &str,
+) -> Result<(), String> {
+    conn.execute(
+        "INSERT INTO accounts (platform, id, content, updated_at) VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(platform, id) DO UPDATE SET content = excluded.content, updated_at = excluded.updated_at",
+        params![platform.as_str(), id, content, now_ts()],
+    )
+    .map_err(db_error)?;
+    Ok(())
+}
+
+/// 打开数据库（启动时调用，尽早完成旧数据导入）
+pub fn init() -> Result<(), String> {
+    with_connection(|_| Ok(()))
+}
+
+/// 读取文档（账号索引、实例配置），不存在时返回 None
+pub fn load_document(name: &str) -> Result<Option<String>, String> {
+    with_connection(|conn| {
+        if is_shared_document(name) {
+            import_shared_changes(conn);
+        }
+        conn.query_row(
+            "SELECT content FROM documents WHERE name = ?1",
+            params![name],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(db_error)
+    })
+}
+
+pub fn save_document(name: &str, content: &str) -> Result<(), String> {
+    with_connection(|conn| upsert_document(conn, name, content))?;
+    if is_shared_document(name) {
+        write_shared(vec![SharedWrite::Index(content.to_string())]);
+    }
+    Ok(())
+}
+
+/// 文档在错误信息中展示的位置（`<数据库路径>#<文档名>`）
+pub fn document_location(name: &str) -> String {
+    let db = db_path()
+        .map(|path| path.to_string_lossy().to_string())
+        .unwrap_or_else(|_| DB_FILE.to_string());
+    format!("{}#{}", db, name)
+}
+
+/// 从 `document_location` 生成的位置中取出文档名，不是数据库文档位置时返回 None
+pub fn document_name(location: &str) -> Result<Option<String>, String> {
+    let Some((db, name)) = location.rsplit_once('#') else {
+        return Ok(None);
+    };
+    if Path::new(db) != db_path()? {
+        return Ok(None);
+    }
+    Ok(Some(name.to_string()))
+}
+
+/// 文档快照 (保存时间, 内容)，最新的在前
+pub fn document_snapshots(name: &str) -> Result<Vec<(i64, String)>, String> {
+    with_connection(|conn| {
+        let mut stmt = conn
+            .prepare(
+                "SELECT created_at, content FROM document_snapshots WHERE name = ?1 ORDER BY id DESC",
+            )
+            .map_err(db_error)?;
+        let rows = stmt
+            .query_map(params![name], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(db_error)?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(db_error)
+    })
+}
+
+/// 把损坏的文档改名为 `<文档名>.corrupted.<时间戳>` 保留在数据库中，返回新文档名；
+/// `location` 不是数据库文档位置时返回 None
+pub fn quarantine_document(location: &str) -> Result<Option<String>, String> {
+    let Some(name) = document_name(location)? else {
+        return Ok(None);
+    };
+    let backup_name = format!("{}.corrupted.{}", name, now_ts());
+    with_connection(|conn| {
+        conn.execute(
+            "UPDATE documents SET name = ?1 WHERE name = ?2",
+            params![backup_name, name],
+        )
+        .map_err(db_error)
+    })?;
+    logger::log_info(&format!(
+        "[DataStore] 已隔离损坏的文档: {} -> {}",
+        name, backup_name
+    ));
+    Ok(Some(backup_name))
+}
+
+/// 把数据库完整快照写入 `target`（目标文件必须不存在），用于迁移前备份
+pub fn backup_to(target: &Path) -> Result<(), String> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
+    }
+    with_connection(|conn| {
+        conn.execute(
+            "VACUUM INTO ?1",
+            params![target.to_string_lossy().to_string()],
+        )
+        .map_err(db_error)?;
+        Ok(())
+    })
+}
+
+/// 重建数据库文件并截断 WAL，避免改写前的内容残留在空闲页中（启用凭据库后调用）
+pub fn vacuum() -> Result<(), String> {
+    with_connection(|conn| {
+        conn.execute_batch("VACUUM").map_err(db_error)?;
+        conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+            .map_err(db_error)
+    })
+}
+
+/// 旧版 JSON 文件的备份目录（`legacy_json/<时间>/`），其中的账号数据为明文
+pub fn legacy_backup_dirs() -> Vec<PathBuf> {
+    let Ok(root) = account::get_data_dir().map(|dir| dir.join(LEGACY_BACKUP_DIR)) else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(root) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+/// 读取单个账号详情（已解密），不存在时返回 None
+pub fn load_account(platform: AccountPlatform, id: &str) -> Result<Option<String>, String> {
+    let content: Option<String> = with_connection(|conn| {
+        if platform == AccountPlatform::Antigravity {
+            import_shared_changes(conn);
+        }
+        conn.query_row(
+            "SELECT content FROM accounts WHERE platform = ?1 AND id = ?2",
+            params![platform.as_str(), id],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(db_error)
+    })?;
+    content.map(|content| vault::open(&content)).transpose()
+}
+
+/// 一次读取平台下全部账号详情（已解密），返回 (账号 ID, 内容)；解密失败的账号会被跳过并记录日志
+pub fn load_accounts(platform: AccountPlatform) -> Result<Vec<(String, String)>, String> {
+    Ok(load_stored_accounts(platform)?
+        .into_iter()
+        .filter_map(|(id, content)| match vault::open(&content) {
+            Ok(content) => Some((id, content)),
+            Err(e) => {
+                logger::log_warn(&format!(
+                    "[DataStore] 读取账号失败: platform={}, id={}, {}",
+                    platform.as_str(),
+                    id,
+                    e
+                ));
+                None
+            }
+        })
+        .collect())
+}
+
+/// 与 `load_accounts` 相同，但任一账号无法解密时返回错误（备份等不能缺少账号的场景）
+pub fn load_accounts_strict(platform: AccountPlatform) -> Result<Vec<(String, String)>, String> {
+    load_stored_accounts(platform)?
+        .into_iter()
+        .map(|(id, content)| {
+            vault::open(&content)
+                .map(|content| (id.clone(), content))
+                .map_err(|e| {
+                    format!(
+                        "读取账号失败: platform={}, id={}, {}",
+                        platform.as_str(),
+                        id,
+                        e
+                    )
+                })
+        })
+        .collect()
+}
+
+/// 读取平台下全部账号的存储内容（未解密）
+fn load_stored_accounts(platform: AccountPlatform) -> Result<Vec<(String, String)>, String> {
+    with_connection(|conn| {
+        if platform == AccountPlatform::Antigravity {
+            import_shared_changes(conn);
+        }
+        let mut stmt = conn
+            .prepare("SELECT id, content FROM accounts WHERE platform = ?1")
+            .map_err(db_error)?;
+        let rows = stmt
+            .query_map(params![platform.as_str()], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .map_err(db_error)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(db_error);
+        rows
+    })
+}
+
+/// 按索引顺序一次性加载账号详情，缺失或无法解析的账号会被跳过并记录日志
+pub fn load_indexed_accounts<'a, T: DeserializeOwned>(
+    platform: AccountPlatform,
+    ids: impl IntoIterator<Item = &'a str>,
+) -> Vec<T> {
+    let mut stored: HashMap<String, String> = match load_accounts(platform) {
+        Ok(rows) => rows.into_iter().collect(),
+        Err(e) => {
+            logger::log_error(&format!(
+                "[DataStore] 加载账号列表失败: platform={}, {}",
+                platform.as_str(),
+                e
+            ));
+            return Vec::new();
+        }
+    };
+    ids.into_iter()
+        .filter_map(|id| {
+            let content = stored.remove(id)?;
+            match serde_json::from_str(&content) {
+                Ok(account) => Some(account),
+                Err(e) => {
+                    logger::log_warn(&format!(
+                        "[DataStore] 解析账号数据失败: platform={}, id={}, {}",
+                        platform.as_str(),
+                        id,
+                        e
+                    ));
+                    None
+                }
+            }
+        })
+        .collect()
+}
+
+pub fn save_account(platform: AccountPlatform, id: &str, content: &str) -> Result<(), String> {
+    let sealed = vault::seal(content)?;
+    with_connection(|conn| upsert_account(conn, platform, id, &sealed))?;
+    if platform == AccountPlatform::Antigravity {
+        write_shared(vec![SharedWrite::Account(
+            id.to_string(),
+            Some(content.to_string()),
+        )]);
+    }
+    Ok(())
+}
+
+pub fn delete_account(platform: AccountPlatform, id: &str) -> Result<(), String> {
+    transaction(|tx| tx.delete_account(platform, id))
+}
+
+/// 账号的展示标识（邮箱或 GitHub 登录名），账号不存在或无法读取时返回 None
+pub fn account_label(platform: AccountPlatform, id: &str) -> Option<String> {
+    content_label(&load_account(platform, id).ok()??)
+}
+
+// ---------------------------------------------------------------------------
+// 账号回收站
+// ---------------------------------------------------------------------------
+
+/// 回收站中的账号
+#[derive(Debug, Clone)]
+pub struct TrashedAccount {
+    pub platform: AccountPlatform,
+    pub id: String,
+    /// 删除时记录的邮箱或登录名
+    pub label: Option<String>,
+    pub deleted_at: i64,
+}
+
+/// 把账号详情移入回收站，账号不存在时返回 false
+pub fn trash_account(platform: AccountPlatform, id: &str) -> Result<bool, String> {
+    transaction(|tx| tx.trash_account(platform, id))
+}
+
+/// 列出回收站中的账号（`platform` 为 None 时列出全部平台），最近删除的在前
+pub fn list_trash(platform: Option<AccountPlatform>) -> Result<Vec<TrashedAccount>, String> {
+    let rows: Vec<(String, String, Option<String>, i64)> = with_connection(|conn| {
+        let mut stmt = conn
+            .prepare(
+                "SELECT platform, id, label, deleted_at FROM account_trash
+                 WHERE ?1 IS NULL OR platform = ?1 ORDER BY deleted_at DESC, id",
+            )
+            .map_err(db_error)?;
+        let rows = stmt
+            .query_map(params![platform.map(|p| p.as_str())], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+            })
+            .map_err(db_error)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(db_error);
+        rows
+    })?;
+    Ok(rows
+        .into_iter()
+        .filter_map(|(platform, id, label, deleted_at)| {
+            Some(TrashedAccount {
+                platform: AccountPlatform::parse(&platform)?,
+                id,
+                label,
+                deleted_at,
+            })
+        })
+        .collect())
+}
+
+/// 把回收站中的账号放回账号表，并在同一事务中以解密后的内容调用 `apply`（用于更新账号索引），
+/// `apply` 失败时整体回滚；同 ID 的账号已重新添加时返回错误
+pub fn restore_trashed_account<T>(
+    platform: AccountPlatform,
+    id: &str,
+    apply: impl FnOnce(&StoreTx, &str) -> Result<T, String>,
+) -> Result<T, String> {
+    transaction(|tx| {
+        let exists = tx
+            .tx
+            .query_row(
+                "SELECT 1 FROM accounts WHERE platform = ?1 AND id = ?2",
+                params![platform.as_str(), id],
+                |_| Ok(()),
+            )
+            .optional()
+            .map_err(db_error)?
+            .is_some();
+        if exists {
+            return Err(format!("账号已重新添加，无法从回收站恢复: {}", id));
+        }
+        let sealed: String = tx
+            .tx
+            .query_row(
+                "SELECT content FROM account_trash WHERE platform = ?1 AND id = ?2",
+                params![platform.as_str(), id],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(db_error)?
+            .ok_or_else(|| format!("回收站中不存在该账号: {}", id))?;
+        let content = vault::open(&sealed)?;
+        upsert_account(tx.tx, platform, id, &sealed)?;
+        tx.share_account(platform, id, Some(sealed));
+        tx.tx
+            .execute(
+                "DELETE FROM account_trash WHERE platform = ?1 AND id = ?2",
+                params![platform.as_str(), id],
+            )
+            .map_err(db_error)?;
+        apply(tx, &content)
+    })
+}
+
+/// 彻底删除回收站中的账号，返回删除的条数
+pub fn purge_trash(platform: AccountPlatform, ids: &[String]) -> Result<usize, String> {
+    transaction(|tx| {
+        let mut purged = 0usize;
+        for id in ids {
+            purged += tx
+                .tx
+                .execute(
+                    "DELETE FROM account_trash WHERE platform = ?1 AND id = ?2",
+                    params![platform.as_str(), id],
+                )
+                .map_err(db_error)?;
+        }
+        Ok(purged)
+    })
+}
+
+/// 清理删除时间早于 `before`（秒）的回收记录，返回清理的条数
+pub fn purge_trash_before(before: i64) -> Result<usize, String> {
+    with_connection(|conn| {
+        conn.execute(
+            "DELETE FROM account_trash WHERE deleted_at < ?1",
+            params![before],
+        )
+        .map_err(db_error)
+    })
+}
+
+/// 逐条改写全部账号详情（含回收站，凭据库启用/停用时加密或解密），`rewrite` 返回 None
+/// 表示保持不变；全部成功后才提交，返回改写的条数
+pub fn rewrite_accounts(
+    mut rewrite: impl FnMut(&str) -> Result<Option<String>, String>,
+) -> Result<usize, String> {
+    with_connection(|conn| {
+        let tx = conn.transaction().map_err(db_error)?;
+        let mut changed = 0usize;
+        for table in ["accounts", "account_trash"] {
+            let rows: Vec<(String, String, String)> = {
+                let mut stmt = tx
+                    .prepare(&format!("SELECT platform, id, content FROM {}", table))
+                    .map_err(db_error)?;
+                let rows = stmt
+                    .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+                    .map_err(db_error)?
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(db_error)?;
+                rows
+            };
+            for (platform, id, content) in rows {
+                if let Some(updated) = rewrite(&content)? {
+                    tx.execute(
+                        &format!(
+                            "UPDATE {} SET content = ?1 WHERE platform = ?2 AND id = ?3",
+                            table
+                        ),
+                        params![updated, platform, id],
+                    )
+                    .map_err(db_error)?;
+                    changed += 1;
+                }
+            }
+        }
+        // 共享文件保存的是明文，加解密不改变其内容，无需同步
+        tx.commit().map_err(db_error)?;
+        Ok(changed)
+    })
+}
+
+// ---------------------------------------------------------------------------
+// 旧版 JSON 布局
+// ---------------------------------------------------------------------------
+
+/// 待导入的旧版文件：(文档名或账号, 源文件)
+enum LegacyEntry {
+    Document(String, PathBuf),
+    Account(AccountPlatform, String, PathBuf),
+}
+
+fn collect_legacy_entries() -> Result<Vec<LegacyEntry>, String> {
+    let data_dir = account::get_data_dir()?;
+    let mut entries = Vec::new();
+    for platform in AccountPlatform::ALL {
+        let name = platform.instance_store_name();
+        let path = data_dir.join(name);
+        if path.is_file() {
+            entries.push(LegacyEntry::Document(name.to_string(), path));
+        }
+    }
+    for platform in AccountPlatform::ALL {
+        let root = platform.legacy_root()?;
+        let index_path = root.join(platform.index_name());
+        if index_path.is_file() {
+            entries.push(LegacyEntry::Document(
+                platform.index_name().to_string(),
+                index_path,
+            ));
+        }
+        let Ok(dir) = fs::read_dir(root.join(platform.accounts_dir_name())) else {
+            continue;
+        };
+        for entry in dir.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            if let Some(id) = path.file_stem().and_then(|stem| stem.to_str()) {
+                entries.push(LegacyEntry::Account(platform, id.to_string(), path.clone()));
+            }
+        }
+    }
+    Ok(entries)
+}
+
+/// 首次打开数据库时导入旧版 JSON 文件（原样导入，已加密的账号文件保持加密），
+/// 提交后把旧文件移入备份目录
+fn import_legacy_json(conn: &mut Connection) -> Result<(), String> {
+    let imported: Option<String> = conn
+        .query_row(
+            "SELECT value FROM meta WHERE key = ?1",
+            params![META_LEGACY_IMPORTED],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(db_error)?;
+    if imported.is_some() {
+        return Ok(());
+    }
+
+    let entries = collect_legacy_entries()?;
+    let tx = conn.transaction().map_err(db_error)?;
+    for entry in &entries {
+        match entry {
+            LegacyEntry::Document(name, path) => {
+                let content = fs::read_to_string(path)
+                    .map_err(|e| format!("读取旧数据失败({}): {}", path.display(), e))?;
+                if !content.trim().is_empty() {
+                    upsert_document(&tx, name, &content)?;
+                }
+            }
+            LegacyEntry::Account(platform, id, path) => {
+                let content = fs::read_to_string(path)
+                    .map_err(|e| format!("读取旧数据失败({}): {}", path.display(), e))?;
+                upsert_account(&tx, *platform, id, &content)?;
+            }
+        }
+    }
+    tx.execute(
+        "INSERT INTO meta (key, value) VALUES (?1, ?2)",
+        params![META_LEGACY_IMPORTED, now_ts().to_string()],
+    )
+    .map_err(db_error)?;
+    tx.commit().map_err(db_error)?;
+
+    if entries.is_empty() {
+        return Ok(());
+    }
+    logger::log_info(&format!(
+        "[DataStore] 已将 {} 个旧版 JSON 文件导入数据库",
+        entries.len()
+    ));
+    if let Err(e) = move_legacy_files(&entries) {
+        logger::log_warn(&format!("[DataStore] 备份旧版 JSON 文件失败: {}", e));
+    }
+    Ok(())
+}
+
+fn move_legacy_files(entries: &[LegacyEntry]) -> Result<(), String> {
+    let backup_root = account::get_data_dir()?
+        .join(LEGACY_BACKUP_DIR)
+        .join(chrono::Local::now().format("%Y%m%d-%H%M%S").to_string());
+    for entry in entries {
+        let (relative, source) = match entry {
+            LegacyEntry::Document(name, path) => (PathBuf::from(name), path),
+            LegacyEntry::Account(platform, id, path) => (
+                Path::new(platform.accounts_dir_name()).join(format!("{}.json", id)),
+                path,
+            ),
+        };
+        let target = backup_root.join(relative);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("创建备份目录失败: {}", e))?;
+        }
+        if is_shared_entry(entry) {
+            // VS Code 扩展仍在使用，只复制备份，原文件保留
+            fs::copy(source, &target).map_err(|e| format!("备份旧文件失败: {}", e))?;
+            continue;
+        }
+        if fs::rename(source, &target).is_err() {
+            // 跨分区（Codex 目录）时无法 rename，改为复制后删除
+            fs::copy(source, &target).map_err(|e| format!("备份旧文件失败: {}", e))?;
+            fs::remove_file(source).map_err(|e| format!("删除旧文件失败: {}", e))?;
+        }
+    }
+    Ok(())
+}
+
+fn is_shared_entry(entry: &LegacyEntry) -> bool {
+    match entry {
+        LegacyEntry::Document(name, _) => is_shared_document(name),
+        LegacyEntry::Account(platform, _, _) => *platform == AccountPlatform::Antigravity,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// 与 VS Code 扩展共享的 Antigravity 账号文件
+// ---------------------------------------------------------------------------
+
+/// 提交后同步到共享文件的写入
+enum SharedWrite {
+    /// 账号索引内容
+    Index(String),
+    /// (账号 ID, 明文内容)，None 表示删除
+    Account(String, Option<String>),
+}
+
+/// 共享文件最近一次读入或写出时的修改时间，未变化的文件读取前不再比较内容
+static SHARED_MTIMES: LazyLock<Mutex<HashMap<PathBuf, SystemTime>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+/// 上次扫描共享文件的时间：逐个读取账号时间隔内只扫描一次，避免每次读取都遍历目录
+static SHARED_SCANNED_AT: Mutex<Option<Instant>> = Mutex::new(None);
+/// 两次扫描共享文件的最小间隔
+const SHARED_SCAN_INTERVAL: Duration = Duration::from_secs(2);
+
+fn is_shared_document(name: &str) -> bool {
+    name == AccountPlatform::Antigravity.index_name()
+}
+
+fn shared_index_path() -> Result<PathBuf, String> {
+    let platform = AccountPlatform::Antigravity;
+    Ok(platform.legacy_root()?.join(platform.index_name()))
+}
+
+fn shared_accounts_dir() -> Result<PathBuf, String> {
+    let platform = AccountPlatform::Antigravity;
+    Ok(platform.legacy_root()?.join(platform.accounts_dir_name()))
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+fn remember_mtime(mtimes: &mut HashMap<PathBuf, SystemTime>, path: &Path) {
+    match modified_time(path) {
+        Some(modified) => {
+            mtimes.insert(path.to_path_buf(), modified);
+        }
+        None => {
+            mtimes.remove(path);
+        }
+    }
+}
+
+fn write_shared_file(write: &SharedWrite) -> Result<PathBuf, String> {
+    match write {
+        SharedWrite::Index(content) => {
+            let path = shared_index_path()?;
+            atomic_file::write(&path, content)?;
+            Ok(path)
+        }
+        SharedWrite::Account(id, Some(content)) => {
+            let path = shared_accounts_dir()?.join(format!("{}.json", id));
+            atomic_file::write(&path, content)?;
+            Ok(path)
+        }
+        SharedWrite::Account(id, None) => {
+            let path = shared_accounts_dir()?.join(format!("{}.json", id));
+            if path.exists() {
+                fs::remove_file(&path).map_err(|e| format!("删除账号文件失败: {}", e))?;
+            }
+            Ok(path)
+        }
+    }
+}
+
+/// 把已提交的写入同步到共享文件；失败只记录日志，数据库中的数据仍是准确的
+fn write_shared(writes: Vec<SharedWrite>) {
+    if writes.is_empty() {
+        return;
+    }
+    let Ok(mut mtimes) = SHARED_MTIMES.lock() else {
+        return;
+    };
+    for write in &writes {
+        match write_shared_file(write) {
+            Ok(path) => remember_mtime(&mut mtimes, &path),
+            Err(e) => logger::log_warn(&format!("[DataStore] 同步共享账号文件失败: {}", e)),
+        }
+    }
+}
+
+/// 导入 VS Code 扩展修改过的共享文件：修改时间与上次同步时不同且内容与数据库不一致时以文件为准。
+/// 无法解析为 JSON 的文件不会导入；凭据库锁定时暂不导入账号文件（无法加密），解锁后再处理。
+/// 扩展删除的账号文件不会删除数据库中的账号，账号是否存在以索引为准
+fn import_shared_changes(conn: &Connection) {
+    if let Ok(mut scanned_at) = SHARED_SCANNED_AT.lock() {
+        if scanned_at.is_some_and(|at| at.elapsed() < SHARED_SCAN_INTERVAL) {
+            return;
+        }
+        *scanned_at = Some(Instant::now());
+    }
+    if let Err(e) = try_import_shared_changes(conn) {
+        logger::log_warn(&format!("[DataStore] 导入共享账号文件失败: {}", e));
+    }
+}
+
+fn try_import_shared_changes(conn: &Connection) -> Result<(), String> {
+    let mut files = vec![(None, shared_index_path()?)];
+    if let Ok(dir) = fs::read_dir(shared_accounts_dir()?) {
+        for entry in dir.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+                continue;
+            }
+            if let Some(id) = path.file_stem().and_then(|stem| stem.to_str()) {
+                files.push((Some(id.to_string()), path.clone()));
+            }
+        }
+    }
+
+    let locked = vault::ensure_unlocked().is_err();
+    let mut mtimes = SHARED_MTIMES
+        .lock()
+        .map_err(|e| format!("获取锁失败: {}", e))?;
+    for (id, path) in files {
+        let Some(modified) = modified_time(&path) else {
+            continue;
+        };
+        if mtimes.get(&path) == Some(&modified) || (locked && id.is_some()) {
+            continue;
+        }
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("读取共享文件失败({}): {}", path.display(), e))?;
+        if serde_json::from_str::<serde::de::IgnoredAny>(&content).is_err() {
+            // 扩展写入中途或内容损坏：记下修改时间，文件再次变化时重新检查
+            if !content.trim().is_empty() {
+                logger::log_warn(&format!(
+                    "[DataStore] 共享文件不是有效的 JSON，已忽略: {}",
+                    path.display()
+                ));
+            }
+        } else {
+            let changed = match &id {
+                None => {
+                    let name = AccountPlatform::Antigravity.index_name();
+                    let current: Option<String> = conn
+                        .query_row(
+                            "SELECT content FROM documents WHERE name = ?1",
+                            params![name],
+                            |row| row.get(0),
+                        )
+                        .optional()
+                        .map_err(db_error)?;
+                    let changed = current.as_deref() != Some(content.as_str());
+                    if changed {
+                        upsert_document(conn, name, &content)?;
+                    }
+                    changed
+                }
+                Some(id) => {
+                    let current: Option<String> = conn
+                        .query_row(
+                            "SELECT content FROM accounts WHERE platform = ?1 AND id = ?2",
+                            params![AccountPlatform::Antigravity.as_str(), id],
+                            |row| row.get(0),
+                        )
+                        .optional()
+                        .map_err(db_error)?;
+                    let current = current.map(|stored| vault::open(&stored)).transpose()?;
+                    let changed = current.as_deref() != Some(content.as_str());
+                    if changed {
+                        let sealed = vault::seal(&content)?;
+                        upsert_account(conn, AccountPlatform::Antigravity, id, &sealed)?;
+                    }
+                    changed
+                }
+            };
+            if changed {
+                logger::log_info(&format!(
+                    "[DataStore] 已导入 VS Code 扩展修改的共享文件: {}",
+                    path.display()
+                ));
+            }
+        }
+        mtimes.insert(path, modified);
+    }
+    Ok(())
+}
+
+/// 导出用的全部行：(文档名, 内容) 与 (平台, 账号 ID, 内容)
+type ExportRows = (Vec<(String, String)>, Vec<(String, String, String)>);
+
+/// 把数据库内容导出为旧版 JSON 目录结构（账号详情保持数据库中的形式，凭据库启用时为加密内容），
+/// 返回写出的文件数
+pub fn export_json_layout(target_dir: &Path) -> Result<usize, String> {
+    let (documents, accounts): ExportRows = with_connection(|conn| {
+        let mut stmt = conn
+            .prepare("SELECT name, content FROM documents")
+            .map_err(db_error)?;
+        let documents = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .map_err(db_error)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(db_error)?;
+        let mut stmt = conn
+            .prepare("SELECT platform, id, content FROM accounts")
+            .map_err(db_error)?;
+        let accounts = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .map_err(db_error)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(db_error)?;
+        Ok((documents, accounts))
+    })?;
+
+    let mut written = 0usize;
+    for (name, content) in &documents {
+        atomic_file::replace(&target_dir.join(name), content)?;
+        written += 1;
+    }
+    for (platform, id, content) in &accounts {
+        let Some(platform) = AccountPlatform::parse(platform) else {
+            continue;
+        };
+        let path = target_dir
+            .join(platform.accounts_dir_name())
+            .join(format!("{}.json", id));
+        atomic_file::replace(&path, content)?;
+        written += 1;
+    }
+    logger::log_info(&format!(
+        "[DataStore] 已导出 {} 个 JSON 文件到 {}",
+        written,
+        target_dir.display()
+    ));
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema_migrations_create_tables_and_record_version() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        apply_schema(&mut conn).unwrap();
+        apply_schema(&mut conn).unwrap();
+
+        let version: i64 = conn
+            .pragma_query_value(None, "user_version", |row| row.get(0))
+            .unwrap();
+        assert_eq!(version as usize, SCHEMA_MIGRATIONS.len());
+
+        upsert_account(&conn, AccountPlatform::Kiro, "a", "{}").unwrap();
+        upsert_account(&conn, AccountPlatform::Kiro, "a", "{\"v\":2}").unwrap();
+        let content: String = conn
+            .query_row(
+                "SELECT content FROM accounts WHERE platform = 'kiro' AND id = 'a'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(content, "{\"v\":2}");
+    }
+
+    #[test]
+    fn document_writes_keep_rolling_valid_snapshots() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        apply_schema(&mut conn).unwrap();
+
+        for version in 0..SNAPSHOT_LIMIT + 2 {
+            upsert_document(&conn, "accounts.json", &format!("{{\"v\":{}}}", version)).unwrap();
+        }
+        upsert_document(&conn, "accounts.json", "{\"v\":6}").unwrap();
+        upsert_document(&conn, "accounts.json", "{\"v\":").unwrap();
+
+        let snapshots: Vec<String> = conn
+            .prepare("SELECT content FROM document_snapshots WHERE name = 'accounts.json' ORDER BY id DESC")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(snapshots.len(), SNAPSHOT_LIMIT);
+        assert_eq!(snapshots[0], "{\"v\":6}");
+        assert_eq!(snapshots[SNAPSHOT_LIMIT - 1], "{\"v\":2}");
+    }
+
+    #[test]
+    fn trashed_accounts_keep_content_and_label() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        apply_schema(&mut conn).unwrap();
+
+        upsert_account(
+            &conn,
+            AccountPlatform::Kiro,
+            "a",
+            "{\"email\":\"a@example.com\"}",
+        )
+        .unwrap();
+        assert!(trash_account_row(&conn, AccountPlatform::Kiro, "a").unwrap());
+        assert!(!trash_account_row(&conn, AccountPlatform::Kiro, "a").unwrap());
+
+        let remaining: i64 = conn
+            .query_row("SELECT COUNT(*) FROM accounts", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(remaining, 0);
+        let (label, content): (Option<String>, String) = conn
+            .query_row(
+                "SELECT label, content FROM account_trash WHERE platform = 'kiro' AND id = 'a'",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        assert_eq!(label.as_deref(), Some("a@example.com"));
+        assert_eq!(content, "{\"email\":\"a@example.com\"}");
+    }
+}
@@ -1,5 +1,5 @@
 use crate::models::DeviceProfile;
-use crate::modules::logger;
+use crate::modules::{atomic_file, logger};
 use rand::{distributions::Alphanumeric, Rng};
 use rusqlite::Connection;
 use serde_json::Value;
@@ -125,7 +125,7 @@ fn write_machine_id_file(service_id: &str) -> Result<(), String> {
     if let Some(parent) = path.parent() {
         fs::create_dir_all(parent).map_err(|e| format!("创建 machineid 目录失败: {}", e))?;
     }
-    fs::write(&path, service_id).map_err(|e| format!("写入 machineid 失败: {}", e))?;
+    atomic_file::replace(&path, service_id).map_err(|e| format!("写入 machineid 失败: {}", e))?;
     Ok(())
 }
 
@@ -306,7 +306,7 @@ pub fn write_profile(storage_path: &Path, profile: &DeviceProfile) -> Result<(),
     };
 
     let updated = serde_json::to_string_pretty(&json).map_err(|e| format!("序列化失败: {}", e))?;
-    fs::write(storage_path, updated).map_err(|e| format!("写入失败: {}", e))?;
+    atomic_file::replace(storage_path, updated).map_err(|e| format!("写入失败: {}", e))?;
     logger::log_info("已写入设备指纹到 storage.json");
 
     // 同步 machineid 文件
@@ -350,7 +350,7 @@ fn save_global_original_force(profile: &DeviceProfile) -> Result<(), String> {
     let path = dir.join(GLOBAL_BASELINE);
     let content =
         serde_json::to_string_pretty(profile).map_err(|e| format!("序列化失败: {}", e))?;
-    fs::write(&path, content).map_err(|e| format!("写入失败: {}", e))
+    atomic_file::write(&path, content).map_err(|e| format!("写入失败: {}", e))
 }
 
 /// 恢复原始设备指纹到 storage.json
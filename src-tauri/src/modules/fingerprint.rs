@@ -4,7 +4,7 @@ use std::path::PathBuf;
 use uuid::Uuid;
 
 use crate::models::DeviceProfile;
-use crate::modules::{device, logger};
+use crate::modules::{device, logger, recovery};
 
 const FINGERPRINTS_FILE: &str = "fingerprints.json";
 
@@ -99,7 +99,7 @@ pub fn save_fingerprint_store(store: &FingerprintStore) -> Result<(), String> {
     let path = get_fingerprints_path()?;
     let content =
         serde_json::to_string_pretty(store).map_err(|e| format!("序列化指纹存储失败: {}", e))?;
-    fs::write(&path, content).map_err(|e| format!("保存指纹存储失败: {}", e))
+    recovery::write_with_snapshots(&path, &content).map_err(|e| format!("保存指纹存储失败: {}", e))
 }
 
 /// 获取指纹详情
@@ -165,6 +165,28 @@ pub fn create_fingerprint_with_profile(
     Ok(fingerprint)
 }
 
+/// 合并外部指纹（按 ID 去重，已存在的保持不变），返回新增数量
+pub fn merge_fingerprints(fingerprints: &[Fingerprint]) -> Result<usize, String> {
+    if fingerprints.is_empty() {
+        return Ok(0);
+    }
+    let mut store = load_fingerprint_store()?;
+    let mut added = 0;
+    for fingerprint in fingerprints {
+        if fingerprint.id == "original" || store.fingerprints.iter().any(|f| f.id == fingerprint.id)
+        {
+            continue;
+        }
+        store.fingerprints.push(fingerprint.clone());
+        added += 1;
+    }
+    if added > 0 {
+        save_fingerprint_store(&store)?;
+        logger::log_info(&format!("已合并外部指纹: {} 个", added));
+    }
+    Ok(added)
+}
+
 /// 应用指纹到系统
 pub fn apply_fingerprint(fingerprint_id: &str) -> Result<String, String> {
     let fingerprint = get_fingerprint(fingerprint_id)?;
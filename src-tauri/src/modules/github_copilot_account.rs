@@ -1,43 +1,30 @@
 use crate::models::github_copilot::{
     GitHubCopilotAccount, GitHubCopilotAccountIndex, GitHubCopilotOAuthCompletePayload,
 };
-use crate::modules::{account, github_copilot_oauth, logger};
+use crate::modules::audit::{self, AuditAction, AuditEvent, AuditSource};
+use crate::modules::import_plan::ImportOutcome;
+use crate::modules::interchange::{self, InterchangeAccount};
+use crate::modules::quota_history;
+use crate::modules::quota_meter::{self, PlatformQuota};
+use crate::modules::{auto_switch, data_store, github_copilot_oauth, import_plan, logger};
 use std::collections::HashMap;
-use std::fs;
-use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
 
-const ACCOUNTS_INDEX_FILE: &str = "github_copilot_accounts.json";
-const ACCOUNTS_DIR: &str = "github_copilot_accounts";
+const PLATFORM: data_store::AccountPlatform = data_store::AccountPlatform::GitHubCopilot;
 static GHCP_ACCOUNT_INDEX_LOCK: std::sync::LazyLock<Mutex<()>> =
     std::sync::LazyLock::new(|| Mutex::new(()));
 static GHCP_QUOTA_ALERT_LAST_SENT: std::sync::LazyLock<Mutex<HashMap<String, i64>>> =
     std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
+static GHCP_AUTO_SWITCH_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
 const GHCP_QUOTA_ALERT_COOLDOWN_SECONDS: i64 = 300;
 
 fn now_ts() -> i64 {
     chrono::Utc::now().timestamp()
 }
 
-fn get_data_dir() -> Result<PathBuf, String> {
-    account::get_data_dir()
-}
-
-fn get_accounts_dir() -> Result<PathBuf, String> {
-    let base = get_data_dir()?;
-    let dir = base.join(ACCOUNTS_DIR);
-    if !dir.exists() {
-        fs::create_dir_all(&dir).map_err(|e| format!("创建 GitHub Copilot 账号目录失败: {}", e))?;
-    }
-    Ok(dir)
-}
-
-fn get_accounts_index_path() -> Result<PathBuf, String> {
-    Ok(get_data_dir()?.join(ACCOUNTS_INDEX_FILE))
-}
-
 pub fn accounts_index_path_string() -> Result<String, String> {
-    Ok(get_accounts_index_path()?.to_string_lossy().to_string())
+    Ok(data_store::document_location(PLATFORM.index_name()))
 }
 
 /// Load a single account by ID (public wrapper)
@@ -46,54 +33,34 @@ pub fn load_account(account_id: &str) -> Option<GitHubCopilotAccount> {
 }
 
 fn load_account_file(account_id: &str) -> Option<GitHubCopilotAccount> {
-    let account_path = get_accounts_dir()
-        .ok()
-        .map(|dir| dir.join(format!("{}.json", account_id)))?;
-    if !account_path.exists() {
-        return None;
-    }
-    let content = fs::read_to_string(account_path).ok()?;
+    let content = data_store::load_account(PLATFORM, account_id).ok()??;
     serde_json::from_str(&content).ok()
 }
 
 fn save_account_file(account: &GitHubCopilotAccount) -> Result<(), String> {
-    let path = get_accounts_dir()?.join(format!("{}.json", account.id));
     let content =
         serde_json::to_string_pretty(account).map_err(|e| format!("序列化账号失败: {}", e))?;
-    fs::write(path, content).map_err(|e| format!("保存账号失败: {}", e))
+    data_store::save_account(PLATFORM, &account.id, &content)
 }
 
-fn delete_account_file(account_id: &str) -> Result<(), String> {
-    let path = get_accounts_dir()?.join(format!("{}.json", account_id));
-    if path.exists() {
-        fs::remove_file(path).map_err(|e| format!("删除账号失败: {}", e))?;
-    }
-    Ok(())
+fn trash_account_file(account_id: &str) -> Result<(), String> {
+    data_store::trash_account(PLATFORM, account_id).map(|_| ())
 }
 
 fn load_account_index() -> GitHubCopilotAccountIndex {
-    let path = match get_accounts_index_path() {
-        Ok(p) => p,
-        Err(_) => return GitHubCopilotAccountIndex::new(),
-    };
-
-    if !path.exists() {
-        return GitHubCopilotAccountIndex::new();
-    }
-
-    match fs::read_to_string(path) {
-        Ok(content) => {
+    match data_store::load_document(PLATFORM.index_name()) {
+        Ok(Some(content)) => {
             serde_json::from_str(&content).unwrap_or_else(|_| GitHubCopilotAccountIndex::new())
         }
-        Err(_) => GitHubCopilotAccountIndex::new(),
+        _ => GitHubCopilotAccountIndex::new(),
     }
 }
 
 fn save_account_index(index: &GitHubCopilotAccountIndex) -> Result<(), String> {
-    let path = get_accounts_index_path()?;
     let content =
         serde_json::to_string_pretty(index).map_err(|e| format!("序列化账号索引失败: {}", e))?;
-    fs::write(path, content).map_err(|e| format!("写入账号索引失败: {}", e))
+    data_store::save_document(PLATFORM.index_name(), &content)
+        .map_err(|e| format!("写入账号索引失败: {}", e))
 }
 
 fn refresh_summary(index: &mut GitHubCopilotAccountIndex, account: &GitHubCopilotAccount) {
@@ -117,11 +84,10 @@ fn upsert_account_record(account: GitHubCopilotAccount) -> Result<GitHubCopilotA
 
 pub fn list_accounts() -> Vec<GitHubCopilotAccount> {
     let index = load_account_index();
-    index
-        .accounts
-        .iter()
-        .filter_map(|summary| load_account_file(&summary.id))
-        .collect()
+    data_store::load_indexed_accounts(
+        PLATFORM,
+        index.accounts.iter().map(|summary| summary.id.as_str()),
+    )
 }
 
 pub fn upsert_account(
@@ -217,6 +183,7 @@ pub async fn refresh_account_token(account_id: &str) -> Result<GitHubCopilotAcco
 
     let updated = account.clone();
     upsert_account_record(account)?;
+    quota_history::record(PLATFORM, &updated.id, &updated.quota_meters());
     Ok(updated)
 }
 
@@ -257,13 +224,20 @@ pub async fn refresh_all_tokens(
 }
 
 pub fn remove_account(account_id: &str) -> Result<(), String> {
+    let event = AuditEvent::new(PLATFORM, AuditAction::Delete).account(account_id);
+    let result = remove_account_inner(account_id);
+    event.result(&result).record();
+    result
+}
+
+fn remove_account_inner(account_id: &str) -> Result<(), String> {
     let _lock = GHCP_ACCOUNT_INDEX_LOCK
         .lock()
         .map_err(|_| "获取 GitHub Copilot 账号锁失败".to_string())?;
     let mut index = load_account_index();
     index.accounts.retain(|item| item.id != account_id);
     save_account_index(&index)?;
-    delete_account_file(account_id)?;
+    trash_account_file(account_id)?;
     Ok(())
 }
 
@@ -274,31 +248,78 @@ pub fn remove_accounts(account_ids: &[String]) -> Result<(), String> {
     Ok(())
 }
 
+/// 从回收站恢复账号并重新加入账号列表
+pub fn restore_account(account_id: &str) -> Result<GitHubCopilotAccount, String> {
+    let _lock = GHCP_ACCOUNT_INDEX_LOCK
+        .lock()
+        .map_err(|_| "获取 GitHub Copilot 账号锁失败".to_string())?;
+    let mut index = load_account_index();
+    data_store::restore_trashed_account(PLATFORM, account_id, |tx, content| {
+        let account: GitHubCopilotAccount =
+            serde_json::from_str(content).map_err(|e| format!("解析账号数据失败: {}", e))?;
+        refresh_summary(&mut index, &account);
+        let index_content = serde_json::to_string_pretty(&index)
+            .map_err(|e| format!("序列化账号索引失败: {}", e))?;
+        tx.save_document(PLATFORM.index_name(), &index_content)?;
+        Ok(account)
+    })
+}
+
 pub fn update_account_tags(
     account_id: &str,
     tags: Vec<String>,
 ) -> Result<GitHubCopilotAccount, String> {
-    let mut account = load_account_file(account_id).ok_or_else(|| "账号不存在".to_string())?;
-    account.tags = Some(tags);
-    account.last_used = now_ts();
-    let updated = account.clone();
-    upsert_account_record(account)?;
-    Ok(updated)
-}
-
-pub fn import_from_json(json_content: &str) -> Result<Vec<GitHubCopilotAccount>, String> {
-    if let Ok(account) = serde_json::from_str::<GitHubCopilotAccount>(json_content) {
-        let saved = upsert_account_record(account)?;
-        return Ok(vec![saved]);
+    let event = AuditEvent::new(PLATFORM, AuditAction::Tag)
+        .account(account_id)
+        .detail(tags.join(", "));
+    let result = (|| -> Result<GitHubCopilotAccount, String> {
+        let mut account = load_account_file(account_id).ok_or_else(|| "账号不存在".to_string())?;
+        account.tags = Some(tags);
+        account.last_used = now_ts();
+        let updated = account.clone();
+        upsert_account_record(account)?;
+        Ok(updated)
+    })();
+    event.result(&result).record();
+    result
+}
+
+pub fn import_from_json(json_content: &str) -> Result<ImportOutcome<GitHubCopilotAccount>, String> {
+    let result = import_from_json_inner(json_content);
+    audit::record_import(
+        PLATFORM,
+        "json",
+        result.as_ref().map(|outcome| outcome.imported.len()),
+    );
+    result
+}
+
+fn import_from_json_inner(
+    json_content: &str,
+) -> Result<ImportOutcome<GitHubCopilotAccount>, String> {
+    // 统一交换格式：只导入其中的 GitHub Copilot 账号
+    if let Some(document) = interchange::parse_if_interchange(json_content)? {
+        return Ok(import_plan::import_entries(
+            document.entries_for(PLATFORM),
+            import_interchange_entry,
+            update_account_tags,
+        ));
     }
 
-    if let Ok(accounts) = serde_json::from_str::<Vec<GitHubCopilotAccount>>(json_content) {
-        let mut result = Vec::new();
-        for account in accounts {
-            let saved = upsert_account_record(account)?;
-            result.push(saved);
-        }
-        return Ok(result);
+    // 原生账号记录同样按导入计划处理，避免旧的导出文件覆盖本机较新的 Token
+    let accounts = serde_json::from_str::<GitHubCopilotAccount>(json_content)
+        .map(|account| vec![account])
+        .or_else(|_| serde_json::from_str::<Vec<GitHubCopilotAccount>>(json_content));
+    if let Ok(accounts) = accounts {
+        let entries = accounts
+            .iter()
+            .map(to_interchange_entry)
+            .collect::<Result<Vec<_>, String>>()?;
+        return Ok(import_plan::import_entries(
+            &entries,
+            import_interchange_entry,
+            update_account_tags,
+        ));
     }
 
     Err("无法解析 JSON 内容".to_string())
@@ -312,118 +333,122 @@ pub fn export_accounts(account_ids: &[String]) -> Result<String, String> {
     serde_json::to_string_pretty(&accounts).map_err(|e| format!("序列化失败: {}", e))
 }
 
-fn normalize_quota_alert_threshold(raw: i32) -> i32 {
-    raw.clamp(0, 100)
-}
-
-fn clamp_percent(value: f64) -> i32 {
-    value.round().clamp(0.0, 100.0) as i32
+/// 交换格式中 GitHub Copilot 账号的配额字段
+const INTERCHANGE_QUOTA_FIELDS: &[&str] = &[
+    "copilot_quota_snapshots",
+    "copilot_quota_reset_date",
+    "copilot_limited_user_quotas",
+    "copilot_limited_user_reset_date",
+];
+
+/// 导出账号为交换格式条目（ID 为空时导出全部）
+pub fn export_interchange(account_ids: &[String]) -> Result<Vec<InterchangeAccount>, String> {
+    let accounts = if account_ids.is_empty() {
+        list_accounts()
+    } else {
+        account_ids
+            .iter()
+            .filter_map(|id| load_account_file(id))
+            .collect()
+    };
+    accounts.iter().map(to_interchange_entry).collect()
 }
 
-fn parse_token_map(token: &str) -> HashMap<String, String> {
-    let mut map = HashMap::new();
-    let prefix = token.split(':').next().unwrap_or(token);
-    for item in prefix.split(';') {
-        let mut parts = item.splitn(2, '=');
-        let key = parts.next().unwrap_or("").trim();
-        if key.is_empty() {
-            continue;
-        }
-        let value = parts.next().unwrap_or("").trim();
-        map.insert(key.to_string(), value.to_string());
-    }
-    map
+fn to_interchange_entry(account: &GitHubCopilotAccount) -> Result<InterchangeAccount, String> {
+    interchange::to_entry(
+        PLATFORM,
+        account,
+        &display_email(account),
+        INTERCHANGE_QUOTA_FIELDS,
+    )
 }
 
-fn parse_token_number(map: &HashMap<String, String>, key: &str) -> Option<f64> {
-    map.get(key)
-        .and_then(|value| value.trim().parse::<f64>().ok())
-        .filter(|value| value.is_finite())
+/// 查找交换格式条目对应的本机账号（按记录 ID，与导入时一致）
+pub fn find_interchange_match(
+    entry: &InterchangeAccount,
+) -> Result<Option<InterchangeAccount>, String> {
+    load_account_file(&entry.id)
+        .map(|account| to_interchange_entry(&account))
+        .transpose()
 }
 
-fn get_json_number(value: &serde_json::Value) -> Option<f64> {
-    match value {
-        serde_json::Value::Number(num) => num.as_f64(),
-        serde_json::Value::String(text) => text.trim().parse::<f64>().ok(),
-        _ => None,
-    }
-    .filter(|value| value.is_finite())
+/// 导入单个交换格式条目（标签与配额随原生记录一并写入）
+pub fn import_interchange_entry(
+    entry: &InterchangeAccount,
+) -> Result<GitHubCopilotAccount, String> {
+    let account: GitHubCopilotAccount = interchange::into_record(entry, INTERCHANGE_QUOTA_FIELDS)?;
+    upsert_account_record(account)
 }
 
-fn calc_remaining_percent(remaining: f64, total: f64) -> Option<i32> {
-    if total <= 0.0 {
-        return None;
-    }
-    Some(clamp_percent((remaining.max(0.0) / total) * 100.0))
+/// 切换 GitHub Copilot 账号的完整流程（不依赖窗口，供命令行/后台调用）
+pub async fn switch_account_internal(account_id: &str) -> Result<String, String> {
+    let result = switch_account_steps(account_id).await;
+    AuditEvent::new(PLATFORM, AuditAction::Switch)
+        .account(account_id)
+        .result(&result)
+        .record();
+    result
 }
 
-fn extract_limited_metrics(account: &GitHubCopilotAccount) -> Vec<(String, i32)> {
-    let Some(limited) = account
-        .copilot_limited_user_quotas
-        .as_ref()
-        .and_then(|value| value.as_object())
-    else {
-        return Vec::new();
-    };
-
-    let token_map = parse_token_map(&account.copilot_token);
-    let mut metrics = Vec::new();
-
-    if let Some(remaining_completions) = limited.get("completions").and_then(get_json_number) {
-        let total_completions =
-            parse_token_number(&token_map, "cq").unwrap_or(remaining_completions);
-        if let Some(percent) = calc_remaining_percent(remaining_completions, total_completions) {
-            metrics.push(("Inline Suggestions".to_string(), percent));
-        }
-    }
-
-    if let Some(remaining_chat) = limited.get("chat").and_then(get_json_number) {
-        let total_chat = parse_token_number(&token_map, "tq").unwrap_or(remaining_chat);
-        if let Some(percent) = calc_remaining_percent(remaining_chat, total_chat) {
-            metrics.push(("Chat Messages".to_string(), percent));
-        }
-    }
-
-    metrics
-}
-
-fn extract_premium_metric(account: &GitHubCopilotAccount) -> Option<(String, i32)> {
-    let snapshots = account
-        .copilot_quota_snapshots
-        .as_ref()
-        .and_then(|value| value.as_object())?;
-
-    let premium = snapshots
-        .get("premium_interactions")
-        .or_else(|| snapshots.get("premium_models"))
-        .and_then(|value| value.as_object())?;
+async fn switch_account_steps(account_id: &str) -> Result<String, String> {
+    let account_id = account_id.to_string();
+    logger::log_info(&format!("开始切换 GitHub Copilot 账号: {}", account_id));
+    let account = load_account(&account_id)
+        .ok_or_else(|| format!("GitHub Copilot account not found: {}", account_id))?;
+    logger::log_info(&format!(
+        "正在切换到 GitHub Copilot 账号: {} (ID: {})",
+        account.github_login, account.id
+    ));
 
-    if premium.get("unlimited").and_then(|value| value.as_bool()) == Some(true) {
-        return Some(("Premium Interactions".to_string(), 100));
+    // 同步更新 VS Code 默认实例绑定账号，确保后续走默认实例启动链路时注入目标明确。
+    if let Err(e) = crate::modules::github_copilot_instance::update_default_settings(
+        Some(Some(account_id.clone())),
+        None,
+        Some(false),
+    ) {
+        logger::log_warn(&format!("更新 GitHub Copilot 默认实例绑定账号失败: {}", e));
+    } else {
+        logger::log_info(&format!(
+            "已同步更新 GitHub Copilot 默认实例绑定账号: {}",
+            account_id
+        ));
     }
 
-    let percent_remaining = premium
-        .get("percent_remaining")
-        .and_then(get_json_number)
-        .map(clamp_percent)?;
-
-    Some(("Premium Interactions".to_string(), percent_remaining))
-}
+    let launch_warning =
+        match crate::modules::github_copilot_instance::start_instance("__default__").await {
+            Ok(_) => None,
+            Err(e) => {
+                if e.starts_with("APP_PATH_NOT_FOUND:") || e.contains("启动 VS Code 失败") {
+                    logger::log_warn(&format!("GitHub Copilot 默认实例启动失败: {}", e));
+                    if e.starts_with("APP_PATH_NOT_FOUND:") || e.contains("APP_PATH_NOT_FOUND:") {
+                        if let Some(app) = crate::get_app_handle() {
+                            use tauri::Emitter;
+                            let _ = app.emit(
+                            "app:path_missing",
+                            serde_json::json!({ "app": "vscode", "retry": { "kind": "default" } }),
+                        );
+                        }
+                    }
+                    Some(e)
+                } else {
+                    return Err(e);
+                }
+            }
+        };
 
-fn extract_quota_metrics(account: &GitHubCopilotAccount) -> Vec<(String, i32)> {
-    let mut metrics = extract_limited_metrics(account);
-    if let Some(premium) = extract_premium_metric(account) {
-        metrics.push(premium);
+    logger::log_info(&format!(
+        "GitHub Copilot 账号切换完成: {}",
+        account.github_login
+    ));
+    if let Some(err) = launch_warning {
+        Ok(format!("切换完成，但 VS Code 启动失败: {}", err))
+    } else {
+        Ok("切换完成".to_string())
     }
-    metrics
 }
 
-fn average_quota_percentage(metrics: &[(String, i32)]) -> f64 {
-    if metrics.is_empty() {
-        return 0.0;
-    }
-    let sum: i32 = metrics.iter().map(|(_, pct)| *pct).sum();
-    sum as f64 / metrics.len() as f64
+fn normalize_quota_alert_threshold(raw: i32) -> i32 {
+    raw.clamp(0, 100)
 }
 
 fn resolve_current_account_id(accounts: &[GitHubCopilotAccount]) -> Option<String> {
@@ -450,6 +475,108 @@ fn display_email(account: &GitHubCopilotAccount) -> String {
         .unwrap_or_else(|| account.github_login.clone())
 }
 
+/// 将账号注入默认实例：VS Code 运行中时按默认实例启动流程关闭、注入并重启，否则只写入登录态
+async fn switch_default_instance_account(account_id: &str) -> Result<(), String> {
+    let settings = crate::modules::github_copilot_instance::update_default_settings(
+        Some(Some(account_id.to_string())),
+        None,
+        Some(false),
+    )?;
+    if crate::modules::process::resolve_vscode_pid(settings.last_pid, None).is_some() {
+        crate::modules::github_copilot_instance::start_instance("__default__").await?;
+    } else {
+        let default_dir =
+            crate::modules::github_copilot_instance::get_default_vscode_user_data_dir()?;
+        crate::modules::github_copilot_instance::inject_account_to_profile(
+            &default_dir,
+            account_id,
+        )?;
+    }
+    Ok(())
+}
+
+async fn run_auto_switch_if_needed_inner() -> Result<Option<GitHubCopilotAccount>, String> {
+    let cfg = crate::modules::config::get_user_config();
+    if !cfg.ghcp_auto_switch_enabled {
+        return Ok(None);
+    }
+
+    let threshold = auto_switch::normalize_threshold(cfg.ghcp_auto_switch_threshold);
+    let accounts = list_accounts();
+    let current_id = match resolve_current_account_id(&accounts) {
+        Some(id) => id,
+        None => return Ok(None),
+    };
+    let current = match accounts.iter().find(|account| account.id == current_id) {
+        Some(account) => account,
+        None => return Ok(None),
+    };
+
+    if !auto_switch::should_trigger(&quota_meter::metrics(current), threshold) {
+        return Ok(None);
+    }
+
+    let strategy = auto_switch::SelectionStrategy::from_config(
+        &cfg.ghcp_auto_switch_strategy,
+        &cfg.ghcp_auto_switch_strategy_param,
+    );
+    let candidates: Vec<auto_switch::SwitchCandidate> = accounts
+        .iter()
+        .map(|account| {
+            let metrics = quota_meter::metrics(account);
+            auto_switch::SwitchCandidate {
+                id: account.id.clone(),
+                eligible: auto_switch::is_above_threshold(&metrics, threshold),
+                metrics,
+                last_used: account.last_used,
+                tags: account.tags.clone().unwrap_or_default(),
+            }
+        })
+        .collect();
+    let target = auto_switch::pick_candidate(&candidates, &current_id, &strategy)
+        .and_then(|candidate| accounts.iter().find(|account| account.id == candidate.id));
+    let Some(target) = target else {
+        logger::log_warn(&format!(
+            "[AutoSwitch][GitHub Copilot] 当前账号低于阈值 {}%，但没有可切换候选账号: strategy={}",
+            threshold,
+            strategy.as_str()
+        ));
+        audit::auto_switch_event(PLATFORM, &current_id, threshold, strategy.as_str())
+            .account(&current_id)
+            .skipped()
+            .record();
+        return Ok(None);
+    };
+
+    logger::log_info(&format!(
+        "[AutoSwitch][GitHub Copilot] 触发自动切号: current_id={}, target_id={}, threshold={}%, strategy={}",
+        current_id,
+        target.id,
+        threshold,
+        strategy.as_str()
+    ));
+    let result = switch_default_instance_account(&target.id).await;
+    audit::auto_switch_event(PLATFORM, &current_id, threshold, strategy.as_str())
+        .account(&target.id)
+        .result(&result)
+        .record();
+    result?;
+    Ok(Some(target.clone()))
+}
+
+/// 当前账号任一配额低于阈值时自动切换到配额最充足的账号
+pub async fn run_auto_switch_if_needed() -> Result<Option<GitHubCopilotAccount>, String> {
+    if GHCP_AUTO_SWITCH_IN_PROGRESS.swap(true, Ordering::SeqCst) {
+        logger::log_info("[AutoSwitch][GitHub Copilot] 自动切号进行中，跳过本次检查");
+        return Ok(None);
+    }
+
+    let result =
+        audit::with_source(AuditSource::AutoSwitch, run_auto_switch_if_needed_inner()).await;
+    GHCP_AUTO_SWITCH_IN_PROGRESS.store(false, Ordering::SeqCst);
+    result
+}
+
 fn build_quota_alert_cooldown_key(account_id: &str, threshold: i32) -> String {
     format!("github_copilot:{}:{}", account_id, threshold)
 }
@@ -482,7 +609,7 @@ fn pick_quota_alert_recommendation(
     let mut candidates: Vec<GitHubCopilotAccount> = accounts
         .iter()
         .filter(|account| account.id != current_id)
-        .filter(|account| !extract_quota_metrics(account).is_empty())
+        .filter(|account| !quota_meter::metrics(account).is_empty())
         .cloned()
         .collect();
 
@@ -491,8 +618,8 @@ fn pick_quota_alert_recommendation(
     }
 
     candidates.sort_by(|a, b| {
-        let avg_a = average_quota_percentage(&extract_quota_metrics(a));
-        let avg_b = average_quota_percentage(&extract_quota_metrics(b));
+        let avg_a = quota_meter::average_percentage(&quota_meter::metrics(a));
+        let avg_b = quota_meter::average_percentage(&quota_meter::metrics(b));
         avg_b
             .partial_cmp(&avg_a)
             .unwrap_or(std::cmp::Ordering::Equal)
@@ -521,11 +648,7 @@ pub fn run_quota_alert_if_needed(
         None => return Ok(None),
     };
 
-    let metrics = extract_quota_metrics(current);
-    let low_models: Vec<(String, i32)> = metrics
-        .into_iter()
-        .filter(|(_, pct)| *pct <= threshold)
-        .collect();
+    let low_models = quota_meter::low_metrics(current, threshold);
 
     if low_models.is_empty() {
         clear_quota_alert_cooldown(&current_id, threshold);
@@ -542,6 +665,7 @@ pub fn run_quota_alert_if_needed(
     let lowest_percentage = low_models.iter().map(|(_, pct)| *pct).min().unwrap_or(0);
     let payload = crate::modules::account::QuotaAlertPayload {
         platform: "github_copilot".to_string(),
+        kind: crate::modules::account::QuotaAlertKind::Low,
         current_account_id: current_id,
         current_email: display_email(current),
         threshold,
@@ -1,11 +1,11 @@
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 
 use chrono::Utc;
 use uuid::Uuid;
 
-use crate::models::{DefaultInstanceSettings, InstanceProfile, InstanceStore};
+use crate::models::{DefaultInstanceSettings, InstanceProfile, InstanceProfileView, InstanceStore};
 use crate::modules;
 use crate::modules::instance::InstanceDefaults;
 use crate::modules::instance_store;
@@ -16,20 +16,14 @@ static GHCP_INSTANCE_STORE_LOCK: std::sync::LazyLock<Mutex<()>> =
     std::sync::LazyLock::new(|| Mutex::new(()));
 
 const GHCP_INSTANCES_FILE: &str = "github_copilot_instances.json";
-
-fn instances_path() -> Result<PathBuf, String> {
-    let data_dir = modules::account::get_data_dir()?;
-    Ok(data_dir.join(GHCP_INSTANCES_FILE))
-}
+const DEFAULT_INSTANCE_ID: &str = "__default__";
 
 pub fn load_instance_store() -> Result<InstanceStore, String> {
-    let path = instances_path()?;
-    instance_store::load_instance_store(&path, GHCP_INSTANCES_FILE)
+    instance_store::load_instance_store(GHCP_INSTANCES_FILE)
 }
 
 pub fn save_instance_store(store: &InstanceStore) -> Result<(), String> {
-    let path = instances_path()?;
-    instance_store::save_instance_store(&path, GHCP_INSTANCES_FILE, store)
+    instance_store::save_instance_store(GHCP_INSTANCES_FILE, store)
 }
 
 pub fn load_default_settings() -> Result<DefaultInstanceSettings, String> {
@@ -91,6 +85,31 @@ pub fn get_default_vscode_user_data_dir() -> Result<PathBuf, String> {
     Err("GitHub Copilot 多开实例仅支持 macOS、Windows 和 Linux".to_string())
 }
 
+/// 将账号 Token 注入指定 VS Code 用户数据目录
+pub fn inject_account_to_profile(profile_dir: &Path, account_id: &str) -> Result<(), String> {
+    let result = write_account_to_profile(profile_dir, account_id);
+    modules::audit::record_inject(
+        modules::data_store::AccountPlatform::GitHubCopilot,
+        account_id,
+        profile_dir,
+        &result,
+    );
+    result
+}
+
+fn write_account_to_profile(profile_dir: &Path, account_id: &str) -> Result<(), String> {
+    let account = modules::github_copilot_account::load_account(account_id)
+        .ok_or_else(|| format!("绑定账号不存在: {}", account_id))?;
+    let github_id = account.github_id.to_string();
+    modules::vscode_inject::inject_copilot_token_for_user_data_dir(
+        &profile_dir.to_string_lossy(),
+        &account.github_login,
+        &account.github_access_token,
+        Some(&github_id),
+    )?;
+    Ok(())
+}
+
 pub fn get_default_instances_root_dir() -> Result<PathBuf, String> {
     #[cfg(target_os = "macos")]
     {
@@ -338,3 +357,137 @@ pub fn clear_all_pids() -> Result<(), String> {
     save_instance_store(&store)?;
     Ok(())
 }
+
+/// 实例目录存在且非空时视为已初始化
+pub fn is_profile_initialized(user_data_dir: &str) -> bool {
+    let path = Path::new(user_data_dir);
+    if !path.exists() {
+        return false;
+    }
+    match std::fs::read_dir(path) {
+        Ok(mut iter) => iter.next().is_some(),
+        Err(_) => false,
+    }
+}
+
+fn inject_bound_account_for_instance_start(
+    user_data_dir: &str,
+    bind_account_id: Option<&str>,
+) -> Result<(), String> {
+    let bind_id = bind_account_id
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    let Some(bind_id) = bind_id else {
+        return Ok(());
+    };
+
+    let account = modules::github_copilot_account::load_account(bind_id)
+        .ok_or_else(|| format!("绑定账号不存在: {}", bind_id))?;
+    modules::logger::log_info(&format!(
+        "实例启动检测到绑定账号，准备注入: bind_account_id={}, login={}, user_data_dir={}",
+        bind_id, account.github_login, user_data_dir
+    ));
+
+    // Ensure DB is writable before injection.
+    modules::process::close_vscode(&[user_data_dir.to_string()], 20)?;
+
+    modules::logger::log_info("正在向实例目录注入 GitHub Copilot Token...");
+    let github_id = account.github_id.to_string();
+    let result = modules::vscode_inject::inject_copilot_token_for_user_data_dir(
+        user_data_dir,
+        &account.github_login,
+        &account.github_access_token,
+        Some(&github_id),
+    );
+    modules::audit::record_inject(
+        modules::data_store::AccountPlatform::GitHubCopilot,
+        bind_id,
+        Path::new(user_data_dir),
+        &result,
+    );
+    result.map_err(|e| {
+        modules::logger::log_error(&format!("实例绑定账号注入失败: {}", e));
+        format!("按绑定账号注入实例失败（{}）: {}", account.github_login, e)
+    })?;
+
+    modules::logger::log_info(&format!("实例绑定账号注入完成: {}", account.github_login));
+
+    Ok(())
+}
+
+/// 启动实例（`__default__` 为默认实例）：先关闭仍在运行的进程，注入绑定账号后重新启动
+pub async fn start_instance(instance_id: &str) -> Result<InstanceProfileView, String> {
+    modules::logger::log_info(&format!("开始启动 GitHub Copilot 实例: {}", instance_id));
+    if instance_id == DEFAULT_INSTANCE_ID {
+        let default_dir = get_default_vscode_user_data_dir()?;
+        let default_dir_str = default_dir.to_string_lossy().to_string();
+        let default_settings = load_default_settings()?;
+        if let Some(pid) = modules::process::resolve_vscode_pid(default_settings.last_pid, None) {
+            modules::process::close_pid(pid, 20)?;
+            let _ = update_default_pid(None)?;
+        }
+        modules::process::close_vscode(&[default_dir_str.clone()], 20)?;
+        inject_bound_account_for_instance_start(
+            &default_dir_str,
+            default_settings.bind_account_id.as_deref(),
+        )?;
+        let extra_args = modules::process::parse_extra_args(&default_settings.extra_args);
+        let pid =
+            modules::process::start_vscode_default_with_args_with_new_window(&extra_args, true)?;
+        modules::logger::log_info(&format!("GitHub Copilot 默认实例已启动: pid={}", pid));
+        let _ = update_default_pid(Some(pid))?;
+        let running = modules::process::is_pid_running(pid);
+        return Ok(InstanceProfileView {
+            id: DEFAULT_INSTANCE_ID.to_string(),
+            name: String::new(),
+            user_data_dir: default_dir_str,
+            extra_args: default_settings.extra_args,
+            bind_account_id: default_settings.bind_account_id,
+            created_at: 0,
+            last_launched_at: None,
+            last_pid: Some(pid),
+            running,
+            initialized: is_profile_initialized(&default_dir.to_string_lossy()),
+            is_default: true,
+            follow_local_account: false,
+        });
+    }
+
+    let store = load_instance_store()?;
+    let instance = store
+        .instances
+        .into_iter()
+        .find(|item| item.id == instance_id)
+        .ok_or("实例不存在")?;
+
+    if let Some(pid) =
+        modules::process::resolve_vscode_pid(instance.last_pid, Some(&instance.user_data_dir))
+    {
+        modules::process::close_pid(pid, 20)?;
+        let _ = update_instance_pid(&instance.id, None)?;
+    }
+    modules::process::close_vscode(&[instance.user_data_dir.clone()], 20)?;
+
+    inject_bound_account_for_instance_start(
+        &instance.user_data_dir,
+        instance.bind_account_id.as_deref(),
+    )?;
+    let extra_args = modules::process::parse_extra_args(&instance.extra_args);
+    let pid = modules::process::start_vscode_with_args_with_new_window(
+        &instance.user_data_dir,
+        &extra_args,
+        true,
+    )?;
+    modules::logger::log_info(&format!(
+        "GitHub Copilot 实例已启动: instance_id={}, pid={}",
+        instance.id, pid
+    ));
+    let updated = update_instance_after_start(&instance.id, pid)?;
+    let running = modules::process::is_pid_running(pid);
+    let initialized = is_profile_initialized(&updated.user_data_dir);
+    Ok(InstanceProfileView::from_profile(
+        updated,
+        running,
+        initialized,
+    ))
+}
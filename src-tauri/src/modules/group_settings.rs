@@ -15,18 +15,22 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
+use super::atomic_file;
 use super::config::get_shared_dir;
 
 /// 分组配置文件名
-const GROUP_SETTINGS_FILE: &str = "group_settings.json";
+pub(crate) const GROUP_SETTINGS_FILE: &str = "group_settings.json";
 
-const LEGACY_GROUP_NAME_G3_PRO: &str = "G3-Pro";
-const LEGACY_GROUP_NAME_G3_FLASH: &str = "G3-Flash";
-const LEGACY_GROUP_NAME_G3_IMAGE: &str = "G3-Image";
+pub(crate) const GROUP_NAME_GEMINI_PRO: &str = "Gemini Pro";
+pub(crate) const GROUP_NAME_GEMINI_FLASH: &str = "Gemini Flash";
+pub(crate) const GROUP_NAME_GEMINI_IMAGE: &str = "Gemini Image";
 
-const GROUP_NAME_GEMINI_PRO: &str = "Gemini Pro";
-const GROUP_NAME_GEMINI_FLASH: &str = "Gemini Flash";
-const GROUP_NAME_GEMINI_IMAGE: &str = "Gemini Image";
+/// 旧版分组名改名表：(分组 ID, 旧名称, 新名称)
+pub(crate) const LEGACY_GROUP_NAMES: [(&str, &str, &str); 3] = [
+    ("g3_pro", "G3-Pro", GROUP_NAME_GEMINI_PRO),
+    ("g3_flash", "G3-Flash", GROUP_NAME_GEMINI_FLASH),
+    ("g3_image", "G3-Image", GROUP_NAME_GEMINI_IMAGE),
+];
 
 /// 配置来源
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -130,6 +134,10 @@ impl GroupSettings {
     }
 
     /// 获取模型所属分组
+    pub fn get_group_for_model(&self, model_id: &str) -> Option<&str> {
+        self.group_mappings.get(model_id).map(|gid| gid.as_str())
+    }
+
     /// 获取排序后的分组列表（最多返回指定数量）
     pub fn get_ordered_groups(&self, max_count: Option<usize>) -> Vec<String> {
         let mut groups = self.group_order.clone();
@@ -203,32 +211,19 @@ impl GroupSettings {
     }
 }
 
+/// 读取时同样改名：共享文件可能被旧版 VS Code 扩展写回旧名称，数据迁移只会执行一次
 fn migrate_legacy_group_names(settings: &mut GroupSettings) {
-    let mut migrate_name = |group_id: &str, legacy_name: &str, new_name: &str| {
-        if let Some(current_name) = settings.group_names.get(group_id) {
-            if current_name == legacy_name {
-                settings
-                    .group_names
-                    .insert(group_id.to_string(), new_name.to_string());
-            }
+    for (group_id, legacy_name, new_name) in LEGACY_GROUP_NAMES {
+        if settings.group_names.get(group_id).map(String::as_str) == Some(legacy_name) {
+            settings
+                .group_names
+                .insert(group_id.to_string(), new_name.to_string());
         }
-    };
-
-    migrate_name("g3_pro", LEGACY_GROUP_NAME_G3_PRO, GROUP_NAME_GEMINI_PRO);
-    migrate_name(
-        "g3_flash",
-        LEGACY_GROUP_NAME_G3_FLASH,
-        GROUP_NAME_GEMINI_FLASH,
-    );
-    migrate_name(
-        "g3_image",
-        LEGACY_GROUP_NAME_G3_IMAGE,
-        GROUP_NAME_GEMINI_IMAGE,
-    );
+    }
 }
 
 /// 获取分组配置文件路径
-fn get_group_settings_path() -> PathBuf {
+pub(crate) fn get_group_settings_path() -> PathBuf {
     get_shared_dir().join(GROUP_SETTINGS_FILE)
 }
 
@@ -302,7 +297,7 @@ pub fn save_group_settings(settings: &GroupSettings) -> Result<(), String> {
     let content =
         serde_json::to_string_pretty(settings).map_err(|e| format!("序列化失败: {}", e))?;
 
-    fs::write(&path, content).map_err(|e| format!("写入文件失败: {}", e))?;
+    atomic_file::write(&path, content).map_err(|e| format!("写入文件失败: {}", e))?;
 
     crate::modules::logger::log_info(&format!(
         "[GroupSettings] 保存配置成功: {} 个映射, {} 个分组",
@@ -1,8 +1,12 @@
 use crate::models;
 use crate::modules;
+use crate::modules::audit;
+use crate::modules::data_store::AccountPlatform;
+use crate::modules::import_plan::{self, ImportOutcome};
+use crate::modules::interchange::{self, InterchangeAccount};
 use crate::utils;
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use uuid::Uuid;
 
@@ -227,6 +231,16 @@ pub fn resolve_json_import_name(
 
 /// 从旧版 ~/.antigravity_tools/ 导入账号
 pub async fn import_from_old_tools_logic() -> Result<Vec<models::Account>, String> {
+    let result = import_from_old_tools_inner().await;
+    audit::record_import(
+        AccountPlatform::Antigravity,
+        "old_tools",
+        result.as_ref().map(Vec::len),
+    );
+    result
+}
+
+async fn import_from_old_tools_inner() -> Result<Vec<models::Account>, String> {
     use std::fs;
 
     let home = dirs::home_dir().ok_or("无法获取用户主目录")?;
@@ -535,6 +549,16 @@ pub async fn import_fingerprints_from_json_logic(json_content: String) -> Result
 
 /// 从本地 Antigravity 客户端导入当前账号
 pub async fn import_from_local_logic() -> Result<models::Account, String> {
+    let result = import_from_local_inner().await;
+    audit::record_import(
+        AccountPlatform::Antigravity,
+        "local",
+        result.as_ref().map(|_| 1),
+    );
+    result
+}
+
+async fn import_from_local_inner() -> Result<models::Account, String> {
     use base64::{engine::general_purpose, Engine as _};
 
     modules::logger::log_info("开始从本地 Antigravity 客户端导入...");
@@ -599,10 +623,77 @@ pub async fn import_from_local_logic() -> Result<models::Account, String> {
     Ok(account)
 }
 
+/// 导出账号（email + refresh_token 简化格式，ID 为空时导出全部）
+pub fn export_accounts_logic(account_ids: &[String]) -> Result<String, String> {
+    let mut accounts_to_export = Vec::new();
+
+    if account_ids.is_empty() {
+        // 导出全部
+        accounts_to_export = modules::list_accounts()?;
+    } else {
+        for id in account_ids {
+            if let Ok(account) = modules::load_account(id) {
+                accounts_to_export.push(account);
+            }
+        }
+    }
+
+    #[derive(serde::Serialize)]
+    struct SimpleAccount {
+        email: String,
+        refresh_token: String,
+    }
+
+    let simplified: Vec<SimpleAccount> = accounts_to_export
+        .into_iter()
+        .map(|account| SimpleAccount {
+            email: account.email,
+            refresh_token: account.token.refresh_token,
+        })
+        .collect();
+
+    let json =
+        serde_json::to_string_pretty(&simplified).map_err(|e| format!("序列化失败: {}", e))?;
+
+    Ok(json)
+}
+
 /// 从 JSON 导入账号
-pub async fn import_from_json_logic(json_content: String) -> Result<Vec<models::Account>, String> {
+pub async fn import_from_json_logic(
+    json_content: String,
+) -> Result<ImportOutcome<models::Account>, String> {
+    let result = import_from_json_inner(json_content).await;
+    audit::record_import(
+        AccountPlatform::Antigravity,
+        "json",
+        result.as_ref().map(|outcome| outcome.imported.len()),
+    );
+    result
+}
+
+async fn import_from_json_inner(
+    json_content: String,
+) -> Result<ImportOutcome<models::Account>, String> {
     modules::logger::log_info("开始从 JSON 导入账号...");
 
+    // 统一交换格式：只导入其中的 Antigravity 账号及其绑定的指纹
+    if let Some(document) = interchange::parse_if_interchange(&json_content)? {
+        modules::fingerprint::merge_fingerprints(&document.fingerprints)?;
+        let outcome = import_plan::import_entries(
+            document.entries_for(AccountPlatform::Antigravity),
+            import_interchange_entry,
+            modules::account::update_account_tags,
+        );
+        modules::logger::log_info(&format!(
+            "交换文件导入完成，共导入 {} 个账号",
+            outcome.imported.len()
+        ));
+        if !outcome.imported.is_empty() {
+            modules::websocket::broadcast_data_changed("import_from_json");
+        }
+        return Ok(outcome);
+    }
+
     // 简化格式: [{"email": "xxx", "refresh_token": "..."}]
     #[derive(serde::Deserialize)]
     struct SimpleAccount {
@@ -618,7 +709,7 @@ pub async fn import_from_json_logic(json_content: String) -> Result<Vec<models::
         });
 
     if let Ok(accounts) = simple_accounts {
-        let mut imported = Vec::new();
+        let mut outcome = ImportOutcome::default();
 
         for simple in accounts {
             modules::logger::log_info(&format!("正在导入账号: {}", simple.email));
@@ -636,30 +727,43 @@ pub async fn import_from_json_logic(json_content: String) -> Result<Vec<models::
                         None,
                     );
 
+                    let candidate =
+                        models::Account::new(String::new(), simple.email.clone(), token.clone());
+                    match should_import_legacy(&candidate) {
+                        Ok(true) => {}
+                        Ok(false) => {
+                            modules::logger::log_info(&format!(
+                                "本机凭据更新或内容一致，跳过: {}",
+                                simple.email
+                            ));
+                            continue;
+                        }
+                        Err(e) => {
+                            outcome.fail(&simple.email, e);
+                            continue;
+                        }
+                    }
+
                     match modules::upsert_account(simple.email.clone(), None, token) {
                         Ok(new_account) => {
                             modules::logger::log_info(&format!(
                                 "导入账号成功: {}",
                                 new_account.email
                             ));
-                            imported.push(new_account);
-                        }
-                        Err(e) => {
-                            modules::logger::log_error(&format!(
-                                "保存账号失败 {}: {}",
-                                simple.email, e
-                            ));
+                            outcome.imported.push(new_account);
                         }
+                        Err(e) => outcome.fail(&simple.email, format!("保存账号失败: {}", e)),
                     }
                 }
-                Err(e) => {
-                    modules::logger::log_error(&format!("刷新 Token 失败 {}: {}", simple.email, e));
-                }
+                Err(e) => outcome.fail(&simple.email, format!("刷新 Token 失败: {}", e)),
             }
         }
 
-        modules::logger::log_info(&format!("JSON 导入完成，共导入 {} 个账号", imported.len()));
-        return Ok(imported);
+        modules::logger::log_info(&format!(
+            "JSON 导入完成，共导入 {} 个账号",
+            outcome.imported.len()
+        ));
+        return Ok(outcome);
     }
 
     // 尝试解析为完整账号格式（向后兼容）
@@ -667,9 +771,23 @@ pub async fn import_from_json_logic(json_content: String) -> Result<Vec<models::
         .or_else(|_| serde_json::from_str::<models::Account>(&json_content).map(|a| vec![a]))
         .map_err(|e| format!("JSON 格式错误: {}", e))?;
 
-    let mut imported = Vec::new();
+    let mut outcome = ImportOutcome::default();
 
     for old_account in accounts {
+        match should_import_legacy(&old_account) {
+            Ok(true) => {}
+            Ok(false) => {
+                modules::logger::log_info(&format!(
+                    "本机凭据更新或内容一致，跳过: {}",
+                    old_account.email
+                ));
+                continue;
+            }
+            Err(e) => {
+                outcome.fail(&old_account.email, e);
+                continue;
+            }
+        }
         match modules::upsert_account(
             old_account.email.clone(),
             old_account.name.clone(),
@@ -677,26 +795,121 @@ pub async fn import_from_json_logic(json_content: String) -> Result<Vec<models::
         ) {
             Ok(new_account) => {
                 modules::logger::log_info(&format!("导入账号: {}", new_account.email));
-                imported.push(new_account);
-            }
-            Err(e) => {
-                modules::logger::log_error(&format!("导入账号失败 {}: {}", old_account.email, e));
+                outcome.imported.push(new_account);
             }
+            Err(e) => outcome.fail(&old_account.email, e),
         }
     }
 
-    modules::logger::log_info(&format!("JSON 导入完成，共导入 {} 个账号", imported.len()));
+    modules::logger::log_info(&format!(
+        "JSON 导入完成，共导入 {} 个账号",
+        outcome.imported.len()
+    ));
 
     // 广播数据变更通知
-    if !imported.is_empty() {
+    if !outcome.imported.is_empty() {
         modules::websocket::broadcast_data_changed("import_from_json");
     }
 
-    Ok(imported)
+    Ok(outcome)
+}
+
+/// 旧格式只导入邮箱、名称与 Token：按导入计划比对本机账号，本机凭据更新或内容一致时跳过
+fn should_import_legacy(account: &models::Account) -> Result<bool, String> {
+    import_plan::should_import_legacy(&to_interchange_entry(account)?)
+}
+
+/// 交换格式中 Antigravity 账号的配额字段
+const INTERCHANGE_QUOTA_FIELDS: &[&str] = &["quota"];
+
+/// 导出 Antigravity 账号为交换格式条目（ID 为空时导出全部）
+pub fn export_interchange(account_ids: &[String]) -> Result<Vec<InterchangeAccount>, String> {
+    let accounts = if account_ids.is_empty() {
+        modules::list_accounts()?
+    } else {
+        account_ids
+            .iter()
+            .filter_map(|id| modules::load_account(id).ok())
+            .collect()
+    };
+    accounts.iter().map(to_interchange_entry).collect()
+}
+
+fn to_interchange_entry(account: &models::Account) -> Result<InterchangeAccount, String> {
+    interchange::to_entry(
+        AccountPlatform::Antigravity,
+        account,
+        &account.email,
+        INTERCHANGE_QUOTA_FIELDS,
+    )
+}
+
+/// 查找交换格式条目对应的本机账号（与导入时的匹配规则一致）
+pub fn find_interchange_match(
+    entry: &InterchangeAccount,
+) -> Result<Option<InterchangeAccount>, String> {
+    let record: models::Account = interchange::into_record(entry, INTERCHANGE_QUOTA_FIELDS)?;
+    let index = modules::load_account_index()?;
+    let Some(account_id) =
+        modules::account::find_matching_account_id(&index, &record.email, &record.token, true)?
+    else {
+        return Ok(None);
+    };
+    let account = modules::load_account(&account_id)?;
+    to_interchange_entry(&account).map(Some)
+}
+
+/// 导入单个交换格式条目：按邮箱与凭据匹配已有账号，并写入标签、配额与指纹绑定
+pub fn import_interchange_entry(entry: &InterchangeAccount) -> Result<models::Account, String> {
+    let record: models::Account = interchange::into_record(entry, INTERCHANGE_QUOTA_FIELDS)?;
+    let existing_ids: HashSet<String> = modules::load_account_index()?
+        .accounts
+        .into_iter()
+        .map(|summary| summary.id)
+        .collect();
+
+    let mut account = modules::upsert_account(record.email, record.name, record.token)?;
+    let created = !existing_ids.contains(&account.id);
+    let previous_fingerprint = account.fingerprint_id.clone();
+
+    account.tags = record.tags;
+    if record.quota.is_some() {
+        account.quota = record.quota;
+    }
+    // 绑定的指纹在本机不存在时保留当前绑定
+    if let Some(fingerprint_id) = entry
+        .fingerprint_id
+        .as_deref()
+        .filter(|id| *id == "original" || modules::fingerprint::get_fingerprint(id).is_ok())
+    {
+        account.fingerprint_id = Some(fingerprint_id.to_string());
+    }
+    modules::save_account(&account)?;
+
+    // 新建账号时自动生成的指纹已被交换文件中的绑定取代
+    if created && previous_fingerprint != account.fingerprint_id {
+        if let Some(generated) = previous_fingerprint.filter(|id| id != "original") {
+            let _ = modules::fingerprint::delete_fingerprint(&generated);
+        }
+    }
+    Ok(account)
 }
 
 /// 从插件共享目录导入账号（credentials.json）
 pub async fn import_from_extension_credentials() -> Result<usize, String> {
+    let result = import_from_extension_credentials_inner().await;
+    // 共享目录没有凭据文件时不记录
+    if !matches!(result, Ok(0)) {
+        audit::record_import(
+            AccountPlatform::Antigravity,
+            "extension",
+            result.as_ref().copied(),
+        );
+    }
+    result
+}
+
+async fn import_from_extension_credentials_inner() -> Result<usize, String> {
     let data_dir = modules::config::get_data_dir()?;
     let file_path = data_dir.join("credentials.json");
 
@@ -0,0 +1,523 @@
+//! 导入预览与冲突处理
+//!
+//! 导入前逐条与本机账号比对，生成导入计划：新增、更新、相同或冲突（本机凭据比导入内容更新），
+//! 并列出将被修改的字段。实际导入时按所选策略处理本机已存在的账号，避免旧的导出文件覆盖本机
+//! 较新的 Token。各平台原有的 JSON 导入同样先经过导入计划，默认按 `KeepNewest` 处理；只携带身份与
+//! 凭据的旧格式在本机凭据更新时直接跳过（见 `should_import_legacy`）。
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+use crate::modules::codex_account;
+use crate::modules::data_store::AccountPlatform;
+use crate::modules::interchange::{self, InterchangeAccount, InterchangeDocument};
+use crate::modules::logger;
+
+/// 比对时忽略的记录字段（由本机分配或每次使用都会变化）
+const IGNORED_FIELDS: &[&str] = &["id", "created_at", "last_used"];
+/// 字段名包含这些片段时，差异中只显示为已修改，不返回具体值
+const SENSITIVE_MARKERS: &[&str] = &["token", "api_key", "secret", "password"];
+const REDACTED: &str = "******";
+
+/// 已存在账号的处理策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportStrategy {
+    /// 跳过本机已存在的账号
+    SkipExisting,
+    /// 用导入内容覆盖
+    Overwrite,
+    /// 用导入内容覆盖，但本机凭据更新时保留本机凭据
+    #[default]
+    KeepNewest,
+    /// 已存在的账号只合并标签
+    MergeTags,
+}
+
+/// 单条记录的导入计划
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanAction {
+    /// 本机不存在
+    New,
+    /// 本机已存在且导入内容不比本机旧
+    Update,
+    /// 与本机一致
+    Identical,
+    /// 凭据不同且本机凭据更新
+    Conflict,
+    /// 记录无法解析
+    Invalid,
+}
+
+/// 字段差异（敏感字段的值以 `******` 代替）
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FieldChange {
+    pub field: String,
+    pub current: Value,
+    pub incoming: Value,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlanItem {
+    pub platform: String,
+    pub email: String,
+    pub incoming_id: String,
+    pub existing_id: Option<String>,
+    pub action: PlanAction,
+    pub changes: Vec<FieldChange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 导入预览
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportPlan {
+    pub items: Vec<PlanItem>,
+    /// 文件携带的指纹数
+    pub fingerprints: usize,
+    /// 文件中存在但当前版本不支持的平台
+    pub skipped_platforms: Vec<String>,
+}
+
+/// 计划中的记录及其对应的本机账号
+pub(crate) struct PlannedEntry<'a> {
+    pub platform: AccountPlatform,
+    pub entry: &'a InterchangeAccount,
+    pub existing: Option<InterchangeAccount>,
+    pub item: PlanItem,
+}
+
+/// 按计划与策略得出的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Decision {
+    Skip,
+    Import,
+    /// 导入，但凭据字段沿用本机
+    ImportKeepingLocalCredentials,
+    MergeTags,
+}
+
+/// 预览交换文件的导入计划（不写入任何数据）
+pub fn preview_import(json_content: &str) -> Result<ImportPlan, String> {
+    let document =
+        interchange::parse_if_interchange(json_content)?.ok_or("不是有效的账号交换文件")?;
+    let (planned, skipped_platforms) = plan_document(&document);
+    Ok(ImportPlan {
+        items: planned.into_iter().map(|planned| planned.item).collect(),
+        fingerprints: document.fingerprints.len(),
+        skipped_platforms,
+    })
+}
+
+/// 逐条比对交换文件与本机账号，返回 (计划, 不支持的平台)
+pub(crate) fn plan_document(
+    document: &InterchangeDocument,
+) -> (Vec<PlannedEntry<'_>>, Vec<String>) {
+    plan_entries(&document.accounts)
+}
+
+/// 逐条比对导入记录与本机账号，返回 (计划, 不支持的平台)
+pub(crate) fn plan_entries<'a>(
+    entries: impl IntoIterator<Item = &'a InterchangeAccount>,
+) -> (Vec<PlannedEntry<'a>>, Vec<String>) {
+    let mut planned = Vec::new();
+    let mut skipped_platforms: Vec<String> = Vec::new();
+
+    for entry in entries {
+        let Some(platform) = AccountPlatform::parse(&entry.platform) else {
+            if !skipped_platforms.contains(&entry.platform) {
+                skipped_platforms.push(entry.platform.clone());
+            }
+            continue;
+        };
+        let mut item = PlanItem {
+            platform: entry.platform.clone(),
+            email: entry.email.clone(),
+            incoming_id: entry.id.clone(),
+            existing_id: None,
+            action: PlanAction::New,
+            changes: Vec::new(),
+            error: None,
+        };
+        let existing = match interchange::find_existing(platform, entry) {
+            Ok(existing) => existing,
+            Err(e) => {
+                item.action = PlanAction::Invalid;
+                item.error = Some(e);
+                None
+            }
+        };
+        if let Some(existing) = existing.as_ref() {
+            let (action, changes) = classify(platform, existing, entry);
+            item.existing_id = Some(existing.id.clone());
+            item.action = action;
+            item.changes = changes;
+        }
+        planned.push(PlannedEntry {
+            platform,
+            entry,
+            existing,
+            item,
+        });
+    }
+
+    (planned, skipped_platforms)
+}
+
+/// 比对本机账号与导入记录
+fn classify(
+    platform: AccountPlatform,
+    existing: &InterchangeAccount,
+    incoming: &InterchangeAccount,
+) -> (PlanAction, Vec<FieldChange>) {
+    let changes = diff_entries(existing, incoming);
+    if changes.is_empty() {
+        return (PlanAction::Identical, changes);
+    }
+    let credentials_changed = changes
+        .iter()
+        .any(|change| credential_fields(platform).contains(&change.field.as_str()));
+    if credentials_changed && is_fresher(platform, &existing.record, &incoming.record) {
+        return (PlanAction::Conflict, changes);
+    }
+    (PlanAction::Update, changes)
+}
+
+/// 计划与策略对应的处理方式
+pub(crate) fn decide(action: PlanAction, strategy: ImportStrategy) -> Decision {
+    match (action, strategy) {
+        (PlanAction::New, _) => Decision::Import,
+        (PlanAction::Identical | PlanAction::Invalid, _) => Decision::Skip,
+        (_, ImportStrategy::SkipExisting) => Decision::Skip,
+        (_, ImportStrategy::MergeTags) => Decision::MergeTags,
+        (_, ImportStrategy::Overwrite) => Decision::Import,
+        (PlanAction::Update, ImportStrategy::KeepNewest) => Decision::Import,
+        (PlanAction::Conflict, ImportStrategy::KeepNewest) => {
+            Decision::ImportKeepingLocalCredentials
+        }
+    }
+}
+
+/// 按策略写入一条计划记录：`import` 写入完整记录，`update_tags` 只更新标签；返回写入后的账号，跳过时返回 None
+pub(crate) fn apply<T>(
+    planned: &PlannedEntry<'_>,
+    strategy: ImportStrategy,
+    import: impl Fn(&InterchangeAccount) -> Result<T, String>,
+    update_tags: impl Fn(&str, Vec<String>) -> Result<T, String>,
+) -> Result<Option<T>, String> {
+    if let Some(e) = planned.item.error.as_ref() {
+        return Err(e.clone());
+    }
+    let entry = planned.entry;
+    match (
+        decide(planned.item.action, strategy),
+        planned.existing.as_ref(),
+    ) {
+        (Decision::Import, _) | (Decision::ImportKeepingLocalCredentials, None) => {
+            import(entry).map(Some)
+        }
+        (Decision::ImportKeepingLocalCredentials, Some(existing)) => {
+            import(&with_local_credentials(planned.platform, existing, entry)).map(Some)
+        }
+        (Decision::MergeTags, Some(existing)) => match merged_tags(existing, entry) {
+            Some(tags) => update_tags(&existing.id, tags).map(Some),
+            None => Ok(None),
+        },
+        (Decision::MergeTags, None) | (Decision::Skip, _) => Ok(None),
+    }
+}
+
+/// JSON 导入结果：写入的账号与导入失败的记录
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportOutcome<T> {
+    pub imported: Vec<T>,
+    /// 导入失败的账号（邮箱: 原因）
+    pub failed: Vec<String>,
+}
+
+impl<T> Default for ImportOutcome<T> {
+    fn default() -> Self {
+        Self {
+            imported: Vec::new(),
+            failed: Vec::new(),
+        }
+    }
+}
+
+impl<T> ImportOutcome<T> {
+    /// 记录一条失败的账号并继续导入后续记录
+    pub(crate) fn fail(&mut self, email: &str, error: impl std::fmt::Display) {
+        logger::log_warn(&format!(
+            "[Import] 导入账号失败: email={}, error={}",
+            email, error
+        ));
+        self.failed.push(format!("{}: {}", email, error));
+    }
+}
+
+/// 按默认策略导入单个平台的记录（各平台 JSON 导入共用），失败的记录不影响其余记录的导入
+pub(crate) fn import_entries<'a, T>(
+    entries: impl IntoIterator<Item = &'a InterchangeAccount>,
+    import: impl Fn(&InterchangeAccount) -> Result<T, String>,
+    update_tags: impl Fn(&str, Vec<String>) -> Result<T, String>,
+) -> ImportOutcome<T> {
+    let (planned, _) = plan_entries(entries);
+    let mut outcome = ImportOutcome::default();
+    for planned in &planned {
+        match apply(planned, ImportStrategy::default(), &import, &update_tags) {
+            Ok(Some(account)) => outcome.imported.push(account),
+            Ok(None) => {}
+            Err(e) => outcome.fail(&planned.entry.email, e),
+        }
+    }
+    outcome
+}
+
+/// 旧格式记录（只携带身份与凭据，由调用方按原有方式写入）是否需要写入：按默认策略比对本机账号，
+/// 本机凭据更新或内容一致时跳过
+pub(crate) fn should_import_legacy(entry: &InterchangeAccount) -> Result<bool, String> {
+    let (planned, _) = plan_entries(std::iter::once(entry));
+    let Some(planned) = planned.into_iter().next() else {
+        return Err(format!("未知的平台: {}", entry.platform));
+    };
+    if let Some(e) = planned.item.error {
+        return Err(e);
+    }
+    Ok(decide(planned.item.action, ImportStrategy::default()) == Decision::Import)
+}
+
+/// 用本机凭据替换导入记录中的凭据字段
+pub(crate) fn with_local_credentials(
+    platform: AccountPlatform,
+    existing: &InterchangeAccount,
+    incoming: &InterchangeAccount,
+) -> InterchangeAccount {
+    let mut merged = incoming.clone();
+    if let Some(record) = merged.record.as_object_mut() {
+        for field in credential_fields(platform) {
+            match existing.record.get(*field) {
+                Some(value) => record.insert(field.to_string(), value.clone()),
+                None => record.remove(*field),
+            };
+        }
+    }
+    merged
+}
+
+/// 合并标签（保留本机顺序），没有新增标签时返回 None
+pub(crate) fn merged_tags(
+    existing: &InterchangeAccount,
+    incoming: &InterchangeAccount,
+) -> Option<Vec<String>> {
+    let mut tags = existing.tags.clone();
+    for tag in &incoming.tags {
+        if !tags.iter().any(|item| item.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.clone());
+        }
+    }
+    (tags.len() > existing.tags.len()).then_some(tags)
+}
+
+/// 各平台的凭据字段（记录顶层字段名，含凭据过期时间）
+fn credential_fields(platform: AccountPlatform) -> &'static [&'static str] {
+    match platform {
+        AccountPlatform::Antigravity => &["token"],
+        AccountPlatform::Codex => &["tokens"],
+        AccountPlatform::GitHubCopilot => {
+            &["github_access_token", "copilot_token", "copilot_expires_at"]
+        }
+        AccountPlatform::Windsurf => &[
+            "github_access_token",
+            "copilot_token",
+            "copilot_expires_at",
+            "windsurf_api_key",
+            "windsurf_auth_token",
+        ],
+        AccountPlatform::Kiro => &["access_token", "refresh_token", "expires_at"],
+    }
+}
+
+/// 凭据过期时间，过期越晚视为越新
+fn token_expiry(platform: AccountPlatform, record: &Value) -> Option<i64> {
+    match platform {
+        AccountPlatform::Antigravity => record.pointer("/token/expiry_timestamp")?.as_i64(),
+        AccountPlatform::Codex => {
+            let access_token = record.pointer("/tokens/access_token")?.as_str()?;
+            codex_account::decode_jwt_payload(access_token).ok()?.exp
+        }
+        AccountPlatform::GitHubCopilot | AccountPlatform::Windsurf => {
+            record.get("copilot_expires_at")?.as_i64()
+        }
+        AccountPlatform::Kiro => record.get("expires_at")?.as_i64(),
+    }
+}
+
+/// 账号记录的凭据是否比另一条更新（缺少过期时间时按最近使用时间比较）
+pub(crate) fn is_fresher(platform: AccountPlatform, record: &Value, other: &Value) -> bool {
+    match (
+        token_expiry(platform, record),
+        token_expiry(platform, other),
+    ) {
+        (Some(left), Some(right)) => left > right,
+        _ => {
+            let last_used =
+                |record: &Value| record.get("last_used").and_then(Value::as_i64).unwrap_or(0);
+            last_used(record) > last_used(other)
+        }
+    }
+}
+
+fn diff_entries(existing: &InterchangeAccount, incoming: &InterchangeAccount) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+
+    let current_tags: BTreeSet<String> = existing.tags.iter().map(|t| t.to_lowercase()).collect();
+    let incoming_tags: BTreeSet<String> = incoming.tags.iter().map(|t| t.to_lowercase()).collect();
+    if current_tags != incoming_tags {
+        changes.push(FieldChange {
+            field: "tags".to_string(),
+            current: Value::from(existing.tags.clone()),
+            incoming: Value::from(incoming.tags.clone()),
+        });
+    }
+
+    if existing.fingerprint_id != incoming.fingerprint_id && incoming.fingerprint_id.is_some() {
+        changes.push(FieldChange {
+            field: "fingerprint_id".to_string(),
+            current: Value::from(existing.fingerprint_id.clone()),
+            incoming: Value::from(incoming.fingerprint_id.clone()),
+        });
+    }
+
+    let quota_keys: BTreeSet<&String> =
+        existing.quota.keys().chain(incoming.quota.keys()).collect();
+    for key in quota_keys {
+        push_change(
+            &mut changes,
+            format!("quota.{}", key),
+            existing.quota.get(key.as_str()),
+            incoming.quota.get(key.as_str()),
+        );
+    }
+
+    let empty = serde_json::Map::new();
+    let current_record = existing.record.as_object().unwrap_or(&empty);
+    let incoming_record = incoming.record.as_object().unwrap_or(&empty);
+    let record_keys: BTreeSet<&String> = current_record
+        .keys()
+        .chain(incoming_record.keys())
+        .filter(|key| !IGNORED_FIELDS.contains(&key.as_str()))
+        .collect();
+    for key in record_keys {
+        push_change(
+            &mut changes,
+            key.clone(),
+            current_record.get(key.as_str()),
+            incoming_record.get(key.as_str()),
+        );
+    }
+
+    changes
+}
+
+fn push_change(
+    changes: &mut Vec<FieldChange>,
+    field: String,
+    current: Option<&Value>,
+    incoming: Option<&Value>,
+) {
+    let current = current.cloned().unwrap_or(Value::Null);
+    let incoming = incoming.cloned().unwrap_or(Value::Null);
+    if current == incoming {
+        return;
+    }
+    let sensitive = SENSITIVE_MARKERS
+        .iter()
+        .any(|marker| field.to_lowercase().contains(marker));
+    let redact = |value: Value| {
+        if sensitive && !value.is_null() {
+            Value::from(REDACTED)
+        } else {
+            value
+        }
+    };
+    changes.push(FieldChange {
+        field,
+        current: redact(current),
+        incoming: redact(incoming),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kiro_entry(access_token: &str, expires_at: i64, tags: &[&str]) -> InterchangeAccount {
+        InterchangeAccount {
+            platform: "kiro".to_string(),
+            id: "kiro_1".to_string(),
+            email: "a@example.com".to_string(),
+            tags: tags.iter().map(|tag| tag.to_string()).collect(),
+            fingerprint_id: None,
+            quota: serde_json::Map::new(),
+            record: serde_json::json!({
+                "id": "kiro_1",
+                "email": "a@example.com",
+                "access_token": access_token,
+                "expires_at": expires_at,
+                "last_used": expires_at,
+            }),
+        }
+    }
+
+    #[test]
+    fn older_credentials_are_reported_as_conflict() {
+        let local = kiro_entry("fresh", 2_000, &["team"]);
+        let stale = kiro_entry("stale", 1_000, &["team"]);
+        let (action, changes) = classify(AccountPlatform::Kiro, &local, &stale);
+        assert_eq!(action, PlanAction::Conflict);
+        let token_change = changes
+            .iter()
+            .find(|change| change.field == "access_token")
+            .unwrap();
+        assert_eq!(token_change.current, REDACTED);
+
+        let newer = kiro_entry("newer", 3_000, &["team"]);
+        let (action, _) = classify(AccountPlatform::Kiro, &local, &newer);
+        assert_eq!(action, PlanAction::Update);
+
+        let same = kiro_entry("fresh", 2_000, &["TEAM"]);
+        let (action, _) = classify(AccountPlatform::Kiro, &local, &same);
+        assert_eq!(action, PlanAction::Identical);
+    }
+
+    #[test]
+    fn keep_newest_preserves_local_credentials_on_conflict() {
+        assert_eq!(
+            decide(PlanAction::Conflict, ImportStrategy::KeepNewest),
+            Decision::ImportKeepingLocalCredentials
+        );
+        assert_eq!(
+            decide(PlanAction::Conflict, ImportStrategy::SkipExisting),
+            Decision::Skip
+        );
+        assert_eq!(
+            decide(PlanAction::New, ImportStrategy::SkipExisting),
+            Decision::Import
+        );
+
+        let local = kiro_entry("fresh", 2_000, &["team"]);
+        let stale = kiro_entry("stale", 1_000, &["team", "moved"]);
+        let merged = with_local_credentials(AccountPlatform::Kiro, &local, &stale);
+        assert_eq!(merged.record["access_token"], "fresh");
+        assert_eq!(merged.tags, vec!["team", "moved"]);
+        assert_eq!(
+            merged_tags(&local, &stale),
+            Some(vec!["team".to_string(), "moved".to_string()])
+        );
+        assert_eq!(merged_tags(&stale, &local), None);
+    }
+}
@@ -24,19 +24,12 @@ pub struct InstanceDefaults {
     pub default_user_data_dir: String,
 }
 
-fn instances_path() -> Result<PathBuf, String> {
-    let data_dir = modules::account::get_data_dir()?;
-    Ok(data_dir.join(INSTANCES_FILE))
-}
-
 pub fn load_instance_store() -> Result<InstanceStore, String> {
-    let path = instances_path()?;
-    instance_store::load_instance_store(&path, INSTANCES_FILE)
+    instance_store::load_instance_store(INSTANCES_FILE)
 }
 
 pub fn save_instance_store(store: &InstanceStore) -> Result<(), String> {
-    let path = instances_path()?;
-    instance_store::save_instance_store(&path, INSTANCES_FILE, store)
+    instance_store::save_instance_store(INSTANCES_FILE, store)
 }
 
 pub fn load_default_settings() -> Result<DefaultInstanceSettings, String> {
@@ -206,6 +199,17 @@ fn ensure_state_db_for_injection(profile_dir: &Path) -> Result<PathBuf, String>
 }
 
 pub fn inject_account_to_profile(profile_dir: &Path, account_id: &str) -> Result<(), String> {
+    let result = write_account_to_profile(profile_dir, account_id);
+    modules::audit::record_inject(
+        modules::data_store::AccountPlatform::Antigravity,
+        account_id,
+        profile_dir,
+        &result,
+    );
+    result
+}
+
+fn write_account_to_profile(profile_dir: &Path, account_id: &str) -> Result<(), String> {
     let account = modules::load_account(account_id)?;
     let db_path = ensure_state_db_for_injection(profile_dir)?;
     modules::db::inject_token_to_path(
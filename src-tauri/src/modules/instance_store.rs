@@ -4,6 +4,7 @@ use std::path::Path;
 
 use crate::error::file_corrupted_error;
 use crate::models::InstanceStore;
+use crate::modules::data_store;
 
 #[derive(Debug, Clone)]
 pub struct CreateInstanceParams {
@@ -23,32 +24,24 @@ pub struct UpdateInstanceParams {
     pub bind_account_id: Option<Option<String>>,
 }
 
-pub fn load_instance_store(path: &Path, file_name: &str) -> Result<InstanceStore, String> {
-    if !path.exists() {
+/// 读取实例配置，`name` 为数据库中的文档名（沿用原 JSON 文件名）
+pub fn load_instance_store(name: &str) -> Result<InstanceStore, String> {
+    let Some(content) = data_store::load_document(name)? else {
         return Ok(InstanceStore::new());
-    }
-
-    let content = fs::read_to_string(path).map_err(|e| format!("读取实例配置失败: {}", e))?;
+    };
     if content.trim().is_empty() {
         return Ok(InstanceStore::new());
     }
 
-    serde_json::from_str(&content)
-        .map_err(|e| file_corrupted_error(file_name, &path.to_string_lossy(), &e.to_string()))
+    serde_json::from_str(&content).map_err(|e| {
+        file_corrupted_error(name, &data_store::document_location(name), &e.to_string())
+    })
 }
 
-pub fn save_instance_store(
-    path: &Path,
-    file_name: &str,
-    store: &InstanceStore,
-) -> Result<(), String> {
-    let data_dir = path.parent().ok_or("无法获取实例配置目录")?;
-    let temp_path = data_dir.join(format!("{}.tmp", file_name));
+pub fn save_instance_store(name: &str, store: &InstanceStore) -> Result<(), String> {
     let content =
         serde_json::to_string_pretty(store).map_err(|e| format!("序列化实例配置失败: {}", e))?;
-    fs::write(&temp_path, content).map_err(|e| format!("写入实例配置失败: {}", e))?;
-    fs::rename(temp_path, path).map_err(|e| format!("保存实例配置失败: {}", e))?;
-    Ok(())
+    data_store::save_document(name, &content).map_err(|e| format!("保存实例配置失败: {}", e))
 }
 
 pub fn normalize_name(name: &str) -> Result<String, String> {
@@ -0,0 +1,368 @@
+//! 跨平台账号交换格式
+//!
+//! 一个 JSON 文档即可携带任意平台的账号及其标签、指纹绑定与配额快照，用于在设备之间整体迁移账号。
+//! 格式说明见 `docs/interchange-format.md`，JSON Schema 见 `docs/interchange-format.schema.json`。
+//! 每条账号由通用字段（平台、标识、邮箱、标签、指纹、配额）加平台原生记录组成：导出时把标签与
+//! 配额字段从原生记录中提出来，导入时再合并回去。各平台的转换函数与原有的导入导出放在一起
+//! （Antigravity 在 `import` 模块，其余平台在对应的账号模块）。
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashSet};
+
+use crate::modules::data_store::AccountPlatform;
+use crate::modules::fingerprint::{self, Fingerprint};
+use crate::modules::import_plan::{self, ImportStrategy};
+use crate::modules::{
+    account, audit, codex_account, github_copilot_account, import, kiro_account, logger,
+    windsurf_account,
+};
+
+/// 交换文件格式标识
+pub const INTERCHANGE_FORMAT: &str = "cockpit-interchange";
+/// 当前交换格式版本（只增加可选字段时不升级版本）
+pub const INTERCHANGE_VERSION: u32 = 1;
+
+/// 交换文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InterchangeDocument {
+    pub format: String,
+    pub version: u32,
+    #[serde(default)]
+    pub app_version: String,
+    #[serde(default)]
+    pub exported_at: i64,
+    #[serde(default)]
+    pub accounts: Vec<InterchangeAccount>,
+    /// 账号绑定的指纹（不含 "original"）
+    #[serde(default)]
+    pub fingerprints: Vec<Fingerprint>,
+}
+
+/// 交换文件中的单个账号
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InterchangeAccount {
+    pub platform: String,
+    pub id: String,
+    #[serde(default)]
+    pub email: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// 绑定的指纹 ID（目前仅 Antigravity 账号使用）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fingerprint_id: Option<String>,
+    /// 配额快照（平台原生字段名）
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub quota: Map<String, Value>,
+    /// 平台原生账号记录（不含标签与配额字段）
+    pub record: Value,
+}
+
+/// 导入结果
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InterchangeImportResult {
+    /// 各平台导入的账号数
+    pub imported: BTreeMap<String, usize>,
+    /// 按策略跳过的账号数（含与本机一致的账号）
+    pub skipped: usize,
+    /// 导入失败的账号（平台:邮箱: 原因）
+    pub failed: Vec<String>,
+    pub fingerprints_added: usize,
+    /// 文件中存在但当前版本不支持的平台
+    pub skipped_platforms: Vec<String>,
+}
+
+/// 内容为交换格式时解析并返回，否则返回 None（供各平台的 JSON 导入兼容交换文件）
+pub fn parse_if_interchange(json_content: &str) -> Result<Option<InterchangeDocument>, String> {
+    if !json_content.contains(INTERCHANGE_FORMAT) {
+        return Ok(None);
+    }
+    let Ok(value) = serde_json::from_str::<Value>(json_content) else {
+        return Ok(None);
+    };
+    if value.get("format").and_then(Value::as_str) != Some(INTERCHANGE_FORMAT) {
+        return Ok(None);
+    }
+    let document: InterchangeDocument =
+        serde_json::from_value(value).map_err(|e| format!("交换文件格式错误: {}", e))?;
+    if document.version > INTERCHANGE_VERSION {
+        return Err(format!(
+            "交换文件版本 {} 高于当前支持的版本 {}，请先升级应用",
+            document.version, INTERCHANGE_VERSION
+        ));
+    }
+    Ok(Some(document))
+}
+
+impl InterchangeDocument {
+    /// 指定平台的账号
+    pub fn entries_for(
+        &self,
+        platform: AccountPlatform,
+    ) -> impl Iterator<Item = &InterchangeAccount> {
+        self.accounts
+            .iter()
+            .filter(move |entry| entry.platform == platform.as_str())
+    }
+}
+
+/// 把平台原生账号转换为交换格式条目
+pub(crate) fn to_entry<T: Serialize>(
+    platform: AccountPlatform,
+    account: &T,
+    email: &str,
+    quota_fields: &[&str],
+) -> Result<InterchangeAccount, String> {
+    let mut record = serde_json::to_value(account).map_err(|e| format!("序列化失败: {}", e))?;
+    let object = record.as_object_mut().ok_or("账号数据不是 JSON 对象")?;
+
+    let id = object
+        .get("id")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    let tags = object
+        .remove("tags")
+        .and_then(|tags| serde_json::from_value::<Vec<String>>(tags).ok())
+        .unwrap_or_default();
+    let fingerprint_id = object
+        .remove("fingerprint_id")
+        .and_then(|value| value.as_str().map(str::to_string));
+    let mut quota = Map::new();
+    for field in quota_fields {
+        if let Some(value) = object.remove(*field).filter(|value| !value.is_null()) {
+            quota.insert(field.to_string(), value);
+        }
+    }
+
+    Ok(InterchangeAccount {
+        platform: platform.as_str().to_string(),
+        id,
+        email: email.to_string(),
+        tags,
+        fingerprint_id,
+        quota,
+        record,
+    })
+}
+
+/// 把交换格式条目还原为平台原生账号（合并标签与配额字段；指纹绑定由调用方处理）
+pub(crate) fn into_record<T: DeserializeOwned>(
+    entry: &InterchangeAccount,
+    quota_fields: &[&str],
+) -> Result<T, String> {
+    let mut record = entry.record.clone();
+    let object = record
+        .as_object_mut()
+        .ok_or_else(|| format!("账号 {} 的 record 不是 JSON 对象", entry.id))?;
+    object.insert("tags".to_string(), Value::from(entry.tags.clone()));
+    for field in quota_fields {
+        if let Some(value) = entry.quota.get(*field) {
+            object.insert(field.to_string(), value.clone());
+        }
+    }
+    serde_json::from_value(record).map_err(|e| format!("账号 {} 解析失败: {}", entry.id, e))
+}
+
+/// 导出指定平台的全部账号（平台为空时导出全部平台）
+pub fn export_document(platforms: &[AccountPlatform]) -> Result<String, String> {
+    let platforms = if platforms.is_empty() {
+        AccountPlatform::ALL.as_slice()
+    } else {
+        platforms
+    };
+
+    let mut accounts = Vec::new();
+    for platform in platforms {
+        let entries = match platform {
+            AccountPlatform::Antigravity => import::export_interchange(&[])?,
+            AccountPlatform::Codex => codex_account::export_interchange(&[])?,
+            AccountPlatform::GitHubCopilot => github_copilot_account::export_interchange(&[])?,
+            AccountPlatform::Windsurf => windsurf_account::export_interchange(&[])?,
+            AccountPlatform::Kiro => kiro_account::export_interchange(&[])?,
+        };
+        accounts.extend(entries);
+    }
+
+    let bound: HashSet<&str> = accounts
+        .iter()
+        .filter_map(|entry| entry.fingerprint_id.as_deref())
+        .filter(|id| *id != "original")
+        .collect();
+    let fingerprints = if bound.is_empty() {
+        Vec::new()
+    } else {
+        fingerprint::load_fingerprint_store()?
+            .fingerprints
+            .into_iter()
+            .filter(|item| bound.contains(item.id.as_str()))
+            .collect()
+    };
+
+    let document = InterchangeDocument {
+        format: INTERCHANGE_FORMAT.to_string(),
+        version: INTERCHANGE_VERSION,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        exported_at: chrono::Utc::now().timestamp(),
+        accounts,
+        fingerprints,
+    };
+    logger::log_info(&format!(
+        "[Interchange] 导出 {} 个账号、{} 个指纹",
+        document.accounts.len(),
+        document.fingerprints.len()
+    ));
+    serde_json::to_string_pretty(&document).map_err(|e| format!("序列化失败: {}", e))
+}
+
+/// 查找导入记录对应的本机账号（转换为交换格式条目，便于比对）
+pub(crate) fn find_existing(
+    platform: AccountPlatform,
+    entry: &InterchangeAccount,
+) -> Result<Option<InterchangeAccount>, String> {
+    match platform {
+        AccountPlatform::Antigravity => import::find_interchange_match(entry),
+        AccountPlatform::Codex => codex_account::find_interchange_match(entry),
+        AccountPlatform::GitHubCopilot => github_copilot_account::find_interchange_match(entry),
+        AccountPlatform::Windsurf => windsurf_account::find_interchange_match(entry),
+        AccountPlatform::Kiro => kiro_account::find_interchange_match(entry),
+    }
+}
+
+fn import_entry(platform: AccountPlatform, entry: &InterchangeAccount) -> Result<(), String> {
+    match platform {
+        AccountPlatform::Antigravity => import::import_interchange_entry(entry).map(|_| ()),
+        AccountPlatform::Codex => codex_account::import_interchange_entry(entry).map(|_| ()),
+        AccountPlatform::GitHubCopilot => {
+            github_copilot_account::import_interchange_entry(entry).map(|_| ())
+        }
+        AccountPlatform::Windsurf => windsurf_account::import_interchange_entry(entry).map(|_| ()),
+        AccountPlatform::Kiro => kiro_account::import_interchange_entry(entry).map(|_| ()),
+    }
+}
+
+fn update_tags(
+    platform: AccountPlatform,
+    account_id: &str,
+    tags: Vec<String>,
+) -> Result<(), String> {
+    match platform {
+        AccountPlatform::Antigravity => account::update_account_tags(account_id, tags).map(|_| ()),
+        AccountPlatform::Codex => codex_account::update_account_tags(account_id, tags).map(|_| ()),
+        AccountPlatform::GitHubCopilot => {
+            github_copilot_account::update_account_tags(account_id, tags).map(|_| ())
+        }
+        AccountPlatform::Windsurf => {
+            windsurf_account::update_account_tags(account_id, tags).map(|_| ())
+        }
+        AccountPlatform::Kiro => kiro_account::update_account_tags(account_id, tags).map(|_| ()),
+    }
+}
+
+/// 按策略导入交换文件中的全部账号与指纹，单个账号失败不影响其余账号
+pub fn import_document(
+    json_content: &str,
+    strategy: ImportStrategy,
+) -> Result<InterchangeImportResult, String> {
+    let document = parse_if_interchange(json_content)?.ok_or("不是有效的账号交换文件")?;
+    let (planned, skipped_platforms) = import_plan::plan_document(&document);
+    let mut result = InterchangeImportResult {
+        fingerprints_added: fingerprint::merge_fingerprints(&document.fingerprints)?,
+        skipped_platforms,
+        ..Default::default()
+    };
+
+    for planned in planned {
+        let entry = planned.entry;
+        let applied = import_plan::apply(
+            &planned,
+            strategy,
+            |entry| import_entry(planned.platform, entry),
+            |account_id, tags| update_tags(planned.platform, account_id, tags),
+        );
+        match applied {
+            Ok(Some(())) => *result.imported.entry(entry.platform.clone()).or_insert(0) += 1,
+            Ok(None) => result.skipped += 1,
+            Err(e) => {
+                logger::log_warn(&format!(
+                    "[Interchange] 导入账号失败: platform={}, email={}, error={}",
+                    entry.platform, entry.email, e
+                ));
+                audit::record_import(
+                    planned.platform,
+                    &format!("interchange({})", entry.email),
+                    Err(&e),
+                );
+                result
+                    .failed
+                    .push(format!("{}:{}: {}", entry.platform, entry.email, e));
+            }
+        }
+    }
+
+    for (platform, count) in &result.imported {
+        if let Some(platform) = AccountPlatform::parse(platform) {
+            audit::record_import(platform, "interchange", Ok(*count));
+        }
+    }
+
+    logger::log_info(&format!(
+        "[Interchange] 导入完成: strategy={:?}, 导入 {:?}, 跳过 {} 个, 失败 {} 个, 新增指纹 {} 个",
+        strategy,
+        result.imported,
+        result.skipped,
+        result.failed.len(),
+        result.fingerprints_added
+    ));
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_round_trip_moves_tags_and_quota_out_of_record() {
+        let account = serde_json::json!({
+            "id": "kiro_1",
+            "email": "a@example.com",
+            "tags": ["team"],
+            "credits_total": 100.0,
+            "credits_used": null,
+            "access_token": "secret"
+        });
+        let entry = to_entry(
+            AccountPlatform::Kiro,
+            &account,
+            "a@example.com",
+            &["credits_total", "credits_used"],
+        )
+        .unwrap();
+        assert_eq!(entry.id, "kiro_1");
+        assert_eq!(entry.tags, vec!["team"]);
+        assert_eq!(entry.quota.len(), 1);
+        assert!(entry.record.get("tags").is_none());
+        assert!(entry.record.get("credits_total").is_none());
+
+        let restored: Value = into_record(&entry, &["credits_total", "credits_used"]).unwrap();
+        assert_eq!(restored["credits_total"], 100.0);
+        assert_eq!(restored["tags"][0], "team");
+        assert_eq!(restored["access_token"], "secret");
+    }
+
+    #[test]
+    fn detects_interchange_documents_and_rejects_newer_versions() {
+        assert!(parse_if_interchange("[]").unwrap().is_none());
+        let current = format!(
+            r#"{{"format":"{}","version":1,"accounts":[]}}"#,
+            INTERCHANGE_FORMAT
+        );
+        assert!(parse_if_interchange(&current).unwrap().is_some());
+        let newer = format!(r#"{{"format":"{}","version":99}}"#, INTERCHANGE_FORMAT);
+        assert!(parse_if_interchange(&newer).is_err());
+    }
+}
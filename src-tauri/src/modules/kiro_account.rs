@@ -3,17 +3,23 @@ use serde_json::Value;
 use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Mutex;
 use std::time::Instant;
 
 use crate::models::kiro::{KiroAccount, KiroAccountIndex, KiroOAuthCompletePayload};
-use crate::modules::{account, kiro_oauth, logger};
-
-const ACCOUNTS_INDEX_FILE: &str = "kiro_accounts.json";
-const ACCOUNTS_DIR: &str = "kiro_accounts";
+use crate::modules::audit::{self, AuditAction, AuditEvent, AuditSource};
+use crate::modules::import_plan::ImportOutcome;
+use crate::modules::interchange::{self, InterchangeAccount};
+use crate::modules::quota_history;
+use crate::modules::quota_meter::{self, PlatformQuota};
+use crate::modules::{auto_switch, data_store, import_plan, kiro_oauth, logger};
+
+const PLATFORM: data_store::AccountPlatform = data_store::AccountPlatform::Kiro;
 const LOCAL_AUTH_TOKEN_FILE_NAME: &str = "kiro-auth-token.json";
 const LOCAL_USAGE_DB_KEY: &str = "kiro.kiroAgent";
 const KIRO_QUOTA_ALERT_COOLDOWN_SECONDS: i64 = 10 * 60;
+static KIRO_AUTO_SWITCH_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
 
 lazy_static::lazy_static! {
     static ref KIRO_ACCOUNT_INDEX_LOCK: Mutex<()> = Mutex::new(());
@@ -58,25 +64,8 @@ fn is_banned_account(account: &KiroAccount) -> bool {
     is_banned_status(account.status.as_deref()) || is_banned_reason(account.status_reason.as_deref())
 }
 
-fn get_data_dir() -> Result<PathBuf, String> {
-    account::get_data_dir()
-}
-
-fn get_accounts_dir() -> Result<PathBuf, String> {
-    let base = get_data_dir()?;
-    let dir = base.join(ACCOUNTS_DIR);
-    if !dir.exists() {
-        fs::create_dir_all(&dir).map_err(|e| format!("创建 Kiro 账号目录失败: {}", e))?;
-    }
-    Ok(dir)
-}
-
-fn get_accounts_index_path() -> Result<PathBuf, String> {
-    Ok(get_data_dir()?.join(ACCOUNTS_INDEX_FILE))
-}
-
 pub fn accounts_index_path_string() -> Result<String, String> {
-    Ok(get_accounts_index_path()?.to_string_lossy().to_string())
+    Ok(data_store::document_location(PLATFORM.index_name()))
 }
 
 fn normalize_account_id(account_id: &str) -> Result<String, String> {
@@ -85,7 +74,7 @@ fn normalize_account_id(account_id: &str) -> Result<String, String> {
         return Err("账号 ID 不能为空".to_string());
     }
 
-    // 防止目录穿越或创建子目录，账号文件必须落在固定目录下。
+    // 账号 ID 会作为导出 JSON 布局中的文件名，防止目录穿越或创建子目录。
     if trimmed.contains('/') || trimmed.contains('\\') || trimmed.contains("..") {
         return Err("账号 ID 非法，包含路径字符".to_string());
     }
@@ -100,56 +89,43 @@ fn normalize_account_id(account_id: &str) -> Result<String, String> {
     Ok(trimmed.to_string())
 }
 
-fn resolve_account_file_path(account_id: &str) -> Result<PathBuf, String> {
-    let normalized = normalize_account_id(account_id)?;
-    Ok(get_accounts_dir()?.join(format!("{}.json", normalized)))
-}
-
 pub fn load_account(account_id: &str) -> Option<KiroAccount> {
-    let account_path = resolve_account_file_path(account_id).ok()?;
-    if !account_path.exists() {
-        return None;
-    }
-    let content = fs::read_to_string(account_path).ok()?;
+    let account_id = normalize_account_id(account_id).ok()?;
+    let content = data_store::load_account(PLATFORM, &account_id).ok()??;
     serde_json::from_str(&content).ok()
 }
 
 fn save_account_file(account: &KiroAccount) -> Result<(), String> {
-    let path = resolve_account_file_path(account.id.as_str())?;
+    let account_id = normalize_account_id(account.id.as_str())?;
     let content =
         serde_json::to_string_pretty(account).map_err(|e| format!("序列化账号失败: {}", e))?;
-    fs::write(path, content).map_err(|e| format!("保存账号失败: {}", e))
+    data_store::save_account(PLATFORM, &account_id, &content)
 }
 
 fn delete_account_file(account_id: &str) -> Result<(), String> {
-    let path = resolve_account_file_path(account_id)?;
-    if path.exists() {
-        fs::remove_file(path).map_err(|e| format!("删除账号文件失败: {}", e))?;
-    }
-    Ok(())
+    let account_id = normalize_account_id(account_id)?;
+    data_store::delete_account(PLATFORM, &account_id)
 }
 
-fn load_account_index() -> KiroAccountIndex {
-    let path = match get_accounts_index_path() {
-        Ok(p) => p,
-        Err(_) => return KiroAccountIndex::new(),
-    };
-
-    if !path.exists() {
-        return KiroAccountIndex::new();
-    }
+fn trash_account_file(account_id: &str) -> Result<(), String> {
+    let account_id = normalize_account_id(account_id)?;
+    data_store::trash_account(PLATFORM, &account_id).map(|_| ())
+}
 
-    match fs::read_to_string(path) {
-        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|_| KiroAccountIndex::new()),
-        Err(_) => KiroAccountIndex::new(),
+fn load_account_index() -> KiroAccountIndex {
+    match data_store::load_document(PLATFORM.index_name()) {
+        Ok(Some(content)) => {
+            serde_json::from_str(&content).unwrap_or_else(|_| KiroAccountIndex::new())
+        }
+        _ => KiroAccountIndex::new(),
     }
 }
 
 fn save_account_index(index: &KiroAccountIndex) -> Result<(), String> {
-    let path = get_accounts_index_path()?;
     let content =
         serde_json::to_string_pretty(index).map_err(|e| format!("序列化账号索引失败: {}", e))?;
-    fs::write(path, content).map_err(|e| format!("写入账号索引失败: {}", e))
+    data_store::save_document(PLATFORM.index_name(), &content)
+        .map_err(|e| format!("写入账号索引失败: {}", e))
 }
 
 fn refresh_summary(index: &mut KiroAccountIndex, account: &KiroAccount) {
@@ -447,17 +423,14 @@ fn choose_primary_account_index(
 }
 
 fn normalize_account_index(index: &mut KiroAccountIndex) -> Vec<KiroAccount> {
-    let mut loaded_accounts = Vec::new();
     let mut seen_summary_ids = HashSet::new();
-
-    for summary in &index.accounts {
-        if !seen_summary_ids.insert(summary.id.clone()) {
-            continue;
-        }
-        if let Some(account) = load_account(&summary.id) {
-            loaded_accounts.push(account);
-        }
-    }
+    let unique_ids: Vec<&str> = index
+        .accounts
+        .iter()
+        .filter(|summary| seen_summary_ids.insert(summary.id.clone()))
+        .map(|summary| summary.id.as_str())
+        .collect();
+    let loaded_accounts: Vec<KiroAccount> = data_store::load_indexed_accounts(PLATFORM, unique_ids);
 
     if loaded_accounts.len() <= 1 {
         index.accounts = loaded_accounts
@@ -615,7 +588,36 @@ pub fn upsert_account(payload: KiroOAuthCompletePayload) -> Result<KiroAccount,
         .map_err(|_| "获取 Kiro 账号锁失败".to_string())?;
     let now = now_ts();
     let mut index = load_account_index();
-    let incoming_profile_arn = normalize_identity(payload_profile_arn(&payload).as_deref());
+    let account_id = resolve_payload_account_id(&index, &payload);
+
+    let existing = load_account(&account_id);
+    let created_at = existing.as_ref().map(|acc| acc.created_at).unwrap_or(now);
+
+    let mut account =
+        existing.unwrap_or_else(|| account_from_payload(account_id.clone(), &payload, now));
+
+    apply_payload(&mut account, payload);
+    account.id = account_id;
+    account.created_at = created_at;
+    account.last_used = now;
+
+    save_account_file(&account)?;
+    refresh_summary(&mut index, &account);
+    save_account_index(&index)?;
+
+    logger::log_info(&format!(
+        "Kiro 账号已保存: id={}, email={}",
+        account.id, account.email
+    ));
+    Ok(account)
+}
+
+/// 按身份（Profile ARN、用户 ID、邮箱、Refresh Token）匹配本机账号，未匹配时按身份生成新 ID
+fn resolve_payload_account_id(
+    index: &KiroAccountIndex,
+    payload: &KiroOAuthCompletePayload,
+) -> String {
+    let incoming_profile_arn = normalize_identity(payload_profile_arn(payload).as_deref());
     let incoming_user_id = normalize_identity(payload.user_id.as_deref());
     let incoming_email = normalize_email_identity(Some(payload.email.as_str()));
     let incoming_refresh_token = normalize_token_identity(payload.refresh_token.as_deref());
@@ -629,7 +631,7 @@ pub fn upsert_account(payload: KiroOAuthCompletePayload) -> Result<KiroAccount,
         .to_lowercase();
     let generated_id = format!("kiro_{:x}", md5::compute(identity_seed.as_bytes()));
 
-    let account_id = index
+    index
         .accounts
         .iter()
         .filter_map(|item| load_account(&item.id))
@@ -650,18 +652,21 @@ pub fn upsert_account(payload: KiroOAuthCompletePayload) -> Result<KiroAccount,
             )
         })
         .map(|account| account.id)
-        .unwrap_or(generated_id);
-
-    let existing = load_account(&account_id);
-    let tags = existing.as_ref().and_then(|acc| acc.tags.clone());
-    let created_at = existing.as_ref().map(|acc| acc.created_at).unwrap_or(now);
+        .unwrap_or(generated_id)
+}
 
-    let mut account = existing.unwrap_or(KiroAccount {
-        id: account_id.clone(),
+/// 由登录结果构造新账号记录
+fn account_from_payload(
+    account_id: String,
+    payload: &KiroOAuthCompletePayload,
+    now: i64,
+) -> KiroAccount {
+    KiroAccount {
+        id: account_id,
         email: payload.email.clone(),
         user_id: payload.user_id.clone(),
         login_provider: payload.login_provider.clone(),
-        tags,
+        tags: None,
         access_token: payload.access_token.clone(),
         refresh_token: payload.refresh_token.clone(),
         token_type: payload.token_type.clone(),
@@ -684,24 +689,9 @@ pub fn upsert_account(payload: KiroOAuthCompletePayload) -> Result<KiroAccount,
         kiro_usage_raw: payload.kiro_usage_raw.clone(),
         status: payload.status.clone(),
         status_reason: payload.status_reason.clone(),
-        created_at,
+        created_at: now,
         last_used: now,
-    });
-
-    apply_payload(&mut account, payload);
-    account.id = account_id;
-    account.created_at = created_at;
-    account.last_used = now;
-
-    save_account_file(&account)?;
-    refresh_summary(&mut index, &account);
-    save_account_index(&index)?;
-
-    logger::log_info(&format!(
-        "Kiro 账号已保存: id={}, email={}",
-        account.id, account.email
-    ));
-    Ok(account)
+    }
 }
 
 pub async fn refresh_account_token(account_id: &str) -> Result<KiroAccount, String> {
@@ -722,6 +712,7 @@ pub async fn refresh_account_token(account_id: &str) -> Result<KiroAccount, Stri
 
     let updated = account.clone();
     upsert_account_record(account)?;
+    quota_history::record(PLATFORM, &updated.id, &updated.quota_meters());
     logger::log_info(&format!(
         "[Kiro Refresh] 刷新完成: id={}, email={}, elapsed={}ms",
         updated.id,
@@ -780,13 +771,20 @@ pub async fn refresh_all_tokens() -> Result<Vec<(String, Result<KiroAccount, Str
 }
 
 pub fn remove_account(account_id: &str) -> Result<(), String> {
+    let event = AuditEvent::new(PLATFORM, AuditAction::Delete).account(account_id);
+    let result = remove_account_inner(account_id);
+    event.result(&result).record();
+    result
+}
+
+fn remove_account_inner(account_id: &str) -> Result<(), String> {
     let _lock = KIRO_ACCOUNT_INDEX_LOCK
         .lock()
         .map_err(|_| "获取 Kiro 账号锁失败".to_string())?;
     let mut index = load_account_index();
     index.accounts.retain(|item| item.id != account_id);
     save_account_index(&index)?;
-    delete_account_file(account_id)?;
+    trash_account_file(account_id)?;
     Ok(())
 }
 
@@ -797,13 +795,38 @@ pub fn remove_accounts(account_ids: &[String]) -> Result<(), String> {
     Ok(())
 }
 
+/// 从回收站恢复账号并重新加入账号列表
+pub fn restore_account(account_id: &str) -> Result<KiroAccount, String> {
+    let account_id = normalize_account_id(account_id)?;
+    let _lock = KIRO_ACCOUNT_INDEX_LOCK
+        .lock()
+        .map_err(|_| "获取 Kiro 账号锁失败".to_string())?;
+    let mut index = load_account_index();
+    data_store::restore_trashed_account(PLATFORM, &account_id, |tx, content| {
+        let account: KiroAccount =
+            serde_json::from_str(content).map_err(|e| format!("解析账号数据失败: {}", e))?;
+        refresh_summary(&mut index, &account);
+        let index_content = serde_json::to_string_pretty(&index)
+            .map_err(|e| format!("序列化账号索引失败: {}", e))?;
+        tx.save_document(PLATFORM.index_name(), &index_content)?;
+        Ok(account)
+    })
+}
+
 pub fn update_account_tags(account_id: &str, tags: Vec<String>) -> Result<KiroAccount, String> {
-    let mut account = load_account(account_id).ok_or_else(|| "账号不存在".to_string())?;
-    account.tags = Some(tags);
-    account.last_used = now_ts();
-    let updated = account.clone();
-    upsert_account_record(account)?;
-    Ok(updated)
+    let event = AuditEvent::new(PLATFORM, AuditAction::Tag)
+        .account(account_id)
+        .detail(tags.join(", "));
+    let result = (|| -> Result<KiroAccount, String> {
+        let mut account = load_account(account_id).ok_or_else(|| "账号不存在".to_string())?;
+        account.tags = Some(tags);
+        account.last_used = now_ts();
+        let updated = account.clone();
+        upsert_account_record(account)?;
+        Ok(updated)
+    })();
+    event.result(&result).record();
+    result
 }
 
 fn clone_object_value(value: Option<&Value>) -> Option<Value> {
@@ -986,29 +1009,64 @@ fn payloads_from_import_json_value(value: Value) -> Result<Vec<KiroOAuthComplete
     }
 }
 
-pub fn import_from_json(json_content: &str) -> Result<Vec<KiroAccount>, String> {
-    if let Ok(account) = serde_json::from_str::<KiroAccount>(json_content) {
-        let saved = upsert_account_record(account)?;
-        return Ok(vec![saved]);
+pub fn import_from_json(json_content: &str) -> Result<ImportOutcome<KiroAccount>, String> {
+    let result = import_from_json_inner(json_content);
+    audit::record_import(
+        PLATFORM,
+        "json",
+        result.as_ref().map(|outcome| outcome.imported.len()),
+    );
+    result
+}
+
+fn import_from_json_inner(json_content: &str) -> Result<ImportOutcome<KiroAccount>, String> {
+    // 统一交换格式：只导入其中的 Kiro 账号
+    if let Some(document) = interchange::parse_if_interchange(json_content)? {
+        return Ok(import_plan::import_entries(
+            document.entries_for(PLATFORM),
+            import_interchange_entry,
+            update_account_tags,
+        ));
     }
 
-    if let Ok(accounts) = serde_json::from_str::<Vec<KiroAccount>>(json_content) {
-        let mut result = Vec::new();
-        for account in accounts {
-            let saved = upsert_account_record(account)?;
-            result.push(saved);
-        }
-        return Ok(result);
+    // 原生账号记录同样按导入计划处理，避免旧的导出文件覆盖本机较新的 Token
+    let accounts = serde_json::from_str::<KiroAccount>(json_content)
+        .map(|account| vec![account])
+        .or_else(|_| serde_json::from_str::<Vec<KiroAccount>>(json_content));
+    if let Ok(accounts) = accounts {
+        let entries = accounts
+            .iter()
+            .map(to_interchange_entry)
+            .collect::<Result<Vec<_>, String>>()?;
+        return Ok(import_plan::import_entries(
+            &entries,
+            import_interchange_entry,
+            update_account_tags,
+        ));
     }
 
     if let Ok(value) = serde_json::from_str::<Value>(json_content) {
         if let Ok(payloads) = payloads_from_import_json_value(value) {
-            let mut result = Vec::with_capacity(payloads.len());
+            let mut outcome = ImportOutcome::default();
             for payload in payloads {
-                let saved = upsert_account(payload)?;
-                result.push(saved);
+                // 登录结果只携带凭据：本机凭据更新或内容一致时跳过
+                let candidate = account_from_payload(
+                    resolve_payload_account_id(&load_account_index(), &payload),
+                    &payload,
+                    now_ts(),
+                );
+                let imported = to_interchange_entry(&candidate)
+                    .and_then(|entry| import_plan::should_import_legacy(&entry))
+                    .and_then(|should_import| {
+                        should_import.then(|| upsert_account(payload)).transpose()
+                    });
+                match imported {
+                    Ok(Some(saved)) => outcome.imported.push(saved),
+                    Ok(None) => {}
+                    Err(e) => outcome.fail(&candidate.email, e),
+                }
             }
-            return Ok(result);
+            return Ok(outcome);
         }
     }
 
@@ -1023,47 +1081,121 @@ pub fn export_accounts(account_ids: &[String]) -> Result<String, String> {
     serde_json::to_string_pretty(&accounts).map_err(|e| format!("序列化失败: {}", e))
 }
 
-fn normalize_quota_alert_threshold(raw: i32) -> i32 {
-    raw.clamp(0, 100)
+/// 交换格式中 Kiro 账号的配额字段
+const INTERCHANGE_QUOTA_FIELDS: &[&str] = &[
+    "credits_total",
+    "credits_used",
+    "bonus_total",
+    "bonus_used",
+    "usage_reset_at",
+    "bonus_expire_days",
+    "kiro_usage_raw",
+];
+
+/// 导出账号为交换格式条目（ID 为空时导出全部）
+pub fn export_interchange(account_ids: &[String]) -> Result<Vec<InterchangeAccount>, String> {
+    let accounts = if account_ids.is_empty() {
+        list_accounts()
+    } else {
+        account_ids
+            .iter()
+            .filter_map(|id| load_account(id))
+            .collect()
+    };
+    accounts.iter().map(to_interchange_entry).collect()
 }
 
-fn clamp_percent(value: f64) -> i32 {
-    value.round().clamp(0.0, 100.0) as i32
+fn to_interchange_entry(account: &KiroAccount) -> Result<InterchangeAccount, String> {
+    interchange::to_entry(PLATFORM, account, &account.email, INTERCHANGE_QUOTA_FIELDS)
 }
 
-fn calc_remaining_percent(total: Option<f64>, used: Option<f64>) -> Option<i32> {
-    let total = total?;
-    if !total.is_finite() || total <= 0.0 {
-        return None;
-    }
+/// 查找交换格式条目对应的本机账号（按记录 ID，与导入时一致）
+pub fn find_interchange_match(
+    entry: &InterchangeAccount,
+) -> Result<Option<InterchangeAccount>, String> {
+    load_account(&entry.id)
+        .map(|account| to_interchange_entry(&account))
+        .transpose()
+}
 
-    let used = used.unwrap_or(0.0);
-    if !used.is_finite() {
-        return None;
-    }
-    let remaining = (total - used).max(0.0);
-    Some(clamp_percent((remaining / total) * 100.0))
+/// 导入单个交换格式条目（标签与配额随原生记录一并写入）
+pub fn import_interchange_entry(entry: &InterchangeAccount) -> Result<KiroAccount, String> {
+    let account: KiroAccount = interchange::into_record(entry, INTERCHANGE_QUOTA_FIELDS)?;
+    upsert_account_record(account)
+}
+
+/// 切换 Kiro 账号的完整流程（不依赖窗口，供命令行/后台调用）
+pub async fn switch_account_internal(account_id: &str) -> Result<String, String> {
+    let result = switch_account_steps(account_id).await;
+    AuditEvent::new(PLATFORM, AuditAction::Switch)
+        .account(account_id)
+        .result(&result)
+        .record();
+    result
 }
 
-fn extract_quota_metrics(account: &KiroAccount) -> Vec<(String, i32)> {
-    let mut metrics = Vec::new();
+async fn switch_account_steps(account_id: &str) -> Result<String, String> {
+    let account_id = account_id.to_string();
+    let started_at = Instant::now();
+    logger::log_info(&format!(
+        "[Kiro Switch] 开始切换账号: account_id={}",
+        account_id
+    ));
 
-    if let Some(pct) = calc_remaining_percent(account.credits_total, account.credits_used) {
-        metrics.push(("Prompt Credits".to_string(), pct));
-    }
-    if let Some(pct) = calc_remaining_percent(account.bonus_total, account.bonus_used) {
-        metrics.push(("Add-on Credits".to_string(), pct));
-    }
+    let account = load_account(&account_id)
+        .ok_or_else(|| format!("Kiro account not found: {}", account_id))?;
+
+    if let Err(err) = crate::modules::kiro_instance::update_default_settings(
+        Some(Some(account_id.clone())),
+        None,
+        Some(false),
+    ) {
+        logger::log_warn(&format!("更新 Kiro 默认实例绑定账号失败: {}", err));
+    }
+
+    let launch_warning = match crate::modules::kiro_instance::start_instance("__default__").await {
+        Ok(_) => None,
+        Err(err) => {
+            if err.starts_with("APP_PATH_NOT_FOUND:") || err.contains("启动 Kiro 失败") {
+                logger::log_warn(&format!("Kiro 默认实例启动失败: {}", err));
+                if err.starts_with("APP_PATH_NOT_FOUND:") {
+                    if let Some(app) = crate::get_app_handle() {
+                        use tauri::Emitter;
+                        let _ = app.emit(
+                            "app:path_missing",
+                            serde_json::json!({ "app": "kiro", "retry": { "kind": "default" } }),
+                        );
+                    }
+                }
+                Some(err)
+            } else {
+                return Err(err);
+            }
+        }
+    };
 
-    metrics
+    if let Some(err) = launch_warning {
+        logger::log_warn(&format!(
+            "[Kiro Switch] 切号完成但启动失败: account_id={}, email={}, elapsed={}ms, error={}",
+            account.id,
+            account.email,
+            started_at.elapsed().as_millis(),
+            err
+        ));
+        Ok(format!("切换完成，但 Kiro 启动失败: {}", err))
+    } else {
+        logger::log_info(&format!(
+            "[Kiro Switch] 切号成功: account_id={}, email={}, elapsed={}ms",
+            account.id,
+            account.email,
+            started_at.elapsed().as_millis()
+        ));
+        Ok(format!("切换完成: {}", account.email))
+    }
 }
 
-fn average_quota_percentage(metrics: &[(String, i32)]) -> f64 {
-    if metrics.is_empty() {
-        return 0.0;
-    }
-    let sum: i32 = metrics.iter().map(|(_, pct)| *pct).sum();
-    sum as f64 / metrics.len() as f64
+fn normalize_quota_alert_threshold(raw: i32) -> i32 {
+    raw.clamp(0, 100)
 }
 
 fn resolve_current_account_id(accounts: &[KiroAccount]) -> Option<String> {
@@ -1091,6 +1223,104 @@ fn display_email(account: &KiroAccount) -> String {
     }
 }
 
+/// 将账号注入默认实例：Kiro 运行中时按默认实例启动流程关闭、注入并重启，否则只写入登录态
+async fn switch_default_instance_account(account_id: &str) -> Result<(), String> {
+    let settings = crate::modules::kiro_instance::update_default_settings(
+        Some(Some(account_id.to_string())),
+        None,
+        Some(false),
+    )?;
+    if crate::modules::kiro_instance::resolve_kiro_pid(settings.last_pid, None).is_some() {
+        crate::modules::kiro_instance::start_instance("__default__").await?;
+    } else {
+        let default_dir = crate::modules::kiro_instance::get_default_kiro_user_data_dir()?;
+        crate::modules::kiro_instance::inject_account_to_profile(&default_dir, account_id)?;
+    }
+    Ok(())
+}
+
+async fn run_auto_switch_if_needed_inner() -> Result<Option<KiroAccount>, String> {
+    let cfg = crate::modules::config::get_user_config();
+    if !cfg.kiro_auto_switch_enabled {
+        return Ok(None);
+    }
+
+    let threshold = auto_switch::normalize_threshold(cfg.kiro_auto_switch_threshold);
+    let accounts = list_accounts();
+    let current_id = match resolve_current_account_id(&accounts) {
+        Some(id) => id,
+        None => return Ok(None),
+    };
+    let current = match accounts.iter().find(|account| account.id == current_id) {
+        Some(account) => account,
+        None => return Ok(None),
+    };
+
+    if !auto_switch::should_trigger(&quota_meter::metrics(current), threshold) {
+        return Ok(None);
+    }
+
+    let strategy = auto_switch::SelectionStrategy::from_config(
+        &cfg.kiro_auto_switch_strategy,
+        &cfg.kiro_auto_switch_strategy_param,
+    );
+    let candidates: Vec<auto_switch::SwitchCandidate> = accounts
+        .iter()
+        .map(|account| {
+            let metrics = quota_meter::metrics(account);
+            auto_switch::SwitchCandidate {
+                id: account.id.clone(),
+                eligible: auto_switch::is_above_threshold(&metrics, threshold),
+                metrics,
+                last_used: account.last_used,
+                tags: account.tags.clone().unwrap_or_default(),
+            }
+        })
+        .collect();
+    let target = auto_switch::pick_candidate(&candidates, &current_id, &strategy)
+        .and_then(|candidate| accounts.iter().find(|account| account.id == candidate.id));
+    let Some(target) = target else {
+        logger::log_warn(&format!(
+            "[AutoSwitch][Kiro] 当前账号低于阈值 {}%，但没有可切换候选账号: strategy={}",
+            threshold,
+            strategy.as_str()
+        ));
+        audit::auto_switch_event(PLATFORM, &current_id, threshold, strategy.as_str())
+            .account(&current_id)
+            .skipped()
+            .record();
+        return Ok(None);
+    };
+
+    logger::log_info(&format!(
+        "[AutoSwitch][Kiro] 触发自动切号: current_id={}, target_id={}, threshold={}%, strategy={}",
+        current_id,
+        target.id,
+        threshold,
+        strategy.as_str()
+    ));
+    let result = switch_default_instance_account(&target.id).await;
+    audit::auto_switch_event(PLATFORM, &current_id, threshold, strategy.as_str())
+        .account(&target.id)
+        .result(&result)
+        .record();
+    result?;
+    Ok(Some(target.clone()))
+}
+
+/// 当前账号任一配额低于阈值时自动切换到配额最充足的账号
+pub async fn run_auto_switch_if_needed() -> Result<Option<KiroAccount>, String> {
+    if KIRO_AUTO_SWITCH_IN_PROGRESS.swap(true, Ordering::SeqCst) {
+        logger::log_info("[AutoSwitch][Kiro] 自动切号进行中，跳过本次检查");
+        return Ok(None);
+    }
+
+    let result =
+        audit::with_source(AuditSource::AutoSwitch, run_auto_switch_if_needed_inner()).await;
+    KIRO_AUTO_SWITCH_IN_PROGRESS.store(false, Ordering::SeqCst);
+    result
+}
+
 fn build_quota_alert_cooldown_key(account_id: &str, threshold: i32) -> String {
     format!("kiro:{}:{}", account_id, threshold)
 }
@@ -1124,7 +1354,7 @@ fn pick_quota_alert_recommendation(
         .iter()
         .filter(|account| account.id != current_id)
         .filter(|account| !is_banned_account(account))
-        .filter(|account| !extract_quota_metrics(account).is_empty())
+        .filter(|account| !quota_meter::metrics(account).is_empty())
         .cloned()
         .collect();
 
@@ -1133,8 +1363,8 @@ fn pick_quota_alert_recommendation(
     }
 
     candidates.sort_by(|a, b| {
-        let avg_a = average_quota_percentage(&extract_quota_metrics(a));
-        let avg_b = average_quota_percentage(&extract_quota_metrics(b));
+        let avg_a = quota_meter::average_percentage(&quota_meter::metrics(a));
+        let avg_b = quota_meter::average_percentage(&quota_meter::metrics(b));
         avg_b
             .partial_cmp(&avg_a)
             .unwrap_or(std::cmp::Ordering::Equal)
@@ -1166,11 +1396,7 @@ pub fn run_quota_alert_if_needed(
         return Ok(None);
     }
 
-    let metrics = extract_quota_metrics(current);
-    let low_models: Vec<(String, i32)> = metrics
-        .into_iter()
-        .filter(|(_, pct)| *pct <= threshold)
-        .collect();
+    let low_models = quota_meter::low_metrics(current, threshold);
 
     if low_models.is_empty() {
         clear_quota_alert_cooldown(&current_id, threshold);
@@ -1187,6 +1413,7 @@ pub fn run_quota_alert_if_needed(
     let lowest_percentage = low_models.iter().map(|(_, pct)| *pct).min().unwrap_or(0);
     let payload = crate::modules::account::QuotaAlertPayload {
         platform: "kiro".to_string(),
+        kind: crate::modules::account::QuotaAlertKind::Low,
         current_account_id: current_id,
         current_email: display_email(current),
         threshold,
@@ -12,8 +12,9 @@ use sysinfo::System;
 use uuid::Uuid;
 
 use crate::models::kiro::KiroAccount;
-use crate::models::{DefaultInstanceSettings, InstanceProfile, InstanceStore};
+use crate::models::{DefaultInstanceSettings, InstanceProfile, InstanceProfileView, InstanceStore};
 use crate::modules;
+use crate::modules::atomic_file;
 use crate::modules::instance::InstanceDefaults;
 use crate::modules::instance_store;
 use crate::modules::kiro_account;
@@ -24,21 +25,15 @@ static KIRO_INSTANCE_STORE_LOCK: std::sync::LazyLock<Mutex<()>> =
     std::sync::LazyLock::new(|| Mutex::new(()));
 
 const KIRO_INSTANCES_FILE: &str = "kiro_instances.json";
+const DEFAULT_INSTANCE_ID: &str = "__default__";
 const KIRO_USAGE_DB_KEY: &str = "kiro.kiroAgent";
 
-fn instances_path() -> Result<PathBuf, String> {
-    let data_dir = modules::account::get_data_dir()?;
-    Ok(data_dir.join(KIRO_INSTANCES_FILE))
-}
-
 pub fn load_instance_store() -> Result<InstanceStore, String> {
-    let path = instances_path()?;
-    instance_store::load_instance_store(&path, KIRO_INSTANCES_FILE)
+    instance_store::load_instance_store(KIRO_INSTANCES_FILE)
 }
 
 pub fn save_instance_store(store: &InstanceStore) -> Result<(), String> {
-    let path = instances_path()?;
-    instance_store::save_instance_store(&path, KIRO_INSTANCES_FILE, store)
+    instance_store::save_instance_store(KIRO_INSTANCES_FILE, store)
 }
 
 pub fn load_default_settings() -> Result<DefaultInstanceSettings, String> {
@@ -1289,7 +1284,7 @@ fn write_local_auth_token_file(account: &KiroAccount) -> Result<(), String> {
 
     let content =
         serde_json::to_string_pretty(&raw).map_err(|e| format!("序列化本地 token 失败: {}", e))?;
-    fs::write(&token_path, content)
+    atomic_file::replace(&token_path, content)
         .map_err(|e| format!("写入本地 token 文件失败({}): {}", token_path.display(), e))
 }
 
@@ -1347,7 +1342,7 @@ fn write_profile_file(profile_dir: &Path, account: &KiroAccount) -> Result<(), S
 
     let content = serde_json::to_string_pretty(&raw)
         .map_err(|e| format!("序列化 profile.json 失败: {}", e))?;
-    fs::write(&profile_path, content)
+    atomic_file::replace(&profile_path, content)
         .map_err(|e| format!("写入 profile.json 失败({}): {}", profile_path.display(), e))
 }
 
@@ -1404,6 +1399,17 @@ fn write_usage_snapshot_if_exists(profile_dir: &Path, account: &KiroAccount) ->
 }
 
 pub fn inject_account_to_profile(profile_dir: &Path, account_id: &str) -> Result<(), String> {
+    let result = write_account_to_profile(profile_dir, account_id);
+    modules::audit::record_inject(
+        modules::data_store::AccountPlatform::Kiro,
+        account_id,
+        profile_dir,
+        &result,
+    );
+    result
+}
+
+fn write_account_to_profile(profile_dir: &Path, account_id: &str) -> Result<(), String> {
     let account = kiro_account::load_account(account_id)
         .ok_or_else(|| format!("绑定账号不存在: {}", account_id))?;
 
@@ -1412,3 +1418,113 @@ pub fn inject_account_to_profile(profile_dir: &Path, account_id: &str) -> Result
     let _ = write_usage_snapshot_if_exists(profile_dir, &account);
     Ok(())
 }
+
+/// 实例目录存在且非空时视为已初始化
+pub fn is_profile_initialized(user_data_dir: &str) -> bool {
+    let path = Path::new(user_data_dir);
+    if !path.exists() {
+        return false;
+    }
+    match std::fs::read_dir(path) {
+        Ok(mut iter) => iter.next().is_some(),
+        Err(_) => false,
+    }
+}
+
+fn inject_bound_account_for_instance_start(
+    user_data_dir: &str,
+    bind_account_id: Option<&str>,
+) -> Result<(), String> {
+    let bind_id = bind_account_id
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    let Some(bind_id) = bind_id else {
+        return Ok(());
+    };
+
+    let account = modules::kiro_account::load_account(bind_id)
+        .ok_or_else(|| format!("绑定账号不存在: {}", bind_id))?;
+
+    modules::logger::log_info(&format!(
+        "实例启动检测到绑定 Kiro 账号，准备注入: bind_account_id={}, email={}, user_data_dir={}",
+        bind_id, account.email, user_data_dir
+    ));
+
+    close_kiro(&[user_data_dir.to_string()], 20)?;
+    inject_account_to_profile(Path::new(user_data_dir), bind_id)?;
+
+    modules::logger::log_info(&format!("Kiro 账号注入完成: {}", account.email));
+    Ok(())
+}
+
+/// 启动实例（`__default__` 为默认实例）：先关闭仍在运行的进程，注入绑定账号后重新启动
+pub async fn start_instance(instance_id: &str) -> Result<InstanceProfileView, String> {
+    modules::logger::log_info(&format!("开始启动 Kiro 实例: {}", instance_id));
+
+    if instance_id == DEFAULT_INSTANCE_ID {
+        let default_dir = get_default_kiro_user_data_dir()?;
+        let default_dir_str = default_dir.to_string_lossy().to_string();
+        let default_settings = load_default_settings()?;
+
+        if let Some(pid) = resolve_kiro_pid(default_settings.last_pid, None) {
+            modules::process::close_pid(pid, 20)?;
+            let _ = update_default_pid(None)?;
+        }
+
+        close_kiro(&[default_dir_str.clone()], 20)?;
+        inject_bound_account_for_instance_start(
+            &default_dir_str,
+            default_settings.bind_account_id.as_deref(),
+        )?;
+
+        let extra_args = modules::process::parse_extra_args(&default_settings.extra_args);
+        let pid = start_kiro_default_with_args_with_new_window(&extra_args, true)?;
+        let _ = update_default_pid(Some(pid))?;
+
+        let running = resolve_kiro_pid(Some(pid), None).is_some();
+        return Ok(InstanceProfileView {
+            id: DEFAULT_INSTANCE_ID.to_string(),
+            name: String::new(),
+            user_data_dir: default_dir_str,
+            extra_args: default_settings.extra_args,
+            bind_account_id: default_settings.bind_account_id,
+            created_at: 0,
+            last_launched_at: None,
+            last_pid: Some(pid),
+            running,
+            initialized: is_profile_initialized(&default_dir.to_string_lossy()),
+            is_default: true,
+            follow_local_account: false,
+        });
+    }
+
+    let store = load_instance_store()?;
+    let instance = store
+        .instances
+        .into_iter()
+        .find(|item| item.id == instance_id)
+        .ok_or("实例不存在")?;
+
+    if let Some(pid) = resolve_kiro_pid(instance.last_pid, Some(&instance.user_data_dir)) {
+        modules::process::close_pid(pid, 20)?;
+        let _ = update_instance_pid(&instance.id, None)?;
+    }
+
+    close_kiro(&[instance.user_data_dir.clone()], 20)?;
+    inject_bound_account_for_instance_start(
+        &instance.user_data_dir,
+        instance.bind_account_id.as_deref(),
+    )?;
+
+    let extra_args = modules::process::parse_extra_args(&instance.extra_args);
+    let pid = start_kiro_with_args_with_new_window(&instance.user_data_dir, &extra_args, true)?;
+    let updated = update_instance_after_start(&instance.id, pid)?;
+
+    let running = resolve_kiro_pid(Some(pid), Some(&updated.user_data_dir)).is_some();
+    let initialized = is_profile_initialized(&updated.user_data_dir);
+    Ok(InstanceProfileView::from_profile(
+        updated,
+        running,
+        initialized,
+    ))
+}
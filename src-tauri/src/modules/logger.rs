@@ -5,6 +5,7 @@ use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::LazyLock;
 use tracing::{error, info, warn};
+use tracing_subscriber::fmt::writer::BoxMakeWriter;
 use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
 
 const LOG_FILE_PREFIX: &str = "app.log";
@@ -106,6 +107,15 @@ fn cleanup_expired_logs(log_dir: &Path) {
 
 /// 初始化日志系统
 pub fn init_logger() {
+    init_logger_with_console(false);
+}
+
+/// 初始化日志系统（控制台输出写入 stderr，保证 stdout 只包含命令行的 JSON 结果）
+pub fn init_cli_logger() {
+    init_logger_with_console(true);
+}
+
+fn init_logger_with_console(console_to_stderr: bool) {
     let _ = tracing_log::LogTracer::init();
 
     let log_dir = match get_log_dir() {
@@ -119,7 +129,15 @@ pub fn init_logger() {
     let file_appender = tracing_appender::rolling::daily(log_dir.clone(), "app.log");
     let (non_blocking, _guard) = tracing_appender::non_blocking(file_appender);
 
+    let console_writer = if console_to_stderr {
+        BoxMakeWriter::new(std::io::stderr)
+    } else {
+        BoxMakeWriter::new(std::io::stdout)
+    };
+
     let console_layer = fmt::Layer::new()
+        .with_writer(console_writer)
+        .with_ansi(!console_to_stderr)
         .with_target(false)
         .with_thread_ids(false)
         .with_level(true)
@@ -132,7 +150,9 @@ pub fn init_logger() {
         .with_level(true)
         .with_timer(LocalTimer);
 
-    let filter_layer = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let default_level = if console_to_stderr { "warn" } else { "info" };
+    let filter_layer =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default_level));
 
     let _ = tracing_subscriber::registry()
         .with(filter_layer)
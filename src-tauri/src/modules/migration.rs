@@ -0,0 +1,176 @@
+//! 数据格式迁移
+//!
+//! 数据目录下的 `data_version.json` 记录当前数据格式版本。启动时按编号顺序执行尚未执行的迁移，
+//! 执行前把数据库与数据目录下的 JSON 文件快照到 `backups/migration-v<旧版本>-<时间>/`，
+//! 每完成一项迁移立即写入新版本号。迁移必须幂等：失败后版本号停在最后一次成功的迁移，
+//! 下次启动会从失败的那一项重新执行。迁移直接修改 JSON 值而不是反序列化成结构体，
+//! 避免丢弃当前版本不认识的字段。
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+use crate::error::{AppError, AppResult};
+use crate::modules::{account, atomic_file, backup, group_settings, logger};
+
+/// 数据格式版本文件名
+const DATA_VERSION_FILE: &str = "data_version.json";
+
+/// 单项迁移
+struct Migration {
+    /// 执行后的数据格式版本，从 1 开始连续递增
+    version: u32,
+    name: &'static str,
+    run: fn() -> Result<(), String>,
+}
+
+/// 迁移注册表（按版本号升序，只能追加，不能修改已发布的迁移）
+const MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    name: "rename_legacy_gemini_groups",
+    run: migrate_legacy_group_names,
+}];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DataVersion {
+    version: u32,
+    #[serde(default)]
+    app_version: String,
+    #[serde(default)]
+    updated_at: i64,
+}
+
+/// 当前应用支持的数据格式版本
+pub fn latest_version() -> u32 {
+    MIGRATIONS.last().map(|item| item.version).unwrap_or(0)
+}
+
+fn migration_error(version: u32, name: &str, message: String) -> AppError {
+    AppError::Migration {
+        version,
+        name: name.to_string(),
+        message,
+    }
+}
+
+fn load_data_version(path: &Path) -> Result<DataVersion, String> {
+    if !path.exists() {
+        return Ok(DataVersion::default());
+    }
+    let content = fs::read_to_string(path).map_err(|e| format!("读取数据版本失败: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("解析数据版本失败: {}", e))
+}
+
+fn save_data_version(path: &Path, version: u32) -> Result<(), String> {
+    let record = DataVersion {
+        version,
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        updated_at: chrono::Utc::now().timestamp(),
+    };
+    let content =
+        serde_json::to_string_pretty(&record).map_err(|e| format!("序列化失败: {}", e))?;
+    atomic_file::write(path, content)
+}
+
+/// 执行尚未执行的迁移（启动时调用），返回迁移后的数据格式版本
+pub fn run_pending() -> AppResult<u32> {
+    let data_dir = account::get_data_dir().map_err(|e| migration_error(0, "data_dir", e))?;
+    let version_path = data_dir.join(DATA_VERSION_FILE);
+    let current = load_data_version(&version_path)
+        .map_err(|e| migration_error(0, DATA_VERSION_FILE, e))?
+        .version;
+    let latest = latest_version();
+
+    if current > latest {
+        // 数据来自更新版本的应用，继续迁移或回写版本号都可能破坏数据
+        return Err(migration_error(
+            current,
+            DATA_VERSION_FILE,
+            format!("数据格式版本 {} 高于当前应用支持的版本 {}", current, latest),
+        ));
+    }
+    if current == latest {
+        return Ok(current);
+    }
+
+    backup::snapshot_data_dir(&format!("migration-v{}", current))
+        .map_err(|e| migration_error(current, "backup", e))?;
+
+    for migration in MIGRATIONS.iter().filter(|item| item.version > current) {
+        logger::log_info(&format!(
+            "[Migration] 执行迁移 {} ({})",
+            migration.version, migration.name
+        ));
+        (migration.run)()
+            .and_then(|_| save_data_version(&version_path, migration.version))
+            .map_err(|e| migration_error(migration.version, migration.name, e))?;
+    }
+
+    logger::log_info(&format!(
+        "[Migration] 数据格式已升级: {} -> {}",
+        current, latest
+    ));
+    Ok(latest)
+}
+
+/// v1：旧版分组名 G3-Pro / G3-Flash / G3-Image 改为 Gemini Pro / Gemini Flash / Gemini Image
+fn migrate_legacy_group_names() -> Result<(), String> {
+    let path = group_settings::get_group_settings_path();
+    if !path.exists() {
+        return Ok(());
+    }
+    let _lock = atomic_file::lock(&path)?;
+    let content = fs::read_to_string(&path).map_err(|e| format!("读取分组配置失败: {}", e))?;
+    let mut settings: Value =
+        serde_json::from_str(&content).map_err(|e| format!("解析分组配置失败: {}", e))?;
+    if rename_legacy_group_names(&mut settings) {
+        let content =
+            serde_json::to_string_pretty(&settings).map_err(|e| format!("序列化失败: {}", e))?;
+        atomic_file::replace(&path, content)?;
+    }
+    Ok(())
+}
+
+fn rename_legacy_group_names(settings: &mut Value) -> bool {
+    let Some(names) = settings
+        .get_mut("groupNames")
+        .and_then(|names| names.as_object_mut())
+    else {
+        return false;
+    };
+    let mut changed = false;
+    for (group_id, legacy_name, new_name) in group_settings::LEGACY_GROUP_NAMES {
+        if names.get(group_id).and_then(|name| name.as_str()) == Some(legacy_name) {
+            names.insert(group_id.to_string(), Value::from(new_name));
+            changed = true;
+        }
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_versions_are_consecutive() {
+        for (index, migration) in MIGRATIONS.iter().enumerate() {
+            assert_eq!(migration.version, index as u32 + 1, "{}", migration.name);
+        }
+    }
+
+    #[test]
+    fn legacy_group_rename_keeps_unknown_fields_and_is_idempotent() {
+        let mut settings = serde_json::json!({
+            "groupNames": { "g3_pro": "G3-Pro", "g3_flash": "My Flash" },
+            "pluginOnlyField": { "keep": true }
+        });
+        assert!(rename_legacy_group_names(&mut settings));
+        assert_eq!(settings["groupNames"]["g3_pro"], "Gemini Pro");
+        assert_eq!(settings["groupNames"]["g3_flash"], "My Flash");
+        assert_eq!(settings["pluginOnlyField"]["keep"], true);
+        assert!(!rename_legacy_group_names(&mut settings));
+    }
+}
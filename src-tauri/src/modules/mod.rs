@@ -1,9 +1,17 @@
 pub mod account;
+pub mod api_proxy;
+pub mod api_proxy_anthropic;
+pub mod api_proxy_openai;
+pub mod atomic_file;
+pub mod audit;
+pub mod auto_switch;
+pub mod backup;
 pub mod codex_account;
 pub mod codex_instance;
 pub mod codex_oauth;
 pub mod codex_quota;
 pub mod config;
+pub mod data_store;
 pub mod db;
 pub mod device;
 pub mod fingerprint;
@@ -12,22 +20,36 @@ pub mod github_copilot_instance;
 pub mod github_copilot_oauth;
 pub mod group_settings;
 pub mod import;
+pub mod import_plan;
 pub mod instance;
 pub mod instance_store;
+pub mod interchange;
 pub mod kiro_account;
 pub mod kiro_instance;
 pub mod kiro_oauth;
 pub mod logger;
+pub mod migration;
 pub mod oauth;
 pub mod oauth_server;
 pub mod opencode_auth;
 pub mod process;
 pub mod quota;
 pub mod quota_cache;
+pub mod quota_history;
+pub mod quota_meter;
+pub mod quota_protection;
+pub mod quota_reset;
+pub mod quota_watch;
+pub mod recovery;
+pub mod refresh_plan;
+pub mod refresh_scheduler;
+pub mod startup;
 pub mod sync_settings;
+pub mod trash;
 pub mod tray;
 pub mod tray_layout;
 pub mod update_checker;
+pub mod vault;
 pub mod vscode_inject;
 pub mod wakeup;
 pub mod wakeup_history;
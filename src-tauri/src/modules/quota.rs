@@ -1,11 +1,7 @@
 use crate::models::{QuotaData, TokenData};
-use crate::modules;
-use chrono::Utc;
+use crate::modules::quota_cache::{self, CacheFreshness};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
-use sha2::{Digest, Sha256};
-use std::fs;
-use std::path::PathBuf;
 
 const CLOUD_CODE_DAILY_BASE_URL: &str = "https://daily-cloudcode-pa.googleapis.com";
 const CLOUD_CODE_PROD_BASE_URL: &str = "https://cloudcode-pa.googleapis.com";
@@ -19,10 +15,8 @@ const DEFAULT_ATTEMPTS: usize = 2;
 const BACKOFF_BASE_MS: u64 = 500;
 const BACKOFF_MAX_MS: u64 = 4000;
 const ONBOARD_POLL_DELAY_MS: u64 = 500;
-const API_CACHE_DIR: &str = "cache/quota_api_v1_desktop";
-const API_CACHE_VERSION: u8 = 1;
-#[allow(dead_code)]
-const API_CACHE_TTL_MS: i64 = 60_000;
+const API_CACHE_SOURCE: &str = "authorized";
+const API_CACHE_CUSTOM_SOURCE: &str = "desktop";
 
 #[derive(Debug, Clone, Default)]
 pub struct QuotaCloudCodeContext {
@@ -112,84 +106,6 @@ fn log_subscription_tier_result(email: &str, subscription_tier: Option<&String>,
     }
 }
 
-fn hash_email(email: &str) -> String {
-    let normalized = email.trim().to_lowercase();
-    let mut hasher = Sha256::new();
-    hasher.update(normalized.as_bytes());
-    format!("{:x}", hasher.finalize())
-}
-
-fn api_cache_path(source: &str, email: &str) -> Result<PathBuf, String> {
-    let data_dir = modules::account::get_data_dir()?;
-    let dir = data_dir.join(API_CACHE_DIR).join(source);
-    if !dir.exists() {
-        fs::create_dir_all(&dir)
-            .map_err(|e| format!("Failed to create quota api cache dir: {}", e))?;
-    }
-    Ok(dir.join(format!("{}.json", hash_email(email))))
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct QuotaApiCacheRecord {
-    version: u8,
-    source: String,
-    custom_source: String,
-    email: String,
-    project_id: Option<String>,
-    updated_at: i64,
-    payload: serde_json::Value,
-}
-
-#[allow(dead_code)]
-fn read_api_cache(source: &str, email: &str) -> Option<QuotaApiCacheRecord> {
-    let path = api_cache_path(source, email).ok()?;
-    let content = fs::read_to_string(path).ok()?;
-    let record = serde_json::from_str::<QuotaApiCacheRecord>(&content).ok()?;
-    if record.version != API_CACHE_VERSION {
-        return None;
-    }
-    if record.source != source {
-        return None;
-    }
-    Some(record)
-}
-
-#[allow(dead_code)]
-fn is_api_cache_valid(record: &QuotaApiCacheRecord) -> bool {
-    let now_ms = Utc::now().timestamp_millis();
-    now_ms - record.updated_at < API_CACHE_TTL_MS
-}
-
-#[allow(dead_code)]
-fn api_cache_age_secs(record: &QuotaApiCacheRecord) -> i64 {
-    let now_ms = Utc::now().timestamp_millis();
-    std::cmp::max(0, (now_ms - record.updated_at) / 1000)
-}
-
-fn write_api_cache(
-    source: &str,
-    custom_source: &str,
-    email: &str,
-    project_id: Option<String>,
-    payload: serde_json::Value,
-) {
-    if let Ok(path) = api_cache_path(source, email) {
-        let record = QuotaApiCacheRecord {
-            version: API_CACHE_VERSION,
-            source: source.to_string(),
-            custom_source: custom_source.to_string(),
-            email: email.to_string(),
-            project_id,
-            updated_at: Utc::now().timestamp_millis(),
-            payload,
-        };
-        if let Ok(content) = serde_json::to_string_pretty(&record) {
-            let _ = fs::write(path, content);
-        }
-    }
-}
-
 #[derive(Debug, Serialize, Deserialize)]
 struct QuotaResponse {
     models: std::collections::HashMap<String, ModelInfo>,
@@ -225,6 +141,13 @@ pub struct QuotaFetchResult {
     pub error: Option<QuotaFetchError>,
 }
 
+/// fetchAvailableModels 的结果
+enum ModelsResponse {
+    Payload(serde_json::Value),
+    /// 403：账号无权限
+    Forbidden(QuotaFetchError),
+}
+
 #[derive(Debug, Deserialize)]
 struct LoadProjectResponse {
     #[serde(rename = "cloudaicompanionProject")]
@@ -726,36 +649,120 @@ pub async fn fetch_quota_with_context(
 
     // 保留缓存，但缓存命中前仍先执行与 Antigravity.app 对齐的项目识别流程。
     if !skip_cache {
-        if let Some(record) = read_api_cache("authorized", email) {
-            if is_api_cache_valid(&record) {
+        if let Some(record) = quota_cache::read_quota_cache(API_CACHE_SOURCE, email) {
+            let freshness = record.freshness();
+            if freshness == CacheFreshness::Expired {
                 crate::modules::logger::log_info(&format!(
-                    "[QuotaApiCache] Using api cache for {} (age: {}s)",
+                    "[QuotaApiCache] Cache expired for {} (age: {}s), fetching from network",
                     email,
-                    api_cache_age_secs(&record),
+                    record.age_secs(),
                 ));
-                if let Ok(quota_response) =
-                    serde_json::from_value::<QuotaResponse>(record.payload.clone())
-                {
-                    let quota_data =
-                        build_quota_data_from_response(quota_response, subscription_tier.clone());
-                    return Ok(QuotaFetchResult {
-                        quota: quota_data,
-                        project_id: effective_project_id.clone(),
-                        error: None,
-                    });
-                }
-            } else {
+            } else if let Ok(quota_response) =
+                serde_json::from_value::<QuotaResponse>(record.payload().clone())
+            {
                 crate::modules::logger::log_info(&format!(
-                    "[QuotaApiCache] Cache expired for {} (age: {}s), fetching from network",
+                    "[QuotaApiCache] Using {} api cache for {} (age: {}s)",
+                    if freshness == CacheFreshness::Fresh {
+                        "fresh"
+                    } else {
+                        "stale"
+                    },
                     email,
-                    api_cache_age_secs(&record),
+                    record.age_secs(),
+                ));
+                if freshness == CacheFreshness::Stale {
+                    spawn_revalidation(
+                        access_token,
+                        email,
+                        &base_url,
+                        effective_project_id.clone(),
+                    );
+                }
+                let mut quota_data =
+                    build_quota_data_from_response(quota_response, subscription_tier.clone());
+                // 配额时间取缓存写入时间，避免旧数据被当作刚刚拉取的结果
+                quota_data.last_updated = record.updated_at() / 1000;
+                return Ok(QuotaFetchResult {
+                    quota: quota_data,
+                    project_id: effective_project_id,
+                    error: None,
+                });
+            }
+        }
+    }
+
+    match request_models_payload(access_token, email, &base_url, effective_project_id.clone())
+        .await?
+    {
+        ModelsResponse::Payload(payload_value) => {
+            let quota_response: QuotaResponse = serde_json::from_value(payload_value)
+                .map_err(|e| AppError::Unknown(format!("API 响应解析失败: {}", e)))?;
+            Ok(QuotaFetchResult {
+                quota: build_quota_data_from_response(quota_response, subscription_tier),
+                project_id: effective_project_id,
+                error: None,
+            })
+        }
+        ModelsResponse::Forbidden(error) => {
+            let mut quota = QuotaData::new();
+            quota.is_forbidden = true;
+            quota.subscription_tier = subscription_tier;
+            Ok(QuotaFetchResult {
+                quota,
+                project_id: effective_project_id,
+                error: Some(error),
+            })
+        }
+    }
+}
+
+/// 缓存处于 stale 阶段时后台重新拉取；新数据写入缓存，账号列表加载时由
+/// `quota_cache::apply_cached_quota` 合并
+fn spawn_revalidation(access_token: &str, email: &str, base_url: &str, project_id: Option<String>) {
+    let access_token = access_token.to_string();
+    let email = email.to_string();
+    let base_url = base_url.to_string();
+    tauri::async_runtime::spawn(async move {
+        match request_models_payload(&access_token, &email, &base_url, project_id).await {
+            Ok(ModelsResponse::Payload(_)) => {
+                crate::modules::websocket::broadcast_data_changed("quota_cache");
+            }
+            Ok(ModelsResponse::Forbidden(_)) => {}
+            Err(e) => {
+                crate::modules::logger::log_warn(&format!(
+                    "[QuotaApiCache] Background refresh failed for {}: {}",
+                    email, e
                 ));
             }
         }
+    });
+}
+
+/// 请求 fetchAvailableModels 并写入缓存。同一账号的请求串行执行，
+/// 排队期间已有其他请求（包括 VS Code 扩展）写入了上游响应时直接复用，不再请求上游；
+/// 已解析配额的回写不算在内。
+async fn request_models_payload(
+    access_token: &str,
+    email: &str,
+    base_url: &str,
+    project_id: Option<String>,
+) -> crate::error::AppResult<ModelsResponse> {
+    use crate::error::AppError;
+
+    let requested_at = chrono::Utc::now().timestamp_millis();
+    let _guard = quota_cache::lock_refresh(API_CACHE_SOURCE, email).await;
+    if let Some(record) = quota_cache::read_quota_cache(API_CACHE_SOURCE, email) {
+        if record.is_upstream() && record.updated_at() >= requested_at {
+            crate::modules::logger::log_info(&format!(
+                "[QuotaApiCache] Reusing concurrent refresh for {}",
+                email
+            ));
+            return Ok(ModelsResponse::Payload(record.payload().clone()));
+        }
     }
 
     let client = create_client();
-    let payload = effective_project_id
+    let payload = project_id
         .as_ref()
         .map(|id| json!({ "project": id }))
         .unwrap_or_else(|| json!({}));
@@ -782,22 +789,15 @@ pub async fn fetch_quota_with_context(
                             email
                         ));
                         let text = response.text().await.unwrap_or_default();
-                        let mut q = QuotaData::new();
-                        q.is_forbidden = true;
-                        q.subscription_tier = subscription_tier.clone();
                         let message = if text.trim().is_empty() {
                             "API returned 403 Forbidden".to_string()
                         } else {
                             text
                         };
-                        return Ok(QuotaFetchResult {
-                            quota: q,
-                            project_id: effective_project_id.clone(),
-                            error: Some(QuotaFetchError {
-                                code: Some(status.as_u16()),
-                                message,
-                            }),
-                        });
+                        return Ok(ModelsResponse::Forbidden(QuotaFetchError {
+                            code: Some(status.as_u16()),
+                            message,
+                        }));
                     }
 
                     if attempt < max_retries {
@@ -813,24 +813,19 @@ pub async fn fetch_quota_with_context(
                 let payload_value: serde_json::Value = serde_json::from_str(&body)
                     .map_err(|e| AppError::Unknown(format!("API 响应解析失败: {}", e)))?;
 
-                write_api_cache(
-                    "authorized",
-                    "desktop",
+                if let Err(e) = quota_cache::write_api_payload(
+                    API_CACHE_SOURCE,
+                    API_CACHE_CUSTOM_SOURCE,
                     email,
-                    effective_project_id.clone(),
+                    project_id,
                     payload_value.clone(),
-                );
-
-                let quota_response: QuotaResponse = serde_json::from_value(payload_value)
-                    .map_err(|e| AppError::Unknown(format!("API 响应解析失败: {}", e)))?;
-                let quota_data =
-                    build_quota_data_from_response(quota_response, subscription_tier.clone());
-
-                return Ok(QuotaFetchResult {
-                    quota: quota_data,
-                    project_id: effective_project_id.clone(),
-                    error: None,
-                });
+                ) {
+                    crate::modules::logger::log_warn(&format!(
+                        "[QuotaApiCache] Failed to write api cache for {}: {}",
+                        email, e
+                    ));
+                }
+                return Ok(ModelsResponse::Payload(payload_value));
             }
             Err(e) => {
                 if attempt < max_retries {
@@ -1,15 +1,36 @@
+//! 配额 API 缓存
+//!
+//! `fetchAvailableModels` 的响应按来源与邮箱哈希写入 `cache/quota_api_v1_desktop/<source>/`，
+//! 与 VS Code 扩展共用同一份文件。缓存分三个阶段：`API_CACHE_TTL_MS` 内直接使用；
+//! `API_CACHE_STALE_MS` 内先返回旧数据并在后台重新拉取；超过后必须请求接口。
+//! 同一账号的请求通过 `lock_refresh` 串行化，排队的调用方拿到锁后复用前一个请求刚写入的结果，
+//! 界面、托盘、唤醒和扩展同时刷新时只会请求一次上游。由已解析配额回写的记录标记为 `writeBack`，
+//! 不作为刚刚请求到的上游结果复用。
+
+use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
 
+use chrono::Utc;
 use serde::{Deserialize, Serialize};
-use serde_json::Value as JsonValue;
+use serde_json::{json, Value as JsonValue};
 use sha2::{Digest, Sha256};
 
 use crate::models::{Account, QuotaData};
 use crate::modules;
+use crate::modules::atomic_file;
 
 const CACHE_DIR: &str = "cache/quota_api_v1_desktop";
 const CACHE_VERSION: u8 = 1;
+/// 缓存有效期（毫秒），期内直接使用
+const API_CACHE_TTL_MS: i64 = 60_000;
+/// 过期后仍可先返回旧数据、同时后台刷新的时长（毫秒）
+const API_CACHE_STALE_MS: i64 = 10 * 60_000;
+
+/// 每个账号一把刷新锁，key 为 `<source>/<邮箱哈希>`
+static REFRESH_LOCKS: OnceLock<Mutex<HashMap<String, Arc<tokio::sync::Mutex<()>>>>> =
+    OnceLock::new();
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -21,6 +42,52 @@ pub(crate) struct QuotaApiCacheRecord {
     project_id: Option<String>,
     updated_at: i64,
     payload: JsonValue,
+    /// 由 `write_quota_cache` 回写（而非上游响应）
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    write_back: bool,
+}
+
+/// 缓存所处阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CacheFreshness {
+    Fresh,
+    Stale,
+    Expired,
+}
+
+impl QuotaApiCacheRecord {
+    pub(crate) fn payload(&self) -> &JsonValue {
+        &self.payload
+    }
+
+    /// 写入时间（毫秒）
+    pub(crate) fn updated_at(&self) -> i64 {
+        self.updated_at
+    }
+
+    /// 是否为上游接口的原始响应（本应用或 VS Code 扩展写入）
+    pub(crate) fn is_upstream(&self) -> bool {
+        !self.write_back
+    }
+
+    pub(crate) fn age_secs(&self) -> i64 {
+        std::cmp::max(0, (Utc::now().timestamp_millis() - self.updated_at) / 1000)
+    }
+
+    pub(crate) fn freshness(&self) -> CacheFreshness {
+        freshness_at(self.updated_at, Utc::now().timestamp_millis())
+    }
+}
+
+fn freshness_at(updated_at: i64, now_ms: i64) -> CacheFreshness {
+    let age = now_ms - updated_at;
+    if age < API_CACHE_TTL_MS {
+        CacheFreshness::Fresh
+    } else if age < API_CACHE_TTL_MS + API_CACHE_STALE_MS {
+        CacheFreshness::Stale
+    } else {
+        CacheFreshness::Expired
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -78,11 +145,107 @@ pub(crate) fn read_quota_cache(source: &str, email: &str) -> Option<QuotaApiCach
     Some(record)
 }
 
+fn write_record(record: &QuotaApiCacheRecord) -> Result<(), String> {
+    let path = cache_path(&record.source, &record.email)?;
+    let content = serde_json::to_string_pretty(record)
+        .map_err(|e| format!("Failed to serialize quota cache: {}", e))?;
+    atomic_file::write(&path, content)
+}
+
+/// 写入接口原始响应
+pub(crate) fn write_api_payload(
+    source: &str,
+    custom_source: &str,
+    email: &str,
+    project_id: Option<String>,
+    payload: JsonValue,
+) -> Result<(), String> {
+    write_record(&QuotaApiCacheRecord {
+        version: CACHE_VERSION,
+        source: source.to_string(),
+        custom_source: Some(custom_source.to_string()),
+        email: email.to_string(),
+        project_id,
+        updated_at: Utc::now().timestamp_millis(),
+        payload,
+        write_back: false,
+    })
+}
+
+/// 把已解析的配额回写缓存，供 VS Code 扩展读取。
+/// 缓存不比配额旧时跳过，避免用重建的数据覆盖接口原始响应；回写时保留原响应中的其他字段和模型。
 pub fn write_quota_cache(source: &str, email: &str, quota: &QuotaData) -> Result<(), String> {
-    let _ = source;
-    let _ = email;
-    let _ = quota;
-    Ok(())
+    if quota.is_forbidden || quota.models.is_empty() {
+        return Ok(());
+    }
+    let existing = read_quota_cache(source, email);
+    if existing
+        .as_ref()
+        .is_some_and(|record| record.updated_at / 1000 >= quota.last_updated)
+    {
+        return Ok(());
+    }
+
+    let (custom_source, project_id, mut payload) = match existing {
+        Some(record) => (record.custom_source, record.project_id, record.payload),
+        None => (Some("desktop".to_string()), None, JsonValue::Null),
+    };
+    merge_quota_into_payload(&mut payload, quota);
+    write_record(&QuotaApiCacheRecord {
+        version: CACHE_VERSION,
+        source: source.to_string(),
+        custom_source,
+        email: email.to_string(),
+        project_id,
+        updated_at: quota.last_updated * 1000,
+        payload,
+        write_back: true,
+    })
+}
+
+/// 按接口响应格式写入各模型的剩余比例与重置时间
+fn merge_quota_into_payload(payload: &mut JsonValue, quota: &QuotaData) {
+    if !payload.get("models").is_some_and(JsonValue::is_object) {
+        *payload = match payload.take() {
+            JsonValue::Object(mut map) => {
+                map.insert("models".to_string(), json!({}));
+                JsonValue::Object(map)
+            }
+            _ => json!({ "models": {} }),
+        };
+    }
+    let Some(models) = payload.get_mut("models").and_then(JsonValue::as_object_mut) else {
+        return;
+    };
+    for model in &quota.models {
+        let entry = models
+            .entry(model.name.clone())
+            .or_insert_with(|| json!({}));
+        if !entry.is_object() {
+            *entry = json!({});
+        }
+        if let Some(display_name) = &model.display_name {
+            entry["displayName"] = json!(display_name);
+        }
+        let info = &mut entry["quotaInfo"];
+        if !info.is_object() {
+            *info = json!({});
+        }
+        info["remainingFraction"] = json!(model.percentage as f64 / 100.0);
+        if !model.reset_time.is_empty() {
+            info["resetTime"] = json!(model.reset_time);
+        }
+    }
+}
+
+/// 获取账号的刷新锁，持锁期间同一账号的其他刷新排队等待
+pub(crate) async fn lock_refresh(source: &str, email: &str) -> tokio::sync::OwnedMutexGuard<()> {
+    let key = format!("{}/{}", source, hash_email(email));
+    let lock = match REFRESH_LOCKS.get_or_init(Default::default).lock() {
+        Ok(mut locks) => locks.entry(key).or_default().clone(),
+        Err(_) => Arc::default(),
+    };
+    lock.lock_owned().await
 }
 
 pub fn apply_cached_quota(account: &mut Account, source: &str) -> Result<bool, String> {
@@ -153,3 +316,83 @@ pub fn apply_cached_quota(account: &mut Account, source: &str) -> Result<bool, S
     account.update_quota(quota);
     Ok(true)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn freshness_moves_from_fresh_to_stale_to_expired() {
+        assert_eq!(freshness_at(0, API_CACHE_TTL_MS - 1), CacheFreshness::Fresh);
+        assert_eq!(freshness_at(0, API_CACHE_TTL_MS), CacheFreshness::Stale);
+        assert_eq!(
+            freshness_at(0, API_CACHE_TTL_MS + API_CACHE_STALE_MS),
+            CacheFreshness::Expired
+        );
+    }
+
+    #[test]
+    fn only_write_backs_are_flagged() {
+        // 扩展写入的记录不带 writeBack 字段，按上游响应处理
+        let record: QuotaApiCacheRecord = serde_json::from_value(json!({
+            "version": CACHE_VERSION,
+            "source": "authorized",
+            "customSource": "vscode",
+            "email": "a@example.com",
+            "projectId": null,
+            "updatedAt": 1,
+            "payload": {}
+        }))
+        .unwrap();
+        assert!(record.is_upstream());
+        assert!(serde_json::to_value(&record)
+            .unwrap()
+            .get("writeBack")
+            .is_none());
+
+        let write_back = QuotaApiCacheRecord {
+            write_back: true,
+            ..record
+        };
+        let value = serde_json::to_value(&write_back).unwrap();
+        assert_eq!(value["writeBack"], json!(true));
+        let parsed: QuotaApiCacheRecord = serde_json::from_value(value).unwrap();
+        assert!(!parsed.is_upstream());
+    }
+
+    #[test]
+    fn merge_keeps_unknown_models_and_fields() {
+        let mut payload = json!({
+            "models": {
+                "gemini-3-pro": {
+                    "displayName": "Gemini 3 Pro",
+                    "quotaInfo": { "remainingFraction": 1.0, "extra": true }
+                },
+                "chat-model": { "quotaInfo": { "remainingFraction": 0.5 } }
+            },
+            "other": 1
+        });
+        let mut quota = QuotaData::new();
+        quota.add_model(
+            "gemini-3-pro".to_string(),
+            None,
+            40,
+            "2026-01-01T00:00:00Z".to_string(),
+        );
+        merge_quota_into_payload(&mut payload, &quota);
+
+        let info = &payload["models"]["gemini-3-pro"]["quotaInfo"];
+        assert_eq!(info["remainingFraction"], json!(0.4));
+        assert_eq!(info["resetTime"], json!("2026-01-01T00:00:00Z"));
+        assert_eq!(info["extra"], json!(true));
+        assert_eq!(
+            payload["models"]["gemini-3-pro"]["displayName"],
+            json!("Gemini 3 Pro")
+        );
+        assert_eq!(
+            payload["models"]["chat-model"]["quotaInfo"]["remainingFraction"],
+            json!(0.5)
+        );
+        assert_eq!(payload["other"], json!(1));
+    }
+}
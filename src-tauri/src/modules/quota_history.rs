@@ -0,0 +1,327 @@
+//! 配额历史与耗尽预测
+//!
+//! 五个平台每次刷新配额后，按账号把统一配额模型（见 `quota_meter`）中各配额项的剩余百分比写入数据库
+//! `quota_history` 表，保留 `HISTORY_RETENTION_DAYS` 天。预测取最近一段时间内、上次重置之后的
+//! 样本做线性拟合得到消耗速率，推算耗尽时间，并判断是否会在重置前耗尽。
+
+use rusqlite::params;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::modules::data_store::{self, AccountPlatform};
+use crate::modules::logger;
+use crate::modules::quota_meter::QuotaMeter;
+use crate::modules::quota_reset;
+
+/// 历史记录保留天数
+const HISTORY_RETENTION_DAYS: i64 = 30;
+/// 计算消耗速率时回看的时长（秒）
+const FORECAST_LOOKBACK_SECS: i64 = 6 * 60 * 60;
+/// 样本跨度不足该时长（秒）时不做预测
+const MIN_FORECAST_SPAN_SECS: i64 = 10 * 60;
+/// 剩余百分比回升超过该值视为发生了重置
+const RESET_JUMP_PERCENT: f64 = 1.0;
+
+/// 历史数据点
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuotaPoint {
+    pub meter: String,
+    pub remaining_percent: f64,
+    pub used: Option<f64>,
+    pub limit: Option<f64>,
+    pub reset_at: Option<i64>,
+    /// 记录时间（秒）
+    pub recorded_at: i64,
+}
+
+/// 单个配额项的消耗预测
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuotaForecast {
+    pub platform: String,
+    pub account_id: String,
+    /// 账号邮箱或登录名
+    pub account: Option<String>,
+    pub meter: String,
+    pub remaining_percent: f64,
+    /// 每小时消耗的百分比，未在消耗时为 0
+    pub consumption_per_hour: f64,
+    /// 预计耗尽时间（秒），未在消耗时为 None
+    pub exhausts_at: Option<i64>,
+    pub reset_at: Option<i64>,
+    /// 是否会在重置前耗尽（重置时间未知时只要会耗尽即为 true）
+    pub exhausts_before_reset: bool,
+    /// 最新样本的记录时间（秒）
+    pub updated_at: i64,
+}
+
+/// 写入一次刷新结果，并与上一次样本比较发送配额恢复通知；失败只记录日志，不影响刷新流程
+pub fn record(platform: AccountPlatform, account_id: &str, meters: &[QuotaMeter]) {
+    if meters.is_empty() {
+        return;
+    }
+    let previous = latest_remaining(platform, account_id).unwrap_or_default();
+    if let Err(e) = insert_samples(platform, account_id, meters) {
+        logger::log_warn(&format!(
+            "[QuotaHistory] 写入配额历史失败: platform={}, account={}, {}",
+            platform.as_str(),
+            account_id,
+            e
+        ));
+    }
+    quota_reset::notify_if_replenished(platform, account_id, &previous, meters);
+}
+
+/// 账号最近一次记录中各配额项的剩余百分比
+fn latest_remaining(
+    platform: AccountPlatform,
+    account_id: &str,
+) -> Result<HashMap<String, f64>, String> {
+    data_store::with_connection(|conn| {
+        let mut stmt = conn
+            .prepare(
+                "SELECT meter, remaining_percent FROM quota_history
+                 WHERE platform = ?1 AND account_id = ?2
+                   AND recorded_at = (SELECT MAX(recorded_at) FROM quota_history
+                                      WHERE platform = ?1 AND account_id = ?2)",
+            )
+            .map_err(data_store::db_error)?;
+        let rows = stmt
+            .query_map(params![platform.as_str(), account_id], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .map_err(data_store::db_error)?;
+        rows.collect::<Result<HashMap<_, _>, _>>()
+            .map_err(data_store::db_error)
+    })
+}
+
+fn insert_samples(
+    platform: AccountPlatform,
+    account_id: &str,
+    meters: &[QuotaMeter],
+) -> Result<(), String> {
+    let now = chrono::Utc::now().timestamp();
+    data_store::with_connection(|conn| {
+        let tx = conn.transaction().map_err(data_store::db_error)?;
+        for meter in meters {
+            tx.execute(
+                "INSERT INTO quota_history
+                 (platform, account_id, meter, remaining_percent, used, quota_limit, reset_at, recorded_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                params![
+                    platform.as_str(),
+                    account_id,
+                    meter.name,
+                    meter.remaining_percent,
+                    meter.used,
+                    meter.limit,
+                    meter.reset_at,
+                    now
+                ],
+            )
+            .map_err(data_store::db_error)?;
+        }
+        tx.execute(
+            "DELETE FROM quota_history WHERE platform = ?1 AND account_id = ?2 AND recorded_at < ?3",
+            params![
+                platform.as_str(),
+                account_id,
+                now - HISTORY_RETENTION_DAYS * 24 * 60 * 60
+            ],
+        )
+        .map_err(data_store::db_error)?;
+        tx.commit().map_err(data_store::db_error)
+    })
+}
+
+/// 查询账号的配额历史（`meter` 为 None 时返回全部配额项），按时间先后排列
+pub fn series(
+    platform: AccountPlatform,
+    account_id: &str,
+    meter: Option<&str>,
+    since: Option<i64>,
+) -> Result<Vec<QuotaPoint>, String> {
+    data_store::with_connection(|conn| {
+        let mut stmt = conn
+            .prepare(
+                "SELECT meter, remaining_percent, used, quota_limit, reset_at, recorded_at
+                 FROM quota_history
+                 WHERE platform = ?1 AND account_id = ?2 AND (?3 IS NULL OR meter = ?3)
+                   AND recorded_at >= ?4
+                 ORDER BY recorded_at, id",
+            )
+            .map_err(data_store::db_error)?;
+        let rows = stmt
+            .query_map(
+                params![platform.as_str(), account_id, meter, since.unwrap_or(0)],
+                |row| {
+                    Ok(QuotaPoint {
+                        meter: row.get(0)?,
+                        remaining_percent: row.get(1)?,
+                        used: row.get(2)?,
+                        limit: row.get(3)?,
+                        reset_at: row.get(4)?,
+                        recorded_at: row.get(5)?,
+                    })
+                },
+            )
+            .map_err(data_store::db_error)?;
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(data_store::db_error)
+    })
+}
+
+/// 历史记录行：(平台, 账号 ID, 配额项, 记录时间, 剩余百分比, 重置时间)
+type HistoryRow = (String, String, String, i64, f64, Option<i64>);
+/// 同一账号同一配额项的样本 (记录时间, 剩余百分比, 重置时间)，按时间先后
+type MeterSeries = Vec<(i64, f64, Option<i64>)>;
+
+/// 计算全部账号（或指定平台）各配额项的消耗预测，会先耗尽的排在前面
+pub fn forecast(platform: Option<AccountPlatform>) -> Result<Vec<QuotaForecast>, String> {
+    let now = chrono::Utc::now().timestamp();
+    let rows: Vec<HistoryRow> = data_store::with_connection(|conn| {
+        let mut stmt = conn
+            .prepare(
+                "SELECT platform, account_id, meter, recorded_at, remaining_percent, reset_at
+                 FROM quota_history
+                 WHERE recorded_at >= ?1 AND (?2 IS NULL OR platform = ?2)
+                 ORDER BY recorded_at, id",
+            )
+            .map_err(data_store::db_error)?;
+        let rows = stmt
+            .query_map(
+                params![now - FORECAST_LOOKBACK_SECS, platform.map(|p| p.as_str())],
+                |row| {
+                    Ok((
+                        row.get(0)?,
+                        row.get(1)?,
+                        row.get(2)?,
+                        row.get(3)?,
+                        row.get(4)?,
+                        row.get(5)?,
+                    ))
+                },
+            )
+            .map_err(data_store::db_error)?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(data_store::db_error);
+        rows
+    })?;
+
+    let mut grouped: BTreeMap<(String, String, String), MeterSeries> = BTreeMap::new();
+    for (platform, account_id, meter, recorded_at, remaining, reset_at) in rows {
+        grouped
+            .entry((platform, account_id, meter))
+            .or_default()
+            .push((recorded_at, remaining, reset_at));
+    }
+
+    let mut labels: HashMap<(String, String), Option<String>> = HashMap::new();
+    let mut forecasts: Vec<QuotaForecast> = grouped
+        .into_iter()
+        .filter_map(|((platform, account_id, meter), samples)| {
+            let (updated_at, remaining, reset_at) = *samples.last()?;
+            let per_hour = consumption_per_hour(&samples)?;
+            let exhausts_at = exhaustion_time(updated_at, remaining, per_hour);
+            let account = labels
+                .entry((platform.clone(), account_id.clone()))
+                .or_insert_with(|| {
+                    AccountPlatform::parse(&platform)
+                        .and_then(|platform| data_store::account_label(platform, &account_id))
+                })
+                .clone();
+            Some(QuotaForecast {
+                exhausts_before_reset: match (exhausts_at, reset_at) {
+                    (Some(exhausts_at), Some(reset_at)) => exhausts_at < reset_at,
+                    (Some(_), None) => true,
+                    (None, _) => false,
+                },
+                platform,
+                account_id,
+                account,
+                meter,
+                remaining_percent: remaining,
+                consumption_per_hour: per_hour,
+                exhausts_at,
+                reset_at,
+                updated_at,
+            })
+        })
+        .collect();
+    forecasts.sort_by_key(|item| (item.exhausts_at.is_none(), item.exhausts_at));
+    Ok(forecasts)
+}
+
+/// 上次重置之后的样本做最小二乘拟合，返回每小时消耗的百分比（不为负）；
+/// 样本不足或跨度太短时返回 None
+fn consumption_per_hour(samples: &[(i64, f64, Option<i64>)]) -> Option<f64> {
+    let start = samples
+        .windows(2)
+        .rposition(|pair| pair[1].1 - pair[0].1 > RESET_JUMP_PERCENT)
+        .map(|index| index + 1)
+        .unwrap_or(0);
+    let window = &samples[start..];
+    let (first, last) = (window.first()?.0, window.last()?.0);
+    if window.len() < 2 || last - first < MIN_FORECAST_SPAN_SECS {
+        return None;
+    }
+
+    let count = window.len() as f64;
+    let mean_t = window.iter().map(|s| (s.0 - first) as f64).sum::<f64>() / count;
+    let mean_v = window.iter().map(|s| s.1).sum::<f64>() / count;
+    let (mut covariance, mut variance) = (0.0, 0.0);
+    for (recorded_at, remaining, _) in window {
+        let dt = (recorded_at - first) as f64 - mean_t;
+        covariance += dt * (remaining - mean_v);
+        variance += dt * dt;
+    }
+    if variance <= 0.0 {
+        return None;
+    }
+    let slope_per_sec = covariance / variance;
+    Some((-slope_per_sec * 3600.0).max(0.0))
+}
+
+fn exhaustion_time(updated_at: i64, remaining: f64, per_hour: f64) -> Option<i64> {
+    if per_hour <= 0.0 {
+        return None;
+    }
+    if remaining <= 0.0 {
+        return Some(updated_at);
+    }
+    Some(updated_at + (remaining / per_hour * 3600.0).round() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn steady_consumption_projects_exhaustion() {
+        let samples: Vec<_> = (0..5)
+            .map(|i| (i * 1800, 80.0 - 10.0 * i as f64, Some(10 * 3600)))
+            .collect();
+        let per_hour = consumption_per_hour(&samples).unwrap();
+        assert!((per_hour - 20.0).abs() < 1e-9);
+        // 最后一次 40%，每小时 20%，2 小时后耗尽
+        assert_eq!(
+            exhaustion_time(4 * 1800, 40.0, per_hour),
+            Some(4 * 1800 + 7200)
+        );
+    }
+
+    #[test]
+    fn samples_before_a_reset_are_ignored() {
+        let samples = vec![
+            (0, 30.0, None),
+            (1800, 10.0, None),
+            (3600, 100.0, None),
+            (5400, 100.0, None),
+        ];
+        assert_eq!(consumption_per_hour(&samples), Some(0.0));
+        assert_eq!(exhaustion_time(5400, 100.0, 0.0), None);
+        assert_eq!(consumption_per_hour(&samples[..1]), None);
+    }
+}
@@ -0,0 +1,665 @@
+//! 统一配额模型
+//!
+//! 各平台的配额原本各有各的结构：Antigravity 的 `QuotaData` 模型列表、Codex 的 5 小时/周窗口、
+//! Kiro 的 credits/bonus 总量与已用量、GitHub Copilot 与 Windsurf 的 `copilot_*` 配额快照 JSON。
+//! 这里统一换算成若干个命名的 `QuotaMeter`（已用量、总量、剩余百分比、重置时间、窗口时长），
+//! 每个平台的账号实现 `PlatformQuota`。预警、自动切号、配额历史、托盘和 WebSocket 都只读这一份结果。
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+use crate::models::codex::{CodexAccount, CodexQuota};
+use crate::models::github_copilot::GitHubCopilotAccount;
+use crate::models::kiro::KiroAccount;
+use crate::models::windsurf::WindsurfAccount;
+use crate::models::{Account, QuotaData};
+use crate::modules::data_store::AccountPlatform;
+use crate::modules::{
+    account, codex_account, github_copilot_account, kiro_account, windsurf_account,
+};
+
+/// Copilot 免费受限账号未下发对话总量时按 500 次计算
+const FREE_LIMITED_CHAT_TOTAL: f64 = 500.0;
+
+/// 单个配额项
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QuotaMeter {
+    pub name: String,
+    pub used: Option<f64>,
+    pub limit: Option<f64>,
+    /// 剩余百分比 0-100
+    pub remaining_percent: f64,
+    /// 重置时间（秒）
+    pub reset_at: Option<i64>,
+    /// 配额窗口时长（分钟）
+    pub window_minutes: Option<i64>,
+}
+
+impl QuotaMeter {
+    /// 只有剩余百分比的配额项
+    pub fn percent(name: impl Into<String>, remaining_percent: f64, reset_at: Option<i64>) -> Self {
+        Self {
+            name: name.into(),
+            used: None,
+            limit: None,
+            remaining_percent: remaining_percent.clamp(0.0, 100.0),
+            reset_at,
+            window_minutes: None,
+        }
+    }
+
+    /// 按已用量与总量计算，总量无效时返回 None
+    pub fn usage(
+        name: impl Into<String>,
+        used: Option<f64>,
+        limit: Option<f64>,
+        reset_at: Option<i64>,
+    ) -> Option<Self> {
+        let total = limit.filter(|value| value.is_finite() && *value > 0.0)?;
+        let spent = match used {
+            Some(value) if !value.is_finite() => return None,
+            Some(value) => value,
+            None => 0.0,
+        };
+        let remaining = (total - spent).max(0.0) / total * 100.0;
+        Some(Self {
+            used,
+            limit: Some(total),
+            ..Self::percent(name, remaining, reset_at)
+        })
+    }
+
+    fn with_window(mut self, window_minutes: Option<i64>) -> Self {
+        self.window_minutes = window_minutes;
+        self
+    }
+
+    /// 取整后的剩余百分比，预警与切号阈值按此比较
+    pub fn percentage(&self) -> i32 {
+        self.remaining_percent.round() as i32
+    }
+
+    /// 已过重置时间的配额项视为已恢复
+    pub fn effective_percentage(&self, now: i64) -> i32 {
+        match self.reset_at {
+            Some(reset_at) if reset_at <= now => 100,
+            _ => self.percentage(),
+        }
+    }
+}
+
+/// 各平台账号的配额
+pub trait PlatformQuota {
+    fn quota_meters(&self) -> Vec<QuotaMeter>;
+}
+
+/// (名称, 剩余百分比)，供自动切号选号使用
+pub fn metrics(source: &impl PlatformQuota) -> Vec<(String, i32)> {
+    source
+        .quota_meters()
+        .into_iter()
+        .map(|meter| {
+            let percentage = meter.percentage();
+            (meter.name, percentage)
+        })
+        .collect()
+}
+
+/// 计入重置时间后的 (名称, 剩余百分比)
+pub fn effective_metrics(source: &impl PlatformQuota, now: i64) -> Vec<(String, i32)> {
+    source
+        .quota_meters()
+        .into_iter()
+        .map(|meter| {
+            let percentage = meter.effective_percentage(now);
+            (meter.name, percentage)
+        })
+        .collect()
+}
+
+/// 剩余百分比不高于阈值的配额项
+pub fn low_metrics(source: &impl PlatformQuota, threshold: i32) -> Vec<(String, i32)> {
+    metrics(source)
+        .into_iter()
+        .filter(|(_, pct)| *pct <= threshold)
+        .collect()
+}
+
+pub fn average_percentage(metrics: &[(String, i32)]) -> f64 {
+    if metrics.is_empty() {
+        return 0.0;
+    }
+    let sum: i32 = metrics.iter().map(|(_, pct)| *pct).sum();
+    sum as f64 / metrics.len() as f64
+}
+
+fn parse_rfc3339(value: &str) -> Option<i64> {
+    chrono::DateTime::parse_from_rfc3339(value.trim())
+        .ok()
+        .map(|value| value.timestamp())
+}
+
+impl PlatformQuota for QuotaData {
+    fn quota_meters(&self) -> Vec<QuotaMeter> {
+        self.models
+            .iter()
+            .map(|model| {
+                QuotaMeter::percent(
+                    &model.name,
+                    model.percentage as f64,
+                    parse_rfc3339(&model.reset_time),
+                )
+            })
+            .collect()
+    }
+}
+
+impl PlatformQuota for Account {
+    fn quota_meters(&self) -> Vec<QuotaMeter> {
+        self.quota
+            .as_ref()
+            .map(PlatformQuota::quota_meters)
+            .unwrap_or_default()
+    }
+}
+
+/// Codex 窗口标签：按窗口时长显示为 5h / Weekly 等
+pub fn codex_window_label(window_minutes: Option<i64>, fallback: &str) -> String {
+    const HOUR_MINUTES: i64 = 60;
+    const DAY_MINUTES: i64 = 24 * HOUR_MINUTES;
+    const WEEK_MINUTES: i64 = 7 * DAY_MINUTES;
+
+    let Some(minutes) = window_minutes.filter(|value| *value > 0) else {
+        return fallback.to_string();
+    };
+
+    if minutes >= WEEK_MINUTES - 1 {
+        let weeks = (minutes + WEEK_MINUTES - 1) / WEEK_MINUTES;
+        return if weeks <= 1 {
+            "Weekly".to_string()
+        } else {
+            format!("{} Week", weeks)
+        };
+    }
+
+    if minutes >= DAY_MINUTES - 1 {
+        let days = (minutes + DAY_MINUTES - 1) / DAY_MINUTES;
+        return format!("{}d", days);
+    }
+
+    if minutes >= HOUR_MINUTES {
+        let hours = (minutes + HOUR_MINUTES - 1) / HOUR_MINUTES;
+        return format!("{}h", hours);
+    }
+
+    format!("{}m", minutes)
+}
+
+impl PlatformQuota for CodexQuota {
+    /// 接口标明窗口存在时只取存在的窗口；都没有时至少保留主窗口
+    fn quota_meters(&self) -> Vec<QuotaMeter> {
+        let hourly = || {
+            QuotaMeter::percent(
+                codex_window_label(self.hourly_window_minutes, "5h"),
+                self.hourly_percentage as f64,
+                self.hourly_reset_time,
+            )
+            .with_window(self.hourly_window_minutes)
+        };
+        let has_presence =
+            self.hourly_window_present.is_some() || self.weekly_window_present.is_some();
+        let mut meters = Vec::new();
+
+        if !has_presence || self.hourly_window_present.unwrap_or(false) {
+            meters.push(hourly());
+        }
+        if !has_presence || self.weekly_window_present.unwrap_or(false) {
+            meters.push(
+                QuotaMeter::percent(
+                    codex_window_label(self.weekly_window_minutes, "Weekly"),
+                    self.weekly_percentage as f64,
+                    self.weekly_reset_time,
+                )
+                .with_window(self.weekly_window_minutes),
+            );
+        }
+        if meters.is_empty() {
+            meters.push(hourly());
+        }
+        meters
+    }
+}
+
+impl PlatformQuota for CodexAccount {
+    fn quota_meters(&self) -> Vec<QuotaMeter> {
+        self.quota
+            .as_ref()
+            .map(PlatformQuota::quota_meters)
+            .unwrap_or_default()
+    }
+}
+
+impl PlatformQuota for KiroAccount {
+    fn quota_meters(&self) -> Vec<QuotaMeter> {
+        [
+            QuotaMeter::usage(
+                "Prompt Credits",
+                self.credits_used,
+                self.credits_total,
+                self.usage_reset_at,
+            ),
+            QuotaMeter::usage("Add-on Credits", self.bonus_used, self.bonus_total, None),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+}
+
+/// GitHub Copilot 与 Windsurf 共用的 `copilot_*` 配额字段
+struct CopilotQuotaFields<'a> {
+    token: &'a str,
+    plan: Option<&'a str>,
+    limited_quotas: Option<&'a JsonValue>,
+    quota_snapshots: Option<&'a JsonValue>,
+    limited_reset_date: Option<i64>,
+    quota_reset_date: Option<&'a str>,
+}
+
+fn parse_token_map(token: &str) -> HashMap<String, String> {
+    let mut map = HashMap::new();
+    let prefix = token.split(':').next().unwrap_or(token);
+    for item in prefix.split(';') {
+        let mut parts = item.splitn(2, '=');
+        let key = parts.next().unwrap_or("").trim();
+        if key.is_empty() {
+            continue;
+        }
+        let value = parts.next().unwrap_or("").trim();
+        map.insert(key.to_string(), value.to_string());
+    }
+    map
+}
+
+fn parse_token_number(map: &HashMap<String, String>, key: &str) -> Option<f64> {
+    map.get(key)
+        .and_then(|value| value.split(':').next())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .and_then(|value| value.parse::<f64>().ok())
+        .filter(|value| value.is_finite())
+}
+
+fn parse_json_number(value: &JsonValue) -> Option<f64> {
+    match value {
+        JsonValue::Number(num) => num.as_f64(),
+        JsonValue::String(text) => text.trim().parse::<f64>().ok(),
+        _ => None,
+    }
+    .filter(|value| value.is_finite())
+}
+
+/// 受限账号按剩余量与总量计算（总量取令牌中的 cq / tq），付费账号取 premium 快照的剩余百分比
+fn copilot_meters(fields: CopilotQuotaFields, labels: [&str; 2]) -> Vec<QuotaMeter> {
+    let token_map = parse_token_map(fields.token);
+    let reset_at = fields
+        .limited_reset_date
+        .or_else(|| fields.quota_reset_date.and_then(parse_rfc3339))
+        .or_else(|| {
+            parse_token_number(&token_map, "rd")
+                .map(|value| value.floor() as i64)
+                .filter(|value| *value > 0)
+        });
+    let sku = token_map
+        .get("sku")
+        .map(|value| value.to_lowercase())
+        .unwrap_or_default();
+    let is_free_limited = sku.contains("free_limited")
+        || sku.contains("no_auth_limited")
+        || fields
+            .plan
+            .is_some_and(|value| value.to_lowercase().contains("free_limited"));
+
+    let mut meters = Vec::new();
+    let limited = fields.limited_quotas.and_then(JsonValue::as_object);
+    let remaining_of = |key: &str| {
+        limited
+            .and_then(|obj| obj.get(key))
+            .and_then(parse_json_number)
+    };
+
+    if let Some(remaining) = remaining_of("completions") {
+        let total = parse_token_number(&token_map, "cq").unwrap_or(remaining);
+        meters.extend(QuotaMeter::usage(
+            labels[0],
+            Some(total - remaining.max(0.0)),
+            Some(total),
+            reset_at,
+        ));
+    }
+    if let Some(remaining) = remaining_of("chat") {
+        let total = parse_token_number(&token_map, "tq").unwrap_or(if is_free_limited {
+            FREE_LIMITED_CHAT_TOTAL
+        } else {
+            remaining
+        });
+        meters.extend(QuotaMeter::usage(
+            labels[1],
+            Some(total - remaining.max(0.0)),
+            Some(total),
+            reset_at,
+        ));
+    }
+
+    let premium = fields
+        .quota_snapshots
+        .and_then(JsonValue::as_object)
+        .and_then(|snapshots| {
+            snapshots
+                .get("premium_interactions")
+                .or_else(|| snapshots.get("premium_models"))
+        })
+        .and_then(JsonValue::as_object);
+    if let Some(premium) = premium {
+        if premium.get("unlimited").and_then(JsonValue::as_bool) == Some(true) {
+            meters.push(QuotaMeter::percent("Premium Interactions", 100.0, reset_at));
+        } else if let Some(remaining) = premium.get("percent_remaining").and_then(parse_json_number)
+        {
+            meters.push(QuotaMeter::percent(
+                "Premium Interactions",
+                remaining,
+                reset_at,
+            ));
+        }
+    }
+
+    meters
+}
+
+impl PlatformQuota for GitHubCopilotAccount {
+    fn quota_meters(&self) -> Vec<QuotaMeter> {
+        copilot_meters(
+            CopilotQuotaFields {
+                token: &self.copilot_token,
+                plan: self.copilot_plan.as_deref(),
+                limited_quotas: self.copilot_limited_user_quotas.as_ref(),
+                quota_snapshots: self.copilot_quota_snapshots.as_ref(),
+                limited_reset_date: self.copilot_limited_user_reset_date,
+                quota_reset_date: self.copilot_quota_reset_date.as_deref(),
+            },
+            ["Inline Suggestions", "Chat Messages"],
+        )
+    }
+}
+
+fn json_path<'a>(root: Option<&'a JsonValue>, path: &[&str]) -> Option<&'a JsonValue> {
+    let mut current = root?;
+    for key in path {
+        current = current.as_object()?.get(*key)?;
+    }
+    Some(current)
+}
+
+fn parse_timestamp_number(raw: f64) -> Option<i64> {
+    if !raw.is_finite() || raw <= 0.0 {
+        return None;
+    }
+    if raw > 1e12 {
+        return Some((raw / 1000.0).floor() as i64);
+    }
+    Some(raw.floor() as i64)
+}
+
+fn parse_timestamp_like(value: &JsonValue) -> Option<i64> {
+    match value {
+        JsonValue::Number(num) => parse_timestamp_number(num.as_f64()?),
+        JsonValue::String(text) => {
+            let trimmed = text.trim();
+            if trimmed.is_empty() {
+                return None;
+            }
+            if let Ok(n) = trimmed.parse::<f64>() {
+                return parse_timestamp_number(n);
+            }
+            parse_rfc3339(trimmed)
+        }
+        JsonValue::Object(obj) => {
+            if let Some(seconds) = obj.get("seconds").and_then(JsonValue::as_i64) {
+                return Some(seconds);
+            }
+            if let Some(seconds) = obj.get("unixSeconds").and_then(JsonValue::as_i64) {
+                return Some(seconds);
+            }
+            obj.get("value").and_then(parse_timestamp_like)
+        }
+        _ => None,
+    }
+}
+
+/// Windsurf 套餐结束时间，配额快照没有重置时间时作为重置时间
+fn windsurf_plan_end(account: &WindsurfAccount) -> Option<i64> {
+    let user_status = account.windsurf_user_status.as_ref();
+    let snapshots = account.copilot_quota_snapshots.as_ref();
+    let candidates: [(Option<&JsonValue>, &[&str]); 5] = [
+        (user_status, &["userStatus", "planStatus"]),
+        (user_status, &["planStatus"]),
+        (snapshots, &["windsurfPlanStatus"]),
+        (snapshots, &["windsurfPlanStatus", "planStatus"]),
+        (
+            snapshots,
+            &["windsurfUserStatus", "userStatus", "planStatus"],
+        ),
+    ];
+
+    candidates.into_iter().find_map(|(root, path)| {
+        let status = json_path(root, path)?;
+        ["planEnd", "plan_end"]
+            .into_iter()
+            .find_map(|key| status.get(key).and_then(parse_timestamp_like))
+    })
+}
+
+impl PlatformQuota for WindsurfAccount {
+    fn quota_meters(&self) -> Vec<QuotaMeter> {
+        let mut meters = copilot_meters(
+            CopilotQuotaFields {
+                token: &self.copilot_token,
+                plan: self.copilot_plan.as_deref(),
+                limited_quotas: self.copilot_limited_user_quotas.as_ref(),
+                quota_snapshots: self.copilot_quota_snapshots.as_ref(),
+                limited_reset_date: self.copilot_limited_user_reset_date,
+                quota_reset_date: self.copilot_quota_reset_date.as_deref(),
+            },
+            ["Prompt Credits", "Flow Action Credits"],
+        );
+        if meters.iter().any(|meter| meter.reset_at.is_none()) {
+            let plan_end = windsurf_plan_end(self);
+            for meter in meters.iter_mut().filter(|meter| meter.reset_at.is_none()) {
+                meter.reset_at = plan_end;
+            }
+        }
+        meters
+    }
+}
+
+/// 单个账号的配额（WebSocket 等外部使用方）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountQuota {
+    pub platform: String,
+    pub account_id: String,
+    /// 账号邮箱或登录名
+    pub account: String,
+    pub meters: Vec<QuotaMeter>,
+}
+
+fn account_quotas<T: PlatformQuota>(
+    platform: AccountPlatform,
+    accounts: Vec<T>,
+    describe: impl Fn(&T) -> (String, String),
+) -> Vec<AccountQuota> {
+    accounts
+        .iter()
+        .map(|item| {
+            let (account_id, account) = describe(item);
+            AccountQuota {
+                platform: platform.as_str().to_string(),
+                account_id,
+                account,
+                meters: item.quota_meters(),
+            }
+        })
+        .collect()
+}
+
+fn login_label(email: Option<&str>, login: &str) -> String {
+    email
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(login)
+        .to_string()
+}
+
+/// 列出全部账号（或指定平台）的配额
+pub fn list(platform: Option<AccountPlatform>) -> Result<Vec<AccountQuota>, String> {
+    let wanted = |candidate: AccountPlatform| platform.is_none() || platform == Some(candidate);
+    let mut result = Vec::new();
+    if wanted(AccountPlatform::Antigravity) {
+        result.extend(account_quotas(
+            AccountPlatform::Antigravity,
+            account::list_accounts()?,
+            |item| (item.id.clone(), item.email.clone()),
+        ));
+    }
+    if wanted(AccountPlatform::Codex) {
+        result.extend(account_quotas(
+            AccountPlatform::Codex,
+            codex_account::list_accounts(),
+            |item| (item.id.clone(), item.email.clone()),
+        ));
+    }
+    if wanted(AccountPlatform::GitHubCopilot) {
+        result.extend(account_quotas(
+            AccountPlatform::GitHubCopilot,
+            github_copilot_account::list_accounts(),
+            |item| {
+                let label = login_label(item.github_email.as_deref(), &item.github_login);
+                (item.id.clone(), label)
+            },
+        ));
+    }
+    if wanted(AccountPlatform::Windsurf) {
+        result.extend(account_quotas(
+            AccountPlatform::Windsurf,
+            windsurf_account::list_accounts(),
+            |item| {
+                let label = login_label(item.github_email.as_deref(), &item.github_login);
+                (item.id.clone(), label)
+            },
+        ));
+    }
+    if wanted(AccountPlatform::Kiro) {
+        result.extend(account_quotas(
+            AccountPlatform::Kiro,
+            kiro_account::list_accounts(),
+            |item| (item.id.clone(), item.email.clone()),
+        ));
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn fields<'a>(
+        token: &'a str,
+        limited: Option<&'a JsonValue>,
+        snapshots: Option<&'a JsonValue>,
+    ) -> CopilotQuotaFields<'a> {
+        CopilotQuotaFields {
+            token,
+            plan: None,
+            limited_quotas: limited,
+            quota_snapshots: snapshots,
+            limited_reset_date: None,
+            quota_reset_date: None,
+        }
+    }
+
+    #[test]
+    fn limited_copilot_quota_uses_token_totals() {
+        let limited = json!({ "completions": 500, "chat": "10" });
+        let meters = copilot_meters(
+            fields(
+                "tid=1;cq=2000;tq=50;rd=1760000000:sig",
+                Some(&limited),
+                None,
+            ),
+            ["Inline", "Chat"],
+        );
+        assert_eq!(meters.len(), 2);
+        assert_eq!(meters[0].name, "Inline");
+        assert_eq!(meters[0].percentage(), 25);
+        assert_eq!(meters[0].used, Some(1500.0));
+        assert_eq!(meters[1].percentage(), 20);
+        assert_eq!(meters[1].reset_at, Some(1_760_000_000));
+    }
+
+    #[test]
+    fn free_limited_chat_defaults_to_known_total() {
+        let limited = json!({ "chat": 250 });
+        let meters = copilot_meters(
+            fields("sku=free_limited_copilot", Some(&limited), None),
+            ["Inline", "Chat"],
+        );
+        assert_eq!(meters.len(), 1);
+        assert_eq!(meters[0].percentage(), 50);
+    }
+
+    #[test]
+    fn premium_snapshot_reports_remaining_percent() {
+        let snapshots = json!({ "premium_interactions": { "percent_remaining": 62.4 } });
+        let meters = copilot_meters(fields("", None, Some(&snapshots)), ["Inline", "Chat"]);
+        assert_eq!(meters.len(), 1);
+        assert_eq!(meters[0].name, "Premium Interactions");
+        assert_eq!(meters[0].percentage(), 62);
+
+        let unlimited = json!({ "premium_models": { "unlimited": true } });
+        let meters = copilot_meters(fields("", None, Some(&unlimited)), ["Inline", "Chat"]);
+        assert_eq!(meters[0].percentage(), 100);
+    }
+
+    #[test]
+    fn codex_meters_follow_window_presence() {
+        let quota = CodexQuota {
+            hourly_percentage: 40,
+            hourly_reset_time: Some(100),
+            hourly_window_minutes: Some(300),
+            hourly_window_present: Some(false),
+            weekly_percentage: 70,
+            weekly_reset_time: Some(200),
+            weekly_window_minutes: Some(7 * 24 * 60),
+            weekly_window_present: Some(true),
+            raw_data: None,
+        };
+        let meters = quota.quota_meters();
+        assert_eq!(meters.len(), 1);
+        assert_eq!(meters[0].name, "Weekly");
+        assert_eq!(meters[0].percentage(), 70);
+        assert_eq!(meters[0].effective_percentage(200), 100);
+        assert_eq!(meters[0].effective_percentage(199), 70);
+    }
+
+    #[test]
+    fn usage_meter_rejects_missing_total() {
+        assert!(QuotaMeter::usage("Prompt Credits", Some(1.0), None, None).is_none());
+        assert!(QuotaMeter::usage("Prompt Credits", Some(1.0), Some(0.0), None).is_none());
+        let meter = QuotaMeter::usage("Prompt Credits", Some(150.0), Some(100.0), None).unwrap();
+        assert_eq!(meter.percentage(), 0);
+    }
+}
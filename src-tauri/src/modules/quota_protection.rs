@@ -0,0 +1,214 @@
+//! 配额保留策略
+//!
+//! 当模型剩余配额低于保留阈值时，将其加入账号的 `protected_models`，
+//! 唤醒任务、自动切号评分与本地 API 代理都会跳过受保护的模型，
+//! 把最后一部分配额留给手动使用。模型的 `reset_time` 过后自动解除保护。
+//! 策略加入的模型另记在 `reserve_protected_models` 中，解除时只移除这些，不影响手动保护的模型；
+//! 手动保护的模型不受策略开关影响，始终生效。
+
+use chrono::{DateTime, Utc};
+
+use crate::models::Account;
+use crate::modules;
+
+/// 读取当前配置的保留阈值，未启用时返回 None
+pub fn configured_reserve() -> Option<i32> {
+    let cfg = modules::config::get_user_config();
+    if !cfg.quota_protection_enabled {
+        return None;
+    }
+    Some(cfg.quota_protection_reserve.clamp(0, 100))
+}
+
+/// 保留策略是否启用
+pub fn reserve_enabled() -> bool {
+    modules::config::get_user_config().quota_protection_enabled
+}
+
+fn reset_time_passed(reset_time: &str, now: DateTime<Utc>) -> bool {
+    DateTime::parse_from_rfc3339(reset_time.trim())
+        .map(|reset| reset.with_timezone(&Utc) <= now)
+        .unwrap_or(false)
+}
+
+/// 解除策略加入的保护
+fn release(account: &mut Account, model: &str) {
+    if account.reserve_protected_models.remove(model) {
+        account.protected_models.remove(model);
+    }
+}
+
+/// 根据最新配额更新账号的受保护模型列表，返回列表是否发生变化
+pub fn apply_policy(account: &mut Account, reserve: Option<i32>, now: DateTime<Utc>) -> bool {
+    let before = account.protected_models.clone();
+    let Some(reserve) = reserve else {
+        let managed: Vec<String> = account.reserve_protected_models.iter().cloned().collect();
+        for name in managed {
+            release(account, &name);
+        }
+        return account.protected_models != before;
+    };
+
+    let Some(quota) = account.quota.clone() else {
+        return false;
+    };
+    if quota.models.is_empty() {
+        return false;
+    }
+
+    // 已不在配额列表中的模型无法判断何时重置，直接解除保护
+    let stale: Vec<String> = account
+        .reserve_protected_models
+        .iter()
+        .filter(|name| !quota.models.iter().any(|m| &m.name == *name))
+        .cloned()
+        .collect();
+    for name in stale {
+        release(account, &name);
+    }
+
+    for model in &quota.models {
+        if model.percentage < reserve && !reset_time_passed(&model.reset_time, now) {
+            // 已手动保护的模型不记为策略加入
+            if account.protected_models.insert(model.name.clone()) {
+                account.reserve_protected_models.insert(model.name.clone());
+            }
+        } else {
+            release(account, &model.name);
+        }
+    }
+
+    account.protected_models != before
+}
+
+fn is_protected_at(
+    account: &Account,
+    model: &str,
+    reserve_enabled: bool,
+    now: DateTime<Utc>,
+) -> bool {
+    if !account.protected_models.contains(model) {
+        return false;
+    }
+    // 手动保护的模型始终生效，策略加入的模型只在策略启用时生效
+    if !account.reserve_protected_models.contains(model) {
+        return true;
+    }
+    if !reserve_enabled {
+        return false;
+    }
+    // 配额刷新之前，重置时间已过的模型视为已释放
+    let reset_passed = account
+        .quota
+        .as_ref()
+        .and_then(|q| q.models.iter().find(|m| m.name == model))
+        .map(|m| reset_time_passed(&m.reset_time, now))
+        .unwrap_or(false);
+    !reset_passed
+}
+
+/// 判断账号的指定模型当前是否处于保留状态
+///
+/// `reserve_enabled` 为 `reserve_enabled()` 的结果，由调用方每次选号读取一次后传入，避免逐个模型读取配置。
+pub fn is_model_protected(account: &Account, model: &str, reserve_enabled: bool) -> bool {
+    is_protected_at(account, model, reserve_enabled, Utc::now())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    use crate::models::{QuotaData, TokenData};
+
+    fn account_with(models: &[(&str, i32, &str)]) -> Account {
+        let token = TokenData::new(
+            "access".to_string(),
+            "refresh".to_string(),
+            3600,
+            None,
+            None,
+            None,
+        );
+        let mut account = Account::new("id".to_string(), "a@example.com".to_string(), token);
+        let mut quota = QuotaData::new();
+        for (name, pct, reset) in models {
+            quota.add_model(name.to_string(), None, *pct, reset.to_string());
+        }
+        account.quota = Some(quota);
+        account
+    }
+
+    fn at(ts: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(ts)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn protects_models_below_reserve_until_reset() {
+        let mut account = account_with(&[
+            ("gemini-3-pro-high", 3, "2026-01-02T00:00:00Z"),
+            ("claude-sonnet-4-5", 40, "2026-01-02T00:00:00Z"),
+        ]);
+        let now = at("2026-01-01T12:00:00Z");
+
+        assert!(apply_policy(&mut account, Some(5), now));
+        assert!(account.protected_models.contains("gemini-3-pro-high"));
+        assert!(!account.protected_models.contains("claude-sonnet-4-5"));
+        assert!(is_protected_at(&account, "gemini-3-pro-high", true, now));
+        assert!(!apply_policy(&mut account, Some(5), now));
+
+        let after_reset = at("2026-01-02T00:00:01Z");
+        assert!(!is_protected_at(
+            &account,
+            "gemini-3-pro-high",
+            true,
+            after_reset
+        ));
+        assert!(apply_policy(&mut account, Some(5), after_reset));
+        assert!(account.protected_models.is_empty());
+    }
+
+    #[test]
+    fn releases_models_when_quota_recovers_or_policy_disabled() {
+        let mut account = account_with(&[("gemini-3-flash", 2, "2026-01-02T00:00:00Z")]);
+        let now = at("2026-01-01T12:00:00Z");
+        apply_policy(&mut account, Some(5), now);
+        assert_eq!(account.protected_models.len(), 1);
+
+        assert!(apply_policy(&mut account, None, now));
+        assert!(account.protected_models.is_empty());
+        assert!(account.reserve_protected_models.is_empty());
+
+        apply_policy(&mut account, Some(5), now);
+        account.quota.as_mut().unwrap().models[0].percentage = 100;
+        assert!(apply_policy(&mut account, Some(5), now));
+        assert!(account.protected_models.is_empty());
+    }
+
+    #[test]
+    fn manually_protected_models_are_left_alone() {
+        let mut account = account_with(&[
+            ("gemini-3-flash", 2, "2026-01-02T00:00:00Z"),
+            ("claude-sonnet-4-5", 90, "2026-01-02T00:00:00Z"),
+        ]);
+        account.protected_models = HashSet::from([
+            "gemini-3-flash".to_string(),
+            "claude-sonnet-4-5".to_string(),
+        ]);
+        let now = at("2026-01-01T12:00:00Z");
+
+        assert!(!apply_policy(&mut account, Some(5), now));
+        assert!(account.reserve_protected_models.is_empty());
+        assert!(!apply_policy(&mut account, None, now));
+        assert_eq!(account.protected_models.len(), 2);
+        // 策略关闭时手动保护仍然生效
+        assert!(is_protected_at(
+            &account,
+            "claude-sonnet-4-5",
+            false,
+            at("2026-01-03T00:00:00Z")
+        ));
+    }
+}
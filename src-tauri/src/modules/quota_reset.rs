@@ -0,0 +1,272 @@
+//! 配额恢复通知与重置日历
+//!
+//! 每次刷新写入配额历史时（见 `quota_history::record`）与该账号上一次的样本比较：此前处于预警阈值以下的
+//! 配额项回升到阈值以上，说明已经重置，通过 `dispatch_quota_alert` 发送「配额已恢复」通知，
+//! 是否通知与阈值沿用各平台的配额预警设置。另外可以把全部账号即将到来的重置时间导出为
+//! iCalendar (.ics) 文件，导入日历后按重置时间安排使用。
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::modules::account::{self, QuotaAlertKind, QuotaAlertPayload};
+use crate::modules::data_store::{self, AccountPlatform};
+use crate::modules::quota_meter::{self, QuotaMeter};
+use crate::modules::{atomic_file, config, logger};
+
+/// 配额项至少回升该百分比才视为已恢复，避免阈值附近的波动反复通知
+const REPLENISHED_MIN_RISE: f64 = 20.0;
+/// 日历事件时长（分钟）
+const EVENT_DURATION_MINUTES: i64 = 15;
+/// iCalendar 单行最大字节数，超出需折行
+const ICS_LINE_LIMIT: usize = 75;
+
+/// 即将到来的一次重置（同一账号同一时间重置的配额项合并为一条）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UpcomingReset {
+    pub platform: String,
+    pub account_id: String,
+    /// 账号邮箱或登录名
+    pub account: String,
+    /// 重置时间（秒）
+    pub reset_at: i64,
+    pub meters: Vec<QuotaMeter>,
+}
+
+/// 平台的配额预警开关与阈值
+fn alert_policy(platform: AccountPlatform) -> (bool, i32) {
+    let cfg = config::get_user_config();
+    let (enabled, threshold) = match platform {
+        AccountPlatform::Antigravity => (cfg.quota_alert_enabled, cfg.quota_alert_threshold),
+        AccountPlatform::Codex => (
+            cfg.codex_quota_alert_enabled,
+            cfg.codex_quota_alert_threshold,
+        ),
+        AccountPlatform::GitHubCopilot => {
+            (cfg.ghcp_quota_alert_enabled, cfg.ghcp_quota_alert_threshold)
+        }
+        AccountPlatform::Windsurf => (
+            cfg.windsurf_quota_alert_enabled,
+            cfg.windsurf_quota_alert_threshold,
+        ),
+        AccountPlatform::Kiro => (cfg.kiro_quota_alert_enabled, cfg.kiro_quota_alert_threshold),
+    };
+    (enabled, threshold.clamp(0, 100))
+}
+
+/// 比较刷新前后的剩余百分比（`previous` 为上一次样本，按配额项名称索引），有配额项恢复时发送通知
+pub fn notify_if_replenished(
+    platform: AccountPlatform,
+    account_id: &str,
+    previous: &HashMap<String, f64>,
+    meters: &[QuotaMeter],
+) {
+    if previous.is_empty() {
+        return;
+    }
+    let (enabled, threshold) = alert_policy(platform);
+    if !enabled {
+        return;
+    }
+    let replenished = replenished_meters(previous, meters, threshold);
+    if replenished.is_empty() {
+        return;
+    }
+
+    let payload = QuotaAlertPayload {
+        platform: platform.as_str().to_string(),
+        kind: QuotaAlertKind::Replenished,
+        current_account_id: account_id.to_string(),
+        current_email: data_store::account_label(platform, account_id)
+            .unwrap_or_else(|| account_id.to_string()),
+        threshold,
+        lowest_percentage: meters
+            .iter()
+            .map(QuotaMeter::percentage)
+            .min()
+            .unwrap_or(100),
+        low_models: replenished,
+        recommended_account_id: None,
+        recommended_email: None,
+        triggered_at: chrono::Utc::now().timestamp(),
+    };
+    account::dispatch_quota_alert(&payload);
+}
+
+fn replenished_meters(
+    previous: &HashMap<String, f64>,
+    meters: &[QuotaMeter],
+    threshold: i32,
+) -> Vec<String> {
+    let threshold = f64::from(threshold);
+    meters
+        .iter()
+        .filter(|meter| {
+            previous.get(&meter.name).is_some_and(|before| {
+                *before <= threshold
+                    && meter.remaining_percent > threshold
+                    && meter.remaining_percent - before >= REPLENISHED_MIN_RISE
+            })
+        })
+        .map(|meter| meter.name.clone())
+        .collect()
+}
+
+/// 全部账号（或指定平台）即将到来的重置，按时间先后排列
+pub fn upcoming(platform: Option<AccountPlatform>) -> Result<Vec<UpcomingReset>, String> {
+    let now = chrono::Utc::now().timestamp();
+    let mut resets = Vec::new();
+    for quota in quota_meter::list(platform)? {
+        let mut grouped: BTreeMap<i64, Vec<QuotaMeter>> = BTreeMap::new();
+        for meter in quota.meters {
+            if let Some(reset_at) = meter.reset_at.filter(|reset_at| *reset_at > now) {
+                grouped.entry(reset_at).or_default().push(meter);
+            }
+        }
+        resets.extend(grouped.into_iter().map(|(reset_at, meters)| UpcomingReset {
+            platform: quota.platform.clone(),
+            account_id: quota.account_id.clone(),
+            account: quota.account.clone(),
+            reset_at,
+            meters,
+        }));
+    }
+    resets.sort_by_key(|item| item.reset_at);
+    Ok(resets)
+}
+
+/// 导出即将到来的重置为 iCalendar 文件，返回事件数
+pub fn export_calendar(target: &Path) -> Result<usize, String> {
+    let resets = upcoming(None)?;
+    atomic_file::replace(target, to_ics(&resets, chrono::Utc::now().timestamp()))?;
+    logger::log_info(&format!(
+        "[QuotaReset] 已导出 {} 个重置事件: {}",
+        resets.len(),
+        target.display()
+    ));
+    Ok(resets.len())
+}
+
+fn to_ics(resets: &[UpcomingReset], now: i64) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Cockpit Tools//Quota Resets//ZH".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{}", escape_text("配额重置")),
+    ];
+    for reset in resets {
+        let platform = account::platform_display_name(&reset.platform);
+        let description = reset
+            .meters
+            .iter()
+            .map(|meter| format!("{}: {}%", meter.name, meter.percentage()))
+            .collect::<Vec<_>>()
+            .join("\n");
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!(
+                "UID:{}-{}-{}@cockpit-tools",
+                reset.platform, reset.account_id, reset.reset_at
+            ),
+            format!("DTSTAMP:{}", ics_time(now)),
+            format!("DTSTART:{}", ics_time(reset.reset_at)),
+            format!(
+                "DTEND:{}",
+                ics_time(reset.reset_at + EVENT_DURATION_MINUTES * 60)
+            ),
+            format!(
+                "SUMMARY:{}",
+                escape_text(&format!("{} 配额重置：{}", platform, reset.account))
+            ),
+            format!("DESCRIPTION:{}", escape_text(&description)),
+            "TRANSP:TRANSPARENT".to_string(),
+            "END:VEVENT".to_string(),
+        ]);
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut ics = String::new();
+    for line in &lines {
+        ics.push_str(&fold_line(line));
+        ics.push_str("\r\n");
+    }
+    ics
+}
+
+fn ics_time(secs: i64) -> String {
+    chrono::DateTime::from_timestamp(secs, 0)
+        .map(|time| time.format("%Y%m%dT%H%M%SZ").to_string())
+        .unwrap_or_default()
+}
+
+fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\r', "")
+        .replace('\n', "\\n")
+}
+
+/// 按 RFC 5545 折行：超过 75 字节时换行并以空格开头续行，不拆开多字节字符
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut width = 0;
+    for ch in line.chars() {
+        if width + ch.len_utf8() > ICS_LINE_LIMIT {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(ch);
+        width += ch.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_meters_rising_above_threshold_are_replenished() {
+        let previous = HashMap::from([
+            ("a".to_string(), 5.0),
+            ("b".to_string(), 15.0),
+            ("c".to_string(), 60.0),
+        ]);
+        let meters = vec![
+            QuotaMeter::percent("a", 100.0, None),
+            QuotaMeter::percent("b", 25.0, None),
+            QuotaMeter::percent("c", 100.0, None),
+            QuotaMeter::percent("d", 100.0, None),
+        ];
+        assert_eq!(replenished_meters(&previous, &meters, 20), vec!["a"]);
+    }
+
+    #[test]
+    fn calendar_escapes_and_folds_lines() {
+        let resets = vec![UpcomingReset {
+            platform: "codex".to_string(),
+            account_id: "id-1".to_string(),
+            account: "a,b;c@example.com".to_string(),
+            reset_at: 1_767_225_600,
+            meters: vec![QuotaMeter::percent("Weekly", 12.0, Some(1_767_225_600))],
+        }];
+        let ics = to_ics(&resets, 1_767_139_200);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.contains("DTSTART:20260101T000000Z\r\n"));
+        assert!(ics.contains("DTEND:20260101T001500Z\r\n"));
+        assert!(ics.contains("SUMMARY:Codex 配额重置：a\\,b\\;c@example.com\r\n"));
+        assert!(ics.contains("DESCRIPTION:Weekly: 12%\r\n"));
+        assert!(ics.split("\r\n").all(|line| line.len() <= ICS_LINE_LIMIT));
+
+        let long = "x".repeat(200);
+        let folded = fold_line(&long);
+        assert_eq!(folded.replace("\r\n ", ""), long);
+    }
+}
@@ -0,0 +1,186 @@
+//! 按模型分组评估 Antigravity 配额
+//!
+//! 配置了关注分组后，自动切号与配额预警只看这些分组（分组内取最低剩余配额），
+//! 未关注模型的配额耗尽不再触发切号或预警；未配置时仍按单个模型评估。
+
+use crate::models::Account;
+use crate::modules::config::{self, WatchedGroup};
+use crate::modules::group_settings::{self, GroupSettings};
+
+/// 阈值用途
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WatchPurpose {
+    AutoSwitch,
+    QuotaAlert,
+}
+
+/// 解析后的关注分组
+#[derive(Debug, Clone)]
+pub struct WatchGroup {
+    pub id: String,
+    pub name: String,
+    pub models: Vec<String>,
+    pub threshold: i32,
+}
+
+/// 账号在某个关注分组下的剩余配额
+#[derive(Debug, Clone)]
+pub struct GroupQuota<'a> {
+    pub group: &'a WatchGroup,
+    pub percentage: i32,
+}
+
+fn resolve_groups(
+    watched: &[WatchedGroup],
+    settings: &GroupSettings,
+    purpose: WatchPurpose,
+    default_threshold: i32,
+) -> Vec<WatchGroup> {
+    let mut groups: Vec<WatchGroup> = Vec::new();
+    for item in watched {
+        let id = item.group_id.trim();
+        if id.is_empty() || groups.iter().any(|group| group.id == id) {
+            continue;
+        }
+        let mut models = settings.get_models_in_group(id);
+        if models.is_empty() {
+            continue;
+        }
+        models.sort();
+        let threshold = match purpose {
+            WatchPurpose::AutoSwitch => item.auto_switch_threshold,
+            WatchPurpose::QuotaAlert => item.quota_alert_threshold,
+        }
+        .unwrap_or(default_threshold)
+        .clamp(0, 100);
+        groups.push(WatchGroup {
+            id: id.to_string(),
+            name: settings.get_group_name(id),
+            models,
+            threshold,
+        });
+    }
+    groups
+}
+
+/// 读取关注分组；未配置或配置的分组都已不存在时返回 None（按单个模型评估）
+pub fn load_watch_groups(purpose: WatchPurpose, default_threshold: i32) -> Option<Vec<WatchGroup>> {
+    let watched = config::get_user_config().watched_groups;
+    if watched.is_empty() {
+        return None;
+    }
+    let groups = resolve_groups(
+        &watched,
+        &group_settings::load_group_settings(),
+        purpose,
+        default_threshold,
+    );
+    if groups.is_empty() {
+        return None;
+    }
+    Some(groups)
+}
+
+/// 计算账号在各关注分组的剩余配额（分组内最低值），`include` 用于过滤参与计算的模型；
+/// 账号配额中不含该分组模型时跳过该分组
+pub fn group_quotas<'a, F>(
+    account: &Account,
+    groups: &'a [WatchGroup],
+    include: F,
+) -> Vec<GroupQuota<'a>>
+where
+    F: Fn(&str) -> bool,
+{
+    let Some(quota) = account.quota.as_ref() else {
+        return Vec::new();
+    };
+    groups
+        .iter()
+        .filter_map(|group| {
+            quota
+                .models
+                .iter()
+                .filter(|model| group.models.contains(&model.name) && include(&model.name))
+                .map(|model| model.percentage)
+                .min()
+                .map(|percentage| GroupQuota { group, percentage })
+        })
+        .collect()
+}
+
+/// 低于或等于分组阈值的关注分组
+pub fn low_groups<'a>(account: &Account, groups: &'a [WatchGroup]) -> Vec<GroupQuota<'a>> {
+    group_quotas(account, groups, |_| true)
+        .into_iter()
+        .filter(|item| item.percentage <= item.group.threshold)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{QuotaData, TokenData};
+
+    fn account_with(models: &[(&str, i32)]) -> Account {
+        let token = TokenData::new(
+            "access".to_string(),
+            "refresh".to_string(),
+            3600,
+            None,
+            None,
+            None,
+        );
+        let mut account = Account::new("id".to_string(), "a@example.com".to_string(), token);
+        let mut quota = QuotaData::new();
+        for (name, pct) in models {
+            quota.add_model(name.to_string(), None, *pct, String::new());
+        }
+        account.quota = Some(quota);
+        account
+    }
+
+    fn settings() -> GroupSettings {
+        let mut settings = GroupSettings {
+            group_mappings: Default::default(),
+            ..GroupSettings::default()
+        };
+        settings.set_model_group("claude-sonnet-4-5", "claude");
+        settings.set_model_group("claude-opus-4-5-thinking", "claude");
+        settings.set_model_group("gemini-3-pro-image", "image");
+        settings
+    }
+
+    #[test]
+    fn only_watched_groups_trigger_with_per_group_thresholds() {
+        let watched = vec![
+            WatchedGroup {
+                group_id: "claude".to_string(),
+                auto_switch_threshold: Some(30),
+                quota_alert_threshold: None,
+            },
+            WatchedGroup {
+                group_id: "missing".to_string(),
+                auto_switch_threshold: None,
+                quota_alert_threshold: None,
+            },
+        ];
+        let groups = resolve_groups(&watched, &settings(), WatchPurpose::AutoSwitch, 5);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].threshold, 30);
+
+        let account = account_with(&[
+            ("claude-sonnet-4-5", 80),
+            ("claude-opus-4-5-thinking", 40),
+            ("gemini-3-pro-image", 0),
+        ]);
+        assert!(low_groups(&account, &groups).is_empty());
+        let quotas = group_quotas(&account, &groups, |_| true);
+        assert_eq!(quotas[0].percentage, 40);
+
+        let drained = account_with(&[("claude-sonnet-4-5", 80), ("claude-opus-4-5-thinking", 25)]);
+        assert_eq!(low_groups(&drained, &groups).len(), 1);
+
+        let alert_groups = resolve_groups(&watched, &settings(), WatchPurpose::QuotaAlert, 20);
+        assert_eq!(alert_groups[0].threshold, 20);
+    }
+}
@@ -0,0 +1,368 @@
+//! 存储快照与损坏恢复
+//!
+//! 数据库文档（账号索引、实例配置）在每次写入时由 `data_store` 保留快照；数据目录下的 JSON
+//! 存储（如指纹库）通过 `write_with_snapshots` 写入，快照保存在 `snapshots/<文件名>/`。
+//! 读取时报告文件损坏后，`recover` 先用最新的有效快照恢复，没有可用快照时从损坏内容中尽量
+//! 抢救完整的条目；损坏的原内容始终会改名保留。
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::modules::data_store::{self, SNAPSHOT_LIMIT};
+use crate::modules::{account, atomic_file, logger};
+
+/// 文件快照目录（位于数据目录下）
+const SNAPSHOT_DIR: &str = "snapshots";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecoverySource {
+    /// 从快照恢复
+    Snapshot,
+    /// 从损坏内容中抢救
+    Salvage,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecoveryResult {
+    pub source: RecoverySource,
+    /// 所用快照的保存时间（秒）
+    pub snapshot_at: Option<i64>,
+    /// 抢救出的列表条目数
+    pub recovered_entries: usize,
+    /// 无法解析而丢弃的列表条目数
+    pub dropped_entries: usize,
+    /// 损坏内容的保留位置
+    pub corrupted_backup: String,
+}
+
+/// 抢救结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct Salvaged {
+    pub value: Value,
+    pub recovered_entries: usize,
+    pub dropped_entries: usize,
+}
+
+fn snapshot_dir(path: &Path) -> Result<PathBuf, String> {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| format!("无效的文件路径: {}", path.display()))?;
+    Ok(account::get_data_dir()?.join(SNAPSHOT_DIR).join(name))
+}
+
+/// 文件快照 (保存时间毫秒, 路径)，最新的在前
+fn file_snapshots(path: &Path) -> Result<Vec<(i64, PathBuf)>, String> {
+    let dir = snapshot_dir(path)?;
+    let Ok(entries) = fs::read_dir(&dir) else {
+        return Ok(Vec::new());
+    };
+    let mut snapshots: Vec<(i64, PathBuf)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            let millis = path.file_stem()?.to_str()?.parse::<i64>().ok()?;
+            Some((millis, path))
+        })
+        .collect();
+    snapshots.sort_by_key(|(millis, _)| std::cmp::Reverse(*millis));
+    Ok(snapshots)
+}
+
+fn record_file_snapshot(path: &Path, content: &str) -> Result<(), String> {
+    let snapshots = file_snapshots(path)?;
+    if let Some((_, latest)) = snapshots.first() {
+        if fs::read_to_string(latest).ok().as_deref() == Some(content) {
+            return Ok(());
+        }
+    }
+    let dir = snapshot_dir(path)?;
+    let millis = chrono::Utc::now()
+        .timestamp_millis()
+        .max(snapshots.first().map_or(0, |(millis, _)| millis + 1));
+    atomic_file::replace(&dir.join(format!("{}.json", millis)), content)?;
+    for (_, stale) in snapshots.iter().skip(SNAPSHOT_LIMIT - 1) {
+        let _ = fs::remove_file(stale);
+    }
+    Ok(())
+}
+
+/// 原子写入 JSON 存储并保留快照（快照失败只记录日志，不影响写入结果）
+pub fn write_with_snapshots(path: &Path, content: &str) -> Result<(), String> {
+    atomic_file::write(path, content)?;
+    if let Err(e) = record_file_snapshot(path, content) {
+        logger::log_warn(&format!(
+            "[Recovery] 保存快照失败({}): {}",
+            path.display(),
+            e
+        ));
+    }
+    Ok(())
+}
+
+/// 从损坏的 JSON 中抢救内容：逐个解析顶层字段，列表字段逐条解析并跳过损坏的条目
+pub fn salvage_json(content: &str) -> Option<Salvaged> {
+    let text = content.trim_matches(|c: char| c == '\0' || c.is_whitespace());
+    let mut salvaged = Salvaged {
+        value: Value::Null,
+        recovered_entries: 0,
+        dropped_entries: 0,
+    };
+    let mut object = Map::new();
+    let mut pos = text.strip_prefix('{').map(|_| 1)?;
+
+    loop {
+        pos = skip_separators(text, pos);
+        let rest = &text[pos..];
+        if rest.is_empty() || rest.starts_with('}') {
+            break;
+        }
+        let Some((key, consumed)) = parse_value_at(rest) else {
+            break;
+        };
+        let Value::String(key) = key else {
+            break;
+        };
+        pos = skip_whitespace(text, pos + consumed);
+        let Some(after_colon) = text[pos..].strip_prefix(':') else {
+            break;
+        };
+        pos = skip_whitespace(text, text.len() - after_colon.len());
+
+        if let Some((value, consumed)) = parse_value_at(&text[pos..]) {
+            object.insert(key, value);
+            pos += consumed;
+            continue;
+        }
+        if !text[pos..].starts_with('[') {
+            break;
+        }
+        let (items, end) = salvage_array(text, pos + 1, &mut salvaged);
+        object.insert(key, Value::Array(items));
+        match end {
+            Some(end) => pos = end,
+            None => break,
+        }
+    }
+
+    if object.is_empty() {
+        return None;
+    }
+    salvaged.value = Value::Object(object);
+    Some(salvaged)
+}
+
+/// 逐条解析列表，返回 (完整条目, 列表结束后的位置)；无法确定列表结尾时位置为 None
+fn salvage_array(text: &str, start: usize, salvaged: &mut Salvaged) -> (Vec<Value>, Option<usize>) {
+    let mut items = Vec::new();
+    let mut pos = start;
+    let mut indent: Option<usize> = None;
+
+    loop {
+        pos = skip_separators(text, pos);
+        let rest = &text[pos..];
+        if rest.is_empty() {
+            return (items, None);
+        }
+        if rest.starts_with(']') {
+            return (items, Some(pos + 1));
+        }
+        let column = pos - text[..pos].rfind('\n').map_or(0, |index| index + 1);
+        let item_indent = *indent.get_or_insert(column);
+        if let Some((value, consumed)) = parse_value_at(rest) {
+            items.push(value);
+            salvaged.recovered_entries += 1;
+            pos += consumed;
+            continue;
+        }
+
+        // 条目损坏：跳到下一个与条目同缩进的行首继续（仅适用于格式化输出）
+        salvaged.dropped_entries += 1;
+        match next_line_at_indent(text, pos, item_indent) {
+            Some(next) => pos = next,
+            None => return (items, None),
+        }
+    }
+}
+
+/// 在 `pos` 之后查找缩进为 `indent` 且以 `{`、`[` 或 `]` 开头的行
+fn next_line_at_indent(text: &str, pos: usize, indent: usize) -> Option<usize> {
+    if indent == 0 {
+        return None;
+    }
+    let mut offset = pos;
+    while let Some(newline) = text[offset..].find('\n') {
+        let line_start = offset + newline + 1;
+        let line = &text[line_start..];
+        let spaces = line.len() - line.trim_start_matches(' ').len();
+        let rest = &line[spaces..];
+        if spaces == indent && (rest.starts_with('{') || rest.starts_with('[')) {
+            return Some(line_start + spaces);
+        }
+        if spaces < indent && rest.starts_with(']') {
+            return Some(line_start + spaces);
+        }
+        offset = line_start;
+    }
+    None
+}
+
+/// 解析 `text` 开头的一个 JSON 值，返回 (值, 消耗的字节数)
+fn parse_value_at(text: &str) -> Option<(Value, usize)> {
+    let mut stream = serde_json::Deserializer::from_str(text).into_iter::<Value>();
+    let value = stream.next()?.ok()?;
+    Some((value, stream.byte_offset()))
+}
+
+fn skip_whitespace(text: &str, pos: usize) -> usize {
+    text.len() - text[pos..].trim_start().len()
+}
+
+fn skip_separators(text: &str, pos: usize) -> usize {
+    text.len()
+        - text[pos..]
+            .trim_start_matches(|c: char| c == ',' || c.is_whitespace())
+            .len()
+}
+
+/// 选出最新的有效快照
+fn newest_valid<T>(
+    snapshots: Vec<(i64, T)>,
+    read: impl Fn(&T) -> Option<String>,
+) -> Option<(i64, String)> {
+    snapshots.into_iter().find_map(|(saved_at, source)| {
+        let content = read(&source)?;
+        serde_json::from_str::<Value>(&content)
+            .ok()
+            .filter(Value::is_object)
+            .map(|_| (saved_at, content))
+    })
+}
+
+fn salvaged_content(corrupted: &str) -> Result<(String, Salvaged), String> {
+    let salvaged =
+        salvage_json(corrupted).ok_or("没有可用的快照，且无法从损坏内容中恢复任何数据")?;
+    let content = serde_json::to_string_pretty(&salvaged.value)
+        .map_err(|e| format!("序列化恢复数据失败: {}", e))?;
+    Ok((content, salvaged))
+}
+
+fn build_result(
+    snapshot: Option<i64>,
+    salvaged: Option<Salvaged>,
+    corrupted_backup: String,
+) -> RecoveryResult {
+    match salvaged {
+        Some(salvaged) => RecoveryResult {
+            source: RecoverySource::Salvage,
+            snapshot_at: None,
+            recovered_entries: salvaged.recovered_entries,
+            dropped_entries: salvaged.dropped_entries,
+            corrupted_backup,
+        },
+        None => RecoveryResult {
+            source: RecoverySource::Snapshot,
+            snapshot_at: snapshot,
+            recovered_entries: 0,
+            dropped_entries: 0,
+            corrupted_backup,
+        },
+    }
+}
+
+/// 恢复损坏的存储，`location` 为文件损坏错误中的 `file_path`
+pub fn recover(location: &str) -> Result<RecoveryResult, String> {
+    let result = match data_store::document_name(location)? {
+        Some(name) => recover_document(location, &name)?,
+        None => recover_file(Path::new(location))?,
+    };
+    logger::log_info(&format!(
+        "[Recovery] 已恢复 {}: {:?}，抢救 {} 条，丢弃 {} 条",
+        location, result.source, result.recovered_entries, result.dropped_entries
+    ));
+    Ok(result)
+}
+
+fn recover_document(location: &str, name: &str) -> Result<RecoveryResult, String> {
+    let corrupted = data_store::load_document(name)?.unwrap_or_default();
+    let snapshot = newest_valid(data_store::document_snapshots(name)?, |content| {
+        Some(content.clone())
+    });
+    let (content, snapshot_at, salvaged) = match snapshot {
+        Some((saved_at, content)) => (content, Some(saved_at), None),
+        None => {
+            let (content, salvaged) = salvaged_content(&corrupted)?;
+            (content, None, Some(salvaged))
+        }
+    };
+
+    let backup = data_store::quarantine_document(location)?
+        .map(|backup_name| data_store::document_location(&backup_name))
+        .unwrap_or_default();
+    data_store::save_document(name, &content)?;
+    Ok(build_result(snapshot_at, salvaged, backup))
+}
+
+fn recover_file(path: &Path) -> Result<RecoveryResult, String> {
+    let corrupted = fs::read_to_string(path).unwrap_or_default();
+    let snapshots = file_snapshots(path)?
+        .into_iter()
+        .map(|(millis, snapshot)| (millis / 1000, snapshot))
+        .collect();
+    let snapshot = newest_valid(snapshots, |snapshot| fs::read_to_string(snapshot).ok());
+    let (content, snapshot_at, salvaged) = match snapshot {
+        Some((saved_at, content)) => (content, Some(saved_at), None),
+        None => {
+            let (content, salvaged) = salvaged_content(&corrupted)?;
+            (content, None, Some(salvaged))
+        }
+    };
+
+    let backup = format!(
+        "{}.corrupted.{}",
+        path.to_string_lossy(),
+        chrono::Utc::now().timestamp()
+    );
+    if path.exists() {
+        fs::rename(path, &backup).map_err(|e| format!("备份损坏文件失败: {}", e))?;
+    }
+    write_with_snapshots(path, &content)?;
+    Ok(build_result(snapshot_at, salvaged, backup))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn salvages_intact_entries_from_truncated_store() {
+        let content = "{\n  \"version\": \"1.0\",\n  \"accounts\": [\n    {\n      \"id\": \"a\"\n    },\n    {\n      \"id\": \"b\"\n    },\n    {\n      \"id\": \"c\",\n      \"email\": \"c@exa";
+        let salvaged = salvage_json(content).unwrap();
+        assert_eq!(
+            salvaged.value,
+            serde_json::json!({"version": "1.0", "accounts": [{"id": "a"}, {"id": "b"}]})
+        );
+        assert_eq!(salvaged.recovered_entries, 2);
+        assert_eq!(salvaged.dropped_entries, 1);
+    }
+
+    #[test]
+    fn skips_damaged_entry_and_keeps_following_fields() {
+        let content = "{\n  \"version\": \"1.0\",\n  \"accounts\": [\n    {\n      \"id\": \"a\"\n    },\n    {\n      \"id\": \"b\x01,\n      \"email\": 1\n    },\n    {\n      \"id\": \"c\"\n    }\n  ],\n  \"current_account_id\": \"c\"\n}\0\0";
+        let salvaged = salvage_json(content).unwrap();
+        assert_eq!(
+            salvaged.value,
+            serde_json::json!({
+                "version": "1.0",
+                "accounts": [{"id": "a"}, {"id": "c"}],
+                "current_account_id": "c"
+            })
+        );
+        assert_eq!(salvaged.dropped_entries, 1);
+        assert!(salvage_json("\0\0\0").is_none());
+    }
+}
@@ -0,0 +1,212 @@
+//! 按账号的自适应配额刷新计划
+//!
+//! 定时刷新不再每轮拉取全部账号，而是按各账号上次刷新的结果安排下一次刷新时间：
+//! 配额接近预警阈值的账号缩短间隔，配额满的账号拉长间隔，即将到达重置时间的账号在重置后立即刷新；
+//! 刷新失败的账号按指数退避重试。禁用或被封禁（403）的账号不进入计划。
+//! 计划只保存在内存中，应用启动后的第一轮会刷新全部账号。
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use crate::modules::config;
+use crate::modules::data_store::AccountPlatform;
+use crate::modules::quota_meter::QuotaMeter;
+
+/// 未开启定时刷新时（仅手动刷新）使用的基准间隔（分钟）
+const FALLBACK_INTERVAL_MINUTES: i64 = 10;
+/// 最短刷新间隔（秒）
+const MIN_INTERVAL_SECS: i64 = 2 * 60;
+/// 最长刷新间隔（秒），也是失败退避的上限
+const MAX_INTERVAL_SECS: i64 = 6 * 60 * 60;
+/// 剩余百分比低于「预警阈值 + 该值」时视为接近阈值
+const NEAR_THRESHOLD_MARGIN: i32 = 10;
+/// 接近阈值时间隔缩短为基准间隔的几分之一
+const NEAR_THRESHOLD_DIVISOR: i64 = 4;
+/// 配额全满时间隔放大为基准间隔的倍数
+const FULL_QUOTA_MULTIPLIER: i64 = 4;
+/// 重置后延迟多久再刷新（秒），等待上游完成重置
+const RESET_GRACE_SECS: i64 = 60;
+/// 首次失败后的退避时长（秒），之后每次翻倍
+const BACKOFF_BASE_SECS: i64 = 60;
+
+#[derive(Debug, Clone, Copy)]
+struct PlanEntry {
+    /// 下一次刷新时间（秒）
+    next_due_at: i64,
+    /// 连续失败次数
+    failures: u32,
+}
+
+static PLAN: OnceLock<Mutex<HashMap<(AccountPlatform, String), PlanEntry>>> = OnceLock::new();
+
+fn plan() -> &'static Mutex<HashMap<(AccountPlatform, String), PlanEntry>> {
+    PLAN.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn now_secs() -> i64 {
+    chrono::Utc::now().timestamp()
+}
+
+/// 平台的基准刷新间隔（秒）与预警阈值
+fn platform_policy(platform: AccountPlatform) -> (i64, i32) {
+    let cfg = config::get_user_config();
+    let (minutes, threshold) = match platform {
+        AccountPlatform::Antigravity => (cfg.auto_refresh_minutes, cfg.quota_alert_threshold),
+        AccountPlatform::Codex => (
+            cfg.codex_auto_refresh_minutes,
+            cfg.codex_quota_alert_threshold,
+        ),
+        AccountPlatform::GitHubCopilot => (
+            cfg.ghcp_auto_refresh_minutes,
+            cfg.ghcp_quota_alert_threshold,
+        ),
+        AccountPlatform::Windsurf => (
+            cfg.windsurf_auto_refresh_minutes,
+            cfg.windsurf_quota_alert_threshold,
+        ),
+        AccountPlatform::Kiro => (
+            cfg.kiro_auto_refresh_minutes,
+            cfg.kiro_quota_alert_threshold,
+        ),
+    };
+    let minutes = if minutes > 0 {
+        i64::from(minutes)
+    } else {
+        FALLBACK_INTERVAL_MINUTES
+    };
+    (minutes * 60, threshold)
+}
+
+/// 账号是否到了刷新时间（不在计划中的账号视为已到期）
+pub fn is_due(platform: AccountPlatform, account_id: &str) -> bool {
+    let guard = plan().lock().expect("refresh plan lock");
+    guard
+        .get(&(platform, account_id.to_string()))
+        .map(|entry| entry.next_due_at <= now_secs())
+        .unwrap_or(true)
+}
+
+/// 刷新成功后按最新配额安排下一次刷新
+pub fn record_success(platform: AccountPlatform, account_id: &str, meters: &[QuotaMeter]) {
+    let (base_secs, threshold) = platform_policy(platform);
+    let now = now_secs();
+    let entry = PlanEntry {
+        next_due_at: now + next_interval(meters, base_secs, threshold, now),
+        failures: 0,
+    };
+    let mut guard = plan().lock().expect("refresh plan lock");
+    guard.insert((platform, account_id.to_string()), entry);
+}
+
+/// 刷新失败后按指数退避安排重试
+pub fn record_failure(platform: AccountPlatform, account_id: &str) {
+    let now = now_secs();
+    let mut guard = plan().lock().expect("refresh plan lock");
+    let entry = guard
+        .entry((platform, account_id.to_string()))
+        .or_insert(PlanEntry {
+            next_due_at: now,
+            failures: 0,
+        });
+    entry.failures = entry.failures.saturating_add(1);
+    entry.next_due_at = now + backoff_secs(entry.failures);
+}
+
+/// 账号不再参与定时刷新（禁用或被封禁）
+pub fn remove(platform: AccountPlatform, account_id: &str) {
+    let mut guard = plan().lock().expect("refresh plan lock");
+    guard.remove(&(platform, account_id.to_string()));
+}
+
+/// 清理已不存在的账号，`keep` 返回 false 的账号移出计划
+pub fn retain(platform: AccountPlatform, keep: impl Fn(&str) -> bool) {
+    let mut guard = plan().lock().expect("refresh plan lock");
+    guard.retain(|(item, account_id), _| *item != platform || keep(account_id));
+}
+
+/// 平台内最早到期的账号刷新时间（秒），计划为空时返回 None
+pub fn next_due_at(platform: AccountPlatform) -> Option<i64> {
+    let guard = plan().lock().expect("refresh plan lock");
+    guard
+        .iter()
+        .filter(|((item, _), _)| *item == platform)
+        .map(|(_, entry)| entry.next_due_at)
+        .min()
+}
+
+/// 根据账号当前配额计算下一次刷新间隔（秒）
+fn next_interval(meters: &[QuotaMeter], base_secs: i64, threshold: i32, now: i64) -> i64 {
+    let lowest = meters
+        .iter()
+        .map(|meter| meter.effective_percentage(now))
+        .min();
+    let mut interval = match lowest {
+        Some(lowest) if lowest <= threshold + NEAR_THRESHOLD_MARGIN => {
+            base_secs / NEAR_THRESHOLD_DIVISOR
+        }
+        Some(lowest) if lowest >= 100 => base_secs * FULL_QUOTA_MULTIPLIER,
+        _ => base_secs,
+    };
+
+    // 未满的配额项将在本次间隔内重置时，重置后立即刷新
+    let next_reset = meters
+        .iter()
+        .filter(|meter| meter.effective_percentage(now) < 100)
+        .filter_map(|meter| meter.reset_at)
+        .filter(|reset_at| *reset_at > now)
+        .min();
+    if let Some(reset_at) = next_reset {
+        interval = interval.min(reset_at - now + RESET_GRACE_SECS);
+    }
+
+    interval.clamp(MIN_INTERVAL_SECS, MAX_INTERVAL_SECS)
+}
+
+fn backoff_secs(failures: u32) -> i64 {
+    let exponent = failures.saturating_sub(1).min(16);
+    (BACKOFF_BASE_SECS << exponent).min(MAX_INTERVAL_SECS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: i64 = 10 * 60;
+
+    #[test]
+    fn interval_follows_remaining_quota() {
+        let now = 1_000_000;
+        let full = vec![QuotaMeter::percent("a", 100.0, None)];
+        let normal = vec![QuotaMeter::percent("a", 60.0, None)];
+        let low = vec![
+            QuotaMeter::percent("a", 90.0, None),
+            QuotaMeter::percent("b", 25.0, None),
+        ];
+        assert_eq!(next_interval(&full, BASE, 20, now), BASE * 4);
+        assert_eq!(next_interval(&normal, BASE, 20, now), BASE);
+        assert_eq!(next_interval(&low, BASE, 20, now), BASE / 4);
+        assert_eq!(next_interval(&[], BASE, 20, now), BASE);
+    }
+
+    #[test]
+    fn upcoming_reset_pulls_refresh_forward() {
+        let now = 1_000_000;
+        let meters = vec![QuotaMeter::percent("a", 40.0, Some(now + 300))];
+        assert_eq!(
+            next_interval(&meters, BASE, 20, now),
+            300 + RESET_GRACE_SECS
+        );
+
+        // 已过重置时间的配额项按已恢复计算
+        let reset = vec![QuotaMeter::percent("a", 0.0, Some(now - 10))];
+        assert_eq!(next_interval(&reset, BASE, 20, now), BASE * 4);
+    }
+
+    #[test]
+    fn failures_back_off_exponentially() {
+        assert_eq!(backoff_secs(1), 60);
+        assert_eq!(backoff_secs(2), 120);
+        assert_eq!(backoff_secs(4), 480);
+        assert_eq!(backoff_secs(100), MAX_INTERVAL_SECS);
+    }
+}
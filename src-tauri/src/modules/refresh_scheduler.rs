@@ -0,0 +1,472 @@
+//! 后台配额定时刷新调度
+//!
+//! 调度循环每 `TICK_SECS` 秒检查一次各平台：下一次执行时间（`next_run_at`）由上次触发时间加上
+//! 平台的 `*_auto_refresh_minutes` 得出，Antigravity 与 Codex 还会取账号刷新计划（`refresh_plan`）
+//! 中更早的到期时间。到期的平台在后台刷新配额，随后执行自动切号与配额预警，完成后向前端发送
+//! `quota:auto_refreshed` 事件（含成功数、切换到的账号与下一次执行时间）。
+//! 自动切号开启时，另外每分钟刷新一次当前 Antigravity 账号并检查是否需要切号。
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use serde::Serialize;
+use tauri::Emitter;
+use tokio::time::sleep;
+
+use crate::modules::audit::{self, AuditSource};
+use crate::modules::data_store::AccountPlatform;
+use crate::modules::{
+    self, codex_account, codex_quota, config, github_copilot_account, kiro_account, logger,
+    refresh_plan, windsurf_account,
+};
+
+/// 调度循环检查间隔（实际刷新间隔由各平台 *_auto_refresh_minutes 与账号刷新计划决定）
+const TICK_SECS: u64 = 15;
+/// 自动切号开启时，额外检查当前 Antigravity 账号的间隔
+const AUTO_SWITCH_CHECK_INTERVAL_MS: i64 = 60 * 1000;
+/// 每次定时刷新完成后发送给前端的事件
+pub const REFRESH_COMPLETED_EVENT: &str = "quota:auto_refreshed";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RefreshPlatform {
+    Antigravity,
+    Codex,
+    GithubCopilot,
+    Windsurf,
+    Kiro,
+}
+
+impl RefreshPlatform {
+    pub const ALL: [RefreshPlatform; 5] = [
+        RefreshPlatform::Antigravity,
+        RefreshPlatform::Codex,
+        RefreshPlatform::GithubCopilot,
+        RefreshPlatform::Windsurf,
+        RefreshPlatform::Kiro,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Antigravity => "Antigravity",
+            Self::Codex => "Codex",
+            Self::GithubCopilot => "GitHubCopilot",
+            Self::Windsurf => "Windsurf",
+            Self::Kiro => "Kiro",
+        }
+    }
+
+    /// 按账号刷新计划调度的平台（见 `refresh_plan`），其余平台每轮刷新全部账号
+    fn plan_platform(self) -> Option<AccountPlatform> {
+        match self {
+            Self::Antigravity => Some(AccountPlatform::Antigravity),
+            Self::Codex => Some(AccountPlatform::Codex),
+            Self::GithubCopilot | Self::Windsurf | Self::Kiro => None,
+        }
+    }
+
+    /// 刷新间隔（分钟），<= 0 表示禁用
+    pub fn interval_minutes(self, cfg: &config::UserConfig) -> i32 {
+        match self {
+            Self::Antigravity => cfg.auto_refresh_minutes,
+            Self::Codex => cfg.codex_auto_refresh_minutes,
+            Self::GithubCopilot => cfg.ghcp_auto_refresh_minutes,
+            Self::Windsurf => cfg.windsurf_auto_refresh_minutes,
+            Self::Kiro => cfg.kiro_auto_refresh_minutes,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct PlatformState {
+    running: bool,
+    last_run_at: Option<i64>,
+    last_finished_at: Option<i64>,
+    last_error: Option<String>,
+}
+
+#[derive(Debug, Default)]
+struct SchedulerState {
+    started_at: i64,
+    platforms: HashMap<RefreshPlatform, PlatformState>,
+    auto_switch_check_running: bool,
+    last_auto_switch_check_at: Option<i64>,
+}
+
+/// 单个平台的调度状态（供前端展示下一次刷新时间）
+#[derive(Debug, Clone, Serialize)]
+pub struct RefreshPlatformStatus {
+    pub platform: RefreshPlatform,
+    pub enabled: bool,
+    pub interval_minutes: i32,
+    pub running: bool,
+    pub last_run_at: Option<i64>,
+    pub last_finished_at: Option<i64>,
+    pub next_run_at: Option<i64>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RefreshCompletedPayload {
+    pub platform: RefreshPlatform,
+    pub success: usize,
+    pub total: usize,
+    pub error: Option<String>,
+    pub switched_account_id: Option<String>,
+    pub finished_at: i64,
+    pub next_run_at: Option<i64>,
+}
+
+struct RefreshOutcome {
+    success: usize,
+    total: usize,
+    switched_account_id: Option<String>,
+}
+
+static STATE: OnceLock<Mutex<SchedulerState>> = OnceLock::new();
+static STARTED: OnceLock<Mutex<bool>> = OnceLock::new();
+
+fn state() -> &'static Mutex<SchedulerState> {
+    STATE.get_or_init(|| {
+        Mutex::new(SchedulerState {
+            started_at: now_ms(),
+            ..SchedulerState::default()
+        })
+    })
+}
+
+fn started_flag() -> &'static Mutex<bool> {
+    STARTED.get_or_init(|| Mutex::new(false))
+}
+
+fn now_ms() -> i64 {
+    chrono::Utc::now().timestamp_millis()
+}
+
+/// 下一次执行时间：以上次触发时间（未执行过则以调度器启动时间）加上当前配置的间隔，
+/// 因此修改配置后无需重启即可生效。按账号刷新计划调度的平台取其中更早的账号到期时间。
+fn compute_next_run_at(
+    cfg: &config::UserConfig,
+    platform: RefreshPlatform,
+    platform_state: Option<&PlatformState>,
+    started_at: i64,
+) -> Option<i64> {
+    let minutes = platform.interval_minutes(cfg);
+    if minutes <= 0 {
+        return None;
+    }
+    let base = platform_state
+        .and_then(|item| item.last_run_at)
+        .unwrap_or(started_at);
+    let flat = base + i64::from(minutes) * 60 * 1000;
+    let planned = platform
+        .plan_platform()
+        .and_then(refresh_plan::next_due_at)
+        .map(|due_at| (due_at * 1000).max(base + TICK_SECS as i64 * 1000));
+    Some(planned.map_or(flat, |planned| planned.min(flat)))
+}
+
+/// 获取各平台的调度状态
+pub fn get_status() -> Vec<RefreshPlatformStatus> {
+    let cfg = config::get_user_config();
+    let guard = state().lock().expect("refresh scheduler state lock");
+    RefreshPlatform::ALL
+        .iter()
+        .map(|platform| {
+            let platform_state = guard.platforms.get(platform);
+            let interval_minutes = platform.interval_minutes(&cfg);
+            RefreshPlatformStatus {
+                platform: *platform,
+                enabled: interval_minutes > 0,
+                interval_minutes,
+                running: platform_state.map(|item| item.running).unwrap_or(false),
+                last_run_at: platform_state.and_then(|item| item.last_run_at),
+                last_finished_at: platform_state.and_then(|item| item.last_finished_at),
+                next_run_at: compute_next_run_at(&cfg, *platform, platform_state, guard.started_at),
+                last_error: platform_state.and_then(|item| item.last_error.clone()),
+            }
+        })
+        .collect()
+}
+
+/// 启动后台配额刷新调度（重复调用无副作用）
+pub fn ensure_started() {
+    let mut started = started_flag()
+        .lock()
+        .expect("refresh scheduler started lock");
+    if *started {
+        return;
+    }
+    *started = true;
+    // 初始化启动时间
+    let _ = state();
+
+    logger::log_info("[RefreshScheduler] 后台配额刷新调度已启动");
+    tauri::async_runtime::spawn(async move {
+        loop {
+            run_scheduler_once();
+            sleep(Duration::from_secs(TICK_SECS)).await;
+        }
+    });
+}
+
+fn run_scheduler_once() {
+    let cfg = config::get_user_config();
+    let now = now_ms();
+
+    let (due, run_auto_switch_check) = {
+        let mut guard = state().lock().expect("refresh scheduler state lock");
+        let started_at = guard.started_at;
+        let mut due = Vec::new();
+        for platform in RefreshPlatform::ALL {
+            let next_run_at =
+                compute_next_run_at(&cfg, platform, guard.platforms.get(&platform), started_at);
+            let Some(next_run_at) = next_run_at else {
+                continue;
+            };
+            let entry = guard.platforms.entry(platform).or_default();
+            if entry.running || next_run_at > now {
+                continue;
+            }
+            entry.running = true;
+            entry.last_run_at = Some(now);
+            due.push(platform);
+        }
+
+        let run_auto_switch_check = cfg.auto_switch_enabled
+            && !guard.auto_switch_check_running
+            && guard
+                .last_auto_switch_check_at
+                .map(|at| now - at >= AUTO_SWITCH_CHECK_INTERVAL_MS)
+                .unwrap_or(true);
+        if run_auto_switch_check {
+            guard.auto_switch_check_running = true;
+            guard.last_auto_switch_check_at = Some(now);
+        }
+        (due, run_auto_switch_check)
+    };
+
+    for platform in due {
+        tauri::async_runtime::spawn(audit::with_source(
+            AuditSource::Scheduler,
+            run_platform_refresh(platform),
+        ));
+    }
+    if run_auto_switch_check {
+        tauri::async_runtime::spawn(audit::with_source(
+            AuditSource::Scheduler,
+            run_auto_switch_check_task(),
+        ));
+    }
+}
+
+async fn run_platform_refresh(platform: RefreshPlatform) {
+    let started = std::time::Instant::now();
+    let result = refresh_platform(platform).await;
+    let finished_at = now_ms();
+
+    let next_run_at = {
+        let cfg = config::get_user_config();
+        let mut guard = state().lock().expect("refresh scheduler state lock");
+        let started_at = guard.started_at;
+        let entry = guard.platforms.entry(platform).or_default();
+        entry.running = false;
+        entry.last_finished_at = Some(finished_at);
+        entry.last_error = result.as_ref().err().cloned();
+        compute_next_run_at(&cfg, platform, Some(entry), started_at)
+    };
+
+    let payload = match result {
+        Ok(outcome) => {
+            logger::log_info(&format!(
+                "[RefreshScheduler] {} 定时刷新完成: success={}/{}, elapsed={}ms",
+                platform.label(),
+                outcome.success,
+                outcome.total,
+                started.elapsed().as_millis()
+            ));
+            if outcome.success > 0 {
+                modules::websocket::broadcast_data_changed("auto_refresh");
+            }
+            RefreshCompletedPayload {
+                platform,
+                success: outcome.success,
+                total: outcome.total,
+                error: None,
+                switched_account_id: outcome.switched_account_id,
+                finished_at,
+                next_run_at,
+            }
+        }
+        Err(err) => {
+            logger::log_warn(&format!(
+                "[RefreshScheduler] {} 定时刷新失败: {}",
+                platform.label(),
+                err
+            ));
+            RefreshCompletedPayload {
+                platform,
+                success: 0,
+                total: 0,
+                error: Some(err),
+                switched_account_id: None,
+                finished_at,
+                next_run_at,
+            }
+        }
+    };
+
+    if let Some(app) = crate::get_app_handle() {
+        let _ = app.emit(REFRESH_COMPLETED_EVENT, &payload);
+        let _ = modules::tray::update_tray_menu(app);
+    }
+}
+
+async fn refresh_platform(platform: RefreshPlatform) -> Result<RefreshOutcome, String> {
+    let (success, total) = match platform {
+        RefreshPlatform::Antigravity => {
+            // 先与本地客户端对齐当前账号，避免对旧账号做切号判断
+            if let Err(e) = modules::account::sync_current_from_client() {
+                logger::log_info(&format!("[RefreshScheduler] 同步客户端当前账号跳过: {}", e));
+            }
+            let stats = modules::account::refresh_due_quotas_logic().await?;
+            (stats.success, stats.total)
+        }
+        RefreshPlatform::Codex => count_success(&codex_quota::refresh_due_quotas().await?),
+        RefreshPlatform::GithubCopilot => {
+            count_success(&github_copilot_account::refresh_all_tokens().await?)
+        }
+        RefreshPlatform::Windsurf => count_success(&windsurf_account::refresh_all_tokens().await?),
+        RefreshPlatform::Kiro => count_success(&kiro_account::refresh_all_tokens().await?),
+    };
+
+    let mut switched_account_id = None;
+    if success > 0 {
+        switched_account_id = run_auto_switch(platform).await;
+        // 已自动切号时跳过本轮预警
+        if switched_account_id.is_none() {
+            run_quota_alert(platform);
+        }
+    }
+
+    Ok(RefreshOutcome {
+        success,
+        total,
+        switched_account_id,
+    })
+}
+
+fn count_success<T>(results: &[(String, Result<T, String>)]) -> (usize, usize) {
+    let success = results.iter().filter(|(_, r)| r.is_ok()).count();
+    (success, results.len())
+}
+
+async fn run_auto_switch(platform: RefreshPlatform) -> Option<String> {
+    let result = match platform {
+        RefreshPlatform::Antigravity => modules::account::run_auto_switch_if_needed()
+            .await
+            .map(|switched| switched.map(|account| (account.id, account.email))),
+        RefreshPlatform::Codex => codex_account::run_auto_switch_if_needed()
+            .await
+            .map(|switched| switched.map(|account| (account.id, account.email))),
+        RefreshPlatform::GithubCopilot => github_copilot_account::run_auto_switch_if_needed()
+            .await
+            .map(|switched| switched.map(|account| (account.id, account.github_login))),
+        RefreshPlatform::Windsurf => windsurf_account::run_auto_switch_if_needed()
+            .await
+            .map(|switched| switched.map(|account| (account.id, account.github_login))),
+        RefreshPlatform::Kiro => kiro_account::run_auto_switch_if_needed()
+            .await
+            .map(|switched| switched.map(|account| (account.id, account.email))),
+    };
+    match result {
+        Ok(Some((account_id, email))) => {
+            logger::log_info(&format!(
+                "[AutoSwitch][{}] 自动切号完成: {}",
+                platform.label(),
+                email
+            ));
+            Some(account_id)
+        }
+        Ok(None) => None,
+        Err(e) => {
+            logger::log_warn(&format!(
+                "[AutoSwitch][{}] 自动切号执行失败: {}",
+                platform.label(),
+                e
+            ));
+            None
+        }
+    }
+}
+
+fn run_quota_alert(platform: RefreshPlatform) {
+    let result = match platform {
+        RefreshPlatform::Antigravity => modules::account::run_quota_alert_if_needed(),
+        RefreshPlatform::Codex => codex_account::run_quota_alert_if_needed(),
+        RefreshPlatform::GithubCopilot => github_copilot_account::run_quota_alert_if_needed(),
+        RefreshPlatform::Windsurf => windsurf_account::run_quota_alert_if_needed(),
+        RefreshPlatform::Kiro => kiro_account::run_quota_alert_if_needed(),
+    };
+    if let Err(e) = result {
+        logger::log_warn(&format!(
+            "[QuotaAlert][{}] 预警检查失败: {}",
+            platform.label(),
+            e
+        ));
+    }
+}
+
+/// 自动切号开启时，每 60 秒刷新当前 Antigravity 账号并检查是否需要切号
+async fn run_auto_switch_check_task() {
+    let result = refresh_current_antigravity_account().await;
+    {
+        let mut guard = state().lock().expect("refresh scheduler state lock");
+        guard.auto_switch_check_running = false;
+    }
+
+    match result {
+        Ok(switched_account_id) => {
+            modules::websocket::broadcast_data_changed("auto_switch_check");
+            if let Some(app) = crate::get_app_handle() {
+                let payload = RefreshCompletedPayload {
+                    platform: RefreshPlatform::Antigravity,
+                    success: 1,
+                    total: 1,
+                    error: None,
+                    switched_account_id,
+                    finished_at: now_ms(),
+                    next_run_at: None,
+                };
+                let _ = app.emit(REFRESH_COMPLETED_EVENT, &payload);
+                let _ = modules::tray::update_tray_menu(app);
+            }
+        }
+        Err(e) => {
+            logger::log_warn(&format!("[AutoSwitch] 当前账号定时刷新失败: {}", e));
+        }
+    }
+}
+
+async fn refresh_current_antigravity_account() -> Result<Option<String>, String> {
+    if let Err(e) = modules::account::sync_current_from_client() {
+        logger::log_info(&format!("[RefreshScheduler] 同步客户端当前账号跳过: {}", e));
+    }
+    let Some(mut account) = modules::get_current_account()? else {
+        return Ok(None);
+    };
+    if account.disabled {
+        return Ok(None);
+    }
+    let quota = modules::fetch_quota_with_retry(&mut account, true)
+        .await
+        .map_err(|e| e.to_string())?;
+    modules::update_account_quota(&account.id, quota)?;
+
+    let switched_account_id = run_auto_switch(RefreshPlatform::Antigravity).await;
+    if switched_account_id.is_none() {
+        run_quota_alert(RefreshPlatform::Antigravity);
+    }
+    Ok(switched_account_id)
+}
@@ -0,0 +1,130 @@
+//! 启动初始化
+//!
+//! 桌面端、命令行与 daemon 共用同一套数据层初始化：打开本地数据库、解锁凭据库、
+//! 执行待运行的数据迁移并清理过期回收站账号。凭据库需在迁移与后台服务读取账号之前解锁。
+//! 桌面端使用口令模式时保持锁定，等待用户在界面中解锁；无窗口模式下凭据库无法解锁时直接报错，
+//! 避免加密账号被静默跳过后按「没有账号」继续运行。
+
+use std::io::{IsTerminal, Write};
+use std::sync::Mutex;
+
+use zeroize::Zeroizing;
+
+use crate::error::AppError;
+use crate::modules::vault::{self, KeyProviderKind};
+use crate::modules::{data_store, logger, migration, trash};
+
+/// 无窗口模式下读取凭据库口令的环境变量
+pub const VAULT_PASSPHRASE_ENV: &str = "COCKPIT_VAULT_PASSPHRASE";
+
+/// 启动时数据迁移失败的错误，桌面端界面加载后查询并提示
+static MIGRATION_ERROR: Mutex<Option<AppError>> = Mutex::new(None);
+
+/// 桌面端启动初始化，各步骤失败只记录日志
+pub fn init_desktop() {
+    if let Err(e) = data_store::init() {
+        logger::log_error(&format!("[DataStore] 初始化数据库失败: {}", e));
+    }
+    vault::init_on_startup();
+    run_maintenance();
+}
+
+/// 命令行与 daemon 启动初始化：数据库无法打开或凭据库无法解锁时返回错误
+pub fn init_headless() -> Result<(), String> {
+    data_store::init().map_err(|e| format!("初始化数据库失败: {}", e))?;
+    vault::init_on_startup();
+    let status = vault::get_status();
+    if status.enabled && status.locked {
+        unlock_headless(status.provider)?;
+    }
+    run_maintenance();
+    Ok(())
+}
+
+fn run_maintenance() {
+    if let Err(e) = migration::run_pending() {
+        logger::log_error(&format!("[Migration] 数据迁移失败: {}", e));
+        if let Ok(mut slot) = MIGRATION_ERROR.lock() {
+            *slot = Some(e);
+        }
+    }
+    if let Err(e) = trash::purge_expired() {
+        logger::log_warn(&format!("[Trash] 清理过期回收站账号失败: {}", e));
+    }
+}
+
+/// 启动时数据迁移失败的错误，未失败时返回 None
+pub fn migration_error() -> Option<AppError> {
+    let slot = MIGRATION_ERROR.lock().ok()?;
+    match slot.as_ref()? {
+        AppError::Migration {
+            version,
+            name,
+            message,
+        } => Some(AppError::Migration {
+            version: *version,
+            name: name.clone(),
+            message: message.clone(),
+        }),
+        other => Some(AppError::Unknown(other.to_string())),
+    }
+}
+
+/// 口令模式依次尝试环境变量与终端输入；其他密钥来源自动解锁失败时返回原因
+fn unlock_headless(provider: Option<KeyProviderKind>) -> Result<(), String> {
+    if provider != Some(KeyProviderKind::Passphrase) {
+        return vault::unlock(None)
+            .map(|_| ())
+            .map_err(|e| format!("凭据库已锁定，自动解锁失败: {}", e));
+    }
+
+    let passphrase = match std::env::var(VAULT_PASSPHRASE_ENV) {
+        Ok(value) if !value.is_empty() => Zeroizing::new(value),
+        _ if std::io::stdin().is_terminal() => read_passphrase("请输入凭据库口令: ")?,
+        _ => {
+            return Err(format!(
+                "凭据库已锁定：请通过环境变量 {} 提供口令，或在终端中运行以输入口令",
+                VAULT_PASSPHRASE_ENV
+            ))
+        }
+    };
+    vault::unlock(Some(passphrase.as_str()))
+        .map(|_| ())
+        .map_err(|e| format!("凭据库解锁失败: {}", e))
+}
+
+/// 从终端读取口令，Unix 下关闭回显
+fn read_passphrase(prompt: &str) -> Result<Zeroizing<String>, String> {
+    eprint!("{}", prompt);
+    let _ = std::io::stderr().flush();
+
+    #[cfg(unix)]
+    let original = {
+        use std::os::fd::AsRawFd;
+        let fd = std::io::stdin().as_raw_fd();
+        // SAFETY: termios 为纯数据结构，tcgetattr 成功后才使用其内容
+        let mut term: libc::termios = unsafe { std::mem::zeroed() };
+        if unsafe { libc::tcgetattr(fd, &mut term) } == 0 {
+            let original = term;
+            term.c_lflag &= !libc::ECHO;
+            unsafe { libc::tcsetattr(fd, libc::TCSANOW, &term) };
+            Some((fd, original))
+        } else {
+            None
+        }
+    };
+
+    let mut line = Zeroizing::new(String::new());
+    let result = std::io::stdin().read_line(&mut line);
+
+    #[cfg(unix)]
+    if let Some((fd, original)) = original {
+        unsafe { libc::tcsetattr(fd, libc::TCSANOW, &original) };
+        eprintln!();
+    }
+
+    result.map_err(|e| format!("读取口令失败: {}", e))?;
+    Ok(Zeroizing::new(
+        line.trim_end_matches(['\r', '\n']).to_string(),
+    ))
+}
@@ -15,6 +15,7 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
+use super::atomic_file;
 use super::config::get_shared_dir;
 
 /// 同步配置文件名
@@ -91,7 +92,7 @@ fn save_sync_settings(settings: &SyncSettings) -> Result<(), String> {
     let content =
         serde_json::to_string_pretty(settings).map_err(|e| format!("序列化失败: {}", e))?;
 
-    fs::write(&path, content).map_err(|e| format!("写入文件失败: {}", e))?;
+    atomic_file::write(&path, content).map_err(|e| format!("写入文件失败: {}", e))?;
 
     Ok(())
 }
@@ -0,0 +1,118 @@
+//! 账号回收站
+//!
+//! 五个平台删除账号时都会把账号详情移入数据库的回收站表（见 `data_store`），保留期内可恢复。
+//! 保留天数取自 `trash_retention_days`（0 表示永久保留），过期记录在启动时和查看回收站时清理。
+
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::modules::audit::{AuditAction, AuditEvent};
+use crate::modules::data_store::{self, AccountPlatform};
+use crate::modules::{
+    account, codex_account, config, github_copilot_account, kiro_account, logger, websocket,
+    windsurf_account,
+};
+
+const SECONDS_PER_DAY: i64 = 24 * 60 * 60;
+
+/// 回收站条目（前端展示用）
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashEntry {
+    pub platform: String,
+    pub id: String,
+    /// 删除时记录的邮箱或登录名
+    pub label: Option<String>,
+    /// 删除时间（秒）
+    pub deleted_at: i64,
+    /// 到期清理时间（秒），永久保留时为 None
+    pub expires_at: Option<i64>,
+}
+
+fn retention_seconds() -> Option<i64> {
+    let days = config::get_user_config().trash_retention_days;
+    (days > 0).then(|| days as i64 * SECONDS_PER_DAY)
+}
+
+/// 清理超过保留期的回收记录，返回清理的条数
+pub fn purge_expired() -> Result<usize, String> {
+    let Some(retention) = retention_seconds() else {
+        return Ok(0);
+    };
+    let purged = data_store::purge_trash_before(chrono::Utc::now().timestamp() - retention)?;
+    if purged > 0 {
+        logger::log_info(&format!("[Trash] 已清理 {} 个过期的回收站账号", purged));
+    }
+    Ok(purged)
+}
+
+/// 列出回收站中的账号（先清理过期记录），最近删除的在前
+pub fn list(platform: Option<AccountPlatform>) -> Result<Vec<TrashEntry>, String> {
+    purge_expired()?;
+    let retention = retention_seconds();
+    Ok(data_store::list_trash(platform)?
+        .into_iter()
+        .map(|item| TrashEntry {
+            platform: item.platform.as_str().to_string(),
+            id: item.id,
+            label: item.label,
+            deleted_at: item.deleted_at,
+            expires_at: retention.map(|retention| item.deleted_at + retention),
+        })
+        .collect())
+}
+
+fn trashed_labels(platform: AccountPlatform) -> HashMap<String, Option<String>> {
+    data_store::list_trash(Some(platform))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|item| (item.id, item.label))
+        .collect()
+}
+
+fn restore_one(platform: AccountPlatform, account_id: &str) -> Result<(), String> {
+    match platform {
+        AccountPlatform::Antigravity => account::restore_account(account_id).map(|_| ()),
+        AccountPlatform::Codex => codex_account::restore_account(account_id).map(|_| ()),
+        AccountPlatform::GitHubCopilot => {
+            github_copilot_account::restore_account(account_id).map(|_| ())
+        }
+        AccountPlatform::Windsurf => windsurf_account::restore_account(account_id).map(|_| ()),
+        AccountPlatform::Kiro => kiro_account::restore_account(account_id).map(|_| ()),
+    }
+}
+
+/// 从回收站恢复账号，遇到失败立即返回；返回恢复的条数
+pub fn restore(platform: AccountPlatform, account_ids: &[String]) -> Result<usize, String> {
+    let mut labels = trashed_labels(platform);
+    let mut restored = 0usize;
+    for id in account_ids {
+        let result = restore_one(platform, id);
+        AuditEvent::new(platform, AuditAction::Restore)
+            .trashed_account(id, labels.remove(id).flatten())
+            .result(&result)
+            .record();
+        result?;
+        restored += 1;
+    }
+    if restored > 0 {
+        websocket::broadcast_data_changed("trash_restore");
+    }
+    Ok(restored)
+}
+
+/// 彻底删除回收站中的账号，返回删除的条数
+pub fn purge(platform: AccountPlatform, account_ids: &[String]) -> Result<usize, String> {
+    let mut labels = trashed_labels(platform);
+    let result = data_store::purge_trash(platform, account_ids);
+    for id in account_ids {
+        if !labels.contains_key(id) {
+            continue;
+        }
+        AuditEvent::new(platform, AuditAction::Purge)
+            .trashed_account(id, labels.remove(id).flatten())
+            .result(&result)
+            .record();
+    }
+    result
+}
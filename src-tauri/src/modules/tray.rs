@@ -11,6 +11,7 @@ use tauri::{
 use tracing::info;
 
 use crate::modules::logger;
+use crate::modules::quota_meter::{PlatformQuota, QuotaMeter};
 
 /// 托盘菜单 ID
 pub const TRAY_ID: &str = "main-tray";
@@ -104,13 +105,6 @@ struct AccountDisplayInfo {
     quota_lines: Vec<String>,
 }
 
-#[derive(Debug, Clone, Copy)]
-struct CopilotUsage {
-    inline_used_percent: Option<i32>,
-    chat_used_percent: Option<i32>,
-    reset_ts: Option<i64>,
-}
-
 /// 创建系统托盘
 pub fn create_tray<R: Runtime>(app: &tauri::AppHandle<R>) -> Result<TrayIcon<R>, tauri::Error> {
     info!("[Tray] 正在创建系统托盘...");
@@ -374,8 +368,9 @@ fn get_account_display_info(platform: PlatformId, lang: &str) -> AccountDisplayI
 fn build_antigravity_display_info(lang: &str) -> AccountDisplayInfo {
     match crate::modules::account::get_current_account() {
         Ok(Some(account)) => {
-            let quota_lines = if let Some(quota) = &account.quota {
-                build_model_quota_lines(lang, &quota.models)
+            let quota_lines = if account.quota.is_some() {
+                let meters: Vec<QuotaMeter> = account.quota_meters().into_iter().take(4).collect();
+                non_empty_lines(build_meter_lines(lang, &meters), "—".to_string())
             } else {
                 vec![get_text("loading", lang)]
             };
@@ -391,83 +386,17 @@ fn build_antigravity_display_info(lang: &str) -> AccountDisplayInfo {
     }
 }
 
-fn format_codex_window_label(window_minutes: Option<i64>, fallback: &str) -> String {
-    const HOUR_MINUTES: i64 = 60;
-    const DAY_MINUTES: i64 = 24 * HOUR_MINUTES;
-    const WEEK_MINUTES: i64 = 7 * DAY_MINUTES;
-
-    let Some(minutes) = window_minutes.filter(|value| *value > 0) else {
-        return fallback.to_string();
-    };
-
-    if minutes >= WEEK_MINUTES - 1 {
-        let weeks = (minutes + WEEK_MINUTES - 1) / WEEK_MINUTES;
-        return if weeks <= 1 {
-            "Weekly".to_string()
-        } else {
-            format!("{} Week", weeks)
-        };
-    }
-
-    if minutes >= DAY_MINUTES - 1 {
-        let days = (minutes + DAY_MINUTES - 1) / DAY_MINUTES;
-        return format!("{}d", days);
-    }
-
-    if minutes >= HOUR_MINUTES {
-        let hours = (minutes + HOUR_MINUTES - 1) / HOUR_MINUTES;
-        return format!("{}h", hours);
-    }
-
-    format!("{}m", minutes)
-}
-
 fn build_codex_display_info(lang: &str) -> AccountDisplayInfo {
     if let Some(account) = crate::modules::codex_account::get_current_account() {
-        let mut quota_lines = if let Some(quota) = &account.quota {
-            let has_presence =
-                quota.hourly_window_present.is_some() || quota.weekly_window_present.is_some();
-            let mut lines = Vec::new();
-
-            if !has_presence || quota.hourly_window_present.unwrap_or(false) {
-                lines.push(format!(
-                    "{}: {}% · {} {}",
-                    format_codex_window_label(quota.hourly_window_minutes, "5h"),
-                    quota.hourly_percentage.clamp(0, 100),
-                    get_text("reset", lang),
-                    format_reset_time_from_ts(lang, quota.hourly_reset_time)
-                ));
-            }
-
-            if !has_presence || quota.weekly_window_present.unwrap_or(false) {
-                lines.push(format!(
-                    "{}: {}% · {} {}",
-                    format_codex_window_label(quota.weekly_window_minutes, "Weekly"),
-                    quota.weekly_percentage.clamp(0, 100),
-                    get_text("reset", lang),
-                    format_reset_time_from_ts(lang, quota.weekly_reset_time)
-                ));
-            }
-
-            if lines.is_empty() {
-                lines.push(format!(
-                    "{}: {}% · {} {}",
-                    format_codex_window_label(quota.hourly_window_minutes, "5h"),
-                    quota.hourly_percentage.clamp(0, 100),
-                    get_text("reset", lang),
-                    format_reset_time_from_ts(lang, quota.hourly_reset_time)
-                ));
-            }
-
-            lines
+        let quota_lines = if account.quota.is_some() {
+            non_empty_lines(
+                build_meter_lines(lang, &account.quota_meters()),
+                "—".to_string(),
+            )
         } else {
             vec![get_text("loading", lang)]
         };
 
-        if quota_lines.is_empty() {
-            quota_lines.push("—".to_string());
-        }
-
         AccountDisplayInfo {
             account: format!("📧 {}", account.email),
             quota_lines,
@@ -489,21 +418,15 @@ fn build_github_copilot_display_info(lang: &str) -> AccountDisplayInfo {
         };
     };
 
-    let usage = compute_copilot_usage(
-        &account.copilot_token,
-        account.copilot_plan.as_deref(),
-        account.copilot_limited_user_quotas.as_ref(),
-        account.copilot_quota_snapshots.as_ref(),
-        account.copilot_limited_user_reset_date,
-        account.copilot_quota_reset_date.as_deref(),
-    );
-
     AccountDisplayInfo {
         account: format!(
             "📧 {}",
             display_login_email(account.github_email.as_deref(), &account.github_login)
         ),
-        quota_lines: build_copilot_quota_lines(lang, usage, "Inline", "Chat"),
+        quota_lines: non_empty_lines(
+            build_meter_lines(lang, &account.quota_meters()),
+            get_text("loading", lang),
+        ),
     }
 }
 
@@ -516,24 +439,15 @@ fn build_windsurf_display_info(lang: &str) -> AccountDisplayInfo {
         };
     };
 
-    let mut usage = compute_copilot_usage(
-        &account.copilot_token,
-        account.copilot_plan.as_deref(),
-        account.copilot_limited_user_quotas.as_ref(),
-        account.copilot_quota_snapshots.as_ref(),
-        account.copilot_limited_user_reset_date,
-        account.copilot_quota_reset_date.as_deref(),
-    );
-    if usage.reset_ts.is_none() {
-        usage.reset_ts = resolve_windsurf_plan_end_ts(&account);
-    }
-
     AccountDisplayInfo {
         account: format!(
             "📧 {}",
             display_login_email(account.github_email.as_deref(), &account.github_login)
         ),
-        quota_lines: build_windsurf_quota_lines(lang, usage),
+        quota_lines: non_empty_lines(
+            build_meter_lines(lang, &account.quota_meters()),
+            get_text("loading", lang),
+        ),
     }
 }
 
@@ -547,36 +461,12 @@ fn build_kiro_display_info(lang: &str) -> AccountDisplayInfo {
     };
 
     let mut quota_lines = Vec::new();
-    let reset_text = format_reset_time_from_ts(lang, account.usage_reset_at);
-
     if let Some(plan) =
         first_non_empty(&[account.plan_name.as_deref(), account.plan_tier.as_deref()])
     {
         quota_lines.push(format!("Plan: {}", plan));
     }
-
-    if let Some(remaining_pct) = calc_remaining_percent(account.credits_total, account.credits_used)
-    {
-        quota_lines.push(format!(
-            "Prompt: {}% · {} {}",
-            remaining_pct,
-            get_text("reset", lang),
-            reset_text
-        ));
-    }
-
-    if let Some(remaining_pct) = calc_remaining_percent(account.bonus_total, account.bonus_used) {
-        quota_lines.push(format!(
-            "Add-on: {}% · {} {}",
-            remaining_pct,
-            get_text("reset", lang),
-            reset_text
-        ));
-    }
-
-    if quota_lines.is_empty() {
-        quota_lines.push(get_text("loading", lang));
-    }
+    quota_lines.extend(build_meter_lines(lang, &account.quota_meters()));
 
     AccountDisplayInfo {
         account: format!(
@@ -584,7 +474,7 @@ fn build_kiro_display_info(lang: &str) -> AccountDisplayInfo {
             first_non_empty(&[Some(account.email.as_str()), Some(account.id.as_str())])
                 .unwrap_or("—")
         ),
-        quota_lines,
+        quota_lines: non_empty_lines(quota_lines, get_text("loading", lang)),
     }
 }
 
@@ -656,21 +546,6 @@ fn first_non_empty<'a>(values: &[Option<&'a str>]) -> Option<&'a str> {
         .find(|value| !value.is_empty())
 }
 
-fn calc_remaining_percent(total: Option<f64>, used: Option<f64>) -> Option<i32> {
-    let total = total?;
-    if !total.is_finite() || total <= 0.0 {
-        return None;
-    }
-
-    let used = used.unwrap_or(0.0);
-    if !used.is_finite() {
-        return None;
-    }
-
-    let remaining = (total - used).max(0.0);
-    Some(clamp_percent((remaining / total) * 100.0))
-}
-
 fn display_login_email(email: Option<&str>, login: &str) -> String {
     email
         .map(|value| value.trim())
@@ -679,322 +554,29 @@ fn display_login_email(email: Option<&str>, login: &str) -> String {
         .to_string()
 }
 
-fn build_copilot_quota_lines(
-    lang: &str,
-    usage: CopilotUsage,
-    inline_label: &str,
-    chat_label: &str,
-) -> Vec<String> {
-    let mut lines = Vec::new();
-    let reset_text = format_reset_time_from_ts(lang, usage.reset_ts);
-
-    if let Some(percentage) = usage.inline_used_percent {
-        lines.push(format!(
-            "{}: {}% · {} {}",
-            inline_label,
-            percentage,
-            get_text("reset", lang),
-            reset_text
-        ));
-    }
-    if let Some(percentage) = usage.chat_used_percent {
-        lines.push(format!(
-            "{}: {}% · {} {}",
-            chat_label,
-            percentage,
-            get_text("reset", lang),
-            reset_text
-        ));
-    }
-
-    if lines.is_empty() {
-        lines.push(get_text("loading", lang));
-    }
-
-    lines
-}
-
-fn build_windsurf_quota_lines(lang: &str, usage: CopilotUsage) -> Vec<String> {
-    let mut lines = Vec::new();
-    let reset_text = format_reset_time_from_ts(lang, usage.reset_ts);
-
-    if let Some(percentage) = usage.inline_used_percent {
-        lines.push(format!(
-            "Prompt: {}% · {} {}",
-            percentage,
-            get_text("reset", lang),
-            reset_text
-        ));
-    }
-    if let Some(percentage) = usage.chat_used_percent {
-        lines.push(format!(
-            "Flow: {}% · {} {}",
-            percentage,
-            get_text("reset", lang),
-            reset_text
-        ));
-    }
-
-    if lines.is_empty() {
-        lines.push(get_text("loading", lang));
-    }
-
-    lines
-}
-
-fn compute_copilot_usage(
-    token: &str,
-    plan: Option<&str>,
-    limited_quotas: Option<&serde_json::Value>,
-    quota_snapshots: Option<&serde_json::Value>,
-    limited_reset_ts: Option<i64>,
-    quota_reset_date: Option<&str>,
-) -> CopilotUsage {
-    let token_map = parse_token_map(token);
-    let reset_ts = limited_reset_ts
-        .or_else(|| parse_reset_date_to_ts(quota_reset_date))
-        .or_else(|| {
-            parse_token_number(&token_map, "rd")
-                .map(|value| value.floor() as i64)
-                .filter(|value| *value > 0)
-        });
-    let sku = token_map
-        .get("sku")
-        .map(|value| value.to_lowercase())
-        .unwrap_or_default();
-    let is_free_limited = sku.contains("free_limited")
-        || sku.contains("no_auth_limited")
-        || plan
-            .map(|value| value.to_lowercase().contains("free_limited"))
-            .unwrap_or(false);
-
-    if !is_free_limited {
-        if let Some(premium_used) = premium_used_percent(quota_snapshots) {
-            return CopilotUsage {
-                inline_used_percent: Some(premium_used),
-                chat_used_percent: Some(premium_used),
-                reset_ts,
-            };
-        }
-    }
-
-    let limited = limited_quotas.and_then(|value| value.as_object());
-    let remaining_inline = limited
-        .and_then(|obj| obj.get("completions"))
-        .and_then(parse_json_number);
-    let remaining_chat = limited
-        .and_then(|obj| obj.get("chat"))
-        .and_then(parse_json_number);
-
-    let total_inline = parse_token_number(&token_map, "cq").or(remaining_inline);
-    let total_chat = parse_token_number(&token_map, "tq").or_else(|| {
-        if is_free_limited {
-            remaining_chat.map(|_| 500.0)
-        } else {
-            remaining_chat
-        }
-    });
-
-    CopilotUsage {
-        inline_used_percent: calc_used_percent(total_inline, remaining_inline),
-        chat_used_percent: calc_used_percent(total_chat, remaining_chat),
-        reset_ts,
-    }
-}
-
-fn resolve_windsurf_plan_end_ts(account: &crate::models::windsurf::WindsurfAccount) -> Option<i64> {
-    let mut candidates: Vec<Option<&serde_json::Value>> = Vec::new();
-    let user_status = account.windsurf_user_status.as_ref();
-    let snapshots = account.copilot_quota_snapshots.as_ref();
-
-    candidates.push(json_path(
-        user_status,
-        &["userStatus", "planStatus", "planEnd"],
-    ));
-    candidates.push(json_path(
-        user_status,
-        &["userStatus", "planStatus", "plan_end"],
-    ));
-    candidates.push(json_path(user_status, &["planStatus", "planEnd"]));
-    candidates.push(json_path(user_status, &["planStatus", "plan_end"]));
-    candidates.push(json_path(snapshots, &["windsurfPlanStatus", "planEnd"]));
-    candidates.push(json_path(snapshots, &["windsurfPlanStatus", "plan_end"]));
-    candidates.push(json_path(
-        snapshots,
-        &["windsurfPlanStatus", "planStatus", "planEnd"],
-    ));
-    candidates.push(json_path(
-        snapshots,
-        &["windsurfPlanStatus", "planStatus", "plan_end"],
-    ));
-    candidates.push(json_path(
-        snapshots,
-        &["windsurfUserStatus", "userStatus", "planStatus", "planEnd"],
-    ));
-    candidates.push(json_path(
-        snapshots,
-        &["windsurfUserStatus", "userStatus", "planStatus", "plan_end"],
-    ));
-
-    for candidate in candidates.into_iter().flatten() {
-        if let Some(ts) = parse_timestamp_like(candidate) {
-            return Some(ts);
-        }
-    }
-
-    None
-}
-
-fn json_path<'a>(
-    root: Option<&'a serde_json::Value>,
-    path: &[&str],
-) -> Option<&'a serde_json::Value> {
-    let mut current = root?;
-    for key in path {
-        current = current.as_object()?.get(*key)?;
-    }
-    Some(current)
-}
-
-fn parse_timestamp_like(value: &serde_json::Value) -> Option<i64> {
-    match value {
-        serde_json::Value::Number(num) => parse_timestamp_number(num.as_f64()?),
-        serde_json::Value::String(text) => {
-            let trimmed = text.trim();
-            if trimmed.is_empty() {
-                return None;
-            }
-            if let Ok(n) = trimmed.parse::<f64>() {
-                return parse_timestamp_number(n);
-            }
-            chrono::DateTime::parse_from_rfc3339(trimmed)
-                .ok()
-                .map(|dt| dt.timestamp())
-        }
-        serde_json::Value::Object(obj) => {
-            if let Some(seconds) = obj.get("seconds").and_then(|v| v.as_i64()) {
-                return Some(seconds);
-            }
-            if let Some(seconds) = obj.get("unixSeconds").and_then(|v| v.as_i64()) {
-                return Some(seconds);
-            }
-            if let Some(inner) = obj.get("value") {
-                return parse_timestamp_like(inner);
-            }
-            None
-        }
-        _ => None,
-    }
-}
-
-fn parse_timestamp_number(raw: f64) -> Option<i64> {
-    if !raw.is_finite() || raw <= 0.0 {
-        return None;
-    }
-    if raw > 1e12 {
-        return Some((raw / 1000.0).floor() as i64);
-    }
-    Some(raw.floor() as i64)
-}
-
-fn parse_token_map(token: &str) -> HashMap<String, String> {
-    let mut map = HashMap::new();
-    let prefix = token.split(':').next().unwrap_or(token);
-    for item in prefix.split(';') {
-        let mut parts = item.splitn(2, '=');
-        let key = parts.next().unwrap_or("").trim();
-        if key.is_empty() {
-            continue;
-        }
-        let value = parts.next().unwrap_or("").trim();
-        map.insert(key.to_string(), value.to_string());
-    }
-    map
-}
-
-fn parse_token_number(map: &HashMap<String, String>, key: &str) -> Option<f64> {
-    map.get(key)
-        .map(|value| value.trim())
-        .filter(|value| !value.is_empty())
-        .and_then(|value| value.split(':').next())
-        .map(str::trim)
-        .filter(|value| !value.is_empty())
-        .and_then(|value| value.parse::<f64>().ok())
-        .filter(|value| value.is_finite())
-}
-
-fn parse_json_number(value: &serde_json::Value) -> Option<f64> {
-    match value {
-        serde_json::Value::Number(num) => num.as_f64(),
-        serde_json::Value::String(text) => text.trim().parse::<f64>().ok(),
-        _ => None,
-    }
-    .filter(|value| value.is_finite())
-}
-
-fn calc_used_percent(total: Option<f64>, remaining: Option<f64>) -> Option<i32> {
-    let total = total?;
-    let remaining = remaining?;
-    if total <= 0.0 {
-        return None;
-    }
-
-    let used = (total - remaining).max(0.0);
-    Some(clamp_percent((used / total) * 100.0))
-}
-
-fn premium_used_percent(quota_snapshots: Option<&serde_json::Value>) -> Option<i32> {
-    let snapshots = quota_snapshots?.as_object()?;
-    let premium = snapshots
-        .get("premium_interactions")
-        .or_else(|| snapshots.get("premium_models"))
-        .and_then(|value| value.as_object())?;
-
-    if premium.get("unlimited").and_then(|value| value.as_bool()) == Some(true) {
-        return Some(0);
-    }
-
-    let percent_remaining = premium
-        .get("percent_remaining")
-        .and_then(parse_json_number)
-        .map(clamp_percent)?;
-    Some(clamp_percent((100 - percent_remaining) as f64))
-}
-
-fn parse_reset_date_to_ts(reset_date: Option<&str>) -> Option<i64> {
-    let reset_date = reset_date?.trim();
-    if reset_date.is_empty() {
-        return None;
-    }
-    chrono::DateTime::parse_from_rfc3339(reset_date)
-        .ok()
-        .map(|value| value.timestamp())
-}
-
-fn clamp_percent(value: f64) -> i32 {
-    value.round().clamp(0.0, 100.0) as i32
-}
-
-fn build_model_quota_lines(lang: &str, models: &[crate::models::quota::ModelQuota]) -> Vec<String> {
-    let mut lines = Vec::new();
-    for model in models.iter().take(4) {
-        let reset_text = format_reset_time(lang, &model.reset_time);
-        if reset_text.is_empty() {
-            lines.push(format!("{}: {}%", model.name, model.percentage));
-        } else {
-            lines.push(format!(
+/// 统一配额模型的展示行：名称、剩余百分比与距重置的时长
+fn build_meter_lines(lang: &str, meters: &[QuotaMeter]) -> Vec<String> {
+    meters
+        .iter()
+        .map(|meter| match meter.reset_at {
+            Some(reset_at) => format!(
                 "{}: {}% · {} {}",
-                model.name,
-                model.percentage,
+                meter.name,
+                meter.percentage(),
                 get_text("reset", lang),
-                reset_text
-            ));
-        }
-    }
+                format_reset_time_from_ts(lang, Some(reset_at))
+            ),
+            None => format!("{}: {}%", meter.name, meter.percentage()),
+        })
+        .collect()
+}
+
+fn non_empty_lines(lines: Vec<String>, placeholder: String) -> Vec<String> {
     if lines.is_empty() {
-        lines.push("—".to_string());
+        vec![placeholder]
+    } else {
+        lines
     }
-    lines
 }
 
 fn format_reset_time_from_ts(lang: &str, reset_ts: Option<i64>) -> String {
@@ -1026,29 +608,6 @@ fn format_remaining_duration(remaining_secs: i64) -> String {
     }
 }
 
-/// 格式化重置时间
-fn format_reset_time(lang: &str, reset_time: &str) -> String {
-    if let Ok(reset) = chrono::DateTime::parse_from_rfc3339(reset_time) {
-        let now = chrono::Utc::now();
-        let duration = reset.signed_duration_since(now);
-
-        if duration.num_seconds() <= 0 {
-            return get_text("reset_done", lang);
-        }
-
-        let hours = duration.num_hours();
-        let minutes = duration.num_minutes() % 60;
-
-        if hours > 0 {
-            format!("{}h {}m", hours, minutes)
-        } else {
-            format!("{}m", minutes)
-        }
-    } else {
-        reset_time.to_string()
-    }
-}
-
 /// 处理菜单事件
 fn handle_menu_event<R: Runtime>(app: &tauri::AppHandle<R>, event: tauri::menu::MenuEvent) {
     let id = event.id().as_ref();
@@ -5,6 +5,8 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
+use crate::modules::atomic_file;
+
 const TRAY_LAYOUT_FILE: &str = "tray_layout.json";
 
 pub const PLATFORM_ANTIGRAVITY: &str = "antigravity";
@@ -171,6 +173,6 @@ pub fn save_tray_layout(
     let path = get_tray_layout_path()?;
     let content = serde_json::to_string_pretty(&normalized)
         .map_err(|e| format!("序列化托盘布局配置失败: {}", e))?;
-    fs::write(&path, content).map_err(|e| format!("保存托盘布局配置失败: {}", e))?;
+    atomic_file::write(&path, content).map_err(|e| format!("保存托盘布局配置失败: {}", e))?;
     Ok(normalized)
 }
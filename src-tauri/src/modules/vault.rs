@@ -0,0 +1,626 @@
+//! 账号凭据加密存储
+//!
+//! 各平台账号详情在写入本地数据库前经由这里加密，读取后经由这里解密。启用后内容以
+//! AES-256-GCM 加密，密钥来自口令（Argon2id 派生）、系统密钥环（Linux Secret Service）或密钥文件。
+//! 未启用时按明文存取；已启用但未解锁时，加密数据无法读取，写入会被拒绝。
+//! 例外：与 VS Code 扩展共享的 Antigravity 账号文件始终是明文（扩展无法解密，见 `data_store`）。
+//! 启用时会重建数据库文件清除残留的明文；启用前的旧版 JSON 备份与数据目录快照不会改动，
+//! 其位置列在状态的 `plaintext_copies` 中，由用户确认后删除。
+
+use aes_gcm::aead::generic_array::GenericArray;
+use aes_gcm::aead::{Aead, AeadCore, OsRng};
+use aes_gcm::{Aes256Gcm, KeyInit, Nonce};
+use base64::{engine::general_purpose, Engine as _};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex, RwLock};
+use zeroize::Zeroizing;
+
+use crate::modules::{account, atomic_file, backup, data_store, logger};
+
+/// 凭据库配置文件名
+const VAULT_CONFIG_FILE: &str = "vault.json";
+/// 加密文件格式标识
+const ENVELOPE_FORMAT: &str = "cockpit-vault-v1";
+/// 用于校验密钥是否正确的明文样本
+const CHECK_PLAINTEXT: &[u8] = b"cockpit-tools-vault";
+/// Secret Service 中的条目标识
+#[cfg(target_os = "linux")]
+const KEYRING_SERVICE: &str = "cockpit-tools";
+#[cfg(target_os = "linux")]
+const KEYRING_USER: &str = "account-vault";
+
+type VaultKey = Zeroizing<[u8; 32]>;
+
+/// 当前已解锁的密钥
+static VAULT_KEY: LazyLock<RwLock<Option<VaultKey>>> = LazyLock::new(|| RwLock::new(None));
+/// 启用 / 停用 / 迁移互斥
+static VAULT_OP_LOCK: LazyLock<Mutex<()>> = LazyLock::new(|| Mutex::new(()));
+
+/// 密钥来源
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyProviderKind {
+    /// 口令（Argon2id 派生，每次启动需手动解锁）
+    Passphrase,
+    /// 系统密钥环（Linux Secret Service，启动时自动解锁）
+    SecretService,
+    /// 密钥文件（启动时自动解锁，文件可放在移动存储上）
+    KeyFile,
+}
+
+impl KeyProviderKind {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value.trim() {
+            "passphrase" => Ok(KeyProviderKind::Passphrase),
+            "secret_service" => Ok(KeyProviderKind::SecretService),
+            "key_file" => Ok(KeyProviderKind::KeyFile),
+            other => Err(format!("未知的密钥来源: {}", other)),
+        }
+    }
+}
+
+/// 加密后的文件内容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EncryptedEnvelope {
+    format: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// 凭据库配置（不含密钥本身）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct VaultConfig {
+    provider: KeyProviderKind,
+    /// 口令派生使用的盐（Base64）
+    #[serde(default)]
+    salt: Option<String>,
+    /// 密钥文件路径
+    #[serde(default)]
+    key_file_path: Option<String>,
+    /// 用于校验密钥的加密样本
+    check: EncryptedEnvelope,
+    created_at: i64,
+}
+
+/// 凭据库状态（供前端展示）
+#[derive(Debug, Clone, Serialize)]
+pub struct VaultStatus {
+    pub enabled: bool,
+    pub locked: bool,
+    pub provider: Option<KeyProviderKind>,
+    pub key_file_path: Option<String>,
+    /// 启用前留下的明文账号数据副本（旧版 JSON 备份、数据目录快照），由用户确认后手动删除
+    pub plaintext_copies: Vec<String>,
+}
+
+fn config_path() -> Result<PathBuf, String> {
+    Ok(account::get_data_dir()?.join(VAULT_CONFIG_FILE))
+}
+
+fn load_config() -> Result<Option<VaultConfig>, String> {
+    let path = config_path()?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&path).map_err(|e| format!("读取凭据库配置失败: {}", e))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("解析凭据库配置失败: {}", e))
+}
+
+fn save_config(config: &VaultConfig) -> Result<(), String> {
+    let path = config_path()?;
+    let content =
+        serde_json::to_string_pretty(config).map_err(|e| format!("序列化凭据库配置失败: {}", e))?;
+    atomic_file::write(&path, content.as_bytes())
+}
+
+fn current_key() -> Option<VaultKey> {
+    VAULT_KEY.read().ok().and_then(|key| key.clone())
+}
+
+fn set_key(key: Option<VaultKey>) {
+    if let Ok(mut current) = VAULT_KEY.write() {
+        *current = key;
+    }
+}
+
+fn random_key() -> VaultKey {
+    let mut key = Zeroizing::new([0u8; 32]);
+    OsRng.fill_bytes(key.as_mut());
+    key
+}
+
+fn encrypt_bytes(key: &[u8; 32], plaintext: &[u8]) -> Result<EncryptedEnvelope, String> {
+    let cipher = Aes256Gcm::new(GenericArray::from_slice(key));
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|e| format!("加密失败: {}", e))?;
+    Ok(EncryptedEnvelope {
+        format: ENVELOPE_FORMAT.to_string(),
+        nonce: general_purpose::STANDARD.encode(nonce),
+        ciphertext: general_purpose::STANDARD.encode(ciphertext),
+    })
+}
+
+fn decrypt_bytes(key: &[u8; 32], envelope: &EncryptedEnvelope) -> Result<Vec<u8>, String> {
+    let nonce = general_purpose::STANDARD
+        .decode(&envelope.nonce)
+        .map_err(|e| format!("解析加密数据失败: {}", e))?;
+    if nonce.len() != 12 {
+        return Err("解析加密数据失败: nonce 长度无效".to_string());
+    }
+    let ciphertext = general_purpose::STANDARD
+        .decode(&envelope.ciphertext)
+        .map_err(|e| format!("解析加密数据失败: {}", e))?;
+    let cipher = Aes256Gcm::new(GenericArray::from_slice(key));
+    cipher
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_ref())
+        .map_err(|_| "解密失败：密钥不正确或文件已损坏".to_string())
+}
+
+/// 解析加密文件内容，明文 JSON 返回 None
+fn parse_envelope(content: &str) -> Option<EncryptedEnvelope> {
+    if !content.contains(ENVELOPE_FORMAT) {
+        return None;
+    }
+    serde_json::from_str::<EncryptedEnvelope>(content)
+        .ok()
+        .filter(|envelope| envelope.format == ENVELOPE_FORMAT)
+}
+
+fn encrypt_content(key: &[u8; 32], content: &str) -> Result<String, String> {
+    let envelope = encrypt_bytes(key, content.as_bytes())?;
+    serde_json::to_string_pretty(&envelope).map_err(|e| format!("序列化加密数据失败: {}", e))
+}
+
+fn decrypt_content(key: &[u8; 32], envelope: &EncryptedEnvelope) -> Result<String, String> {
+    let plaintext = decrypt_bytes(key, envelope)?;
+    String::from_utf8(plaintext).map_err(|e| format!("解密后的数据无效: {}", e))
+}
+
+// ---------------------------------------------------------------------------
+// 密钥来源
+// ---------------------------------------------------------------------------
+
+fn derive_passphrase_key(passphrase: &str, salt: &[u8]) -> Result<VaultKey, String> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    argon2::Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), salt, key.as_mut())
+        .map_err(|e| format!("口令派生密钥失败: {}", e))?;
+    Ok(key)
+}
+
+fn decode_key(encoded: &str) -> Result<VaultKey, String> {
+    let bytes = Zeroizing::new(
+        general_purpose::STANDARD
+            .decode(encoded.trim())
+            .map_err(|e| format!("密钥格式无效: {}", e))?,
+    );
+    if bytes.len() != 32 {
+        return Err("密钥长度无效".to_string());
+    }
+    let mut key = Zeroizing::new([0u8; 32]);
+    key.copy_from_slice(&bytes);
+    Ok(key)
+}
+
+fn encode_key(key: &[u8; 32]) -> Zeroizing<String> {
+    Zeroizing::new(general_purpose::STANDARD.encode(key))
+}
+
+#[cfg(target_os = "linux")]
+fn secret_service_entry() -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+        .map_err(|e| format!("打开系统密钥环失败: {}", e))
+}
+
+#[cfg(target_os = "linux")]
+fn secret_service_store(key: &[u8; 32]) -> Result<(), String> {
+    secret_service_entry()?
+        .set_password(&encode_key(key))
+        .map_err(|e| format!("写入系统密钥环失败: {}", e))
+}
+
+#[cfg(target_os = "linux")]
+fn secret_service_load() -> Result<VaultKey, String> {
+    let encoded = Zeroizing::new(
+        secret_service_entry()?
+            .get_password()
+            .map_err(|e| format!("读取系统密钥环失败: {}", e))?,
+    );
+    decode_key(&encoded)
+}
+
+#[cfg(target_os = "linux")]
+fn secret_service_delete() {
+    if let Ok(entry) = secret_service_entry() {
+        let _ = entry.delete_credential();
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn secret_service_store(_key: &[u8; 32]) -> Result<(), String> {
+    Err("当前系统不支持 Secret Service 密钥环".to_string())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn secret_service_load() -> Result<VaultKey, String> {
+    Err("当前系统不支持 Secret Service 密钥环".to_string())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn secret_service_delete() {}
+
+/// 写入新密钥文件：先以 0600 权限创建同目录临时文件并落盘，再 rename 到目标路径，
+/// 避免出现短暂可被其他用户读取或只写了一半的密钥文件
+fn key_file_store(path: &Path, key: &[u8; 32]) -> Result<(), String> {
+    let parent = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    fs::create_dir_all(parent).map_err(|e| format!("创建密钥文件目录失败: {}", e))?;
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| format!("无效的密钥文件路径: {}", path.display()))?;
+    let temp_path = parent.join(format!(
+        ".{}.{}.tmp",
+        file_name.to_string_lossy(),
+        std::process::id()
+    ));
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let result = options
+        .open(&temp_path)
+        .and_then(|mut file| {
+            file.write_all(encode_key(key).as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&temp_path, path));
+    if let Err(e) = result {
+        let _ = fs::remove_file(&temp_path);
+        return Err(format!("写入密钥文件失败: {}", e));
+    }
+    Ok(())
+}
+
+fn key_file_load(path: &Path) -> Result<VaultKey, String> {
+    let encoded =
+        Zeroizing::new(fs::read_to_string(path).map_err(|e| format!("读取密钥文件失败: {}", e))?);
+    decode_key(&encoded)
+}
+
+/// 按配置的密钥来源取回密钥
+fn load_key(config: &VaultConfig, passphrase: Option<&str>) -> Result<VaultKey, String> {
+    match config.provider {
+        KeyProviderKind::Passphrase => {
+            let passphrase = passphrase
+                .filter(|value| !value.is_empty())
+                .ok_or("请输入口令")?;
+            let salt = general_purpose::STANDARD
+                .decode(config.salt.as_deref().unwrap_or_default())
+                .map_err(|e| format!("凭据库配置无效: {}", e))?;
+            derive_passphrase_key(passphrase, &salt)
+        }
+        KeyProviderKind::SecretService => secret_service_load(),
+        KeyProviderKind::KeyFile => {
+            let path = config
+                .key_file_path
+                .as_deref()
+                .ok_or("凭据库配置缺少密钥文件路径")?;
+            key_file_load(Path::new(path))
+        }
+    }
+}
+
+/// 为新凭据库生成密钥并交给密钥来源保存，返回 (密钥, 盐, 密钥文件路径)
+fn create_key(
+    provider: KeyProviderKind,
+    passphrase: Option<&str>,
+    key_file_path: Option<&str>,
+) -> Result<(VaultKey, Option<String>, Option<String>), String> {
+    match provider {
+        KeyProviderKind::Passphrase => {
+            let passphrase = passphrase.unwrap_or_default();
+            if passphrase.chars().count() < 8 {
+                return Err("口令长度不能少于 8 个字符".to_string());
+            }
+            let mut salt = [0u8; 16];
+            OsRng.fill_bytes(&mut salt);
+            let key = derive_passphrase_key(passphrase, &salt)?;
+            Ok((key, Some(general_purpose::STANDARD.encode(salt)), None))
+        }
+        KeyProviderKind::SecretService => {
+            let key = random_key();
+            secret_service_store(&key)?;
+            Ok((key, None, None))
+        }
+        KeyProviderKind::KeyFile => {
+            let path = key_file_path
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .ok_or("请选择密钥文件路径")?;
+            let path = PathBuf::from(path);
+            // 已有密钥文件时直接复用（例如在另一台设备上恢复备份）
+            let key = if path.exists() {
+                key_file_load(&path)?
+            } else {
+                let key = random_key();
+                key_file_store(&path, &key)?;
+                key
+            };
+            Ok((key, None, Some(path.to_string_lossy().to_string())))
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// 账号数据加解密
+// ---------------------------------------------------------------------------
+
+/// 还原存储的账号数据，已加密的内容在解锁状态下解密后返回，明文原样返回
+pub fn open(content: &str) -> Result<String, String> {
+    let Some(envelope) = parse_envelope(content) else {
+        return Ok(content.to_string());
+    };
+    let key = current_key().ok_or("凭据库已锁定，请先解锁")?;
+    decrypt_content(&key, &envelope)
+}
+
+/// 生成待存储的账号数据，凭据库启用时加密；已启用但未解锁时拒绝，避免明文落盘
+pub fn seal(content: &str) -> Result<String, String> {
+    if let Some(key) = current_key() {
+        return encrypt_content(&key, content);
+    }
+    if config_path()?.exists() {
+        return Err("凭据库已锁定，请先解锁".to_string());
+    }
+    Ok(content.to_string())
+}
+
+/// 凭据库已启用但未解锁时返回错误（备份、恢复等需要读写全部账号的操作先行检查）
+pub fn ensure_unlocked() -> Result<(), String> {
+    if current_key().is_none() && config_path()?.exists() {
+        return Err("凭据库已锁定，请先解锁".to_string());
+    }
+    Ok(())
+}
+
+/// 用独立口令加密任意内容（如备份归档），与凭据库密钥无关，返回 (盐, 加密内容)
+pub fn seal_with_passphrase(passphrase: &str, content: &str) -> Result<(String, String), String> {
+    if passphrase.chars().count() < 8 {
+        return Err("口令长度不能少于 8 个字符".to_string());
+    }
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+    let key = derive_passphrase_key(passphrase, &salt)?;
+    Ok((
+        general_purpose::STANDARD.encode(salt),
+        encrypt_content(&key, content)?,
+    ))
+}
+
+/// 解密 `seal_with_passphrase` 生成的内容
+pub fn open_with_passphrase(passphrase: &str, salt: &str, sealed: &str) -> Result<String, String> {
+    let salt = general_purpose::STANDARD
+        .decode(salt)
+        .map_err(|e| format!("解析加密数据失败: {}", e))?;
+    let envelope = parse_envelope(sealed).ok_or("解析加密数据失败: 格式无效")?;
+    let key = derive_passphrase_key(passphrase, &salt)?;
+    decrypt_content(&key, &envelope).map_err(|_| "解密失败：口令不正确或文件已损坏".to_string())
+}
+
+/// 迁移数据库中的全部账号数据：`encrypt_key` 为 Some 时加密明文数据，否则用 `decrypt_key` 解密，
+/// 返回处理的条数
+fn migrate_accounts(
+    encrypt_key: Option<&[u8; 32]>,
+    decrypt_key: &[u8; 32],
+) -> Result<usize, String> {
+    data_store::rewrite_accounts(|content| match (encrypt_key, parse_envelope(content)) {
+        (Some(key), None) => encrypt_content(key, content).map(Some),
+        (None, Some(envelope)) => decrypt_content(decrypt_key, &envelope).map(Some),
+        _ => Ok(None),
+    })
+}
+
+// ---------------------------------------------------------------------------
+// 凭据库状态
+// ---------------------------------------------------------------------------
+
+pub fn get_status() -> VaultStatus {
+    match load_config() {
+        Ok(Some(config)) => VaultStatus {
+            enabled: true,
+            locked: current_key().is_none(),
+            provider: Some(config.provider),
+            key_file_path: config.key_file_path,
+            plaintext_copies: plaintext_copies(config.created_at),
+        },
+        Ok(None) => VaultStatus {
+            enabled: false,
+            locked: false,
+            provider: None,
+            key_file_path: None,
+            plaintext_copies: Vec::new(),
+        },
+        Err(e) => {
+            logger::log_error(&format!("[Vault] {}", e));
+            VaultStatus {
+                enabled: true,
+                locked: true,
+                provider: None,
+                key_file_path: None,
+                plaintext_copies: Vec::new(),
+            }
+        }
+    }
+}
+
+/// 启用凭据库之前留下的明文副本路径
+fn plaintext_copies(enabled_at: i64) -> Vec<String> {
+    data_store::legacy_backup_dirs()
+        .into_iter()
+        .chain(backup::snapshots_before(enabled_at))
+        .map(|path| path.to_string_lossy().to_string())
+        .collect()
+}
+
+/// 启动时自动解锁（口令模式需用户手动解锁）
+pub fn init_on_startup() {
+    let config = match load_config() {
+        Ok(Some(config)) => config,
+        Ok(None) => return,
+        Err(e) => {
+            logger::log_error(&format!("[Vault] {}", e));
+            return;
+        }
+    };
+    if config.provider == KeyProviderKind::Passphrase {
+        logger::log_info("[Vault] 凭据库已启用，等待输入口令解锁");
+        return;
+    }
+    match unlock(None) {
+        Ok(_) => logger::log_info("[Vault] 凭据库已自动解锁"),
+        Err(e) => logger::log_warn(&format!("[Vault] 自动解锁失败: {}", e)),
+    }
+}
+
+/// 解锁凭据库
+pub fn unlock(passphrase: Option<&str>) -> Result<VaultStatus, String> {
+    let config = load_config()?.ok_or("凭据库未启用")?;
+    let key = load_key(&config, passphrase)?;
+    decrypt_bytes(&key, &config.check).map_err(|_| "密钥不正确".to_string())?;
+    set_key(Some(key));
+    logger::log_info("[Vault] 凭据库已解锁");
+    Ok(get_status())
+}
+
+/// 锁定凭据库（清除内存中的密钥）
+pub fn lock() -> VaultStatus {
+    set_key(None);
+    logger::log_info("[Vault] 凭据库已锁定");
+    get_status()
+}
+
+/// 启用凭据库并加密已有账号数据
+pub fn enable(
+    provider: KeyProviderKind,
+    passphrase: Option<&str>,
+    key_file_path: Option<&str>,
+) -> Result<VaultStatus, String> {
+    let _lock = VAULT_OP_LOCK
+        .lock()
+        .map_err(|e| format!("获取锁失败: {}", e))?;
+    if load_config()?.is_some() {
+        return Err("凭据库已启用".to_string());
+    }
+
+    let key_file_existed = key_file_path
+        .map(|path| Path::new(path.trim()).exists())
+        .unwrap_or(false);
+    let (key, salt, key_file_path) = create_key(provider, passphrase, key_file_path)?;
+    let config = VaultConfig {
+        provider,
+        salt,
+        key_file_path: key_file_path.clone(),
+        check: encrypt_bytes(&key, CHECK_PLAINTEXT)?,
+        created_at: chrono::Utc::now().timestamp(),
+    };
+    save_config(&config)?;
+    set_key(Some(key.clone()));
+
+    // 迁移在单个数据库事务中完成，失败时数据仍为明文，撤销配置与新建的密钥后保持未启用状态
+    let migrated = match migrate_accounts(Some(&key), &key) {
+        Ok(migrated) => migrated,
+        Err(e) => {
+            set_key(None);
+            if let Ok(path) = config_path() {
+                let _ = fs::remove_file(path);
+            }
+            match (provider, key_file_path) {
+                (KeyProviderKind::SecretService, _) => secret_service_delete(),
+                (KeyProviderKind::KeyFile, Some(path)) if !key_file_existed => {
+                    let _ = fs::remove_file(path);
+                }
+                _ => {}
+            }
+            return Err(format!("加密账号数据失败，凭据库未启用: {}", e));
+        }
+    };
+    logger::log_info(&format!(
+        "[Vault] 凭据库已启用: provider={:?}, 已加密 {} 条账号数据",
+        provider, migrated
+    ));
+    // 改写前的明文仍可能留在数据库空闲页与 WAL 中
+    if let Err(e) = data_store::vacuum() {
+        logger::log_warn(&format!("[Vault] 清理数据库残留数据失败: {}", e));
+    }
+    let status = get_status();
+    if !status.plaintext_copies.is_empty() {
+        logger::log_warn(&format!(
+            "[Vault] 以下位置仍保留启用前的明文账号数据: {}",
+            status.plaintext_copies.join(", ")
+        ));
+    }
+    Ok(status)
+}
+
+/// 停用凭据库：解密全部账号数据并删除凭据库配置（需处于解锁状态）
+pub fn disable() -> Result<VaultStatus, String> {
+    let _lock = VAULT_OP_LOCK
+        .lock()
+        .map_err(|e| format!("获取锁失败: {}", e))?;
+    let config = load_config()?.ok_or("凭据库未启用")?;
+    let key = current_key().ok_or("凭据库已锁定，请先解锁")?;
+
+    let migrated = migrate_accounts(None, &key)?;
+    fs::remove_file(config_path()?).map_err(|e| format!("删除凭据库配置失败: {}", e))?;
+    if config.provider == KeyProviderKind::SecretService {
+        secret_service_delete();
+    }
+    set_key(None);
+    logger::log_info(&format!(
+        "[Vault] 凭据库已停用，已解密 {} 条账号数据",
+        migrated
+    ));
+    Ok(get_status())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypts_and_detects_envelopes() {
+        let key = random_key();
+        let content = r#"{"id":"a","refresh_token":"secret"}"#;
+        let encrypted = encrypt_content(&key, content).unwrap();
+        assert!(!encrypted.contains("secret"));
+
+        let envelope = parse_envelope(&encrypted).unwrap();
+        assert_eq!(decrypt_content(&key, &envelope).unwrap(), content);
+        assert!(decrypt_content(&random_key(), &envelope).is_err());
+        assert!(parse_envelope(content).is_none());
+    }
+
+    #[test]
+    fn passphrase_key_depends_on_salt_and_passphrase() {
+        let salt = [7u8; 16];
+        let a = derive_passphrase_key("correct horse", &salt).unwrap();
+        let b = derive_passphrase_key("correct horse", &salt).unwrap();
+        let c = derive_passphrase_key("wrong horse", &salt).unwrap();
+        assert_eq!(*a, *b);
+        assert_ne!(*a, *c);
+    }
+}
@@ -4,6 +4,7 @@ use serde_json::json;
 use std::collections::HashMap;
 use std::sync::{Mutex, OnceLock};
 
+use crate::models::Account;
 use crate::modules;
 
 const CLOUD_CODE_BASE_URLS: [&str; 3] = [
@@ -83,8 +84,6 @@ fn build_request_body(
     prompt: &str,
     max_output_tokens: u32,
 ) -> serde_json::Value {
-    let request_id = generate_request_id();
-    let session_id = generate_session_id();
     let mut generation_config = json!({ "temperature": 0 });
     if max_output_tokens > 0 {
         if let Some(obj) = generation_config.as_object_mut() {
@@ -92,22 +91,49 @@ fn build_request_body(
         }
     }
 
+    build_generate_body(
+        project_id,
+        model,
+        json!([{ "role": "user", "parts": [ { "text": prompt } ] }]),
+        Vec::new(),
+        generation_config,
+        None,
+    )
+}
+
+/// 构造多轮对话的 streamGenerateContent 请求体（供本地 API 代理使用）
+///
+/// `system_parts` 会追加在 Antigravity 系统提示词之后；`tools` 为 Gemini 格式的工具声明。
+pub(crate) fn build_generate_body(
+    project_id: &str,
+    model: &str,
+    contents: serde_json::Value,
+    system_parts: Vec<serde_json::Value>,
+    generation_config: serde_json::Value,
+    tools: Option<serde_json::Value>,
+) -> serde_json::Value {
+    let request_id = generate_request_id();
+    let session_id = generate_session_id();
+    let mut parts = vec![json!({ "text": ANTIGRAVITY_SYSTEM_PROMPT })];
+    parts.extend(system_parts);
+
+    let mut request = json!({
+        "contents": contents,
+        "session_id": session_id,
+        "systemInstruction": { "parts": parts },
+        "generationConfig": generation_config
+    });
+    if let (Some(tools), Some(obj)) = (tools, request.as_object_mut()) {
+        obj.insert("tools".to_string(), tools);
+    }
+
     json!({
         "project": project_id,
         "requestId": request_id,
         "model": model,
         "userAgent": "antigravity",
         "requestType": "agent",
-        "request": {
-            "contents": [
-                { "role": "user", "parts": [ { "text": prompt } ] }
-            ],
-            "session_id": session_id,
-            "systemInstruction": {
-                "parts": [ { "text": ANTIGRAVITY_SYSTEM_PROMPT } ]
-            },
-            "generationConfig": generation_config
-        }
+        "request": request
     })
 }
 
@@ -128,12 +154,26 @@ fn get_base_url_order() -> Vec<&'static str> {
     }
 }
 
-fn promote_base_url(base: &'static str) {
+/// 请求使用的 Cloud Code 地址列表；设置 `ANTIGRAVITY_CLOUD_CODE_URL_OVERRIDE` 时只使用该地址
+fn get_stream_base_urls() -> Vec<String> {
+    if let Ok(value) = std::env::var("ANTIGRAVITY_CLOUD_CODE_URL_OVERRIDE") {
+        let trimmed = value.trim().trim_end_matches('/');
+        if !trimmed.is_empty() {
+            return vec![trimmed.to_string()];
+        }
+    }
+    get_base_url_order()
+        .into_iter()
+        .map(|base| base.to_string())
+        .collect()
+}
+
+fn promote_base_url(base: &str) {
     let lock = BASE_URL_ORDER.get_or_init(|| Mutex::new(CLOUD_CODE_BASE_URLS.to_vec()));
     if let Ok(mut list) = lock.lock() {
         if let Some(pos) = list.iter().position(|item| *item == base) {
-            list.remove(pos);
-            list.insert(0, base);
+            let item = list.remove(pos);
+            list.insert(0, item);
         }
     }
 }
@@ -324,7 +364,7 @@ async fn send_stream_request(
     body: &serde_json::Value,
 ) -> Result<StreamParseResult, String> {
     let mut last_error: Option<String> = None;
-    for base in get_base_url_order() {
+    for base in get_stream_base_urls() {
         for attempt in 1..=DEFAULT_ATTEMPTS {
             let url = format!("{}{}", base, STREAM_PATH);
             crate::modules::logger::log_info(&format!(
@@ -352,7 +392,7 @@ async fn send_stream_request(
                         ));
                         match parse_stream_result(&text) {
                             Ok(parsed) => {
-                                promote_base_url(base);
+                                promote_base_url(&base);
                                 crate::modules::logger::log_info(&format!(
                                     "[Wakeup] 请求成功: url={}, status={}",
                                     url, status
@@ -434,30 +474,19 @@ async fn send_stream_request(
     Err(last_error.unwrap_or_else(|| "唤醒请求失败".to_string()))
 }
 
-/// 触发单个账号的唤醒请求
-pub async fn trigger_wakeup(
-    account_id: &str,
-    model: &str,
-    prompt: &str,
-    max_output_tokens: u32,
-) -> Result<WakeupResponse, String> {
-    let mut account = modules::load_account(account_id)?;
-    crate::modules::logger::log_info(&format!(
-        "[Wakeup] 开始唤醒: email={}, model={}, max_tokens={}, prompt={}",
-        account.email,
-        model,
-        max_output_tokens,
-        format_prompt_for_log(prompt)
-    ));
+/// 刷新账号 Token 并解析项目 ID，返回 (access_token, project_id)
+///
+/// Token 或项目 ID 有变化时会写回账号文件。
+pub(crate) async fn prepare_account_request(
+    account: &mut Account,
+) -> Result<(String, String), String> {
     let mut token = modules::oauth::ensure_fresh_token(&account.token).await?;
 
-    let (project_id, _) =
-        modules::quota::fetch_project_id_for_token(&token, &account.email).await;
+    let (project_id, _) = modules::quota::fetch_project_id_for_token(&token, &account.email).await;
     let final_project_id = project_id
         .clone()
         .or_else(|| token.project_id.clone())
         .unwrap_or_else(generate_fallback_project_id);
-    crate::modules::logger::log_info(&format!("[Wakeup] 项目ID: {}", final_project_id));
 
     if token.project_id.is_none() && project_id.is_some() {
         token.project_id = project_id.clone();
@@ -468,14 +497,284 @@ pub async fn trigger_wakeup(
         || token.project_id != account.token.project_id
     {
         account.token = token.clone();
-        let _ = modules::save_account(&account);
+        let _ = modules::save_account(account);
+    }
+
+    Ok((token.access_token, final_project_id))
+}
+
+/// 打开流式请求失败的原因
+#[derive(Debug)]
+pub(crate) struct StreamOpenError {
+    /// 上游 HTTP 状态码（网络错误时为 None）
+    pub status: Option<u16>,
+    pub message: String,
+}
+
+impl StreamOpenError {
+    /// 授权失效、无权限或限流，属于账号级错误，调用方应换号重试
+    pub fn is_account_error(&self) -> bool {
+        matches!(self.status, Some(401) | Some(403) | Some(429))
+    }
+}
+
+impl std::fmt::Display for StreamOpenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+/// 流式响应中的单个内容片段
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum StreamPart {
+    Text(String),
+    Thought {
+        text: String,
+        signature: Option<String>,
+    },
+    FunctionCall {
+        id: Option<String>,
+        name: String,
+        args: serde_json::Value,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct StreamUsage {
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+    pub total_tokens: u32,
+}
+
+/// 一条 SSE 事件解析后的内容
+#[derive(Debug, Clone, Default)]
+pub(crate) struct StreamEvent {
+    pub parts: Vec<StreamPart>,
+    pub finish_reason: Option<String>,
+    pub usage: Option<StreamUsage>,
+    pub response_id: Option<String>,
+}
+
+pub(crate) fn parse_stream_event(obj: &serde_json::Value) -> StreamEvent {
+    let response = obj.get("response").unwrap_or(obj);
+    let candidate = response.get("candidates").and_then(|value| value.get(0));
+    let mut event = StreamEvent::default();
+
+    if let Some(parts) = candidate
+        .and_then(|value| value.get("content"))
+        .and_then(|value| value.get("parts"))
+        .and_then(|value| value.as_array())
+    {
+        for part in parts {
+            if let Some(call) = part.get("functionCall") {
+                event.parts.push(StreamPart::FunctionCall {
+                    id: call
+                        .get("id")
+                        .and_then(|value| value.as_str())
+                        .map(|value| value.to_string()),
+                    name: call
+                        .get("name")
+                        .and_then(|value| value.as_str())
+                        .unwrap_or_default()
+                        .to_string(),
+                    args: call.get("args").cloned().unwrap_or_else(|| json!({})),
+                });
+                continue;
+            }
+            let text = part
+                .get("text")
+                .and_then(|value| value.as_str())
+                .unwrap_or_default();
+            let signature = part
+                .get("thoughtSignature")
+                .and_then(|value| value.as_str())
+                .map(|value| value.to_string());
+            if part.get("thought").and_then(|value| value.as_bool()) == Some(true) {
+                if !text.is_empty() || signature.is_some() {
+                    event.parts.push(StreamPart::Thought {
+                        text: text.to_string(),
+                        signature,
+                    });
+                }
+            } else if !text.is_empty() {
+                event.parts.push(StreamPart::Text(text.to_string()));
+            }
+        }
     }
 
+    event.finish_reason = candidate
+        .and_then(|value| value.get("finishReason"))
+        .and_then(|value| value.as_str())
+        .map(|value| value.to_string());
+
+    if let Some(usage) = response.get("usageMetadata") {
+        let read = |key: &str| usage.get(key).and_then(|value| value.as_u64()).unwrap_or(0) as u32;
+        event.usage = Some(StreamUsage {
+            prompt_tokens: read("promptTokenCount"),
+            completion_tokens: read("candidatesTokenCount"),
+            total_tokens: read("totalTokenCount"),
+        });
+    }
+
+    event.response_id = response
+        .get("responseId")
+        .and_then(|value| value.as_str())
+        .map(|value| value.to_string());
+    event
+}
+
+/// 逐条读取 Cloud Code SSE 事件（不缓冲整个响应）
+pub(crate) struct CloudCodeStream {
+    response: reqwest::Response,
+    buffer: Vec<u8>,
+    finished: bool,
+}
+
+impl CloudCodeStream {
+    pub async fn next_event(&mut self) -> Result<Option<StreamEvent>, String> {
+        loop {
+            if let Some(pos) = self.buffer.iter().position(|byte| *byte == b'\n') {
+                let line: Vec<u8> = self.buffer.drain(..=pos).collect();
+                let line = String::from_utf8_lossy(&line);
+                let trimmed = line.trim();
+                let payload = if let Some(rest) = trimmed.strip_prefix("data:") {
+                    rest.trim()
+                } else if trimmed.starts_with('{') {
+                    trimmed
+                } else {
+                    continue;
+                };
+                if payload.is_empty() || payload == "[DONE]" {
+                    continue;
+                }
+                if let Ok(value) = serde_json::from_str::<serde_json::Value>(payload) {
+                    return Ok(Some(parse_stream_event(&value)));
+                }
+                continue;
+            }
+
+            if self.finished {
+                if self.buffer.is_empty() {
+                    return Ok(None);
+                }
+                self.buffer.push(b'\n');
+                continue;
+            }
+
+            match self.response.chunk().await {
+                Ok(Some(bytes)) => self.buffer.extend_from_slice(&bytes),
+                Ok(None) => self.finished = true,
+                Err(err) => return Err(format!("读取流式响应失败: {}", err)),
+            }
+        }
+    }
+}
+
+/// 发起流式请求并返回逐条读取的事件流
+///
+/// 与唤醒请求不同，4xx 错误不会在同一账号上重试，由调用方决定是否换号。
+pub(crate) async fn open_stream(
+    client: &reqwest::Client,
+    access_token: &str,
+    body: &serde_json::Value,
+) -> Result<CloudCodeStream, StreamOpenError> {
+    open_stream_at(client, &get_stream_base_urls(), access_token, body).await
+}
+
+/// 依次尝试 `base_urls` 中的 Cloud Code 地址发起流式请求
+pub(crate) async fn open_stream_at(
+    client: &reqwest::Client,
+    base_urls: &[String],
+    access_token: &str,
+    body: &serde_json::Value,
+) -> Result<CloudCodeStream, StreamOpenError> {
+    let mut last_error = StreamOpenError {
+        status: None,
+        message: "Cloud Code 请求失败".to_string(),
+    };
+    for base in base_urls {
+        for attempt in 1..=DEFAULT_ATTEMPTS {
+            let url = format!("{}{}", base, STREAM_PATH);
+            let response = client
+                .post(&url)
+                .bearer_auth(access_token)
+                .header(reqwest::header::USER_AGENT, USER_AGENT)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header(reqwest::header::ACCEPT_ENCODING, "gzip")
+                .json(body)
+                .send()
+                .await;
+
+            match response {
+                Ok(res) if res.status().is_success() => {
+                    promote_base_url(base);
+                    return Ok(CloudCodeStream {
+                        response: res,
+                        buffer: Vec::new(),
+                        finished: false,
+                    });
+                }
+                Ok(res) => {
+                    let status = res.status();
+                    let text = res.text().await.unwrap_or_default();
+                    last_error = StreamOpenError {
+                        status: Some(status.as_u16()),
+                        message: format!(
+                            "Cloud Code 请求失败: {} - {}",
+                            status,
+                            truncate_log_text(&text, 500)
+                        ),
+                    };
+                    if status.as_u16() < 500 {
+                        return Err(last_error);
+                    }
+                }
+                Err(err) => {
+                    last_error = StreamOpenError {
+                        status: None,
+                        message: format!("Cloud Code 请求失败: {}", err),
+                    };
+                }
+            }
+
+            crate::modules::logger::log_warn(&format!(
+                "[Wakeup] 流式请求失败: url={}, attempt={}/{}, error={}",
+                url, attempt, DEFAULT_ATTEMPTS, last_error.message
+            ));
+            if attempt < DEFAULT_ATTEMPTS {
+                let delay = get_backoff_delay_ms(attempt + 1);
+                if delay > 0 {
+                    tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
+                }
+            }
+        }
+    }
+    Err(last_error)
+}
+
+/// 触发单个账号的唤醒请求
+pub async fn trigger_wakeup(
+    account_id: &str,
+    model: &str,
+    prompt: &str,
+    max_output_tokens: u32,
+) -> Result<WakeupResponse, String> {
+    let mut account = modules::load_account(account_id)?;
+    crate::modules::logger::log_info(&format!(
+        "[Wakeup] 开始唤醒: email={}, model={}, max_tokens={}, prompt={}",
+        account.email,
+        model,
+        max_output_tokens,
+        format_prompt_for_log(prompt)
+    ));
+    let (access_token, final_project_id) = prepare_account_request(&mut account).await?;
+    crate::modules::logger::log_info(&format!("[Wakeup] 项目ID: {}", final_project_id));
+
     let client = crate::utils::http::create_client(15);
     let body = build_request_body(&final_project_id, model, prompt, max_output_tokens);
     let started = std::time::Instant::now();
 
-    match send_stream_request(&client, &token.access_token, &body).await {
+    match send_stream_request(&client, &access_token, &body).await {
         Ok(parsed) => {
             let duration_ms = started.elapsed().as_millis() as u64;
             crate::modules::logger::log_info(&format!(
@@ -4,16 +4,18 @@ use std::time::Duration;
 
 use chrono::{DateTime, Datelike, Local, TimeZone, Timelike};
 use serde::{Deserialize, Serialize};
-use tauri::{AppHandle, Emitter};
+use tauri::Emitter;
 use tokio::time::sleep;
 
 use crate::modules;
+use crate::modules::atomic_file;
 
 const DEFAULT_PROMPT: &str = "hi";
 const RESET_TRIGGER_COOLDOWN_MS: i64 = 10 * 60 * 1000;
 const RESET_SAFETY_MARGIN_MS: i64 = 2 * 60 * 1000;
+const TASKS_FILE: &str = "wakeup_tasks.json";
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct WakeupTaskInput {
     pub id: String,
@@ -24,7 +26,7 @@ pub struct WakeupTaskInput {
     pub schedule: ScheduleConfig,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ScheduleConfig {
     pub repeat_mode: String,
@@ -152,7 +154,53 @@ fn normalize_schedule(raw: ScheduleConfig) -> ScheduleConfigNormalized {
     }
 }
 
+/// 前端同步的任务快照，落盘后供无窗口（daemon）模式恢复
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PersistedWakeupState {
+    enabled: bool,
+    #[serde(default)]
+    tasks: Vec<WakeupTaskInput>,
+}
+
+fn tasks_path() -> Result<std::path::PathBuf, String> {
+    let data_dir = modules::account::get_data_dir()?;
+    Ok(data_dir.join(TASKS_FILE))
+}
+
+fn save_persisted_state(persisted: &PersistedWakeupState) -> Result<(), String> {
+    let path = tasks_path()?;
+    let content = serde_json::to_string_pretty(persisted)
+        .map_err(|e| format!("序列化唤醒任务失败: {}", e))?;
+    atomic_file::write(&path, content).map_err(|e| format!("保存唤醒任务文件失败: {}", e))
+}
+
+/// 从磁盘恢复最近一次同步的任务（无窗口模式下没有前端推送任务）
+pub fn restore_persisted_state() -> Result<usize, String> {
+    let path = tasks_path()?;
+    if !path.exists() {
+        return Ok(0);
+    }
+    let content = std::fs::read_to_string(&path).map_err(|e| format!("读取唤醒任务失败: {}", e))?;
+    let persisted: PersistedWakeupState =
+        serde_json::from_str(&content).map_err(|e| format!("解析唤醒任务失败: {}", e))?;
+    let count = persisted.tasks.len();
+    apply_state(persisted.enabled, persisted.tasks);
+    Ok(count)
+}
+
 pub fn sync_state(enabled: bool, tasks: Vec<WakeupTaskInput>) {
+    let persisted = PersistedWakeupState {
+        enabled,
+        tasks: tasks.clone(),
+    };
+    if let Err(e) = save_persisted_state(&persisted) {
+        modules::logger::log_warn(&format!("保存唤醒任务失败: {}", e));
+    }
+    apply_state(enabled, tasks);
+}
+
+fn apply_state(enabled: bool, tasks: Vec<WakeupTaskInput>) {
     let mut guard = state().lock().expect("wakeup state lock");
     guard.enabled = enabled;
     guard.tasks = tasks
@@ -168,7 +216,7 @@ pub fn sync_state(enabled: bool, tasks: Vec<WakeupTaskInput>) {
         .collect();
 }
 
-pub fn ensure_started(app: AppHandle) {
+pub fn ensure_started() {
     let mut started = started_flag().lock().expect("wakeup started lock");
     if *started {
         return;
@@ -177,7 +225,7 @@ pub fn ensure_started(app: AppHandle) {
 
     tauri::async_runtime::spawn(async move {
         loop {
-            run_scheduler_once(&app).await;
+            run_scheduler_once().await;
             sleep(Duration::from_secs(30)).await;
         }
     });
@@ -444,7 +492,7 @@ fn mark_reset_triggered(state: &mut ResetState, model_key: &str, reset_at: &str)
         .insert(model_key.to_string(), chrono::Utc::now().timestamp_millis());
 }
 
-async fn run_scheduler_once(app: &AppHandle) {
+async fn run_scheduler_once() {
     let snapshot = {
         let guard = state().lock().expect("wakeup state lock");
         guard.clone()
@@ -465,7 +513,7 @@ async fn run_scheduler_once(app: &AppHandle) {
         }
 
         if task.schedule.wake_on_reset {
-            handle_quota_reset_task(app, task, now).await;
+            handle_quota_reset_task(task, now).await;
             continue;
         }
 
@@ -485,13 +533,13 @@ async fn run_scheduler_once(app: &AppHandle) {
         // 只有到达预定时间才触发（不再提前30秒）
         if let Some(next_run) = next_run {
             if next_run <= now {
-                run_task(app, task, "scheduled").await;
+                run_task(task, "scheduled").await;
             }
         }
     }
 }
 
-async fn handle_quota_reset_task(app: &AppHandle, task: &WakeupTask, now: DateTime<Local>) {
+async fn handle_quota_reset_task(task: &WakeupTask, now: DateTime<Local>) {
     let mut should_run_fallback = false;
     if task.schedule.time_window_enabled
         && !is_in_time_window(
@@ -510,7 +558,7 @@ async fn handle_quota_reset_task(app: &AppHandle, task: &WakeupTask, now: DateTi
             }
         }
         if should_run_fallback {
-            run_task(app, task, "scheduled").await;
+            run_task(task, "scheduled").await;
         }
         return;
     }
@@ -567,32 +615,15 @@ async fn handle_quota_reset_task(app: &AppHandle, task: &WakeupTask, now: DateTi
     };
 
     if !models_to_trigger.is_empty() {
-        run_task_with_models(
-            app,
-            task,
-            "quota_reset",
-            models_to_trigger.into_iter().collect(),
-        )
-        .await;
+        run_task_with_models(task, "quota_reset", models_to_trigger.into_iter().collect()).await;
     }
 }
 
-async fn run_task(app: &AppHandle, task: &WakeupTask, trigger_source: &str) {
-    run_task_with_models(
-        app,
-        task,
-        trigger_source,
-        task.schedule.selected_models.clone(),
-    )
-    .await;
+async fn run_task(task: &WakeupTask, trigger_source: &str) {
+    run_task_with_models(task, trigger_source, task.schedule.selected_models.clone()).await;
 }
 
-async fn run_task_with_models(
-    app: &AppHandle,
-    task: &WakeupTask,
-    trigger_source: &str,
-    models: Vec<String>,
-) {
+async fn run_task_with_models(task: &WakeupTask, trigger_source: &str, models: Vec<String>) {
     if models.is_empty() {
         return;
     }
@@ -636,9 +667,17 @@ async fn run_task_with_models(
         .unwrap_or_else(|| DEFAULT_PROMPT.to_string());
     let max_tokens = normalize_max_tokens(task.schedule.max_output_tokens);
 
+    let reserve_enabled = modules::quota_protection::reserve_enabled();
     let mut history: Vec<modules::wakeup_history::WakeupHistoryItem> = Vec::new();
     for account in &selected_accounts {
         for model in &models {
+            if modules::quota_protection::is_model_protected(account, model, reserve_enabled) {
+                modules::logger::log_info(&format!(
+                    "[Wakeup] 模型处于配额保留状态，跳过: account={}, model={}",
+                    account.email, model
+                ));
+                continue;
+            }
             let started = chrono::Utc::now();
             let result =
                 modules::wakeup::trigger_wakeup(&account.id, model, &prompt, max_tokens).await;
@@ -696,7 +735,9 @@ async fn run_task_with_models(
         last_run_at: chrono::Utc::now().timestamp_millis(),
         records: history,
     };
-    let _ = app.emit("wakeup://task-result", payload);
+    if let Some(app) = crate::get_app_handle() {
+        let _ = app.emit("wakeup://task-result", payload);
+    }
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -10,7 +10,10 @@ use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::{broadcast, RwLock};
 use tokio_tungstenite::tungstenite::Message;
 
+use super::audit::{self, AuditSource};
 use super::config::{get_preferred_port, init_server_status, PORT_RANGE};
+use super::data_store::AccountPlatform;
+use super::quota_meter::{self, AccountQuota};
 
 /// 消息类型
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -54,6 +57,13 @@ pub enum WsMessage {
     #[serde(rename = "request.get_current_account")]
     GetCurrentAccount { request_id: String },
 
+    /// 请求获取各平台账号的配额（platform 为空时返回全部平台）
+    #[serde(rename = "request.get_quotas")]
+    GetQuotas {
+        request_id: String,
+        platform: Option<String>,
+    },
+
     /// 请求切换账号（真正的切换）
     #[serde(rename = "request.switch_account")]
     SwitchAccount { account_id: String },
@@ -116,6 +126,13 @@ pub enum WsMessage {
         account: Option<AccountInfo>,
     },
 
+    /// 配额响应
+    #[serde(rename = "response.quotas")]
+    QuotasResponse {
+        request_id: String,
+        quotas: Vec<AccountQuota>,
+    },
+
     /// 操作成功响应
     #[serde(rename = "response.success")]
     SuccessResponse { request_id: String, message: String },
@@ -304,7 +321,10 @@ pub async fn start_server() {
 
     while let Ok((stream, addr)) = listener.accept().await {
         let server_clone = Arc::clone(server);
-        tokio::spawn(handle_connection(server_clone, stream, addr));
+        tokio::spawn(audit::with_source(
+            AuditSource::WebSocket,
+            handle_connection(server_clone, stream, addr),
+        ));
     }
 }
 
@@ -424,6 +444,36 @@ async fn handle_client_message(
             }
         }
 
+        WsMessage::GetQuotas {
+            request_id,
+            platform,
+        } => {
+            crate::modules::logger::log_info("[WS] 收到获取配额请求");
+
+            let result = platform
+                .as_deref()
+                .map(|value| {
+                    AccountPlatform::parse(value.trim())
+                        .ok_or_else(|| format!("未知平台: {}", value))
+                })
+                .transpose()
+                .and_then(quota_meter::list);
+            let response = match result {
+                Ok(quotas) => WsMessage::QuotasResponse { request_id, quotas },
+                Err(e) => WsMessage::ErrorResponse {
+                    request_id,
+                    error: e,
+                },
+            };
+
+            if let Ok(json) = serde_json::to_string(&response) {
+                sender
+                    .send(Message::Text(json.into()))
+                    .await
+                    .map_err(|e| format!("发送响应失败: {}", e))?;
+            }
+        }
+
         WsMessage::GetAccountsWithTokens { request_id } => {
             crate::modules::logger::log_info("[WS] 收到获取账号列表(含Token)请求");
 
@@ -474,7 +524,7 @@ async fn handle_client_message(
 
             // 异步执行切换
             let server_clone = server.tx.clone();
-            tokio::spawn(async move {
+            tokio::spawn(audit::with_source(AuditSource::WebSocket, async move {
                 match crate::modules::account::switch_account_internal(&account_id).await {
                     Ok(account) => {
                         let msg = WsMessage::AccountSwitched {
@@ -492,7 +542,7 @@ async fn handle_client_message(
                         }
                     }
                 }
-            });
+            }));
         }
 
         WsMessage::SetLanguage {
@@ -779,6 +829,27 @@ fn handle_set_language(language: &str, source: Option<&str>) -> Result<String, S
         codex_launch_on_switch: current.codex_launch_on_switch,
         auto_switch_enabled: current.auto_switch_enabled,
         auto_switch_threshold: current.auto_switch_threshold,
+        auto_switch_strategy: current.auto_switch_strategy,
+        auto_switch_strategy_param: current.auto_switch_strategy_param,
+        watched_groups: current.watched_groups,
+        quota_protection_enabled: current.quota_protection_enabled,
+        quota_protection_reserve: current.quota_protection_reserve,
+        codex_auto_switch_enabled: current.codex_auto_switch_enabled,
+        codex_auto_switch_threshold: current.codex_auto_switch_threshold,
+        codex_auto_switch_strategy: current.codex_auto_switch_strategy,
+        codex_auto_switch_strategy_param: current.codex_auto_switch_strategy_param,
+        ghcp_auto_switch_enabled: current.ghcp_auto_switch_enabled,
+        ghcp_auto_switch_threshold: current.ghcp_auto_switch_threshold,
+        ghcp_auto_switch_strategy: current.ghcp_auto_switch_strategy,
+        ghcp_auto_switch_strategy_param: current.ghcp_auto_switch_strategy_param,
+        windsurf_auto_switch_enabled: current.windsurf_auto_switch_enabled,
+        windsurf_auto_switch_threshold: current.windsurf_auto_switch_threshold,
+        windsurf_auto_switch_strategy: current.windsurf_auto_switch_strategy,
+        windsurf_auto_switch_strategy_param: current.windsurf_auto_switch_strategy_param,
+        kiro_auto_switch_enabled: current.kiro_auto_switch_enabled,
+        kiro_auto_switch_threshold: current.kiro_auto_switch_threshold,
+        kiro_auto_switch_strategy: current.kiro_auto_switch_strategy,
+        kiro_auto_switch_strategy_param: current.kiro_auto_switch_strategy_param,
         quota_alert_enabled: current.quota_alert_enabled,
         quota_alert_threshold: current.quota_alert_threshold,
         codex_quota_alert_enabled: current.codex_quota_alert_enabled,
@@ -789,6 +860,10 @@ fn handle_set_language(language: &str, source: Option<&str>) -> Result<String, S
         windsurf_quota_alert_threshold: current.windsurf_quota_alert_threshold,
         kiro_quota_alert_enabled: current.kiro_quota_alert_enabled,
         kiro_quota_alert_threshold: current.kiro_quota_alert_threshold,
+        proxy_enabled: current.proxy_enabled,
+        proxy_port: current.proxy_port,
+        proxy_api_key: current.proxy_api_key,
+        trash_retention_days: current.trash_retention_days,
     };
 
     config::save_user_config(&new_config)?;
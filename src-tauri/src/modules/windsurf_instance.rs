@@ -35,7 +35,7 @@ use windows::Win32::Foundation::{LocalFree, HLOCAL};
 use windows::Win32::Security::Cryptography::{CryptUnprotectData, CRYPT_INTEGER_BLOB};
 
 use crate::models::windsurf::WindsurfAccount;
-use crate::models::{DefaultInstanceSettings, InstanceProfile, InstanceStore};
+use crate::models::{DefaultInstanceSettings, InstanceProfile, InstanceProfileView, InstanceStore};
 use crate::modules;
 use crate::modules::instance::InstanceDefaults;
 use crate::modules::instance_store;
@@ -47,6 +47,7 @@ static WINDSURF_INSTANCE_STORE_LOCK: std::sync::LazyLock<Mutex<()>> =
     std::sync::LazyLock::new(|| Mutex::new(()));
 
 const WINDSURF_INSTANCES_FILE: &str = "windsurf_instances.json";
+const DEFAULT_INSTANCE_ID: &str = "__default__";
 const WINDSURF_DEFAULT_API_SERVER_URL: &str = "https://server.codeium.com";
 const WINDSURF_AUTH_STATUS_KEY: &str = "windsurfAuthStatus";
 const WINDSURF_SESSIONS_SECRET_KEY: &str =
@@ -520,19 +521,12 @@ fn write_windsurf_auth_data(
     Ok(())
 }
 
-fn instances_path() -> Result<PathBuf, String> {
-    let data_dir = modules::account::get_data_dir()?;
-    Ok(data_dir.join(WINDSURF_INSTANCES_FILE))
-}
-
 pub fn load_instance_store() -> Result<InstanceStore, String> {
-    let path = instances_path()?;
-    instance_store::load_instance_store(&path, WINDSURF_INSTANCES_FILE)
+    instance_store::load_instance_store(WINDSURF_INSTANCES_FILE)
 }
 
 pub fn save_instance_store(store: &InstanceStore) -> Result<(), String> {
-    let path = instances_path()?;
-    instance_store::save_instance_store(&path, WINDSURF_INSTANCES_FILE, store)
+    instance_store::save_instance_store(WINDSURF_INSTANCES_FILE, store)
 }
 
 pub fn load_default_settings() -> Result<DefaultInstanceSettings, String> {
@@ -1575,6 +1569,17 @@ fn ensure_state_db_for_injection(profile_dir: &Path) -> Result<PathBuf, String>
 }
 
 pub fn inject_account_to_profile(profile_dir: &Path, account_id: &str) -> Result<(), String> {
+    let result = write_account_to_profile(profile_dir, account_id);
+    modules::audit::record_inject(
+        modules::data_store::AccountPlatform::Windsurf,
+        account_id,
+        profile_dir,
+        &result,
+    );
+    result
+}
+
+fn write_account_to_profile(profile_dir: &Path, account_id: &str) -> Result<(), String> {
     let account = windsurf_account::load_account(account_id)
         .ok_or_else(|| format!("绑定账号不存在: {}", account_id))?;
     let db_path = ensure_state_db_for_injection(profile_dir)?;
@@ -1620,3 +1625,103 @@ pub fn inject_account_to_profile(profile_dir: &Path, account_id: &str) -> Result
 
     Ok(())
 }
+
+/// 实例目录存在且非空时视为已初始化
+pub fn is_profile_initialized(user_data_dir: &str) -> bool {
+    let path = Path::new(user_data_dir);
+    if !path.exists() {
+        return false;
+    }
+    match std::fs::read_dir(path) {
+        Ok(mut iter) => iter.next().is_some(),
+        Err(_) => false,
+    }
+}
+
+fn inject_bound_account_for_instance_start(
+    user_data_dir: &str,
+    bind_account_id: Option<&str>,
+) -> Result<(), String> {
+    let bind_id = bind_account_id
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    let Some(bind_id) = bind_id else {
+        return Ok(());
+    };
+
+    let account = modules::windsurf_account::load_account(bind_id)
+        .ok_or_else(|| format!("绑定账号不存在: {}", bind_id))?;
+    modules::logger::log_info(&format!(
+        "实例启动检测到绑定账号，准备注入: bind_account_id={}, login={}, user_data_dir={}",
+        bind_id, account.github_login, user_data_dir
+    ));
+
+    close_windsurf(&[user_data_dir.to_string()], 20)?;
+    inject_account_to_profile(Path::new(user_data_dir), bind_id)?;
+    modules::logger::log_info(&format!("Windsurf 账号注入完成: {}", account.github_login));
+    Ok(())
+}
+
+/// 启动实例（`__default__` 为默认实例）：先关闭仍在运行的进程，注入绑定账号后重新启动
+pub async fn start_instance(instance_id: &str) -> Result<InstanceProfileView, String> {
+    modules::logger::log_info(&format!("开始启动 Windsurf 实例: {}", instance_id));
+    if instance_id == DEFAULT_INSTANCE_ID {
+        let default_dir = get_default_windsurf_user_data_dir()?;
+        let default_dir_str = default_dir.to_string_lossy().to_string();
+        let default_settings = load_default_settings()?;
+        if let Some(pid) = resolve_windsurf_pid(default_settings.last_pid, None) {
+            modules::process::close_pid(pid, 20)?;
+            let _ = update_default_pid(None)?;
+        }
+        close_windsurf(&[default_dir_str.clone()], 20)?;
+        inject_bound_account_for_instance_start(
+            &default_dir_str,
+            default_settings.bind_account_id.as_deref(),
+        )?;
+        let extra_args = modules::process::parse_extra_args(&default_settings.extra_args);
+        let pid = start_windsurf_default_with_args_with_new_window(&extra_args, true)?;
+        let _ = update_default_pid(Some(pid))?;
+        let running = resolve_windsurf_pid(Some(pid), None).is_some();
+        return Ok(InstanceProfileView {
+            id: DEFAULT_INSTANCE_ID.to_string(),
+            name: String::new(),
+            user_data_dir: default_dir_str,
+            extra_args: default_settings.extra_args,
+            bind_account_id: default_settings.bind_account_id,
+            created_at: 0,
+            last_launched_at: None,
+            last_pid: Some(pid),
+            running,
+            initialized: is_profile_initialized(&default_dir.to_string_lossy()),
+            is_default: true,
+            follow_local_account: false,
+        });
+    }
+
+    let store = load_instance_store()?;
+    let instance = store
+        .instances
+        .into_iter()
+        .find(|item| item.id == instance_id)
+        .ok_or("实例不存在")?;
+
+    if let Some(pid) = resolve_windsurf_pid(instance.last_pid, Some(&instance.user_data_dir)) {
+        modules::process::close_pid(pid, 20)?;
+        let _ = update_instance_pid(&instance.id, None)?;
+    }
+    close_windsurf(&[instance.user_data_dir.clone()], 20)?;
+    inject_bound_account_for_instance_start(
+        &instance.user_data_dir,
+        instance.bind_account_id.as_deref(),
+    )?;
+    let extra_args = modules::process::parse_extra_args(&instance.extra_args);
+    let pid = start_windsurf_with_args_with_new_window(&instance.user_data_dir, &extra_args, true)?;
+    let updated = update_instance_after_start(&instance.id, pid)?;
+    let running = resolve_windsurf_pid(Some(pid), Some(&updated.user_data_dir)).is_some();
+    let initialized = is_profile_initialized(&updated.user_data_dir);
+    Ok(InstanceProfileView::from_profile(
+        updated,
+        running,
+        initialized,
+    ))
+}
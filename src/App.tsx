@@ -111,6 +111,7 @@ type WakeupTaskResultPayload = {
 
 type QuotaAlertPayload = {
   platform?: string;
+  kind?: 'low' | 'replenished';
   current_account_id: string;
   current_email: string;
   threshold: number;
@@ -397,6 +398,30 @@ function App() {
     };
   }, []);
 
+  useEffect(() => {
+    invoke('get_startup_migration_error').catch((error) => {
+      if (error?.error_type !== 'migration_failed') {
+        return;
+      }
+      showModal({
+        title: t('error.migrationFailed.title', '数据迁移失败'),
+        description: t(
+          'error.migrationFailed.description',
+          '数据迁移 {{version}}（{{name}}）执行失败，迁移前的数据已备份到 backups 目录：{{message}}',
+          { version: error.version, name: error.name, message: error.message }
+        ),
+        width: 'md',
+        actions: [
+          {
+            id: 'migration-failed-ok',
+            label: t('common.confirm', '确定'),
+            variant: 'primary',
+          },
+        ],
+      });
+    });
+  }, [showModal, t]);
+
   useEffect(() => {
     let unlisten: UnlistenFn | undefined;
     let disposed = false;
@@ -414,6 +439,45 @@ function App() {
         ? payload.low_models.join(', ')
         : t('quotaAlert.modal.unknownModel', '未知模型');
 
+      if (payload.kind === 'replenished') {
+        showModal({
+          title: t('quotaAlert.modal.replenishedTitle', '配额已恢复'),
+          description: t(
+            'quotaAlert.modal.replenishedDesc',
+            '之前配额不足的账号已重置，可以继续使用。'
+          ),
+          width: 'md',
+          content: (
+            <div className="quota-alert-modal-content">
+              <div className="quota-alert-modal-row">
+                <span>{t('quotaAlert.modal.platform', '平台')}</span>
+                <strong>{platformLabel}</strong>
+              </div>
+              <div className="quota-alert-modal-row">
+                <span>{t('quotaAlert.modal.replenishedAccount', '账号')}</span>
+                <strong>{payload.current_email}</strong>
+              </div>
+              <div className="quota-alert-modal-row">
+                <span>{t('quotaAlert.modal.lowest', '当前最低')}</span>
+                <strong>{payload.lowest_percentage}%</strong>
+              </div>
+              <div className="quota-alert-modal-row quota-alert-modal-row--stack">
+                <span>{t('quotaAlert.modal.replenishedModels', '已恢复模型')}</span>
+                <strong>{modelsText}</strong>
+              </div>
+            </div>
+          ),
+          actions: [
+            {
+              id: 'quota-replenished-ok',
+              label: t('common.confirm', '确定'),
+              variant: 'primary',
+            },
+          ],
+        });
+        return;
+      }
+
       showModal({
         title: t('quotaAlert.modal.title', '配额预警'),
         description: t(
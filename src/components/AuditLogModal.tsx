@@ -0,0 +1,174 @@
+import { useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { save } from '@tauri-apps/plugin-dialog';
+import { Download, RefreshCw, X } from 'lucide-react';
+import * as auditService from '../services/auditService';
+import { ALL_PLATFORM_IDS, PlatformId } from '../types/platform';
+import { getPlatformLabel } from '../utils/platformMeta';
+import './AuditLogModal.css';
+
+interface AuditLogModalProps {
+  onClose: () => void;
+}
+
+/** 弹窗中最多展示的条数（导出不受限制） */
+const PAGE_SIZE = 200;
+
+const ACTIONS: auditService.AuditAction[] = [
+  'switch',
+  'inject',
+  'delete',
+  'restore',
+  'purge',
+  'tag',
+  'import',
+  'auto_switch',
+];
+
+export function AuditLogModal({ onClose }: AuditLogModalProps) {
+  const { t } = useTranslation();
+  const [platform, setPlatform] = useState('');
+  const [action, setAction] = useState<auditService.AuditAction | ''>('');
+  const [account, setAccount] = useState('');
+  const [entries, setEntries] = useState<auditService.AuditEntry[]>([]);
+  const [loading, setLoading] = useState(false);
+  const [error, setError] = useState('');
+
+  const buildQuery = (): auditService.AuditQuery => ({
+    platform: platform || undefined,
+    action: action || undefined,
+    account: account.trim() || undefined,
+  });
+
+  const load = async () => {
+    setLoading(true);
+    setError('');
+    try {
+      setEntries(await auditService.queryAuditLog({ ...buildQuery(), limit: PAGE_SIZE }));
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  useEffect(() => {
+    void load();
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [platform, action]);
+
+  const handleExport = async (format: 'json' | 'csv') => {
+    try {
+      const stamp = new Date().toISOString().slice(0, 19).replace(/[-:]/g, '').replace('T', '-');
+      const targetPath = await save({
+        defaultPath: `cockpit-audit-${stamp}.${format}`,
+        filters: [{ name: format.toUpperCase(), extensions: [format] }],
+      });
+      if (!targetPath) return;
+      const count = await auditService.exportAuditLog(targetPath, format, buildQuery());
+      alert(t('settings.general.audit.exported', '已导出 {count} 条记录').replace('{count}', String(count)));
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const platformLabel = (value: string) => getPlatformLabel(value.replace(/_/g, '-') as PlatformId, t);
+  const actionLabel = (value: auditService.AuditAction) => t(`settings.general.audit.actions.${value}`, value);
+  const sourceLabel = (value: auditService.AuditSource) => t(`settings.general.audit.sources.${value}`, value);
+  const outcomeLabel = (value: auditService.AuditOutcome) => t(`settings.general.audit.outcomes.${value}`, value);
+
+  return (
+    <div className="modal-overlay" onClick={onClose}>
+      <div className="modal modal-lg audit-log-modal" onClick={(event) => event.stopPropagation()}>
+        <div className="modal-header">
+          <h2>{t('settings.general.audit.title', '操作审计日志')}</h2>
+          <button className="modal-close" onClick={onClose} aria-label={t('common.close', '关闭')}>
+            <X />
+          </button>
+        </div>
+
+        <div className="modal-body audit-log-body">
+          <div className="audit-log-filters">
+            <select className="settings-select" value={platform} onChange={(e) => setPlatform(e.target.value)}>
+              <option value="">{t('settings.general.audit.allPlatforms', '全部平台')}</option>
+              {ALL_PLATFORM_IDS.map((id) => (
+                <option key={id} value={id.replace(/-/g, '_')}>{getPlatformLabel(id, t)}</option>
+              ))}
+            </select>
+            <select
+              className="settings-select"
+              value={action}
+              onChange={(e) => setAction(e.target.value as auditService.AuditAction | '')}
+            >
+              <option value="">{t('settings.general.audit.allActions', '全部操作')}</option>
+              {ACTIONS.map((value) => (
+                <option key={value} value={value}>{actionLabel(value)}</option>
+              ))}
+            </select>
+            <input
+              className="settings-input"
+              value={account}
+              placeholder={t('settings.general.audit.accountPlaceholder', '账号 ID 或邮箱')}
+              onChange={(e) => setAccount(e.target.value)}
+              onKeyDown={(e) => {
+                if (e.key === 'Enter') void load();
+              }}
+            />
+            <button className="btn btn-secondary" onClick={() => void load()} disabled={loading}>
+              <RefreshCw size={16} />
+            </button>
+          </div>
+
+          {entries.length === 0 ? (
+            <div className="audit-log-empty">
+              {loading ? t('common.loading', '加载中...') : t('settings.general.audit.empty', '暂无记录')}
+            </div>
+          ) : (
+            <div className="audit-log-table-wrap">
+              <table className="audit-log-table">
+                <thead>
+                  <tr>
+                    <th>{t('settings.general.audit.time', '时间')}</th>
+                    <th>{t('settings.general.audit.platform', '平台')}</th>
+                    <th>{t('settings.general.audit.action', '操作')}</th>
+                    <th>{t('settings.general.audit.account', '账号')}</th>
+                    <th>{t('settings.general.audit.source', '来源')}</th>
+                    <th>{t('settings.general.audit.actor', '执行者')}</th>
+                    <th>{t('settings.general.audit.outcome', '结果')}</th>
+                  </tr>
+                </thead>
+                <tbody>
+                  {entries.map((entry) => (
+                    <tr key={entry.id} title={[entry.detail, entry.error].filter(Boolean).join('\n')}>
+                      <td>{new Date(entry.createdAt * 1000).toLocaleString()}</td>
+                      <td>{platformLabel(entry.platform)}</td>
+                      <td>{actionLabel(entry.action)}</td>
+                      <td className="audit-log-account">{entry.account || entry.accountId || '-'}</td>
+                      <td>{sourceLabel(entry.source)}</td>
+                      <td>{entry.actor}</td>
+                      <td className={`audit-log-outcome is-${entry.outcome}`}>{outcomeLabel(entry.outcome)}</td>
+                    </tr>
+                  ))}
+                </tbody>
+              </table>
+            </div>
+          )}
+
+          {error && <div className="audit-log-error">{error}</div>}
+        </div>
+
+        <div className="modal-footer">
+          <button className="btn btn-secondary" onClick={() => void handleExport('csv')}>
+            <Download size={16} />CSV
+          </button>
+          <button className="btn btn-secondary" onClick={() => void handleExport('json')}>
+            <Download size={16} />JSON
+          </button>
+          <button className="btn btn-primary" onClick={onClose}>
+            {t('common.close', '关闭')}
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,147 @@
+import { useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { ArchiveRestore, X } from 'lucide-react';
+import * as backupService from '../services/backupService';
+import { PlatformId } from '../types/platform';
+import { getPlatformLabel } from '../utils/platformMeta';
+import './BackupRestoreModal.css';
+
+interface BackupRestoreModalProps {
+  archivePath: string;
+  passphrase: string;
+  preview: backupService.RestorePreview;
+  onClose: () => void;
+  onRestored: (result: backupService.RestoreResult) => void;
+}
+
+/** 预览中每类差异最多展示的条目数 */
+const MAX_LISTED_ITEMS = 5;
+
+export function BackupRestoreModal({ archivePath, passphrase, preview, onClose, onRestored }: BackupRestoreModalProps) {
+  const { t } = useTranslation();
+  const [selected, setSelected] = useState<Set<string>>(new Set());
+  const [restoring, setRestoring] = useState(false);
+  const [error, setError] = useState('');
+
+  useEffect(() => {
+    // 默认勾选有差异的分区
+    setSelected(new Set(
+      preview.sections
+        .filter((diff) => diff.added.length + diff.changed.length + diff.removed.length > 0)
+        .map((diff) => diff.section),
+    ));
+  }, [preview]);
+
+  const sectionLabel = (section: string) => (
+    section === 'settings'
+      ? t('settings.general.backup.settingsSection', '设置与指纹')
+      : getPlatformLabel(section.replace(/_/g, '-') as PlatformId, t)
+  );
+
+  const toggleSection = (section: string) => {
+    setSelected((prev) => {
+      const next = new Set(prev);
+      if (next.has(section)) {
+        next.delete(section);
+      } else {
+        next.add(section);
+      }
+      return next;
+    });
+  };
+
+  const renderItems = (label: string, items: string[], className: string) => {
+    if (items.length === 0) return null;
+    const listed = items.slice(0, MAX_LISTED_ITEMS).join(', ');
+    const more = items.length > MAX_LISTED_ITEMS
+      ? ` ${t('settings.general.backup.more', '等 {count} 项').replace('{count}', String(items.length))}`
+      : '';
+    return (
+      <div className={`backup-diff-line ${className}`}>
+        <span className="backup-diff-kind">{label}</span>
+        <span className="backup-diff-items">{listed}{more}</span>
+      </div>
+    );
+  };
+
+  const handleRestore = async () => {
+    if (restoring || selected.size === 0) return;
+    setRestoring(true);
+    setError('');
+    try {
+      const result = await backupService.restoreDataBackup(archivePath, Array.from(selected), passphrase);
+      onRestored(result);
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setRestoring(false);
+    }
+  };
+
+  return (
+    <div className="modal-overlay" onClick={onClose}>
+      <div className="modal modal-lg backup-restore-modal" onClick={(event) => event.stopPropagation()}>
+        <div className="modal-header">
+          <h2>{t('settings.general.backup.previewTitle', '恢复备份')}</h2>
+          <button className="modal-close" onClick={onClose} aria-label={t('common.close', '关闭')}>
+            <X />
+          </button>
+        </div>
+
+        <div className="modal-body backup-restore-body">
+          <div className="backup-restore-meta">
+            {t('settings.general.backup.previewMeta', '备份时间 {time} · 应用版本 {version}')
+              .replace('{time}', new Date(preview.created_at * 1000).toLocaleString())
+              .replace('{version}', preview.app_version)}
+          </div>
+          <div className="backup-restore-tip">
+            {t('settings.general.backup.previewTip', '恢复平台数据会用备份内容替换该平台的全部账号与实例；恢复前会自动快照当前数据。')}
+          </div>
+
+          {preview.sections.map((diff) => {
+            const unchangedOnly = diff.added.length + diff.changed.length + diff.removed.length === 0;
+            return (
+              <label key={diff.section} className={`backup-section ${unchangedOnly ? 'is-unchanged' : ''}`}>
+                <input
+                  type="checkbox"
+                  checked={selected.has(diff.section)}
+                  onChange={() => toggleSection(diff.section)}
+                  disabled={restoring}
+                />
+                <div className="backup-section-main">
+                  <div className="backup-section-title">
+                    <span>{sectionLabel(diff.section)}</span>
+                    <span className="backup-section-summary">
+                      {t('settings.general.backup.summary', '新增 {added} · 变更 {changed} · 删除 {removed} · 相同 {unchanged}')
+                        .replace('{added}', String(diff.added.length))
+                        .replace('{changed}', String(diff.changed.length))
+                        .replace('{removed}', String(diff.removed.length))
+                        .replace('{unchanged}', String(diff.unchanged))}
+                    </span>
+                  </div>
+                  {renderItems(t('settings.general.backup.added', '新增'), diff.added, 'is-added')}
+                  {renderItems(t('settings.general.backup.changed', '变更'), diff.changed, 'is-changed')}
+                  {renderItems(t('settings.general.backup.removed', '删除'), diff.removed, 'is-removed')}
+                </div>
+              </label>
+            );
+          })}
+
+          {error && <div className="backup-restore-error">{error}</div>}
+        </div>
+
+        <div className="modal-footer">
+          <button className="btn btn-secondary" onClick={onClose} disabled={restoring}>
+            {t('common.cancel', '取消')}
+          </button>
+          <button className="btn btn-primary" onClick={handleRestore} disabled={restoring || selected.size === 0}>
+            <ArchiveRestore size={16} />
+            {restoring
+              ? t('settings.general.backup.restoring', '恢复中...')
+              : t('settings.general.backup.restoreSelected', '恢复所选内容')}
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -1,6 +1,7 @@
+import { useState } from 'react';
 import { useTranslation } from 'react-i18next';
 import { invoke } from '@tauri-apps/api/core';
-import { FolderOpen, X } from 'lucide-react';
+import { FolderOpen, RotateCcw, X } from 'lucide-react';
 
 export interface FileCorruptedError {
   error_type: 'file_corrupted';
@@ -9,6 +10,14 @@ export interface FileCorruptedError {
   original_error: string;
 }
 
+interface RecoveryResult {
+  source: 'snapshot' | 'salvage';
+  snapshotAt: number | null;
+  recoveredEntries: number;
+  droppedEntries: number;
+  corruptedBackup: string;
+}
+
 interface FileCorruptedModalProps {
   error: FileCorruptedError;
   onClose: () => void;
@@ -48,6 +57,34 @@ export function parseFileCorruptedError(error: unknown): FileCorruptedError | nu
 
 export function FileCorruptedModal({ error, onClose }: FileCorruptedModalProps) {
   const { t } = useTranslation();
+  const [recovering, setRecovering] = useState(false);
+  const [recovery, setRecovery] = useState<RecoveryResult | null>(null);
+  const [recoverError, setRecoverError] = useState('');
+
+  const handleRecover = async () => {
+    if (recovering) return;
+    setRecovering(true);
+    setRecoverError('');
+    try {
+      setRecovery(await invoke<RecoveryResult>('recover_corrupted_file', { path: error.file_path }));
+    } catch (e) {
+      setRecoverError(String(e));
+    } finally {
+      setRecovering(false);
+    }
+  };
+
+  const recoveryMessage = (result: RecoveryResult) => {
+    if (result.source === 'snapshot') {
+      return t('error.fileCorrupted.recoveredFromSnapshot', '已从 {{time}} 的快照恢复。', {
+        time: result.snapshotAt ? new Date(result.snapshotAt * 1000).toLocaleString() : '-',
+      });
+    }
+    return t('error.fileCorrupted.recoveredBySalvage', '没有可用的快照，已从损坏文件中恢复 {{recovered}} 条记录，{{dropped}} 条无法恢复。', {
+      recovered: result.recoveredEntries,
+      dropped: result.droppedEntries,
+    });
+  };
 
   const handleOpenFolder = async () => {
     try {
@@ -90,12 +127,27 @@ export function FileCorruptedModal({ error, onClose }: FileCorruptedModalProps)
             </div>
           </div>
 
-          <p style={{ fontSize: 13, color: 'var(--text-secondary)', marginBottom: 0 }}>
-            {t(
-              'error.fileCorrupted.helpText',
-              '请打开文件夹手动修复或删除该文件，然后重新启动应用。'
-            )}
-          </p>
+          {recovery ? (
+            <p style={{ fontSize: 13, color: 'var(--text-primary)', marginBottom: 0 }}>
+              {recoveryMessage(recovery)}
+              <br />
+              {t('error.fileCorrupted.backupKept', '损坏的内容已保留在: {{path}}', {
+                path: recovery.corruptedBackup,
+              })}
+            </p>
+          ) : (
+            <p style={{ fontSize: 13, color: 'var(--text-secondary)', marginBottom: 0 }}>
+              {t(
+                'error.fileCorrupted.helpText',
+                '可从最近的自动快照恢复；没有快照时会尽量保留文件中完好的记录。也可以打开文件夹手动修复。'
+              )}
+            </p>
+          )}
+          {recoverError && (
+            <p style={{ fontSize: 13, color: 'var(--danger)', marginTop: 12, marginBottom: 0 }}>
+              {t('error.fileCorrupted.recoverFailed', '恢复失败: {{error}}', { error: recoverError })}
+            </p>
+          )}
         </div>
 
         <div className="modal-footer">
@@ -103,9 +155,23 @@ export function FileCorruptedModal({ error, onClose }: FileCorruptedModalProps)
             <FolderOpen size={16} />
             {t('error.fileCorrupted.openFolder', '打开文件夹')}
           </button>
-          <button className="btn btn-primary" onClick={onClose}>
-            {t('common.close', '关闭')}
-          </button>
+          {recovery ? (
+            <button className="btn btn-primary" onClick={() => window.location.reload()}>
+              {t('error.fileCorrupted.reload', '重新加载')}
+            </button>
+          ) : (
+            <>
+              <button className="btn btn-secondary" onClick={onClose}>
+                {t('common.close', '关闭')}
+              </button>
+              <button className="btn btn-primary" onClick={handleRecover} disabled={recovering}>
+                <RotateCcw size={16} />
+                {recovering
+                  ? t('error.fileCorrupted.recovering', '恢复中...')
+                  : t('error.fileCorrupted.recover', '自动恢复')}
+              </button>
+            </>
+          )}
         </div>
       </div>
     </div>
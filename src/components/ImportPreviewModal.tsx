@@ -0,0 +1,152 @@
+import { useMemo, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { Upload, X } from 'lucide-react';
+import * as interchangeService from '../services/interchangeService';
+import { PlatformId } from '../types/platform';
+import { getPlatformLabel } from '../utils/platformMeta';
+import './ImportPreviewModal.css';
+
+interface ImportPreviewModalProps {
+  jsonContent: string;
+  plan: interchangeService.ImportPlan;
+  onClose: () => void;
+  onImported: (result: interchangeService.InterchangeImportResult) => void;
+}
+
+const ACTIONS: interchangeService.PlanAction[] = ['new', 'update', 'conflict', 'identical', 'invalid'];
+const STRATEGIES: interchangeService.ImportStrategy[] = ['keep_newest', 'skip_existing', 'overwrite', 'merge_tags'];
+/** 差异值展示的最大长度 */
+const MAX_VALUE_LENGTH = 60;
+
+function formatValue(value: unknown): string {
+  if (value === null || value === undefined) return '—';
+  const text = typeof value === 'string' ? value : JSON.stringify(value);
+  return text.length > MAX_VALUE_LENGTH ? `${text.slice(0, MAX_VALUE_LENGTH)}…` : text;
+}
+
+export function ImportPreviewModal({ jsonContent, plan, onClose, onImported }: ImportPreviewModalProps) {
+  const { t } = useTranslation();
+  const [strategy, setStrategy] = useState<interchangeService.ImportStrategy>('keep_newest');
+  const [importing, setImporting] = useState(false);
+  const [error, setError] = useState('');
+
+  const counts = useMemo(() => {
+    const result: Record<string, number> = {};
+    plan.items.forEach((item) => {
+      result[item.action] = (result[item.action] ?? 0) + 1;
+    });
+    return result;
+  }, [plan]);
+
+  const actionLabel = (action: interchangeService.PlanAction) => ({
+    new: t('settings.general.interchange.preview.new', '新增'),
+    update: t('settings.general.interchange.preview.update', '更新'),
+    identical: t('settings.general.interchange.preview.identical', '相同'),
+    conflict: t('settings.general.interchange.preview.conflict', '冲突'),
+    invalid: t('settings.general.interchange.preview.invalid', '无效'),
+  })[action];
+
+  const strategyLabel = (value: interchangeService.ImportStrategy) => ({
+    keep_newest: t('settings.general.interchange.strategy.keepNewest', '保留较新的 Token'),
+    skip_existing: t('settings.general.interchange.strategy.skipExisting', '跳过已存在的账号'),
+    overwrite: t('settings.general.interchange.strategy.overwrite', '全部覆盖'),
+    merge_tags: t('settings.general.interchange.strategy.mergeTags', '仅合并标签'),
+  })[value];
+
+  const handleImport = async () => {
+    if (importing) return;
+    setImporting(true);
+    setError('');
+    try {
+      const result = await interchangeService.importInterchangeAccounts(jsonContent, strategy);
+      onImported(result);
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setImporting(false);
+    }
+  };
+
+  return (
+    <div className="modal-overlay" onClick={onClose}>
+      <div className="modal modal-lg import-preview-modal" onClick={(event) => event.stopPropagation()}>
+        <div className="modal-header">
+          <h2>{t('settings.general.interchange.preview.title', '导入预览')}</h2>
+          <button className="modal-close" onClick={onClose} aria-label={t('common.close', '关闭')}>
+            <X />
+          </button>
+        </div>
+
+        <div className="modal-body import-preview-body">
+          <div className="import-preview-summary">
+            {ACTIONS.filter((action) => counts[action]).map((action) => (
+              <span key={action} className={`import-preview-badge is-${action}`}>
+                {actionLabel(action)} {counts[action]}
+              </span>
+            ))}
+          </div>
+          {plan.skippedPlatforms.length > 0 && (
+            <div className="import-preview-tip">
+              {t('settings.general.interchange.skippedPlatforms', '不支持的平台已跳过: {platforms}')
+                .replace('{platforms}', plan.skippedPlatforms.join(', '))}
+            </div>
+          )}
+
+          <label className="import-preview-strategy">
+            <span>{t('settings.general.interchange.strategy.label', '已存在的账号')}</span>
+            <select
+              className="settings-select"
+              value={strategy}
+              onChange={(e) => setStrategy(e.target.value as interchangeService.ImportStrategy)}
+              disabled={importing}
+            >
+              {STRATEGIES.map((value) => (
+                <option key={value} value={value}>{strategyLabel(value)}</option>
+              ))}
+            </select>
+          </label>
+          <div className="import-preview-tip">
+            {t('settings.general.interchange.strategy.tip', '冲突表示本机的 Token 比导入文件中的更新；「保留较新的 Token」会导入其余字段但保留本机 Token。')}
+          </div>
+
+          <div className="import-preview-list">
+            {plan.items.map((item, index) => (
+              <div key={`${item.platform}-${item.incomingId}-${index}`} className="import-preview-item">
+                <div className="import-preview-item-title">
+                  <span className={`import-preview-badge is-${item.action}`}>{actionLabel(item.action)}</span>
+                  <span className="import-preview-platform">
+                    {getPlatformLabel(item.platform.replace(/_/g, '-') as PlatformId, t)}
+                  </span>
+                  <span className="import-preview-email">{item.email || item.incomingId}</span>
+                </div>
+                {item.error && <div className="import-preview-error">{item.error}</div>}
+                {item.changes.map((change) => (
+                  <div key={change.field} className="import-preview-change">
+                    <span className="import-preview-field">{change.field}</span>
+                    <span className="import-preview-values">
+                      {formatValue(change.current)} → {formatValue(change.incoming)}
+                    </span>
+                  </div>
+                ))}
+              </div>
+            ))}
+          </div>
+
+          {error && <div className="import-preview-error">{error}</div>}
+        </div>
+
+        <div className="modal-footer">
+          <button className="btn btn-secondary" onClick={onClose} disabled={importing}>
+            {t('common.cancel', '取消')}
+          </button>
+          <button className="btn btn-primary" onClick={handleImport} disabled={importing || plan.items.length === 0}>
+            <Upload size={16} />
+            {importing
+              ? t('settings.general.interchange.preview.importing', '导入中...')
+              : t('settings.general.interchange.preview.confirm', '开始导入')}
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,139 @@
+import { useEffect, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { CalendarDays, RefreshCw, X } from 'lucide-react';
+import { save } from '@tauri-apps/plugin-dialog';
+import * as quotaHistoryService from '../services/quotaHistoryService';
+import { ALL_PLATFORM_IDS, PlatformId } from '../types/platform';
+import { getPlatformLabel } from '../utils/platformMeta';
+import './QuotaForecastModal.css';
+
+interface QuotaForecastModalProps {
+  onClose: () => void;
+}
+
+export function QuotaForecastModal({ onClose }: QuotaForecastModalProps) {
+  const { t } = useTranslation();
+  const [platform, setPlatform] = useState('');
+  const [atRiskOnly, setAtRiskOnly] = useState(true);
+  const [forecasts, setForecasts] = useState<quotaHistoryService.QuotaForecast[]>([]);
+  const [loading, setLoading] = useState(false);
+  const [error, setError] = useState('');
+
+  const load = async () => {
+    setLoading(true);
+    setError('');
+    try {
+      setForecasts(await quotaHistoryService.getQuotaForecasts(platform || undefined));
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  useEffect(() => {
+    void load();
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [platform]);
+
+  const handleExportCalendar = async () => {
+    try {
+      const targetPath = await save({
+        defaultPath: 'cockpit-quota-resets.ics',
+        filters: [{ name: 'iCalendar', extensions: ['ics'] }],
+      });
+      if (!targetPath) return;
+      const count = await quotaHistoryService.exportQuotaResetCalendar(targetPath);
+      alert(t('settings.general.quotaForecast.calendarExported', '已导出 {count} 个重置事件').replace('{count}', String(count)));
+    } catch (err) {
+      setError(String(err));
+    }
+  };
+
+  const visible = atRiskOnly ? forecasts.filter((item) => item.exhaustsBeforeReset) : forecasts;
+  const platformLabel = (value: string) => getPlatformLabel(value.replace(/_/g, '-') as PlatformId, t);
+  const formatTime = (seconds: number | null) => (seconds ? new Date(seconds * 1000).toLocaleString() : '-');
+
+  return (
+    <div className="modal-overlay" onClick={onClose}>
+      <div className="modal modal-lg quota-forecast-modal" onClick={(event) => event.stopPropagation()}>
+        <div className="modal-header">
+          <h2>{t('settings.general.quotaForecast.title', '配额耗尽预测')}</h2>
+          <button className="modal-close" onClick={onClose} aria-label={t('common.close', '关闭')}>
+            <X />
+          </button>
+        </div>
+
+        <div className="modal-body quota-forecast-body">
+          <div className="quota-forecast-filters">
+            <select className="settings-select" value={platform} onChange={(e) => setPlatform(e.target.value)}>
+              <option value="">{t('settings.general.audit.allPlatforms', '全部平台')}</option>
+              {ALL_PLATFORM_IDS.map((id) => (
+                <option key={id} value={id.replace(/-/g, '_')}>{getPlatformLabel(id, t)}</option>
+              ))}
+            </select>
+            <label className="quota-forecast-toggle">
+              <input type="checkbox" checked={atRiskOnly} onChange={(e) => setAtRiskOnly(e.target.checked)} />
+              {t('settings.general.quotaForecast.atRiskOnly', '只看重置前会耗尽的')}
+            </label>
+            <button className="btn btn-secondary" onClick={() => void load()} disabled={loading}>
+              <RefreshCw size={16} />
+            </button>
+            <button className="btn btn-secondary" onClick={() => void handleExportCalendar()}>
+              <CalendarDays size={16} />
+              {t('settings.general.quotaForecast.exportCalendar', '导出重置日历 (.ics)')}
+            </button>
+          </div>
+
+          {visible.length === 0 ? (
+            <div className="quota-forecast-empty">
+              {loading ? t('common.loading', '加载中...') : t('settings.general.quotaForecast.empty', '暂无需要关注的配额')}
+            </div>
+          ) : (
+            <div className="quota-forecast-table-wrap">
+              <table className="quota-forecast-table">
+                <thead>
+                  <tr>
+                    <th>{t('settings.general.audit.platform', '平台')}</th>
+                    <th>{t('settings.general.audit.account', '账号')}</th>
+                    <th>{t('settings.general.quotaForecast.meter', '配额项')}</th>
+                    <th>{t('settings.general.quotaForecast.remaining', '剩余')}</th>
+                    <th>{t('settings.general.quotaForecast.rate', '每小时消耗')}</th>
+                    <th>{t('settings.general.quotaForecast.exhaustsAt', '预计耗尽')}</th>
+                    <th>{t('settings.general.quotaForecast.resetAt', '重置时间')}</th>
+                  </tr>
+                </thead>
+                <tbody>
+                  {visible.map((item) => (
+                    <tr
+                      key={`${item.platform}:${item.accountId}:${item.meter}`}
+                      className={item.exhaustsBeforeReset ? 'is-at-risk' : undefined}
+                    >
+                      <td>{platformLabel(item.platform)}</td>
+                      <td className="quota-forecast-account" title={item.accountId}>
+                        {item.account || item.accountId}
+                      </td>
+                      <td>{item.meter}</td>
+                      <td>{Math.round(item.remainingPercent)}%</td>
+                      <td>{item.consumptionPerHour.toFixed(1)}%</td>
+                      <td className="quota-forecast-exhausts">{formatTime(item.exhaustsAt)}</td>
+                      <td>{formatTime(item.resetAt)}</td>
+                    </tr>
+                  ))}
+                </tbody>
+              </table>
+            </div>
+          )}
+
+          {error && <div className="quota-forecast-error">{error}</div>}
+        </div>
+
+        <div className="modal-footer">
+          <button className="btn btn-primary" onClick={onClose}>
+            {t('common.close', '关闭')}
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -0,0 +1,204 @@
+import { useEffect, useMemo, useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { RefreshCw, RotateCcw, Trash2, X } from 'lucide-react';
+import * as trashService from '../services/trashService';
+import { ALL_PLATFORM_IDS, PlatformId } from '../types/platform';
+import { getPlatformLabel } from '../utils/platformMeta';
+import './TrashModal.css';
+
+interface TrashModalProps {
+  onClose: () => void;
+}
+
+const entryKey = (entry: trashService.TrashEntry) => `${entry.platform}:${entry.id}`;
+
+/** 按平台分组，便于逐平台调用后端 */
+function groupByPlatform(entries: trashService.TrashEntry[]): Map<string, string[]> {
+  const groups = new Map<string, string[]>();
+  for (const entry of entries) {
+    const ids = groups.get(entry.platform) ?? [];
+    ids.push(entry.id);
+    groups.set(entry.platform, ids);
+  }
+  return groups;
+}
+
+export function TrashModal({ onClose }: TrashModalProps) {
+  const { t } = useTranslation();
+  const [platform, setPlatform] = useState('');
+  const [entries, setEntries] = useState<trashService.TrashEntry[]>([]);
+  const [selected, setSelected] = useState<Set<string>>(new Set());
+  const [loading, setLoading] = useState(false);
+  const [busy, setBusy] = useState(false);
+  const [error, setError] = useState('');
+
+  const load = async () => {
+    setLoading(true);
+    setError('');
+    try {
+      setEntries(await trashService.listAccountTrash(platform || undefined));
+      setSelected(new Set());
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setLoading(false);
+    }
+  };
+
+  useEffect(() => {
+    void load();
+    // eslint-disable-next-line react-hooks/exhaustive-deps
+  }, [platform]);
+
+  const selectedEntries = useMemo(
+    () => entries.filter((entry) => selected.has(entryKey(entry))),
+    [entries, selected],
+  );
+  const allSelected = entries.length > 0 && selectedEntries.length === entries.length;
+
+  const toggle = (entry: trashService.TrashEntry) => {
+    setSelected((prev) => {
+      const next = new Set(prev);
+      const key = entryKey(entry);
+      if (next.has(key)) {
+        next.delete(key);
+      } else {
+        next.add(key);
+      }
+      return next;
+    });
+  };
+
+  const toggleAll = () => {
+    setSelected(allSelected ? new Set() : new Set(entries.map(entryKey)));
+  };
+
+  const runForSelected = async (
+    action: (platform: string, ids: string[]) => Promise<number>,
+    targets: trashService.TrashEntry[],
+  ) => {
+    setBusy(true);
+    setError('');
+    try {
+      for (const [target, ids] of groupByPlatform(targets)) {
+        await action(target, ids);
+      }
+    } catch (err) {
+      setError(String(err));
+    } finally {
+      setBusy(false);
+      await load();
+    }
+  };
+
+  const handleRestore = () => runForSelected(trashService.restoreTrashedAccounts, selectedEntries);
+
+  const handlePurge = (targets: trashService.TrashEntry[]) => {
+    const message = t('settings.general.trash.purgeConfirm', '确定彻底删除 {count} 个账号？此操作无法撤销').replace(
+      '{count}',
+      String(targets.length),
+    );
+    if (!window.confirm(message)) return;
+    void runForSelected(trashService.purgeAccountTrash, targets);
+  };
+
+  const platformLabel = (value: string) => getPlatformLabel(value.replace(/_/g, '-') as PlatformId, t);
+  const formatTime = (seconds: number) => new Date(seconds * 1000).toLocaleString();
+
+  return (
+    <div className="modal-overlay" onClick={onClose}>
+      <div className="modal modal-lg trash-modal" onClick={(event) => event.stopPropagation()}>
+        <div className="modal-header">
+          <h2>{t('settings.general.trash.title', '账号回收站')}</h2>
+          <button className="modal-close" onClick={onClose} aria-label={t('common.close', '关闭')}>
+            <X />
+          </button>
+        </div>
+
+        <div className="modal-body trash-body">
+          <div className="trash-filters">
+            <select className="settings-select" value={platform} onChange={(e) => setPlatform(e.target.value)}>
+              <option value="">{t('settings.general.audit.allPlatforms', '全部平台')}</option>
+              {ALL_PLATFORM_IDS.map((id) => (
+                <option key={id} value={id.replace(/-/g, '_')}>{getPlatformLabel(id, t)}</option>
+              ))}
+            </select>
+            <button className="btn btn-secondary" onClick={() => void load()} disabled={loading || busy}>
+              <RefreshCw size={16} />
+            </button>
+          </div>
+
+          {entries.length === 0 ? (
+            <div className="trash-empty">
+              {loading ? t('common.loading', '加载中...') : t('settings.general.trash.empty', '回收站为空')}
+            </div>
+          ) : (
+            <div className="trash-table-wrap">
+              <table className="trash-table">
+                <thead>
+                  <tr>
+                    <th>
+                      <input type="checkbox" checked={allSelected} onChange={toggleAll} />
+                    </th>
+                    <th>{t('settings.general.audit.platform', '平台')}</th>
+                    <th>{t('settings.general.audit.account', '账号')}</th>
+                    <th>{t('settings.general.trash.deletedAt', '删除时间')}</th>
+                    <th>{t('settings.general.trash.expiresAt', '到期清理')}</th>
+                  </tr>
+                </thead>
+                <tbody>
+                  {entries.map((entry) => (
+                    <tr key={entryKey(entry)} onClick={() => toggle(entry)}>
+                      <td>
+                        <input
+                          type="checkbox"
+                          checked={selected.has(entryKey(entry))}
+                          onChange={() => toggle(entry)}
+                          onClick={(event) => event.stopPropagation()}
+                        />
+                      </td>
+                      <td>{platformLabel(entry.platform)}</td>
+                      <td className="trash-account" title={entry.id}>{entry.label || entry.id}</td>
+                      <td>{formatTime(entry.deletedAt)}</td>
+                      <td>
+                        {entry.expiresAt
+                          ? formatTime(entry.expiresAt)
+                          : t('settings.general.trash.keepForever', '永久保留')}
+                      </td>
+                    </tr>
+                  ))}
+                </tbody>
+              </table>
+            </div>
+          )}
+
+          {error && <div className="trash-error">{error}</div>}
+        </div>
+
+        <div className="modal-footer">
+          <button
+            className="btn btn-secondary"
+            onClick={() => handlePurge(entries)}
+            disabled={busy || entries.length === 0}
+          >
+            <Trash2 size={16} />{t('settings.general.trash.emptyTrash', '清空')}
+          </button>
+          <button
+            className="btn btn-secondary"
+            onClick={() => handlePurge(selectedEntries)}
+            disabled={busy || selectedEntries.length === 0}
+          >
+            <Trash2 size={16} />{t('settings.general.trash.purge', '彻底删除')}
+          </button>
+          <button
+            className="btn btn-primary"
+            onClick={() => void handleRestore()}
+            disabled={busy || selectedEntries.length === 0}
+          >
+            <RotateCcw size={16} />{t('settings.general.trash.restore', '恢复')}
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+}
@@ -1,5 +1,6 @@
 import { useCallback, useEffect, useRef } from 'react';
 import { invoke } from '@tauri-apps/api/core';
+import { listen, UnlistenFn } from '@tauri-apps/api/event';
 import { useAccountStore } from '../stores/useAccountStore';
 import { useCodexAccountStore } from '../stores/useCodexAccountStore';
 import { useGitHubCopilotAccountStore } from '../stores/useGitHubCopilotAccountStore';
@@ -25,62 +26,36 @@ interface GeneralConfig {
   codex_launch_on_switch?: boolean;
 }
 
+type RefreshPlatform = 'antigravity' | 'codex' | 'github_copilot' | 'windsurf' | 'kiro';
+
+interface RefreshCompletedPayload {
+  platform: RefreshPlatform;
+  success: number;
+  total: number;
+  error?: string | null;
+  switched_account_id?: string | null;
+  finished_at: number;
+  next_run_at?: number | null;
+}
+
+/**
+ * 定时刷新由后端调度器负责（窗口隐藏/挂起时不会中断），
+ * 前端只负责修正配置并在收到刷新完成事件后同步各平台 store。
+ */
 export function useAutoRefresh() {
-  const refreshAllQuotas = useAccountStore((state) => state.refreshAllQuotas);
-  const syncCurrentFromClient = useAccountStore((state) => state.syncCurrentFromClient);
   const fetchAccounts = useAccountStore((state) => state.fetchAccounts);
   const fetchCurrentAccount = useAccountStore((state) => state.fetchCurrentAccount);
 
-  const refreshAllCodexQuotas = useCodexAccountStore((state) => state.refreshAllQuotas);
-  const refreshAllGhcpTokens = useGitHubCopilotAccountStore((state) => state.refreshAllTokens);
-  const refreshAllWindsurfTokens = useWindsurfAccountStore((state) => state.refreshAllTokens);
-  const refreshAllKiroTokens = useKiroAccountStore((state) => state.refreshAllTokens);
-
-  const agIntervalRef = useRef<number | null>(null);
-  const autoSwitchIntervalRef = useRef<number | null>(null);
-  const codexIntervalRef = useRef<number | null>(null);
-  const ghcpIntervalRef = useRef<number | null>(null);
-  const windsurfIntervalRef = useRef<number | null>(null);
-  const kiroIntervalRef = useRef<number | null>(null);
-
-  const agRefreshingRef = useRef(false);
-  const codexRefreshingRef = useRef(false);
-  const ghcpRefreshingRef = useRef(false);
-  const windsurfRefreshingRef = useRef(false);
-  const kiroRefreshingRef = useRef(false);
-  const autoSwitchRefreshingRef = useRef(false);
+  const fetchCodexAccounts = useCodexAccountStore((state) => state.fetchAccounts);
+  const fetchCurrentCodexAccount = useCodexAccountStore((state) => state.fetchCurrentAccount);
+  const fetchGhcpAccounts = useGitHubCopilotAccountStore((state) => state.fetchAccounts);
+  const fetchWindsurfAccounts = useWindsurfAccountStore((state) => state.fetchAccounts);
+  const fetchKiroAccounts = useKiroAccountStore((state) => state.fetchAccounts);
 
   const setupRunningRef = useRef(false);
   const setupPendingRef = useRef(false);
   const destroyedRef = useRef(false);
 
-  const clearAllIntervals = useCallback(() => {
-    if (agIntervalRef.current) {
-      window.clearInterval(agIntervalRef.current);
-      agIntervalRef.current = null;
-    }
-    if (codexIntervalRef.current) {
-      window.clearInterval(codexIntervalRef.current);
-      codexIntervalRef.current = null;
-    }
-    if (ghcpIntervalRef.current) {
-      window.clearInterval(ghcpIntervalRef.current);
-      ghcpIntervalRef.current = null;
-    }
-    if (autoSwitchIntervalRef.current) {
-      window.clearInterval(autoSwitchIntervalRef.current);
-      autoSwitchIntervalRef.current = null;
-    }
-    if (windsurfIntervalRef.current) {
-      window.clearInterval(windsurfIntervalRef.current);
-      windsurfIntervalRef.current = null;
-    }
-    if (kiroIntervalRef.current) {
-      window.clearInterval(kiroIntervalRef.current);
-      kiroIntervalRef.current = null;
-    }
-  }, []);
-
   const setupAutoRefresh = useCallback(async () => {
     if (destroyedRef.current) {
       return;
@@ -155,147 +130,14 @@ export function useAutoRefresh() {
             return;
           }
 
-          clearAllIntervals();
-
-          if (config.auto_refresh_minutes > 0) {
-            console.log(`[AutoRefresh] Antigravity 已启用: 每 ${config.auto_refresh_minutes} 分钟`);
-            const agMs = config.auto_refresh_minutes * 60 * 1000;
-
-            agIntervalRef.current = window.setInterval(async () => {
-              if (agRefreshingRef.current) {
-                return;
-              }
-              agRefreshingRef.current = true;
-
-              try {
-                console.log('[AutoRefresh] 触发定时配额刷新...');
-                await syncCurrentFromClient();
-                await refreshAllQuotas();
-              } catch (e) {
-                console.error('[AutoRefresh] 刷新失败:', e);
-              } finally {
-                agRefreshingRef.current = false;
-              }
-            }, agMs);
-          } else {
-            console.log('[AutoRefresh] Antigravity 已禁用');
-          }
-
-          if (config.codex_auto_refresh_minutes > 0) {
-            console.log(`[AutoRefresh] Codex 已启用: 每 ${config.codex_auto_refresh_minutes} 分钟`);
-            const codexMs = config.codex_auto_refresh_minutes * 60 * 1000;
-
-            codexIntervalRef.current = window.setInterval(async () => {
-              if (codexRefreshingRef.current) {
-                return;
-              }
-              codexRefreshingRef.current = true;
-
-              try {
-                console.log('[AutoRefresh] 触发 Codex 配额刷新...');
-                await refreshAllCodexQuotas();
-              } catch (e) {
-                console.error('[AutoRefresh] Codex 刷新失败:', e);
-              } finally {
-                codexRefreshingRef.current = false;
-              }
-            }, codexMs);
-          } else {
-            console.log('[AutoRefresh] Codex 已禁用');
-          }
-
-          if (config.ghcp_auto_refresh_minutes > 0) {
-            console.log(`[AutoRefresh] GitHub Copilot 已启用: 每 ${config.ghcp_auto_refresh_minutes} 分钟`);
-            const ghcpMs = config.ghcp_auto_refresh_minutes * 60 * 1000;
-
-            ghcpIntervalRef.current = window.setInterval(async () => {
-              if (ghcpRefreshingRef.current) {
-                return;
-              }
-              ghcpRefreshingRef.current = true;
-
-              try {
-                console.log('[AutoRefresh] 触发 GitHub Copilot Token 刷新...');
-                await refreshAllGhcpTokens();
-              } catch (e) {
-                console.error('[AutoRefresh] GitHub Copilot 刷新失败:', e);
-              } finally {
-                ghcpRefreshingRef.current = false;
-              }
-            }, ghcpMs);
-          } else {
-            console.log('[AutoRefresh] GitHub Copilot 已禁用');
-          }
-
-          if (config.windsurf_auto_refresh_minutes > 0) {
-            console.log(`[AutoRefresh] Windsurf 已启用: 每 ${config.windsurf_auto_refresh_minutes} 分钟`);
-            const windsurfMs = config.windsurf_auto_refresh_minutes * 60 * 1000;
-
-            windsurfIntervalRef.current = window.setInterval(async () => {
-              if (windsurfRefreshingRef.current) {
-                return;
-              }
-              windsurfRefreshingRef.current = true;
-
-              try {
-                console.log('[AutoRefresh] 触发 Windsurf 配额刷新...');
-                await refreshAllWindsurfTokens();
-              } catch (e) {
-                console.error('[AutoRefresh] Windsurf 刷新失败:', e);
-              } finally {
-                windsurfRefreshingRef.current = false;
-              }
-            }, windsurfMs);
-          } else {
-            console.log('[AutoRefresh] Windsurf 已禁用');
-          }
-
-          if (config.kiro_auto_refresh_minutes > 0) {
-            console.log(`[AutoRefresh] Kiro 已启用: 每 ${config.kiro_auto_refresh_minutes} 分钟`);
-            const kiroMs = config.kiro_auto_refresh_minutes * 60 * 1000;
-
-            kiroIntervalRef.current = window.setInterval(async () => {
-              if (kiroRefreshingRef.current) {
-                return;
-              }
-              kiroRefreshingRef.current = true;
-
-              try {
-                console.log('[AutoRefresh] 触发 Kiro 配额刷新...');
-                await refreshAllKiroTokens();
-              } catch (e) {
-                console.error('[AutoRefresh] Kiro 刷新失败:', e);
-              } finally {
-                kiroRefreshingRef.current = false;
-              }
-            }, kiroMs);
-          } else {
-            console.log('[AutoRefresh] Kiro 已禁用');
-          }
-
-          // 自动切号开启时，额外每 60 秒刷新当前账号（不影响原有配额自动刷新规则）
-          if (config.auto_switch_enabled) {
-            console.log('[AutoRefresh] 自动切号已启用: 每 60 秒刷新当前账号');
-            autoSwitchIntervalRef.current = window.setInterval(async () => {
-              if (autoSwitchRefreshingRef.current) {
-                return;
-              }
-              autoSwitchRefreshingRef.current = true;
-
-              try {
-                await syncCurrentFromClient();
-                await invoke('refresh_current_quota');
-                await fetchAccounts();
-                await fetchCurrentAccount();
-              } catch (e) {
-                console.error('[AutoRefresh] 自动切号-当前账号刷新失败:', e);
-              } finally {
-                autoSwitchRefreshingRef.current = false;
-              }
-            }, 60 * 1000);
-          } else {
-            console.log('[AutoRefresh] 自动切号未启用，跳过 60 秒当前账号刷新');
-          }
+          const enabledPlatforms = [
+            config.auto_refresh_minutes > 0 ? `antigravity=${config.auto_refresh_minutes}m` : null,
+            config.codex_auto_refresh_minutes > 0 ? `codex=${config.codex_auto_refresh_minutes}m` : null,
+            config.ghcp_auto_refresh_minutes > 0 ? `github_copilot=${config.ghcp_auto_refresh_minutes}m` : null,
+            config.windsurf_auto_refresh_minutes > 0 ? `windsurf=${config.windsurf_auto_refresh_minutes}m` : null,
+            config.kiro_auto_refresh_minutes > 0 ? `kiro=${config.kiro_auto_refresh_minutes}m` : null,
+          ].filter(Boolean);
+          console.log(`[AutoRefresh] 后台定时刷新: ${enabledPlatforms.join(', ') || '全部禁用'}`);
         } catch (err) {
           console.error('[AutoRefresh] 加载配置失败:', err);
         }
@@ -303,24 +145,75 @@ export function useAutoRefresh() {
     } finally {
       setupRunningRef.current = false;
     }
-  }, [
-    clearAllIntervals,
-    fetchAccounts,
-    fetchCurrentAccount,
-    refreshAllCodexQuotas,
-    refreshAllGhcpTokens,
-    refreshAllKiroTokens,
-    refreshAllQuotas,
-    refreshAllWindsurfTokens,
-    syncCurrentFromClient,
-  ]);
+  }, []);
+
+  const handleRefreshCompleted = useCallback(
+    async (payload: RefreshCompletedPayload) => {
+      if (payload.error) {
+        console.error(`[AutoRefresh] ${payload.platform} 后台刷新失败:`, payload.error);
+      }
+      try {
+        switch (payload.platform) {
+          case 'antigravity':
+            await fetchAccounts();
+            await fetchCurrentAccount();
+            break;
+          case 'codex':
+            await fetchCodexAccounts();
+            await fetchCurrentCodexAccount();
+            break;
+          case 'github_copilot':
+            await fetchGhcpAccounts();
+            break;
+          case 'windsurf':
+            await fetchWindsurfAccounts();
+            break;
+          case 'kiro':
+            await fetchKiroAccounts();
+            break;
+        }
+      } catch (e) {
+        console.error('[AutoRefresh] 同步刷新结果失败:', e);
+      }
+    },
+    [
+      fetchAccounts,
+      fetchCodexAccounts,
+      fetchCurrentAccount,
+      fetchCurrentCodexAccount,
+      fetchGhcpAccounts,
+      fetchKiroAccounts,
+      fetchWindsurfAccounts,
+    ],
+  );
+
+  useEffect(() => {
+    let disposed = false;
+    let unlisten: UnlistenFn | undefined;
+    listen<RefreshCompletedPayload>('quota:auto_refreshed', (event) => {
+      void handleRefreshCompleted(event.payload);
+    }).then((fn) => {
+      if (disposed) {
+        fn();
+      } else {
+        unlisten = fn;
+      }
+    });
+
+    return () => {
+      disposed = true;
+      if (unlisten) {
+        unlisten();
+      }
+    };
+  }, [handleRefreshCompleted]);
 
   useEffect(() => {
     destroyedRef.current = false;
     void setupAutoRefresh();
 
     const handleConfigUpdate = () => {
-      console.log('[AutoRefresh] 检测到配置变更，重新设置定时器');
+      console.log('[AutoRefresh] 检测到配置变更，重新检查刷新配置');
       void setupAutoRefresh();
     };
 
@@ -329,8 +222,7 @@ export function useAutoRefresh() {
     return () => {
       destroyedRef.current = true;
       setupPendingRef.current = false;
-      clearAllIntervals();
       window.removeEventListener('config-updated', handleConfigUpdate);
     };
-  }, [clearAllIntervals, setupAutoRefresh]);
+  }, [setupAutoRefresh]);
 }
@@ -556,18 +556,30 @@ export function CodexAccountsPage() {
     setAddMessage(t('common.shared.token.importing', '正在导入...'));
 
     try {
-      const accounts = await codexService.importCodexFromJson(trimmed);
+      const outcome = await codexService.importCodexFromJson(trimmed);
       await fetchAccounts();
-      for (const acc of accounts) {
+      for (const acc of outcome.imported) {
         await refreshQuota(acc.id).catch(() => {});
       }
       await fetchAccounts();
-      setAddStatus('success');
-      setAddMessage(t('common.shared.token.importSuccessMsg', '成功导入 {{count}} 个账号').replace('{{count}}', String(accounts.length)));
-      setTimeout(() => {
-        setShowAddModal(false);
-        resetAddModalState();
-      }, 1200);
+      if (outcome.failed.length > 0) {
+        setAddStatus('error');
+        setAddMessage(
+          t('common.shared.import.partialFailedMsg', {
+            count: outcome.imported.length,
+            failed: outcome.failed.length,
+            error: outcome.failed.join('; '),
+            defaultValue: '已导入 {{count}} 个账号，{{failed}} 个失败: {{error}}',
+          })
+        );
+      } else {
+        setAddStatus('success');
+        setAddMessage(t('common.shared.token.importSuccessMsg', '成功导入 {{count}} 个账号').replace('{{count}}', String(outcome.imported.length)));
+        setTimeout(() => {
+          setShowAddModal(false);
+          resetAddModalState();
+        }, 1200);
+      }
     } catch (e) {
       setAddStatus('error');
       const errorMsg = String(e).replace(/^Error:\s*/, '');
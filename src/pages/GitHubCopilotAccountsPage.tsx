@@ -28,6 +28,7 @@ import {
 import { useTranslation } from 'react-i18next';
 import { useGitHubCopilotAccountStore } from '../stores/useGitHubCopilotAccountStore';
 import * as githubCopilotService from '../services/githubCopilotService';
+import { ImportOutcome } from '../services/interchangeService';
 import { TagEditModal } from '../components/TagEditModal';
 import {
   getGitHubCopilotPlanDisplayName,
@@ -407,6 +408,18 @@ export function GitHubCopilotAccountsPage() {
     importFileInputRef.current?.click();
   };
 
+  const showImportFailures = (outcome: ImportOutcome<unknown>) => {
+    setAddStatus('error');
+    setAddMessage(
+      t('common.shared.import.partialFailedMsg', {
+        count: outcome.imported.length,
+        failed: outcome.failed.length,
+        error: outcome.failed.join('; '),
+        defaultValue: '已导入 {{count}} 个账号，{{failed}} 个失败: {{error}}',
+      })
+    );
+  };
+
   const handleImportJsonFile = async (file: File) => {
     setImporting(true);
     setAddStatus('loading');
@@ -414,20 +427,24 @@ export function GitHubCopilotAccountsPage() {
 
     try {
       const content = await file.text();
-      const imported = await githubCopilotService.importGitHubCopilotFromJson(content);
+      const outcome = await githubCopilotService.importGitHubCopilotFromJson(content);
       await fetchAccounts();
 
-      setAddStatus('success');
-      setAddMessage(
-        t('common.shared.token.importSuccessMsg', {
-          count: imported.length,
-          defaultValue: '成功导入 {{count}} 个账号',
-        })
-      );
-      setTimeout(() => {
-        setShowAddModal(false);
-        resetAddModalState();
-      }, 1200);
+      if (outcome.failed.length > 0) {
+        showImportFailures(outcome);
+      } else {
+        setAddStatus('success');
+        setAddMessage(
+          t('common.shared.token.importSuccessMsg', {
+            count: outcome.imported.length,
+            defaultValue: '成功导入 {{count}} 个账号',
+          })
+        );
+        setTimeout(() => {
+          setShowAddModal(false);
+          resetAddModalState();
+        }, 1200);
+      }
     } catch (e) {
       setAddStatus('error');
       const errorMsg = String(e).replace(/^Error:\s*/, '');
@@ -456,25 +473,33 @@ export function GitHubCopilotAccountsPage() {
 
     try {
       let importedCount = 0;
+      let failedOutcome: ImportOutcome<unknown> | null = null;
       if (trimmed.startsWith('{') || trimmed.startsWith('[')) {
-        const accounts = await githubCopilotService.importGitHubCopilotFromJson(trimmed);
-        importedCount = accounts.length;
+        const outcome = await githubCopilotService.importGitHubCopilotFromJson(trimmed);
+        importedCount = outcome.imported.length;
+        if (outcome.failed.length > 0) {
+          failedOutcome = outcome;
+        }
       } else {
         await githubCopilotService.addGitHubCopilotAccountWithToken(trimmed);
         importedCount = 1;
       }
       await fetchAccounts();
-      setAddStatus('success');
-      setAddMessage(
-        t('common.shared.token.importSuccessMsg', {
-          count: importedCount,
-          defaultValue: '成功导入 {{count}} 个账号',
-        })
-      );
-      setTimeout(() => {
-        setShowAddModal(false);
-        resetAddModalState();
-      }, 1200);
+      if (failedOutcome) {
+        showImportFailures(failedOutcome);
+      } else {
+        setAddStatus('success');
+        setAddMessage(
+          t('common.shared.token.importSuccessMsg', {
+            count: importedCount,
+            defaultValue: '成功导入 {{count}} 个账号',
+          })
+        );
+        setTimeout(() => {
+          setShowAddModal(false);
+          resetAddModalState();
+        }, 1200);
+      }
     } catch (e) {
       setAddStatus('error');
       const errorMsg = String(e).replace(/^Error:\s*/, '');
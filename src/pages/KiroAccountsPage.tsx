@@ -27,6 +27,7 @@ import {
 import { useTranslation } from 'react-i18next';
 import { useKiroAccountStore } from '../stores/useKiroAccountStore';
 import * as kiroService from '../services/kiroService';
+import { ImportOutcome } from '../services/interchangeService';
 import { TagEditModal } from '../components/TagEditModal';
 import {
   getKiroPlanDisplayName,
@@ -418,9 +419,16 @@ export function KiroAccountsPage() {
     importFileInputRef.current?.click();
   };
 
-  const importFromJsonContent = async (content: string): Promise<number> => {
-    const imported = await kiroService.importKiroFromJson(content);
-    return imported.length;
+  const showImportFailures = (outcome: ImportOutcome<unknown>) => {
+    setAddStatus('error');
+    setAddMessage(
+      t('common.shared.import.partialFailedMsg', {
+        count: outcome.imported.length,
+        failed: outcome.failed.length,
+        error: outcome.failed.join('; '),
+        defaultValue: '已导入 {{count}} 个账号，{{failed}} 个失败: {{error}}',
+      })
+    );
   };
 
   const handleImportJsonFile = async (file: File) => {
@@ -430,20 +438,24 @@ export function KiroAccountsPage() {
 
     try {
       const content = await file.text();
-      const importedCount = await importFromJsonContent(content);
+      const outcome = await kiroService.importKiroFromJson(content);
       await fetchAccounts();
 
-      setAddStatus('success');
-      setAddMessage(
-        t('common.shared.token.importSuccessMsg', {
-          count: importedCount,
-          defaultValue: '成功导入 {{count}} 个账号',
-        })
-      );
-      setTimeout(() => {
-        setShowAddModal(false);
-        resetAddModalState();
-      }, 1200);
+      if (outcome.failed.length > 0) {
+        showImportFailures(outcome);
+      } else {
+        setAddStatus('success');
+        setAddMessage(
+          t('common.shared.token.importSuccessMsg', {
+            count: outcome.imported.length,
+            defaultValue: '成功导入 {{count}} 个账号',
+          })
+        );
+        setTimeout(() => {
+          setShowAddModal(false);
+          resetAddModalState();
+        }, 1200);
+      }
     } catch (e) {
       setAddStatus('error');
       const errorMsg = String(e).replace(/^Error:\s*/, '');
@@ -503,25 +515,33 @@ export function KiroAccountsPage() {
 
     try {
       let importedCount = 0;
+      let failedOutcome: ImportOutcome<unknown> | null = null;
       if (trimmed.startsWith('{') || trimmed.startsWith('[')) {
-        const accounts = await kiroService.importKiroFromJson(trimmed);
-        importedCount = accounts.length;
+        const outcome = await kiroService.importKiroFromJson(trimmed);
+        importedCount = outcome.imported.length;
+        if (outcome.failed.length > 0) {
+          failedOutcome = outcome;
+        }
       } else {
         await kiroService.addKiroAccountWithToken(trimmed);
         importedCount = 1;
       }
       await fetchAccounts();
-      setAddStatus('success');
-      setAddMessage(
-        t('common.shared.token.importSuccessMsg', {
-          count: importedCount,
-          defaultValue: '成功导入 {{count}} 个账号',
-        })
-      );
-      setTimeout(() => {
-        setShowAddModal(false);
-        resetAddModalState();
-      }, 1200);
+      if (failedOutcome) {
+        showImportFailures(failedOutcome);
+      } else {
+        setAddStatus('success');
+        setAddMessage(
+          t('common.shared.token.importSuccessMsg', {
+            count: importedCount,
+            defaultValue: '成功导入 {{count}} 个账号',
+          })
+        );
+        setTimeout(() => {
+          setShowAddModal(false);
+          resetAddModalState();
+        }, 1200);
+      }
     } catch (e) {
       setAddStatus('error');
       const errorMsg = String(e).replace(/^Error:\s*/, '');
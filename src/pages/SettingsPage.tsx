@@ -1,18 +1,27 @@
 import { useState, useEffect, useMemo, useRef } from 'react';
 import { useTranslation } from 'react-i18next';
 import { openUrl } from '@tauri-apps/plugin-opener';
-import { open } from '@tauri-apps/plugin-dialog';
+import { open, save } from '@tauri-apps/plugin-dialog';
 import { invoke } from '@tauri-apps/api/core';
 import { getVersion } from '@tauri-apps/api/app';
 import { changeLanguage, getCurrentLanguage, normalizeLanguage } from '../i18n';
 import * as accountService from '../services/accountService';
+import { getGroupSettings } from '../services/groupService';
+import * as vaultService from '../services/vaultService';
+import * as backupService from '../services/backupService';
+import * as interchangeService from '../services/interchangeService';
+import { BackupRestoreModal } from '../components/BackupRestoreModal';
+import { ImportPreviewModal } from '../components/ImportPreviewModal';
+import { AuditLogModal } from '../components/AuditLogModal';
+import { TrashModal } from '../components/TrashModal';
+import { QuotaForecastModal } from '../components/QuotaForecastModal';
 import { usePlatformRuntimeSupport } from '../hooks/usePlatformRuntimeSupport';
 import { usePlatformLayoutStore } from '../stores/usePlatformLayoutStore';
 import { ALL_PLATFORM_IDS, PlatformId } from '../types/platform';
 import './settings/Settings.css';
 import { 
-  Github, User, Rocket, Save, FolderOpen,
-  AlertCircle, RefreshCw, Heart, MessageSquare
+  Github, User, Rocket, Save, FolderOpen, Download, Upload, Archive, ArchiveRestore,
+  AlertCircle, RefreshCw, Heart, MessageSquare, Lock, LockOpen, ScrollText, Trash2, TrendingDown
 } from 'lucide-react';
 
 
@@ -23,6 +32,18 @@ interface NetworkConfig {
   ws_port: number;
   actual_port: number | null;
   default_port: number;
+  proxy_enabled: boolean;
+  proxy_port: number;
+  proxy_api_key: string;
+  proxy_actual_port: number | null;
+  proxy_default_port: number;
+}
+
+/** 关注分组配置 */
+interface WatchedGroupConfig {
+  group_id: string;
+  auto_switch_threshold: number | null;
+  quota_alert_threshold: number | null;
 }
 
 /** 通用配置类型 */
@@ -47,16 +68,38 @@ interface GeneralConfig {
   codex_launch_on_switch: boolean;
   auto_switch_enabled: boolean;
   auto_switch_threshold: number;
+  auto_switch_strategy: string;
+  auto_switch_strategy_param: string;
+  watched_groups: WatchedGroupConfig[];
+  quota_protection_enabled: boolean;
+  quota_protection_reserve: number;
   quota_alert_enabled: boolean;
   quota_alert_threshold: number;
+  codex_auto_switch_enabled: boolean;
+  codex_auto_switch_threshold: number;
+  codex_auto_switch_strategy: string;
+  codex_auto_switch_strategy_param: string;
   codex_quota_alert_enabled: boolean;
   codex_quota_alert_threshold: number;
+  ghcp_auto_switch_enabled: boolean;
+  ghcp_auto_switch_threshold: number;
+  ghcp_auto_switch_strategy: string;
+  ghcp_auto_switch_strategy_param: string;
   ghcp_quota_alert_enabled: boolean;
   ghcp_quota_alert_threshold: number;
+  windsurf_auto_switch_enabled: boolean;
+  windsurf_auto_switch_threshold: number;
+  windsurf_auto_switch_strategy: string;
+  windsurf_auto_switch_strategy_param: string;
   windsurf_quota_alert_enabled: boolean;
   windsurf_quota_alert_threshold: number;
+  kiro_auto_switch_enabled: boolean;
+  kiro_auto_switch_threshold: number;
+  kiro_auto_switch_strategy: string;
+  kiro_auto_switch_strategy_param: string;
   kiro_quota_alert_enabled: boolean;
   kiro_quota_alert_threshold: number;
+  trash_retention_days: number;
 }
 
 type AppPathTarget = 'antigravity' | 'codex' | 'vscode' | 'opencode' | 'windsurf' | 'kiro';
@@ -134,14 +177,58 @@ export function SettingsPage() {
   const [codexLaunchOnSwitch, setCodexLaunchOnSwitch] = useState(true);
   const [autoSwitchEnabled, setAutoSwitchEnabled] = useState(false);
   const [autoSwitchThreshold, setAutoSwitchThreshold] = useState('20');
+  const [autoSwitchStrategy, setAutoSwitchStrategy] = useState('highest_quota');
+  const [autoSwitchStrategyParam, setAutoSwitchStrategyParam] = useState('');
+  const [autoSwitchGroups, setAutoSwitchGroups] = useState<Array<{ id: string; name: string }>>([]);
+  const [watchedGroups, setWatchedGroups] = useState<WatchedGroupConfig[]>([]);
+  const [vaultStatus, setVaultStatus] = useState<vaultService.VaultStatus | null>(null);
+  const [vaultProvider, setVaultProvider] = useState<vaultService.VaultKeyProvider>('passphrase');
+  const [vaultPassphrase, setVaultPassphrase] = useState('');
+  const [vaultKeyFilePath, setVaultKeyFilePath] = useState('');
+  const [vaultBusy, setVaultBusy] = useState(false);
+  const [exportingJson, setExportingJson] = useState(false);
+  const [backupPassphrase, setBackupPassphrase] = useState('');
+  const [backupBusy, setBackupBusy] = useState(false);
+  const [interchangeBusy, setInterchangeBusy] = useState(false);
+  const [showAuditLog, setShowAuditLog] = useState(false);
+  const [showTrash, setShowTrash] = useState(false);
+  const [showQuotaForecast, setShowQuotaForecast] = useState(false);
+  const interchangeFileInputRef = useRef<HTMLInputElement | null>(null);
+  const [importPreview, setImportPreview] = useState<{
+    jsonContent: string;
+    plan: interchangeService.ImportPlan;
+  } | null>(null);
+  const [restoreTarget, setRestoreTarget] = useState<{
+    path: string;
+    preview: backupService.RestorePreview;
+  } | null>(null);
+  const [quotaProtectionEnabled, setQuotaProtectionEnabled] = useState(false);
+  const [quotaProtectionReserve, setQuotaProtectionReserve] = useState('5');
+  const [trashRetentionDays, setTrashRetentionDays] = useState('30');
   const [quotaAlertEnabled, setQuotaAlertEnabled] = useState(false);
   const [quotaAlertThreshold, setQuotaAlertThreshold] = useState('20');
+  const [codexAutoSwitchEnabled, setCodexAutoSwitchEnabled] = useState(false);
+  const [codexAutoSwitchThreshold, setCodexAutoSwitchThreshold] = useState('5');
+  const [codexAutoSwitchStrategy, setCodexAutoSwitchStrategy] = useState('highest_quota');
+  const [codexAutoSwitchStrategyParam, setCodexAutoSwitchStrategyParam] = useState('');
   const [codexQuotaAlertEnabled, setCodexQuotaAlertEnabled] = useState(false);
   const [codexQuotaAlertThreshold, setCodexQuotaAlertThreshold] = useState('20');
+  const [ghcpAutoSwitchEnabled, setGhcpAutoSwitchEnabled] = useState(false);
+  const [ghcpAutoSwitchThreshold, setGhcpAutoSwitchThreshold] = useState('5');
+  const [ghcpAutoSwitchStrategy, setGhcpAutoSwitchStrategy] = useState('highest_quota');
+  const [ghcpAutoSwitchStrategyParam, setGhcpAutoSwitchStrategyParam] = useState('');
   const [ghcpQuotaAlertEnabled, setGhcpQuotaAlertEnabled] = useState(false);
   const [ghcpQuotaAlertThreshold, setGhcpQuotaAlertThreshold] = useState('20');
+  const [windsurfAutoSwitchEnabled, setWindsurfAutoSwitchEnabled] = useState(false);
+  const [windsurfAutoSwitchThreshold, setWindsurfAutoSwitchThreshold] = useState('5');
+  const [windsurfAutoSwitchStrategy, setWindsurfAutoSwitchStrategy] = useState('highest_quota');
+  const [windsurfAutoSwitchStrategyParam, setWindsurfAutoSwitchStrategyParam] = useState('');
   const [windsurfQuotaAlertEnabled, setWindsurfQuotaAlertEnabled] = useState(false);
   const [windsurfQuotaAlertThreshold, setWindsurfQuotaAlertThreshold] = useState('20');
+  const [kiroAutoSwitchEnabled, setKiroAutoSwitchEnabled] = useState(false);
+  const [kiroAutoSwitchThreshold, setKiroAutoSwitchThreshold] = useState('5');
+  const [kiroAutoSwitchStrategy, setKiroAutoSwitchStrategy] = useState('highest_quota');
+  const [kiroAutoSwitchStrategyParam, setKiroAutoSwitchStrategyParam] = useState('');
   const [kiroQuotaAlertEnabled, setKiroQuotaAlertEnabled] = useState(false);
   const [kiroQuotaAlertThreshold, setKiroQuotaAlertThreshold] = useState('20');
   const [autoRefreshCustomMode, setAutoRefreshCustomMode] = useState(false);
@@ -151,9 +238,13 @@ export function SettingsPage() {
   const [kiroAutoRefreshCustomMode, setKiroAutoRefreshCustomMode] = useState(false);
   const [autoSwitchThresholdCustomMode, setAutoSwitchThresholdCustomMode] = useState(false);
   const [quotaAlertThresholdCustomMode, setQuotaAlertThresholdCustomMode] = useState(false);
+  const [codexAutoSwitchThresholdCustomMode, setCodexAutoSwitchThresholdCustomMode] = useState(false);
   const [codexQuotaAlertThresholdCustomMode, setCodexQuotaAlertThresholdCustomMode] = useState(false);
+  const [ghcpAutoSwitchThresholdCustomMode, setGhcpAutoSwitchThresholdCustomMode] = useState(false);
   const [ghcpQuotaAlertThresholdCustomMode, setGhcpQuotaAlertThresholdCustomMode] = useState(false);
+  const [windsurfAutoSwitchThresholdCustomMode, setWindsurfAutoSwitchThresholdCustomMode] = useState(false);
   const [windsurfQuotaAlertThresholdCustomMode, setWindsurfQuotaAlertThresholdCustomMode] = useState(false);
+  const [kiroAutoSwitchThresholdCustomMode, setKiroAutoSwitchThresholdCustomMode] = useState(false);
   const [kiroQuotaAlertThresholdCustomMode, setKiroQuotaAlertThresholdCustomMode] = useState(false);
   const [generalLoaded, setGeneralLoaded] = useState(false);
   const generalSaveTimerRef = useRef<number | null>(null);
@@ -222,6 +313,11 @@ export function SettingsPage() {
   const [wsPort, setWsPort] = useState('19528');
   const [actualPort, setActualPort] = useState<number | null>(null);
   const [defaultPort, setDefaultPort] = useState(19528);
+  const [proxyEnabled, setProxyEnabled] = useState(false);
+  const [proxyPort, setProxyPort] = useState('19530');
+  const [proxyApiKey, setProxyApiKey] = useState('');
+  const [proxyActualPort, setProxyActualPort] = useState<number | null>(null);
+  const [proxyDefaultPort, setProxyDefaultPort] = useState(19530);
   const [needsRestart, setNeedsRestart] = useState(false);
   const [networkSaving, setNetworkSaving] = useState(false);
   
@@ -232,6 +328,8 @@ export function SettingsPage() {
   useEffect(() => {
     loadGeneralConfig();
     loadNetworkConfig();
+    loadAutoSwitchGroups();
+    loadVaultStatus();
   }, []);
   
   useEffect(() => {
@@ -267,11 +365,17 @@ export function SettingsPage() {
     const windsurfAutoRefreshNum = parseInt(windsurfAutoRefresh, 10) || -1;
     const kiroAutoRefreshNum = parseInt(kiroAutoRefresh, 10) || -1;
     const parsedAutoSwitchThreshold = Number.parseInt(autoSwitchThreshold, 10);
+    const parsedQuotaProtectionReserve = Number.parseInt(quotaProtectionReserve, 10);
     const parsedQuotaAlertThreshold = Number.parseInt(quotaAlertThreshold, 10);
+    const parsedCodexAutoSwitchThreshold = Number.parseInt(codexAutoSwitchThreshold, 10);
     const parsedCodexQuotaAlertThreshold = Number.parseInt(codexQuotaAlertThreshold, 10);
+    const parsedGhcpAutoSwitchThreshold = Number.parseInt(ghcpAutoSwitchThreshold, 10);
     const parsedGhcpQuotaAlertThreshold = Number.parseInt(ghcpQuotaAlertThreshold, 10);
+    const parsedWindsurfAutoSwitchThreshold = Number.parseInt(windsurfAutoSwitchThreshold, 10);
     const parsedWindsurfQuotaAlertThreshold = Number.parseInt(windsurfQuotaAlertThreshold, 10);
+    const parsedKiroAutoSwitchThreshold = Number.parseInt(kiroAutoSwitchThreshold, 10);
     const parsedKiroQuotaAlertThreshold = Number.parseInt(kiroQuotaAlertThreshold, 10);
+    const parsedTrashRetentionDays = Number.parseInt(trashRetentionDays, 10);
 
     if (suppressGeneralSaveRef.current) {
       suppressGeneralSaveRef.current = false;
@@ -300,24 +404,58 @@ export function SettingsPage() {
           codexLaunchOnSwitch,
           autoSwitchEnabled,
           autoSwitchThreshold: Number.isNaN(parsedAutoSwitchThreshold) ? 20 : parsedAutoSwitchThreshold,
+          autoSwitchStrategy,
+          autoSwitchStrategyParam: autoSwitchStrategyParam.trim(),
+          watchedGroups,
           quotaAlertEnabled,
           quotaAlertThreshold: Number.isNaN(parsedQuotaAlertThreshold) ? 20 : parsedQuotaAlertThreshold,
+          codexAutoSwitchEnabled,
+          codexAutoSwitchThreshold: Number.isNaN(parsedCodexAutoSwitchThreshold)
+            ? 5
+            : parsedCodexAutoSwitchThreshold,
+          codexAutoSwitchStrategy,
+          codexAutoSwitchStrategyParam: codexAutoSwitchStrategyParam.trim(),
           codexQuotaAlertEnabled,
           codexQuotaAlertThreshold: Number.isNaN(parsedCodexQuotaAlertThreshold)
             ? 20
             : parsedCodexQuotaAlertThreshold,
+          ghcpAutoSwitchEnabled,
+          ghcpAutoSwitchThreshold: Number.isNaN(parsedGhcpAutoSwitchThreshold)
+            ? 5
+            : parsedGhcpAutoSwitchThreshold,
+          ghcpAutoSwitchStrategy,
+          ghcpAutoSwitchStrategyParam: ghcpAutoSwitchStrategyParam.trim(),
           ghcpQuotaAlertEnabled,
           ghcpQuotaAlertThreshold: Number.isNaN(parsedGhcpQuotaAlertThreshold)
             ? 20
             : parsedGhcpQuotaAlertThreshold,
+          windsurfAutoSwitchEnabled,
+          windsurfAutoSwitchThreshold: Number.isNaN(parsedWindsurfAutoSwitchThreshold)
+            ? 5
+            : parsedWindsurfAutoSwitchThreshold,
+          windsurfAutoSwitchStrategy,
+          windsurfAutoSwitchStrategyParam: windsurfAutoSwitchStrategyParam.trim(),
           windsurfQuotaAlertEnabled,
           windsurfQuotaAlertThreshold: Number.isNaN(parsedWindsurfQuotaAlertThreshold)
             ? 20
             : parsedWindsurfQuotaAlertThreshold,
+          kiroAutoSwitchEnabled,
+          kiroAutoSwitchThreshold: Number.isNaN(parsedKiroAutoSwitchThreshold)
+            ? 5
+            : parsedKiroAutoSwitchThreshold,
+          kiroAutoSwitchStrategy,
+          kiroAutoSwitchStrategyParam: kiroAutoSwitchStrategyParam.trim(),
           kiroQuotaAlertEnabled,
           kiroQuotaAlertThreshold: Number.isNaN(parsedKiroQuotaAlertThreshold)
             ? 20
             : parsedKiroQuotaAlertThreshold,
+          quotaProtectionEnabled,
+          quotaProtectionReserve: Number.isNaN(parsedQuotaProtectionReserve)
+            ? 5
+            : parsedQuotaProtectionReserve,
+          trashRetentionDays: Number.isNaN(parsedTrashRetentionDays)
+            ? 30
+            : parsedTrashRetentionDays,
         });
         window.dispatchEvent(new Event('config-updated'));
       } catch (err) {
@@ -352,16 +490,38 @@ export function SettingsPage() {
     codexLaunchOnSwitch,
     autoSwitchEnabled,
     autoSwitchThreshold,
+    autoSwitchStrategy,
+    autoSwitchStrategyParam,
+    watchedGroups,
     quotaAlertEnabled,
     quotaAlertThreshold,
+    codexAutoSwitchEnabled,
+    codexAutoSwitchThreshold,
+    codexAutoSwitchStrategy,
+    codexAutoSwitchStrategyParam,
     codexQuotaAlertEnabled,
     codexQuotaAlertThreshold,
+    ghcpAutoSwitchEnabled,
+    ghcpAutoSwitchThreshold,
+    ghcpAutoSwitchStrategy,
+    ghcpAutoSwitchStrategyParam,
     ghcpQuotaAlertEnabled,
     ghcpQuotaAlertThreshold,
+    windsurfAutoSwitchEnabled,
+    windsurfAutoSwitchThreshold,
+    windsurfAutoSwitchStrategy,
+    windsurfAutoSwitchStrategyParam,
     windsurfQuotaAlertEnabled,
     windsurfQuotaAlertThreshold,
+    kiroAutoSwitchEnabled,
+    kiroAutoSwitchThreshold,
+    kiroAutoSwitchStrategy,
+    kiroAutoSwitchStrategyParam,
     kiroQuotaAlertEnabled,
     kiroQuotaAlertThreshold,
+    quotaProtectionEnabled,
+    quotaProtectionReserve,
+    trashRetentionDays,
     t,
   ]);
 
@@ -479,6 +639,163 @@ export function SettingsPage() {
     };
   }, [theme]);
   
+  const loadAutoSwitchGroups = async () => {
+    try {
+      const settings = await getGroupSettings();
+      const ids = [
+        ...settings.groupOrder,
+        ...Object.keys(settings.groupNames).filter((id) => !settings.groupOrder.includes(id)),
+      ];
+      setAutoSwitchGroups(ids.map((id) => ({ id, name: settings.groupNames[id] || id })));
+    } catch (err) {
+      console.error('加载分组配置失败:', err);
+    }
+  };
+
+  const loadVaultStatus = async () => {
+    try {
+      setVaultStatus(await vaultService.getVaultStatus());
+    } catch (err) {
+      console.error('加载凭据库状态失败:', err);
+    }
+  };
+
+  const runVaultAction = async (action: () => Promise<vaultService.VaultStatus>) => {
+    if (vaultBusy) return;
+    setVaultBusy(true);
+    try {
+      setVaultStatus(await action());
+      setVaultPassphrase('');
+    } catch (err) {
+      alert(t('settings.general.vault.failed', '操作失败: {error}').replace('{error}', String(err)));
+    } finally {
+      setVaultBusy(false);
+    }
+  };
+
+  const handlePickVaultKeyFile = async () => {
+    try {
+      const selected = await open({ multiple: false, directory: false });
+      const path = Array.isArray(selected) ? selected[0] : selected;
+      if (path) setVaultKeyFilePath(path);
+    } catch (err) {
+      console.error('选择密钥文件失败:', err);
+    }
+  };
+
+  const handleExportJson = async () => {
+    if (exportingJson) return;
+    try {
+      const selected = await open({ multiple: false, directory: true });
+      const dir = Array.isArray(selected) ? selected[0] : selected;
+      if (!dir) return;
+      setExportingJson(true);
+      const count = await accountService.exportDataStoreJson(dir);
+      alert(t('settings.general.exportJsonSuccess', '已导出 {count} 个文件').replace('{count}', String(count)));
+    } catch (err) {
+      alert(t('settings.general.exportJsonFailed', '导出失败: {error}').replace('{error}', String(err)));
+    } finally {
+      setExportingJson(false);
+    }
+  };
+
+  const handleCreateBackup = async () => {
+    if (backupBusy) return;
+    try {
+      const stamp = new Date().toISOString().slice(0, 19).replace(/[-:]/g, '').replace('T', '-');
+      const targetPath = await save({
+        defaultPath: `cockpit-backup-${stamp}.json`,
+        filters: [{ name: 'JSON', extensions: ['json'] }],
+      });
+      if (!targetPath) return;
+      setBackupBusy(true);
+      const summary = await backupService.createDataBackup(targetPath, backupPassphrase);
+      alert(t('settings.general.backup.created', '备份已保存（{count} 项）: {path}')
+        .replace('{count}', String(summary.entries))
+        .replace('{path}', summary.path));
+    } catch (err) {
+      alert(t('settings.general.backup.failed', '操作失败: {error}').replace('{error}', String(err)));
+    } finally {
+      setBackupBusy(false);
+    }
+  };
+
+  const handleExportInterchange = async () => {
+    if (interchangeBusy) return;
+    try {
+      const filePath = await save({
+        defaultPath: `cockpit-accounts-${new Date().toISOString().slice(0, 10)}.json`,
+        filters: [{ name: 'JSON', extensions: ['json'] }],
+      });
+      if (!filePath) return;
+      setInterchangeBusy(true);
+      const json = await interchangeService.exportInterchangeAccounts();
+      await invoke('save_text_file', { path: filePath, content: json });
+      alert(t('settings.general.interchange.exported', '已导出到: {path}').replace('{path}', filePath));
+    } catch (err) {
+      alert(t('settings.general.interchange.failed', '操作失败: {error}').replace('{error}', String(err)));
+    } finally {
+      setInterchangeBusy(false);
+    }
+  };
+
+  const handleImportInterchangeFile = async (file: File) => {
+    setInterchangeBusy(true);
+    try {
+      const jsonContent = await file.text();
+      const plan = await interchangeService.previewInterchangeImport(jsonContent);
+      setImportPreview({ jsonContent, plan });
+    } catch (err) {
+      alert(t('settings.general.interchange.failed', '操作失败: {error}').replace('{error}', String(err)));
+    } finally {
+      setInterchangeBusy(false);
+    }
+  };
+
+  const handleInterchangeImported = (result: interchangeService.InterchangeImportResult) => {
+    setImportPreview(null);
+    const total = Object.values(result.imported).reduce((sum, count) => sum + count, 0);
+    let message = t('settings.general.interchange.imported', '已导入 {count} 个账号，跳过 {skipped} 个，新增 {fingerprints} 个指纹')
+      .replace('{count}', String(total))
+      .replace('{skipped}', String(result.skipped))
+      .replace('{fingerprints}', String(result.fingerprintsAdded));
+    if (result.failed.length > 0) {
+      message += `\n${t('settings.general.interchange.importFailedList', '以下账号导入失败：')}\n${result.failed.join('\n')}`;
+    }
+    alert(message);
+  };
+
+  const handlePickRestoreArchive = async () => {
+    if (backupBusy) return;
+    try {
+      const selected = await open({
+        multiple: false,
+        directory: false,
+        filters: [{ name: 'JSON', extensions: ['json'] }],
+      });
+      const path = Array.isArray(selected) ? selected[0] : selected;
+      if (!path) return;
+      setBackupBusy(true);
+      const preview = await backupService.previewDataBackup(path, backupPassphrase);
+      setRestoreTarget({ path, preview });
+    } catch (err) {
+      alert(t('settings.general.backup.failed', '操作失败: {error}').replace('{error}', String(err)));
+    } finally {
+      setBackupBusy(false);
+    }
+  };
+
+  const handleRestored = (result: backupService.RestoreResult) => {
+    setRestoreTarget(null);
+    alert(t('settings.general.backup.restored', '恢复完成：写入 {written} 项，删除 {removed} 项。恢复前的数据已快照到 {path}')
+      .replace('{written}', String(result.written))
+      .replace('{removed}', String(result.removed))
+      .replace('{path}', result.snapshot_path));
+    if (result.sections.includes('settings')) {
+      void loadGeneralConfig();
+    }
+  };
+
   const loadGeneralConfig = async () => {
     try {
       const config = await invoke<GeneralConfig>('get_general_config');
@@ -501,14 +818,36 @@ export function SettingsPage() {
       setCodexLaunchOnSwitch(config.codex_launch_on_switch ?? true);
       setAutoSwitchEnabled(config.auto_switch_enabled ?? false);
       setAutoSwitchThreshold(String(config.auto_switch_threshold ?? 20));
+      setAutoSwitchStrategy(config.auto_switch_strategy || 'highest_quota');
+      setAutoSwitchStrategyParam(config.auto_switch_strategy_param ?? '');
+      setWatchedGroups(config.watched_groups ?? []);
+      setQuotaProtectionEnabled(config.quota_protection_enabled ?? false);
+      setQuotaProtectionReserve(String(config.quota_protection_reserve ?? 5));
+      setTrashRetentionDays(String(config.trash_retention_days ?? 30));
       setQuotaAlertEnabled(config.quota_alert_enabled ?? false);
       setQuotaAlertThreshold(String(config.quota_alert_threshold ?? 20));
+      setCodexAutoSwitchEnabled(config.codex_auto_switch_enabled ?? false);
+      setCodexAutoSwitchThreshold(String(config.codex_auto_switch_threshold ?? 5));
+      setCodexAutoSwitchStrategy(config.codex_auto_switch_strategy || 'highest_quota');
+      setCodexAutoSwitchStrategyParam(config.codex_auto_switch_strategy_param ?? '');
       setCodexQuotaAlertEnabled(config.codex_quota_alert_enabled ?? false);
       setCodexQuotaAlertThreshold(String(config.codex_quota_alert_threshold ?? 20));
+      setGhcpAutoSwitchEnabled(config.ghcp_auto_switch_enabled ?? false);
+      setGhcpAutoSwitchThreshold(String(config.ghcp_auto_switch_threshold ?? 5));
+      setGhcpAutoSwitchStrategy(config.ghcp_auto_switch_strategy || 'highest_quota');
+      setGhcpAutoSwitchStrategyParam(config.ghcp_auto_switch_strategy_param ?? '');
       setGhcpQuotaAlertEnabled(config.ghcp_quota_alert_enabled ?? false);
       setGhcpQuotaAlertThreshold(String(config.ghcp_quota_alert_threshold ?? 20));
+      setWindsurfAutoSwitchEnabled(config.windsurf_auto_switch_enabled ?? false);
+      setWindsurfAutoSwitchThreshold(String(config.windsurf_auto_switch_threshold ?? 5));
+      setWindsurfAutoSwitchStrategy(config.windsurf_auto_switch_strategy || 'highest_quota');
+      setWindsurfAutoSwitchStrategyParam(config.windsurf_auto_switch_strategy_param ?? '');
       setWindsurfQuotaAlertEnabled(config.windsurf_quota_alert_enabled ?? false);
       setWindsurfQuotaAlertThreshold(String(config.windsurf_quota_alert_threshold ?? 20));
+      setKiroAutoSwitchEnabled(config.kiro_auto_switch_enabled ?? false);
+      setKiroAutoSwitchThreshold(String(config.kiro_auto_switch_threshold ?? 5));
+      setKiroAutoSwitchStrategy(config.kiro_auto_switch_strategy || 'highest_quota');
+      setKiroAutoSwitchStrategyParam(config.kiro_auto_switch_strategy_param ?? '');
       setKiroQuotaAlertEnabled(config.kiro_quota_alert_enabled ?? false);
       setKiroQuotaAlertThreshold(String(config.kiro_quota_alert_threshold ?? 20));
       setAutoRefreshCustomMode(false);
@@ -518,9 +857,13 @@ export function SettingsPage() {
       setKiroAutoRefreshCustomMode(false);
       setAutoSwitchThresholdCustomMode(false);
       setQuotaAlertThresholdCustomMode(false);
+      setCodexAutoSwitchThresholdCustomMode(false);
       setCodexQuotaAlertThresholdCustomMode(false);
+      setGhcpAutoSwitchThresholdCustomMode(false);
       setGhcpQuotaAlertThresholdCustomMode(false);
+      setWindsurfAutoSwitchThresholdCustomMode(false);
       setWindsurfQuotaAlertThresholdCustomMode(false);
+      setKiroAutoSwitchThresholdCustomMode(false);
       setKiroQuotaAlertThresholdCustomMode(false);
       // 同步语言
       changeLanguage(config.language);
@@ -538,6 +881,11 @@ export function SettingsPage() {
       setWsPort(String(config.ws_port));
       setActualPort(config.actual_port);
       setDefaultPort(config.default_port);
+      setProxyEnabled(config.proxy_enabled);
+      setProxyPort(String(config.proxy_port));
+      setProxyApiKey(config.proxy_api_key ?? '');
+      setProxyActualPort(config.proxy_actual_port);
+      setProxyDefaultPort(config.proxy_default_port);
       setNeedsRestart(false);
     } catch (err) {
       console.error('加载网络配置失败:', err);
@@ -549,10 +897,17 @@ export function SettingsPage() {
     setNetworkSaving(true);
     try {
       const portNum = parseInt(wsPort, 10) || defaultPort;
+      const proxyPortNum = parseInt(proxyPort, 10) || proxyDefaultPort;
       const result = await invoke<boolean>('save_network_config', {
         wsEnabled,
         wsPort: portNum,
+        proxyEnabled,
+        proxyPort: proxyPortNum,
+        proxyApiKey,
       });
+      // 启用代理且未填写密钥时后端会自动生成，回填展示
+      const saved = await invoke<NetworkConfig>('get_network_config');
+      setProxyApiKey(saved.proxy_api_key ?? '');
       
       if (result) {
         setNeedsRestart(true);
@@ -645,6 +1000,26 @@ export function SettingsPage() {
 
   const sanitizeNumberInput = (value: string) => value.replace(/[^\d]/g, '');
 
+  const toggleWatchedGroup = (groupId: string, checked: boolean) => {
+    setWatchedGroups((prev) =>
+      checked
+        ? [...prev, { group_id: groupId, auto_switch_threshold: null, quota_alert_threshold: null }]
+        : prev.filter((item) => item.group_id !== groupId),
+    );
+  };
+
+  const updateWatchedGroupThreshold = (
+    groupId: string,
+    field: 'auto_switch_threshold' | 'quota_alert_threshold',
+    raw: string,
+  ) => {
+    const sanitized = sanitizeNumberInput(raw);
+    const value = sanitized === '' ? null : Math.min(100, Number.parseInt(sanitized, 10));
+    setWatchedGroups((prev) =>
+      prev.map((item) => (item.group_id === groupId ? { ...item, [field]: value } : item)),
+    );
+  };
+
   const normalizeNumberInput = (value: string, min: number, max?: number): string => {
     const parsed = Number.parseInt(value, 10);
     if (Number.isNaN(parsed)) {
@@ -661,9 +1036,13 @@ export function SettingsPage() {
   const kiroAutoRefreshIsPreset = REFRESH_PRESET_VALUES.includes(kiroAutoRefresh);
   const autoSwitchThresholdIsPreset = THRESHOLD_PRESET_VALUES.includes(autoSwitchThreshold);
   const quotaAlertThresholdIsPreset = THRESHOLD_PRESET_VALUES.includes(quotaAlertThreshold);
+  const codexAutoSwitchThresholdIsPreset = THRESHOLD_PRESET_VALUES.includes(codexAutoSwitchThreshold);
   const codexQuotaAlertThresholdIsPreset = THRESHOLD_PRESET_VALUES.includes(codexQuotaAlertThreshold);
+  const ghcpAutoSwitchThresholdIsPreset = THRESHOLD_PRESET_VALUES.includes(ghcpAutoSwitchThreshold);
   const ghcpQuotaAlertThresholdIsPreset = THRESHOLD_PRESET_VALUES.includes(ghcpQuotaAlertThreshold);
+  const windsurfAutoSwitchThresholdIsPreset = THRESHOLD_PRESET_VALUES.includes(windsurfAutoSwitchThreshold);
   const windsurfQuotaAlertThresholdIsPreset = THRESHOLD_PRESET_VALUES.includes(windsurfQuotaAlertThreshold);
+  const kiroAutoSwitchThresholdIsPreset = THRESHOLD_PRESET_VALUES.includes(kiroAutoSwitchThreshold);
   const kiroQuotaAlertThresholdIsPreset = THRESHOLD_PRESET_VALUES.includes(kiroQuotaAlertThreshold);
 
   // 检查更新
@@ -808,6 +1187,18 @@ export function SettingsPage() {
                 </div>
               </div>
 
+              <div className="settings-row">
+                <div className="row-label">
+                  <div className="row-title">{t('settings.general.exportJson', '导出 JSON 数据')}</div>
+                  <div className="row-desc">{t('settings.general.exportJsonDesc', '账号与实例数据保存在本地数据库中，可按旧版 JSON 目录结构导出供外部工具读取')}</div>
+                </div>
+                <div className="row-control">
+                  <button className="btn btn-secondary" onClick={handleExportJson} disabled={exportingJson}>
+                    <Download size={16} />{t('settings.general.exportJsonAction', '导出')}
+                  </button>
+                </div>
+              </div>
+
               <div className="settings-row">
                 <div className="row-label">
                   <div className="row-title">{t('settings.general.fpDir')}</div>
@@ -819,6 +1210,255 @@ export function SettingsPage() {
                   </button>
                 </div>
               </div>
+
+              <div className="settings-row">
+                <div className="row-label">
+                  <div className="row-title">{t('settings.general.vault.title', '账号凭据加密')}</div>
+                  <div className="row-desc">
+                    {!vaultStatus?.enabled
+                      ? t('settings.general.vault.disabledDesc', '启用后各平台账号文件将加密保存，密钥来自口令、系统密钥环或密钥文件')
+                      : vaultStatus.locked
+                        ? t('settings.general.vault.lockedDesc', '凭据库已锁定，解锁前无法读取或保存账号')
+                        : t('settings.general.vault.unlockedDesc', '凭据库已解锁，账号文件以加密形式保存')}
+                  </div>
+                </div>
+                <div className="row-control">
+                  {vaultStatus?.enabled && !vaultStatus.locked ? (
+                    <div style={{ display: 'flex', gap: '8px', alignItems: 'center' }}>
+                      <button
+                        className="btn btn-secondary"
+                        disabled={vaultBusy}
+                        onClick={() => runVaultAction(vaultService.lockVault)}
+                      >
+                        <Lock size={16} />{t('settings.general.vault.lock', '锁定')}
+                      </button>
+                      <button
+                        className="btn btn-secondary"
+                        disabled={vaultBusy}
+                        onClick={() => {
+                          if (window.confirm(t('settings.general.vault.disableConfirm', '停用后账号文件将解密为明文保存，确定继续？'))) {
+                            runVaultAction(vaultService.disableVault);
+                          }
+                        }}
+                      >
+                        {t('settings.general.vault.disable', '停用')}
+                      </button>
+                    </div>
+                  ) : vaultStatus?.enabled ? (
+                    <div style={{ display: 'flex', gap: '8px', alignItems: 'center' }}>
+                      {vaultStatus.provider === 'passphrase' && (
+                        <input
+                          type="password"
+                          className="settings-input"
+                          value={vaultPassphrase}
+                          placeholder={t('settings.general.vault.passphrasePlaceholder', '输入口令')}
+                          onChange={(e) => setVaultPassphrase(e.target.value)}
+                        />
+                      )}
+                      <button
+                        className="btn btn-primary"
+                        disabled={vaultBusy}
+                        onClick={() => runVaultAction(() => vaultService.unlockVault(vaultPassphrase))}
+                      >
+                        <LockOpen size={16} />{t('settings.general.vault.unlock', '解锁')}
+                      </button>
+                    </div>
+                  ) : (
+                    <select
+                      className="settings-select"
+                      value={vaultProvider}
+                      onChange={(e) => setVaultProvider(e.target.value as vaultService.VaultKeyProvider)}
+                    >
+                      <option value="passphrase">{t('settings.general.vault.providers.passphrase', '口令')}</option>
+                      <option value="secret_service">{t('settings.general.vault.providers.secretService', '系统密钥环（Linux）')}</option>
+                      <option value="key_file">{t('settings.general.vault.providers.keyFile', '密钥文件')}</option>
+                    </select>
+                  )}
+                </div>
+              </div>
+              {vaultStatus?.enabled && vaultStatus.plaintext_copies.length > 0 && (
+                <div className="settings-row">
+                  <div className="row-label">
+                    <div className="row-title">{t('settings.general.vault.plaintextCopies', '残留的明文副本')}</div>
+                    <div className="row-desc">
+                      {t('settings.general.vault.plaintextCopiesDesc', '以下目录仍保存着启用加密前的明文账号数据，确认不再需要后请手动删除')}
+                      {vaultStatus.plaintext_copies.map((path) => (
+                        <div key={path}><code>{path}</code></div>
+                      ))}
+                    </div>
+                  </div>
+                </div>
+              )}
+              {vaultStatus && !vaultStatus.enabled && (
+                <div className="settings-row" style={{ animation: 'fadeUp 0.3s ease both' }}>
+                  <div className="row-label">
+                    <div className="row-title">
+                      {vaultProvider === 'key_file'
+                        ? t('settings.general.vault.keyFile', '密钥文件路径')
+                        : vaultProvider === 'passphrase'
+                          ? t('settings.general.vault.passphrase', '口令')
+                          : t('settings.general.vault.secretService', '系统密钥环')}
+                    </div>
+                    <div className="row-desc">
+                      {vaultProvider === 'key_file'
+                        ? t('settings.general.vault.keyFileDesc', '文件不存在时会自动生成密钥；已存在时直接使用其中的密钥')
+                        : vaultProvider === 'passphrase'
+                          ? t('settings.general.vault.passphraseDesc', '至少 8 个字符，每次启动后需输入口令解锁，遗忘后无法恢复账号数据')
+                          : t('settings.general.vault.secretServiceDesc', '密钥保存在系统密钥环中，启动时自动解锁')}
+                    </div>
+                  </div>
+                  <div className="row-control row-control--grow">
+                    <div style={{ display: 'flex', gap: '8px', alignItems: 'center', flex: 1 }}>
+                      {vaultProvider === 'passphrase' && (
+                        <input
+                          type="password"
+                          className="settings-input"
+                          value={vaultPassphrase}
+                          placeholder={t('settings.general.vault.passphrasePlaceholder', '输入口令')}
+                          onChange={(e) => setVaultPassphrase(e.target.value)}
+                        />
+                      )}
+                      {vaultProvider === 'key_file' && (
+                        <>
+                          <input
+                            type="text"
+                            className="settings-input settings-input--path"
+                            value={vaultKeyFilePath}
+                            placeholder={t('settings.general.vault.keyFilePlaceholder', '密钥文件完整路径')}
+                            onChange={(e) => setVaultKeyFilePath(e.target.value)}
+                          />
+                          <button className="btn btn-secondary" onClick={handlePickVaultKeyFile}>
+                            {t('settings.general.codexPathSelect', '选择')}
+                          </button>
+                        </>
+                      )}
+                      <button
+                        className="btn btn-primary"
+                        disabled={vaultBusy}
+                        onClick={() =>
+                          runVaultAction(() =>
+                            vaultService.enableVault(vaultProvider, vaultPassphrase, vaultKeyFilePath),
+                          )
+                        }
+                      >
+                        <Lock size={16} />{t('settings.general.vault.enable', '启用加密')}
+                      </button>
+                    </div>
+                  </div>
+                </div>
+              )}
+
+              <div className="settings-row">
+                <div className="row-label">
+                  <div className="row-title">{t('settings.general.backup.title', '备份与恢复')}</div>
+                  <div className="row-desc">
+                    {t('settings.general.backup.desc', '把全部平台账号、实例、指纹与设置备份为单个文件；填写口令时加密备份，恢复加密备份时也需填写')}
+                  </div>
+                </div>
+                <div className="row-control row-control--grow">
+                  <div style={{ display: 'flex', gap: '8px', alignItems: 'center', flex: 1 }}>
+                    <input
+                      type="password"
+                      className="settings-input"
+                      value={backupPassphrase}
+                      placeholder={t('settings.general.backup.passphrasePlaceholder', '备份口令（可选）')}
+                      onChange={(e) => setBackupPassphrase(e.target.value)}
+                    />
+                    <button className="btn btn-secondary" onClick={handleCreateBackup} disabled={backupBusy}>
+                      <Archive size={16} />{t('settings.general.backup.create', '创建备份')}
+                    </button>
+                    <button className="btn btn-secondary" onClick={handlePickRestoreArchive} disabled={backupBusy}>
+                      <ArchiveRestore size={16} />{t('settings.general.backup.restore', '恢复')}
+                    </button>
+                  </div>
+                </div>
+              </div>
+
+              <div className="settings-row">
+                <div className="row-label">
+                  <div className="row-title">{t('settings.general.interchange.title', '账号迁移')}</div>
+                  <div className="row-desc">
+                    {t('settings.general.interchange.desc', '把全部平台账号连同标签、指纹绑定与配额导出为一个交换文件，在另一台设备上一次导入')}
+                  </div>
+                </div>
+                <div className="row-control">
+                  <button className="btn btn-secondary" onClick={handleExportInterchange} disabled={interchangeBusy}>
+                    <Download size={16} />{t('settings.general.interchange.export', '导出账号')}
+                  </button>
+                  <button
+                    className="btn btn-secondary"
+                    onClick={() => interchangeFileInputRef.current?.click()}
+                    disabled={interchangeBusy}
+                  >
+                    <Upload size={16} />{t('settings.general.interchange.import', '导入账号')}
+                  </button>
+                  <input
+                    ref={interchangeFileInputRef}
+                    type="file"
+                    accept=".json,application/json"
+                    style={{ display: 'none' }}
+                    onChange={(e) => {
+                      const file = e.target.files?.[0];
+                      e.target.value = '';
+                      if (file) void handleImportInterchangeFile(file);
+                    }}
+                  />
+                </div>
+              </div>
+
+              <div className="settings-row">
+                <div className="row-label">
+                  <div className="row-title">{t('settings.general.audit.title', '操作审计日志')}</div>
+                  <div className="row-desc">
+                    {t('settings.general.audit.desc', '记录切号、注入、删除、改标签、导入与自动切号的来源、执行者和结果，可按条件筛选并导出为 JSON 或 CSV')}
+                  </div>
+                </div>
+                <div className="row-control">
+                  <button className="btn btn-secondary" onClick={() => setShowAuditLog(true)}>
+                    <ScrollText size={16} />{t('settings.general.audit.open', '查看')}
+                  </button>
+                </div>
+              </div>
+
+              <div className="settings-row">
+                <div className="row-label">
+                  <div className="row-title">{t('settings.general.trash.title', '账号回收站')}</div>
+                  <div className="row-desc">
+                    {t('settings.general.trash.desc', '删除的账号会先移入回收站，保留期内可恢复；保留天数为 0 时永久保留')}
+                  </div>
+                </div>
+                <div className="row-control">
+                  <div className="settings-inline-input">
+                    <input
+                      type="number"
+                      min={0}
+                      max={3650}
+                      className="settings-select settings-select--input-mode settings-select--with-unit"
+                      value={trashRetentionDays}
+                      onChange={(e) => setTrashRetentionDays(sanitizeNumberInput(e.target.value))}
+                      onBlur={() => setTrashRetentionDays(normalizeNumberInput(trashRetentionDays, 0, 3650))}
+                    />
+                    <span className="settings-input-unit">{t('settings.general.trash.days', '天')}</span>
+                  </div>
+                  <button className="btn btn-secondary" onClick={() => setShowTrash(true)}>
+                    <Trash2 size={16} />{t('settings.general.trash.open', '查看')}
+                  </button>
+                </div>
+              </div>
+
+              <div className="settings-row">
+                <div className="row-label">
+                  <div className="row-title">{t('settings.general.quotaForecast.title', '配额耗尽预测')}</div>
+                  <div className="row-desc">
+                    {t('settings.general.quotaForecast.desc', '根据每次刷新记录的配额历史推算消耗速率，提前提示哪些账号会在重置前耗尽')}
+                  </div>
+                </div>
+                <div className="row-control">
+                  <button className="btn btn-secondary" onClick={() => setShowQuotaForecast(true)}>
+                    <TrendingDown size={16} />{t('settings.general.quotaForecast.open', '查看')}
+                  </button>
+                </div>
+              </div>
             </div>
 
             <div style={{ display: 'flex', flexDirection: 'column' }}>
@@ -1021,31 +1661,181 @@ export function SettingsPage() {
                   </div>
                 </div>
               )}
+              {autoSwitchEnabled && (
+                <div className="settings-row" style={{ animation: 'fadeUp 0.3s ease both' }}>
+                  <div className="row-label">
+                    <div className="row-title">{t('quickSettings.autoSwitch.strategy', '选号策略')}</div>
+                    <div className="row-desc">{t('quickSettings.autoSwitch.strategyDesc', '触发自动切号时如何挑选目标账号')}</div>
+                  </div>
+                  <div className="row-control">
+                    <select
+                      className="settings-select"
+                      value={autoSwitchStrategy}
+                      onChange={(e) => {
+                        setAutoSwitchStrategy(e.target.value);
+                        setAutoSwitchStrategyParam('');
+                      }}
+                    >
+                      <option value="highest_quota">{t('quickSettings.autoSwitch.strategies.highestQuota', '剩余配额最高')}</option>
+                      <option value="round_robin">{t('quickSettings.autoSwitch.strategies.roundRobin', '按顺序轮换')}</option>
+                      <option value="least_recently_used">{t('quickSettings.autoSwitch.strategies.leastRecentlyUsed', '最久未使用')}</option>
+                      <option value="group_quota">{t('quickSettings.autoSwitch.strategies.groupQuota', '指定分组配额最高')}</option>
+                      <option value="tag">{t('quickSettings.autoSwitch.strategies.tag', '仅限指定标签')}</option>
+                      <option value="priority">{t('quickSettings.autoSwitch.strategies.priority', '按账号排序优先')}</option>
+                    </select>
+                  </div>
+                </div>
+              )}
+              {autoSwitchEnabled && autoSwitchStrategy === 'tag' && (
+                <div className="settings-row" style={{ animation: 'fadeUp 0.3s ease both' }}>
+                  <div className="row-label">
+                    <div className="row-title">{t('quickSettings.autoSwitch.strategyTag', '限定标签')}</div>
+                    <div className="row-desc">{t('quickSettings.autoSwitch.strategyTagDesc', '只会切换到带有该标签的账号')}</div>
+                  </div>
+                  <div className="row-control">
+                    <input
+                      type="text"
+                      className="settings-input"
+                      value={autoSwitchStrategyParam}
+                      placeholder={t('quickSettings.autoSwitch.strategyTagPlaceholder', '输入标签')}
+                      onChange={(e) => setAutoSwitchStrategyParam(e.target.value)}
+                    />
+                  </div>
+                </div>
+              )}
+              {autoSwitchEnabled && autoSwitchStrategy === 'group_quota' && (
+                <div className="settings-row" style={{ animation: 'fadeUp 0.3s ease both' }}>
+                  <div className="row-label">
+                    <div className="row-title">{t('quickSettings.autoSwitch.strategyGroup', '目标分组')}</div>
+                    <div className="row-desc">{t('quickSettings.autoSwitch.strategyGroupDesc', '优先切换到该分组剩余配额最高的账号')}</div>
+                  </div>
+                  <div className="row-control">
+                    <select
+                      className="settings-select"
+                      value={autoSwitchStrategyParam}
+                      onChange={(e) => setAutoSwitchStrategyParam(e.target.value)}
+                    >
+                      <option value="">{t('quickSettings.autoSwitch.strategyGroupPlaceholder', '选择分组')}</option>
+                      {autoSwitchGroups.map((group) => (
+                        <option key={group.id} value={group.id}>{group.name}</option>
+                      ))}
+                    </select>
+                  </div>
+                </div>
+              )}
+
+              {autoSwitchGroups.length > 0 && (
+                <div className="settings-row">
+                  <div className="row-label">
+                    <div className="row-title">{t('settings.general.watchedGroups', '关注分组')}</div>
+                    <div className="row-desc">{t('settings.general.watchedGroupsDesc', '勾选后，自动切号与超额预警只评估所选分组（分组内取最低剩余配额），其他模型耗尽不会触发；阈值留空则使用全局阈值。未勾选任何分组时按单个模型评估。')}</div>
+                  </div>
+                </div>
+              )}
+              {autoSwitchGroups.map((group) => {
+                const watched = watchedGroups.find((item) => item.group_id === group.id);
+                return (
+                  <div className="settings-row" key={group.id}>
+                    <div className="row-label">
+                      <label style={{ display: 'flex', alignItems: 'center', gap: '8px', cursor: 'pointer' }}>
+                        <input
+                          type="checkbox"
+                          checked={!!watched}
+                          onChange={(e) => toggleWatchedGroup(group.id, e.target.checked)}
+                        />
+                        <span className="row-title">{group.name}</span>
+                      </label>
+                    </div>
+                    {watched && (
+                      <div className="row-control" style={{ display: 'flex', gap: '8px' }}>
+                        <input
+                          type="number"
+                          min={0}
+                          max={100}
+                          className="settings-input"
+                          value={watched.auto_switch_threshold ?? ''}
+                          placeholder={t('settings.general.watchedGroupSwitchThreshold', '切号阈值 %')}
+                          onChange={(e) => updateWatchedGroupThreshold(group.id, 'auto_switch_threshold', e.target.value)}
+                        />
+                        <input
+                          type="number"
+                          min={0}
+                          max={100}
+                          className="settings-input"
+                          value={watched.quota_alert_threshold ?? ''}
+                          placeholder={t('settings.general.watchedGroupAlertThreshold', '预警阈值 %')}
+                          onChange={(e) => updateWatchedGroupThreshold(group.id, 'quota_alert_threshold', e.target.value)}
+                        />
+                      </div>
+                    )}
+                  </div>
+                );
+              })}
 
               <div className="settings-row">
                 <div className="row-label">
-                  <div className="row-title">{t('quickSettings.quotaAlert.enable', '超额预警')}</div>
-                  <div className="row-desc">{t('quickSettings.quotaAlert.hint', '当当前账号任意模型配额低于阈值时，发送原生通知并在页面提示快捷切号。')}</div>
+                  <div className="row-title">{t('settings.general.quotaProtection', '配额保留')}</div>
+                  <div className="row-desc">{t('settings.general.quotaProtectionDesc', '为每个模型保留最后一部分配额供手动使用。低于保留值的模型在配额重置前不会被唤醒任务、自动切号和本地 API 代理消耗。')}</div>
                 </div>
                 <div className="row-control">
                   <label className="switch">
                     <input
                       type="checkbox"
-                      checked={quotaAlertEnabled}
-                      onChange={(e) => setQuotaAlertEnabled(e.target.checked)}
+                      checked={quotaProtectionEnabled}
+                      onChange={(e) => setQuotaProtectionEnabled(e.target.checked)}
                     />
                     <span className="slider"></span>
                   </label>
                 </div>
               </div>
-              {quotaAlertEnabled && (
+              {quotaProtectionEnabled && (
                 <div className="settings-row" style={{ animation: 'fadeUp 0.3s ease both' }}>
                   <div className="row-label">
-                    <div className="row-title">{t('quickSettings.quotaAlert.threshold', '预警阈值')}</div>
-                    <div className="row-desc">{t('quickSettings.quotaAlert.thresholdDesc', '任意模型配额低于此百分比时触发预警')}</div>
+                    <div className="row-title">{t('settings.general.quotaProtectionReserve', '保留阈值')}</div>
+                    <div className="row-desc">{t('settings.general.quotaProtectionReserveDesc', '模型剩余配额低于此百分比时进入保护状态')}</div>
                   </div>
                   <div className="row-control">
-                    {quotaAlertThresholdCustomMode ? (
+                    <div className="settings-inline-input">
+                      <input
+                        type="number"
+                        min={0}
+                        max={100}
+                        className="settings-select settings-select--input-mode settings-select--with-unit"
+                        value={quotaProtectionReserve}
+                        placeholder={t('quickSettings.inputPercent', '输入百分比')}
+                        onChange={(e) => setQuotaProtectionReserve(sanitizeNumberInput(e.target.value))}
+                        onBlur={() => setQuotaProtectionReserve(normalizeNumberInput(quotaProtectionReserve, 0, 100))}
+                      />
+                      <span className="settings-input-unit">%</span>
+                    </div>
+                  </div>
+                </div>
+              )}
+
+              <div className="settings-row">
+                <div className="row-label">
+                  <div className="row-title">{t('quickSettings.quotaAlert.enable', '超额预警')}</div>
+                  <div className="row-desc">{t('quickSettings.quotaAlert.hint', '当当前账号任意模型配额低于阈值时，发送原生通知并在页面提示快捷切号。')}</div>
+                </div>
+                <div className="row-control">
+                  <label className="switch">
+                    <input
+                      type="checkbox"
+                      checked={quotaAlertEnabled}
+                      onChange={(e) => setQuotaAlertEnabled(e.target.checked)}
+                    />
+                    <span className="slider"></span>
+                  </label>
+                </div>
+              </div>
+              {quotaAlertEnabled && (
+                <div className="settings-row" style={{ animation: 'fadeUp 0.3s ease both' }}>
+                  <div className="row-label">
+                    <div className="row-title">{t('quickSettings.quotaAlert.threshold', '预警阈值')}</div>
+                    <div className="row-desc">{t('quickSettings.quotaAlert.thresholdDesc', '任意模型配额低于此百分比时触发预警')}</div>
+                  </div>
+                  <div className="row-control">
+                    {quotaAlertThresholdCustomMode ? (
                       <div className="settings-inline-input">
                         <input
                           type="number"
@@ -1278,6 +2068,125 @@ export function SettingsPage() {
                 </div>
               </div>
 
+              <div className="settings-row">
+                <div className="row-label">
+                  <div className="row-title">{t('quickSettings.autoSwitch.enable', '自动切号')}</div>
+                  <div className="row-desc">{t('quickSettings.autoSwitch.codexHint', '当前账号的 5 小时或周配额低于阈值时，自动切换到剩余配额最多的账号，并按上方设置重启 OpenCode / Codex。已过重置时间的窗口视为已恢复。')}</div>
+                </div>
+                <div className="row-control">
+                  <label className="switch">
+                    <input
+                      type="checkbox"
+                      checked={codexAutoSwitchEnabled}
+                      onChange={(e) => setCodexAutoSwitchEnabled(e.target.checked)}
+                    />
+                    <span className="slider"></span>
+                  </label>
+                </div>
+              </div>
+              {codexAutoSwitchEnabled && (
+                <div className="settings-row" style={{ animation: 'fadeUp 0.3s ease both' }}>
+                  <div className="row-label">
+                    <div className="row-title">{t('quickSettings.autoSwitch.threshold', '切号阈值')}</div>
+                    <div className="row-desc">{t('quickSettings.autoSwitch.codexThresholdDesc', '5 小时或周配额低于此百分比时触发自动切号')}</div>
+                  </div>
+                  <div className="row-control">
+                    {codexAutoSwitchThresholdCustomMode ? (
+                      <div className="settings-inline-input">
+                        <input
+                          type="number"
+                          min={0}
+                          max={100}
+                          className="settings-select settings-select--input-mode settings-select--with-unit"
+                          value={codexAutoSwitchThreshold}
+                          placeholder={t('quickSettings.inputPercent', '输入百分比')}
+                          onChange={(e) => setCodexAutoSwitchThreshold(sanitizeNumberInput(e.target.value))}
+                          onBlur={() => {
+                            const normalized = normalizeNumberInput(codexAutoSwitchThreshold, 0, 100);
+                            setCodexAutoSwitchThreshold(normalized);
+                            setCodexAutoSwitchThresholdCustomMode(false);
+                          }}
+                          onKeyDown={(e) => {
+                            if (e.key === 'Enter') {
+                              e.preventDefault();
+                              const normalized = normalizeNumberInput(codexAutoSwitchThreshold, 0, 100);
+                              setCodexAutoSwitchThreshold(normalized);
+                              setCodexAutoSwitchThresholdCustomMode(false);
+                            }
+                          }}
+                        />
+                        <span className="settings-input-unit">%</span>
+                      </div>
+                    ) : (
+                      <select
+                        className="settings-select"
+                        value={codexAutoSwitchThreshold}
+                        onChange={(e) => {
+                          const val = e.target.value;
+                          if (val === 'custom') {
+                            setCodexAutoSwitchThresholdCustomMode(true);
+                            setCodexAutoSwitchThreshold(codexAutoSwitchThreshold || '5');
+                            return;
+                          }
+                          setCodexAutoSwitchThresholdCustomMode(false);
+                          setCodexAutoSwitchThreshold(val);
+                        }}
+                      >
+                        {!codexAutoSwitchThresholdIsPreset && (
+                          <option value={codexAutoSwitchThreshold}>{codexAutoSwitchThreshold}%</option>
+                        )}
+                        <option value="0">0%</option>
+                        <option value="20">20%</option>
+                        <option value="40">40%</option>
+                        <option value="60">60%</option>
+                        <option value="custom">{t('settings.general.autoRefreshCustom')}</option>
+                      </select>
+                    )}
+                  </div>
+                </div>
+              )}
+              {codexAutoSwitchEnabled && (
+                <div className="settings-row" style={{ animation: 'fadeUp 0.3s ease both' }}>
+                  <div className="row-label">
+                    <div className="row-title">{t('quickSettings.autoSwitch.strategy', '选号策略')}</div>
+                    <div className="row-desc">{t('quickSettings.autoSwitch.strategyDesc', '触发自动切号时如何挑选目标账号')}</div>
+                  </div>
+                  <div className="row-control">
+                    <select
+                      className="settings-select"
+                      value={codexAutoSwitchStrategy}
+                      onChange={(e) => {
+                        setCodexAutoSwitchStrategy(e.target.value);
+                        setCodexAutoSwitchStrategyParam('');
+                      }}
+                    >
+                      <option value="highest_quota">{t('quickSettings.autoSwitch.strategies.highestQuota', '剩余配额最高')}</option>
+                      <option value="round_robin">{t('quickSettings.autoSwitch.strategies.roundRobin', '按顺序轮换')}</option>
+                      <option value="least_recently_used">{t('quickSettings.autoSwitch.strategies.leastRecentlyUsed', '最久未使用')}</option>
+                      <option value="tag">{t('quickSettings.autoSwitch.strategies.tag', '仅限指定标签')}</option>
+                      <option value="priority">{t('quickSettings.autoSwitch.strategies.priority', '按账号排序优先')}</option>
+                    </select>
+                  </div>
+                </div>
+              )}
+              {codexAutoSwitchEnabled && codexAutoSwitchStrategy === 'tag' && (
+                <div className="settings-row" style={{ animation: 'fadeUp 0.3s ease both' }}>
+                  <div className="row-label">
+                    <div className="row-title">{t('quickSettings.autoSwitch.strategyTag', '限定标签')}</div>
+                    <div className="row-desc">{t('quickSettings.autoSwitch.strategyTagDesc', '只会切换到带有该标签的账号')}</div>
+                  </div>
+                  <div className="row-control">
+                    <input
+                      type="text"
+                      className="settings-input"
+                      value={codexAutoSwitchStrategyParam}
+                      placeholder={t('quickSettings.autoSwitch.strategyTagPlaceholder', '输入标签')}
+                      onChange={(e) => setCodexAutoSwitchStrategyParam(e.target.value)}
+                    />
+                  </div>
+                </div>
+              )}
+
               <div className="settings-row">
                 <div className="row-label">
                   <div className="row-title">{t('quickSettings.quotaAlert.enable', '超额预警')}</div>
@@ -1463,6 +2372,125 @@ export function SettingsPage() {
                 </div>
               </div>
 
+              <div className="settings-row">
+                <div className="row-label">
+                  <div className="row-title">{t('quickSettings.autoSwitch.enable', '自动切号')}</div>
+                  <div className="row-desc">{t('quickSettings.autoSwitch.ideHint', '当前账号任一配额低于阈值时，自动切换到剩余配额最多的账号并注入默认实例；仅在 IDE 运行中时才会重启。')}</div>
+                </div>
+                <div className="row-control">
+                  <label className="switch">
+                    <input
+                      type="checkbox"
+                      checked={ghcpAutoSwitchEnabled}
+                      onChange={(e) => setGhcpAutoSwitchEnabled(e.target.checked)}
+                    />
+                    <span className="slider"></span>
+                  </label>
+                </div>
+              </div>
+              {ghcpAutoSwitchEnabled && (
+                <div className="settings-row" style={{ animation: 'fadeUp 0.3s ease both' }}>
+                  <div className="row-label">
+                    <div className="row-title">{t('quickSettings.autoSwitch.threshold', '切号阈值')}</div>
+                    <div className="row-desc">{t('quickSettings.autoSwitch.thresholdDesc', '任意模型配额低于此百分比时触发自动切号')}</div>
+                  </div>
+                  <div className="row-control">
+                    {ghcpAutoSwitchThresholdCustomMode ? (
+                      <div className="settings-inline-input">
+                        <input
+                          type="number"
+                          min={0}
+                          max={100}
+                          className="settings-select settings-select--input-mode settings-select--with-unit"
+                          value={ghcpAutoSwitchThreshold}
+                          placeholder={t('quickSettings.inputPercent', '输入百分比')}
+                          onChange={(e) => setGhcpAutoSwitchThreshold(sanitizeNumberInput(e.target.value))}
+                          onBlur={() => {
+                            const normalized = normalizeNumberInput(ghcpAutoSwitchThreshold, 0, 100);
+                            setGhcpAutoSwitchThreshold(normalized);
+                            setGhcpAutoSwitchThresholdCustomMode(false);
+                          }}
+                          onKeyDown={(e) => {
+                            if (e.key === 'Enter') {
+                              e.preventDefault();
+                              const normalized = normalizeNumberInput(ghcpAutoSwitchThreshold, 0, 100);
+                              setGhcpAutoSwitchThreshold(normalized);
+                              setGhcpAutoSwitchThresholdCustomMode(false);
+                            }
+                          }}
+                        />
+                        <span className="settings-input-unit">%</span>
+                      </div>
+                    ) : (
+                      <select
+                        className="settings-select"
+                        value={ghcpAutoSwitchThreshold}
+                        onChange={(e) => {
+                          const val = e.target.value;
+                          if (val === 'custom') {
+                            setGhcpAutoSwitchThresholdCustomMode(true);
+                            setGhcpAutoSwitchThreshold(ghcpAutoSwitchThreshold || '5');
+                            return;
+                          }
+                          setGhcpAutoSwitchThresholdCustomMode(false);
+                          setGhcpAutoSwitchThreshold(val);
+                        }}
+                      >
+                        {!ghcpAutoSwitchThresholdIsPreset && (
+                          <option value={ghcpAutoSwitchThreshold}>{ghcpAutoSwitchThreshold}%</option>
+                        )}
+                        <option value="0">0%</option>
+                        <option value="20">20%</option>
+                        <option value="40">40%</option>
+                        <option value="60">60%</option>
+                        <option value="custom">{t('settings.general.autoRefreshCustom')}</option>
+                      </select>
+                    )}
+                  </div>
+                </div>
+              )}
+              {ghcpAutoSwitchEnabled && (
+                <div className="settings-row" style={{ animation: 'fadeUp 0.3s ease both' }}>
+                  <div className="row-label">
+                    <div className="row-title">{t('quickSettings.autoSwitch.strategy', '选号策略')}</div>
+                    <div className="row-desc">{t('quickSettings.autoSwitch.strategyDesc', '触发自动切号时如何挑选目标账号')}</div>
+                  </div>
+                  <div className="row-control">
+                    <select
+                      className="settings-select"
+                      value={ghcpAutoSwitchStrategy}
+                      onChange={(e) => {
+                        setGhcpAutoSwitchStrategy(e.target.value);
+                        setGhcpAutoSwitchStrategyParam('');
+                      }}
+                    >
+                      <option value="highest_quota">{t('quickSettings.autoSwitch.strategies.highestQuota', '剩余配额最高')}</option>
+                      <option value="round_robin">{t('quickSettings.autoSwitch.strategies.roundRobin', '按顺序轮换')}</option>
+                      <option value="least_recently_used">{t('quickSettings.autoSwitch.strategies.leastRecentlyUsed', '最久未使用')}</option>
+                      <option value="tag">{t('quickSettings.autoSwitch.strategies.tag', '仅限指定标签')}</option>
+                      <option value="priority">{t('quickSettings.autoSwitch.strategies.priority', '按账号排序优先')}</option>
+                    </select>
+                  </div>
+                </div>
+              )}
+              {ghcpAutoSwitchEnabled && ghcpAutoSwitchStrategy === 'tag' && (
+                <div className="settings-row" style={{ animation: 'fadeUp 0.3s ease both' }}>
+                  <div className="row-label">
+                    <div className="row-title">{t('quickSettings.autoSwitch.strategyTag', '限定标签')}</div>
+                    <div className="row-desc">{t('quickSettings.autoSwitch.strategyTagDesc', '只会切换到带有该标签的账号')}</div>
+                  </div>
+                  <div className="row-control">
+                    <input
+                      type="text"
+                      className="settings-input"
+                      value={ghcpAutoSwitchStrategyParam}
+                      placeholder={t('quickSettings.autoSwitch.strategyTagPlaceholder', '输入标签')}
+                      onChange={(e) => setGhcpAutoSwitchStrategyParam(e.target.value)}
+                    />
+                  </div>
+                </div>
+              )}
+
               <div className="settings-row">
                 <div className="row-label">
                   <div className="row-title">{t('quickSettings.quotaAlert.enable', '超额预警')}</div>
@@ -1648,6 +2676,125 @@ export function SettingsPage() {
                 </div>
               </div>
 
+              <div className="settings-row">
+                <div className="row-label">
+                  <div className="row-title">{t('quickSettings.autoSwitch.enable', '自动切号')}</div>
+                  <div className="row-desc">{t('quickSettings.autoSwitch.ideHint', '当前账号任一配额低于阈值时，自动切换到剩余配额最多的账号并注入默认实例；仅在 IDE 运行中时才会重启。')}</div>
+                </div>
+                <div className="row-control">
+                  <label className="switch">
+                    <input
+                      type="checkbox"
+                      checked={windsurfAutoSwitchEnabled}
+                      onChange={(e) => setWindsurfAutoSwitchEnabled(e.target.checked)}
+                    />
+                    <span className="slider"></span>
+                  </label>
+                </div>
+              </div>
+              {windsurfAutoSwitchEnabled && (
+                <div className="settings-row" style={{ animation: 'fadeUp 0.3s ease both' }}>
+                  <div className="row-label">
+                    <div className="row-title">{t('quickSettings.autoSwitch.threshold', '切号阈值')}</div>
+                    <div className="row-desc">{t('quickSettings.autoSwitch.thresholdDesc', '任意模型配额低于此百分比时触发自动切号')}</div>
+                  </div>
+                  <div className="row-control">
+                    {windsurfAutoSwitchThresholdCustomMode ? (
+                      <div className="settings-inline-input">
+                        <input
+                          type="number"
+                          min={0}
+                          max={100}
+                          className="settings-select settings-select--input-mode settings-select--with-unit"
+                          value={windsurfAutoSwitchThreshold}
+                          placeholder={t('quickSettings.inputPercent', '输入百分比')}
+                          onChange={(e) => setWindsurfAutoSwitchThreshold(sanitizeNumberInput(e.target.value))}
+                          onBlur={() => {
+                            const normalized = normalizeNumberInput(windsurfAutoSwitchThreshold, 0, 100);
+                            setWindsurfAutoSwitchThreshold(normalized);
+                            setWindsurfAutoSwitchThresholdCustomMode(false);
+                          }}
+                          onKeyDown={(e) => {
+                            if (e.key === 'Enter') {
+                              e.preventDefault();
+                              const normalized = normalizeNumberInput(windsurfAutoSwitchThreshold, 0, 100);
+                              setWindsurfAutoSwitchThreshold(normalized);
+                              setWindsurfAutoSwitchThresholdCustomMode(false);
+                            }
+                          }}
+                        />
+                        <span className="settings-input-unit">%</span>
+                      </div>
+                    ) : (
+                      <select
+                        className="settings-select"
+                        value={windsurfAutoSwitchThreshold}
+                        onChange={(e) => {
+                          const val = e.target.value;
+                          if (val === 'custom') {
+                            setWindsurfAutoSwitchThresholdCustomMode(true);
+                            setWindsurfAutoSwitchThreshold(windsurfAutoSwitchThreshold || '5');
+                            return;
+                          }
+                          setWindsurfAutoSwitchThresholdCustomMode(false);
+                          setWindsurfAutoSwitchThreshold(val);
+                        }}
+                      >
+                        {!windsurfAutoSwitchThresholdIsPreset && (
+                          <option value={windsurfAutoSwitchThreshold}>{windsurfAutoSwitchThreshold}%</option>
+                        )}
+                        <option value="0">0%</option>
+                        <option value="20">20%</option>
+                        <option value="40">40%</option>
+                        <option value="60">60%</option>
+                        <option value="custom">{t('settings.general.autoRefreshCustom')}</option>
+                      </select>
+                    )}
+                  </div>
+                </div>
+              )}
+              {windsurfAutoSwitchEnabled && (
+                <div className="settings-row" style={{ animation: 'fadeUp 0.3s ease both' }}>
+                  <div className="row-label">
+                    <div className="row-title">{t('quickSettings.autoSwitch.strategy', '选号策略')}</div>
+                    <div className="row-desc">{t('quickSettings.autoSwitch.strategyDesc', '触发自动切号时如何挑选目标账号')}</div>
+                  </div>
+                  <div className="row-control">
+                    <select
+                      className="settings-select"
+                      value={windsurfAutoSwitchStrategy}
+                      onChange={(e) => {
+                        setWindsurfAutoSwitchStrategy(e.target.value);
+                        setWindsurfAutoSwitchStrategyParam('');
+                      }}
+                    >
+                      <option value="highest_quota">{t('quickSettings.autoSwitch.strategies.highestQuota', '剩余配额最高')}</option>
+                      <option value="round_robin">{t('quickSettings.autoSwitch.strategies.roundRobin', '按顺序轮换')}</option>
+                      <option value="least_recently_used">{t('quickSettings.autoSwitch.strategies.leastRecentlyUsed', '最久未使用')}</option>
+                      <option value="tag">{t('quickSettings.autoSwitch.strategies.tag', '仅限指定标签')}</option>
+                      <option value="priority">{t('quickSettings.autoSwitch.strategies.priority', '按账号排序优先')}</option>
+                    </select>
+                  </div>
+                </div>
+              )}
+              {windsurfAutoSwitchEnabled && windsurfAutoSwitchStrategy === 'tag' && (
+                <div className="settings-row" style={{ animation: 'fadeUp 0.3s ease both' }}>
+                  <div className="row-label">
+                    <div className="row-title">{t('quickSettings.autoSwitch.strategyTag', '限定标签')}</div>
+                    <div className="row-desc">{t('quickSettings.autoSwitch.strategyTagDesc', '只会切换到带有该标签的账号')}</div>
+                  </div>
+                  <div className="row-control">
+                    <input
+                      type="text"
+                      className="settings-input"
+                      value={windsurfAutoSwitchStrategyParam}
+                      placeholder={t('quickSettings.autoSwitch.strategyTagPlaceholder', '输入标签')}
+                      onChange={(e) => setWindsurfAutoSwitchStrategyParam(e.target.value)}
+                    />
+                  </div>
+                </div>
+              )}
+
               <div className="settings-row">
                 <div className="row-label">
                   <div className="row-title">{t('quickSettings.quotaAlert.enable', '超额预警')}</div>
@@ -1833,6 +2980,125 @@ export function SettingsPage() {
                 </div>
               </div>
 
+              <div className="settings-row">
+                <div className="row-label">
+                  <div className="row-title">{t('quickSettings.autoSwitch.enable', '自动切号')}</div>
+                  <div className="row-desc">{t('quickSettings.autoSwitch.ideHint', '当前账号任一配额低于阈值时，自动切换到剩余配额最多的账号并注入默认实例；仅在 IDE 运行中时才会重启。')}</div>
+                </div>
+                <div className="row-control">
+                  <label className="switch">
+                    <input
+                      type="checkbox"
+                      checked={kiroAutoSwitchEnabled}
+                      onChange={(e) => setKiroAutoSwitchEnabled(e.target.checked)}
+                    />
+                    <span className="slider"></span>
+                  </label>
+                </div>
+              </div>
+              {kiroAutoSwitchEnabled && (
+                <div className="settings-row" style={{ animation: 'fadeUp 0.3s ease both' }}>
+                  <div className="row-label">
+                    <div className="row-title">{t('quickSettings.autoSwitch.threshold', '切号阈值')}</div>
+                    <div className="row-desc">{t('quickSettings.autoSwitch.thresholdDesc', '任意模型配额低于此百分比时触发自动切号')}</div>
+                  </div>
+                  <div className="row-control">
+                    {kiroAutoSwitchThresholdCustomMode ? (
+                      <div className="settings-inline-input">
+                        <input
+                          type="number"
+                          min={0}
+                          max={100}
+                          className="settings-select settings-select--input-mode settings-select--with-unit"
+                          value={kiroAutoSwitchThreshold}
+                          placeholder={t('quickSettings.inputPercent', '输入百分比')}
+                          onChange={(e) => setKiroAutoSwitchThreshold(sanitizeNumberInput(e.target.value))}
+                          onBlur={() => {
+                            const normalized = normalizeNumberInput(kiroAutoSwitchThreshold, 0, 100);
+                            setKiroAutoSwitchThreshold(normalized);
+                            setKiroAutoSwitchThresholdCustomMode(false);
+                          }}
+                          onKeyDown={(e) => {
+                            if (e.key === 'Enter') {
+                              e.preventDefault();
+                              const normalized = normalizeNumberInput(kiroAutoSwitchThreshold, 0, 100);
+                              setKiroAutoSwitchThreshold(normalized);
+                              setKiroAutoSwitchThresholdCustomMode(false);
+                            }
+                          }}
+                        />
+                        <span className="settings-input-unit">%</span>
+                      </div>
+                    ) : (
+                      <select
+                        className="settings-select"
+                        value={kiroAutoSwitchThreshold}
+                        onChange={(e) => {
+                          const val = e.target.value;
+                          if (val === 'custom') {
+                            setKiroAutoSwitchThresholdCustomMode(true);
+                            setKiroAutoSwitchThreshold(kiroAutoSwitchThreshold || '5');
+                            return;
+                          }
+                          setKiroAutoSwitchThresholdCustomMode(false);
+                          setKiroAutoSwitchThreshold(val);
+                        }}
+                      >
+                        {!kiroAutoSwitchThresholdIsPreset && (
+                          <option value={kiroAutoSwitchThreshold}>{kiroAutoSwitchThreshold}%</option>
+                        )}
+                        <option value="0">0%</option>
+                        <option value="20">20%</option>
+                        <option value="40">40%</option>
+                        <option value="60">60%</option>
+                        <option value="custom">{t('settings.general.autoRefreshCustom')}</option>
+                      </select>
+                    )}
+                  </div>
+                </div>
+              )}
+              {kiroAutoSwitchEnabled && (
+                <div className="settings-row" style={{ animation: 'fadeUp 0.3s ease both' }}>
+                  <div className="row-label">
+                    <div className="row-title">{t('quickSettings.autoSwitch.strategy', '选号策略')}</div>
+                    <div className="row-desc">{t('quickSettings.autoSwitch.strategyDesc', '触发自动切号时如何挑选目标账号')}</div>
+                  </div>
+                  <div className="row-control">
+                    <select
+                      className="settings-select"
+                      value={kiroAutoSwitchStrategy}
+                      onChange={(e) => {
+                        setKiroAutoSwitchStrategy(e.target.value);
+                        setKiroAutoSwitchStrategyParam('');
+                      }}
+                    >
+                      <option value="highest_quota">{t('quickSettings.autoSwitch.strategies.highestQuota', '剩余配额最高')}</option>
+                      <option value="round_robin">{t('quickSettings.autoSwitch.strategies.roundRobin', '按顺序轮换')}</option>
+                      <option value="least_recently_used">{t('quickSettings.autoSwitch.strategies.leastRecentlyUsed', '最久未使用')}</option>
+                      <option value="tag">{t('quickSettings.autoSwitch.strategies.tag', '仅限指定标签')}</option>
+                      <option value="priority">{t('quickSettings.autoSwitch.strategies.priority', '按账号排序优先')}</option>
+                    </select>
+                  </div>
+                </div>
+              )}
+              {kiroAutoSwitchEnabled && kiroAutoSwitchStrategy === 'tag' && (
+                <div className="settings-row" style={{ animation: 'fadeUp 0.3s ease both' }}>
+                  <div className="row-label">
+                    <div className="row-title">{t('quickSettings.autoSwitch.strategyTag', '限定标签')}</div>
+                    <div className="row-desc">{t('quickSettings.autoSwitch.strategyTagDesc', '只会切换到带有该标签的账号')}</div>
+                  </div>
+                  <div className="row-control">
+                    <input
+                      type="text"
+                      className="settings-input"
+                      value={kiroAutoSwitchStrategyParam}
+                      placeholder={t('quickSettings.autoSwitch.strategyTagPlaceholder', '输入标签')}
+                      onChange={(e) => setKiroAutoSwitchStrategyParam(e.target.value)}
+                    />
+                  </div>
+                </div>
+              )}
+
               <div className="settings-row">
                 <div className="row-label">
                   <div className="row-title">{t('quickSettings.quotaAlert.enable', '超额预警')}</div>
@@ -1987,6 +3253,83 @@ export function SettingsPage() {
                 </>
               )}
             </div>
+
+            <div className="group-title">{t('settings.network.proxyTitle')}</div>
+            <div className="settings-group">
+              <div className="settings-row">
+                <div className="row-label">
+                  <div className="row-title">{t('settings.network.proxyService')}</div>
+                  <div className="row-desc">{t('settings.network.proxyServiceDesc')}</div>
+                </div>
+                <div className="row-control">
+                  <label className="switch">
+                    <input
+                      type="checkbox"
+                      checked={proxyEnabled}
+                      onChange={(e) => setProxyEnabled(e.target.checked)}
+                    />
+                    <span className="slider"></span>
+                  </label>
+                </div>
+              </div>
+
+              {proxyEnabled && (
+                <>
+                  <div className="settings-row" style={{ animation: 'fadeUp 0.3s ease both' }}>
+                    <div className="row-label">
+                      <div className="row-title">{t('settings.network.proxyPort')}</div>
+                      <div className="row-desc">
+                        {t('settings.network.preferredPortDesc').replace('{port}', String(proxyDefaultPort))}
+                      </div>
+                    </div>
+                    <div className="row-control">
+                      <input
+                        type="number"
+                        className="settings-input"
+                        value={proxyPort}
+                        onChange={(e) => setProxyPort(e.target.value)}
+                        placeholder={String(proxyDefaultPort)}
+                        min="1024"
+                        max="65535"
+                      />
+                    </div>
+                  </div>
+
+                  <div className="settings-row" style={{ animation: 'fadeUp 0.3s ease both' }}>
+                    <div className="row-label">
+                      <div className="row-title">{t('settings.network.proxyApiKey')}</div>
+                      <div className="row-desc">{t('settings.network.proxyApiKeyDesc')}</div>
+                    </div>
+                    <div className="row-control">
+                      <input
+                        type="text"
+                        className="settings-input"
+                        value={proxyApiKey}
+                        onChange={(e) => setProxyApiKey(e.target.value)}
+                      />
+                    </div>
+                  </div>
+
+                  {proxyActualPort && (
+                    <div className="settings-row" style={{ animation: 'fadeUp 0.3s ease both' }}>
+                      <div className="row-label">
+                        <div className="row-title">{t('settings.network.proxyEndpoint')}</div>
+                        <div className="row-desc">{t('settings.network.proxyEndpointDesc')}</div>
+                      </div>
+                      <div className="row-control">
+                        <span style={{
+                          fontFamily: 'var(--font-mono)',
+                          fontSize: '14px',
+                          color: 'var(--accent)'
+                        }}>
+                          http://127.0.0.1:{proxyActualPort}/v1
+                        </span>
+                      </div>
+                    </div>
+                  )}
+                </>
+              )}
+            </div>
             
             {needsRestart && (
               <div style={{ 
@@ -2090,6 +3433,29 @@ export function SettingsPage() {
         )}
         </div>
       </div>
+
+      {importPreview && (
+        <ImportPreviewModal
+          jsonContent={importPreview.jsonContent}
+          plan={importPreview.plan}
+          onClose={() => setImportPreview(null)}
+          onImported={handleInterchangeImported}
+        />
+      )}
+
+      {restoreTarget && (
+        <BackupRestoreModal
+          archivePath={restoreTarget.path}
+          passphrase={backupPassphrase}
+          preview={restoreTarget.preview}
+          onClose={() => setRestoreTarget(null)}
+          onRestored={handleRestored}
+        />
+      )}
+
+      {showAuditLog && <AuditLogModal onClose={() => setShowAuditLog(false)} />}
+      {showTrash && <TrashModal onClose={() => setShowTrash(false)} />}
+      {showQuotaForecast && <QuotaForecastModal onClose={() => setShowQuotaForecast(false)} />}
     </main>
   );
 }
@@ -27,6 +27,7 @@ import {
 import { useTranslation } from 'react-i18next';
 import { useWindsurfAccountStore } from '../stores/useWindsurfAccountStore';
 import * as windsurfService from '../services/windsurfService';
+import { ImportOutcome } from '../services/interchangeService';
 import { TagEditModal } from '../components/TagEditModal';
 import {
   getWindsurfPlanDisplayName,
@@ -409,6 +410,18 @@ export function WindsurfAccountsPage() {
     importFileInputRef.current?.click();
   };
 
+  const showImportFailures = (outcome: ImportOutcome<unknown>) => {
+    setAddStatus('error');
+    setAddMessage(
+      t('common.shared.import.partialFailedMsg', {
+        count: outcome.imported.length,
+        failed: outcome.failed.length,
+        error: outcome.failed.join('; '),
+        defaultValue: '已导入 {{count}} 个账号，{{failed}} 个失败: {{error}}',
+      })
+    );
+  };
+
   const handleImportJsonFile = async (file: File) => {
     setImporting(true);
     setAddStatus('loading');
@@ -416,20 +429,24 @@ export function WindsurfAccountsPage() {
 
     try {
       const content = await file.text();
-      const imported = await windsurfService.importWindsurfFromJson(content);
+      const outcome = await windsurfService.importWindsurfFromJson(content);
       await fetchAccounts();
 
-      setAddStatus('success');
-      setAddMessage(
-        t('common.shared.token.importSuccessMsg', {
-          count: imported.length,
-          defaultValue: '成功导入 {{count}} 个账号',
-        })
-      );
-      setTimeout(() => {
-        setShowAddModal(false);
-        resetAddModalState();
-      }, 1200);
+      if (outcome.failed.length > 0) {
+        showImportFailures(outcome);
+      } else {
+        setAddStatus('success');
+        setAddMessage(
+          t('common.shared.token.importSuccessMsg', {
+            count: outcome.imported.length,
+            defaultValue: '成功导入 {{count}} 个账号',
+          })
+        );
+        setTimeout(() => {
+          setShowAddModal(false);
+          resetAddModalState();
+        }, 1200);
+      }
     } catch (e) {
       setAddStatus('error');
       const errorMsg = String(e).replace(/^Error:\s*/, '');
@@ -489,25 +506,33 @@ export function WindsurfAccountsPage() {
 
     try {
       let importedCount = 0;
+      let failedOutcome: ImportOutcome<unknown> | null = null;
       if (trimmed.startsWith('{') || trimmed.startsWith('[')) {
-        const accounts = await windsurfService.importWindsurfFromJson(trimmed);
-        importedCount = accounts.length;
+        const outcome = await windsurfService.importWindsurfFromJson(trimmed);
+        importedCount = outcome.imported.length;
+        if (outcome.failed.length > 0) {
+          failedOutcome = outcome;
+        }
       } else {
         await windsurfService.addWindsurfAccountWithToken(trimmed);
         importedCount = 1;
       }
       await fetchAccounts();
-      setAddStatus('success');
-      setAddMessage(
-        t('common.shared.token.importSuccessMsg', {
-          count: importedCount,
-          defaultValue: '成功导入 {{count}} 个账号',
-        })
-      );
-      setTimeout(() => {
-        setShowAddModal(false);
-        resetAddModalState();
-      }, 1200);
+      if (failedOutcome) {
+        showImportFailures(failedOutcome);
+      } else {
+        setAddStatus('success');
+        setAddMessage(
+          t('common.shared.token.importSuccessMsg', {
+            count: importedCount,
+            defaultValue: '成功导入 {{count}} 个账号',
+          })
+        );
+        setTimeout(() => {
+          setShowAddModal(false);
+          resetAddModalState();
+        }, 1200);
+      }
     } catch (e) {
       setAddStatus('error');
       const errorMsg = String(e).replace(/^Error:\s*/, '');
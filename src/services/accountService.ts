@@ -1,5 +1,6 @@
 import { invoke } from '@tauri-apps/api/core';
 import { Account, QuotaData, DeviceProfile, DeviceProfiles, RefreshStats, Fingerprint, FingerprintWithStats } from '../types/account';
+import { ImportOutcome } from './interchangeService';
 
 export async function listAccounts(): Promise<Account[]> {
     return await invoke('list_accounts');
@@ -93,6 +94,10 @@ export async function openDataFolder(): Promise<void> {
     return await invoke('open_data_folder');
 }
 
+export async function exportDataStoreJson(targetDir: string): Promise<number> {
+    return await invoke('export_data_store_json', { targetDir });
+}
+
 export async function switchAccount(accountId: string): Promise<Account> {
     return await invoke('switch_account', { accountId });
 }
@@ -121,7 +126,7 @@ export async function importFromLocal(): Promise<Account> {
     return await invoke('import_from_local');
 }
 
-export async function importFromJson(jsonContent: string): Promise<Account[]> {
+export async function importFromJson(jsonContent: string): Promise<ImportOutcome<Account>> {
     return await invoke('import_from_json', { jsonContent });
 }
 
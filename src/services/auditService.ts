@@ -0,0 +1,79 @@
+/**
+ * 审计日志服务
+ * 与后端 audit 模块交互
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+/** 操作来源 */
+export type AuditSource = 'ui' | 'web_socket' | 'scheduler' | 'auto_switch' | 'cli';
+
+/** 操作类型 */
+export type AuditAction =
+  | 'switch'
+  | 'inject'
+  | 'delete'
+  | 'restore'
+  | 'purge'
+  | 'tag'
+  | 'import'
+  | 'auto_switch';
+
+/** 操作结果 */
+export type AuditOutcome = 'success' | 'failure' | 'skipped';
+
+/** 审计日志条目 */
+export interface AuditEntry {
+  id: number;
+  /** 记录时间（秒） */
+  createdAt: number;
+  platform: string;
+  action: AuditAction;
+  accountId: string | null;
+  /** 账号邮箱或登录名 */
+  account: string | null;
+  source: AuditSource;
+  /** 执行者（系统用户@主机名） */
+  actor: string;
+  outcome: AuditOutcome;
+  error: string | null;
+  detail: string | null;
+}
+
+/** 查询条件，均为可选 */
+export interface AuditQuery {
+  platform?: string;
+  action?: AuditAction;
+  source?: AuditSource;
+  outcome?: AuditOutcome;
+  /** 匹配账号 ID 或邮箱 */
+  account?: string;
+  /** 起始时间（秒） */
+  since?: number;
+  /** 结束时间（秒） */
+  until?: number;
+  limit?: number;
+  offset?: number;
+}
+
+/**
+ * 按条件查询审计日志（最新的在前）
+ */
+export async function queryAuditLog(query?: AuditQuery): Promise<AuditEntry[]> {
+  return invoke<AuditEntry[]>('query_audit_log', { query: query ?? null });
+}
+
+/**
+ * 导出符合条件的审计日志，返回导出条数
+ */
+export async function exportAuditLog(
+  targetPath: string,
+  format: 'json' | 'csv',
+  query?: AuditQuery,
+): Promise<number> {
+  return invoke<number>('export_audit_log', {
+    targetPath,
+    format,
+    query: query ?? null,
+  });
+}
@@ -0,0 +1,75 @@
+/**
+ * 数据备份服务
+ * 与后端 backup 模块交互
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+/** 创建备份的结果 */
+export interface BackupSummary {
+  path: string;
+  entries: number;
+  encrypted: boolean;
+}
+
+/** 单个分区的差异 */
+export interface SectionDiff {
+  /** 平台（antigravity / codex / github_copilot / windsurf / kiro）或 settings */
+  section: string;
+  added: string[];
+  changed: string[];
+  removed: string[];
+  unchanged: number;
+}
+
+/** 恢复预览 */
+export interface RestorePreview {
+  app_version: string;
+  data_version: number;
+  created_at: number;
+  encrypted: boolean;
+  sections: SectionDiff[];
+}
+
+/** 恢复结果 */
+export interface RestoreResult {
+  sections: string[];
+  written: number;
+  removed: number;
+  snapshot_path: string;
+}
+
+/**
+ * 创建完整备份归档（口令为空时不加密）
+ */
+export async function createDataBackup(targetPath: string, passphrase?: string): Promise<BackupSummary> {
+  return invoke<BackupSummary>('create_data_backup', {
+    targetPath,
+    passphrase: passphrase || null,
+  });
+}
+
+/**
+ * 校验备份归档并预览与本机数据的差异
+ */
+export async function previewDataBackup(archivePath: string, passphrase?: string): Promise<RestorePreview> {
+  return invoke<RestorePreview>('preview_data_backup', {
+    archivePath,
+    passphrase: passphrase || null,
+  });
+}
+
+/**
+ * 从备份归档恢复选中的分区
+ */
+export async function restoreDataBackup(
+  archivePath: string,
+  sections: string[],
+  passphrase?: string,
+): Promise<RestoreResult> {
+  return invoke<RestoreResult>('restore_data_backup', {
+    archivePath,
+    passphrase: passphrase || null,
+    sections,
+  });
+}
@@ -1,5 +1,6 @@
 import { invoke } from '@tauri-apps/api/core';
 import { CodexAccount, CodexQuota } from '../types/codex';
+import { ImportOutcome } from './interchangeService';
 
 export interface CodexOAuthLoginStartResponse {
   loginId: string;
@@ -37,7 +38,7 @@ export async function importCodexFromLocal(): Promise<CodexAccount> {
 }
 
 /** 从 JSON 字符串导入账号 */
-export async function importCodexFromJson(jsonContent: string): Promise<CodexAccount[]> {
+export async function importCodexFromJson(jsonContent: string): Promise<ImportOutcome<CodexAccount>> {
   return await invoke('import_codex_from_json', { jsonContent });
 }
 
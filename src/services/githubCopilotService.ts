@@ -1,5 +1,6 @@
 import { invoke } from '@tauri-apps/api/core';
 import { GitHubCopilotAccount } from '../types/githubCopilot';
+import { ImportOutcome } from './interchangeService';
 
 export interface GitHubCopilotOAuthLoginStartResponse {
   loginId: string;
@@ -26,7 +27,7 @@ export async function deleteGitHubCopilotAccounts(accountIds: string[]): Promise
 }
 
 /** 从 JSON 字符串导入账号 */
-export async function importGitHubCopilotFromJson(jsonContent: string): Promise<GitHubCopilotAccount[]> {
+export async function importGitHubCopilotFromJson(jsonContent: string): Promise<ImportOutcome<GitHubCopilotAccount>> {
   return await invoke('import_github_copilot_from_json', { jsonContent });
 }
 
@@ -0,0 +1,81 @@
+/**
+ * 账号交换格式服务
+ * 与后端 interchange 模块交互（格式说明见 docs/interchange-format.md）
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+/** 本机已存在账号的处理策略 */
+export type ImportStrategy = 'skip_existing' | 'overwrite' | 'keep_newest' | 'merge_tags';
+
+/** 单条记录的导入计划 */
+export type PlanAction = 'new' | 'update' | 'identical' | 'conflict' | 'invalid';
+
+/** 字段差异（敏感字段的值为 ******） */
+export interface FieldChange {
+  field: string;
+  current: unknown;
+  incoming: unknown;
+}
+
+export interface PlanItem {
+  platform: string;
+  email: string;
+  incomingId: string;
+  existingId: string | null;
+  action: PlanAction;
+  changes: FieldChange[];
+  error?: string;
+}
+
+/** 导入预览 */
+export interface ImportPlan {
+  items: PlanItem[];
+  /** 文件携带的指纹数 */
+  fingerprints: number;
+  skippedPlatforms: string[];
+}
+
+/** 导入结果 */
+export interface InterchangeImportResult {
+  /** 各平台导入的账号数 */
+  imported: Record<string, number>;
+  /** 按策略跳过的账号数 */
+  skipped: number;
+  /** 导入失败的账号（平台:邮箱: 原因） */
+  failed: string[];
+  fingerprintsAdded: number;
+  /** 文件中存在但当前版本不支持的平台 */
+  skippedPlatforms: string[];
+}
+
+/** 各平台 JSON 导入的结果 */
+export interface ImportOutcome<T> {
+  imported: T[];
+  /** 导入失败的账号（邮箱: 原因） */
+  failed: string[];
+}
+
+/**
+ * 导出交换文件内容（platforms 为空时导出全部平台）
+ */
+export async function exportInterchangeAccounts(platforms: string[] = []): Promise<string> {
+  return invoke<string>('export_interchange_accounts', { platforms });
+}
+
+/**
+ * 预览交换文件的导入计划（不写入数据）
+ */
+export async function previewInterchangeImport(jsonContent: string): Promise<ImportPlan> {
+  return invoke<ImportPlan>('preview_interchange_import', { jsonContent });
+}
+
+/**
+ * 按策略导入交换文件中的全部平台账号
+ */
+export async function importInterchangeAccounts(
+  jsonContent: string,
+  strategy: ImportStrategy = 'keep_newest',
+): Promise<InterchangeImportResult> {
+  return invoke<InterchangeImportResult>('import_interchange_accounts', { jsonContent, strategy });
+}
@@ -1,5 +1,6 @@
 import { invoke } from '@tauri-apps/api/core';
 import { KiroAccount } from '../types/kiro';
+import { ImportOutcome } from './interchangeService';
 
 export interface KiroOAuthLoginStartResponse {
   loginId: string;
@@ -27,7 +28,7 @@ export async function deleteKiroAccounts(accountIds: string[]): Promise<void> {
 }
 
 /** 从 JSON 字符串导入账号 */
-export async function importKiroFromJson(jsonContent: string): Promise<KiroAccount[]> {
+export async function importKiroFromJson(jsonContent: string): Promise<ImportOutcome<KiroAccount>> {
   return await invoke('import_kiro_from_json', { jsonContent });
 }
 
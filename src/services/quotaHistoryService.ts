@@ -0,0 +1,90 @@
+/**
+ * 配额历史服务
+ * 与后端 quota_history 模块交互
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+/** 配额历史数据点 */
+export interface QuotaPoint {
+  meter: string;
+  /** 剩余百分比 0-100 */
+  remainingPercent: number;
+  used: number | null;
+  limit: number | null;
+  /** 重置时间（秒） */
+  resetAt: number | null;
+  /** 记录时间（秒） */
+  recordedAt: number;
+}
+
+/** 配额项消耗预测 */
+export interface QuotaForecast {
+  platform: string;
+  accountId: string;
+  /** 账号邮箱或登录名 */
+  account: string | null;
+  meter: string;
+  remainingPercent: number;
+  /** 每小时消耗的百分比 */
+  consumptionPerHour: number;
+  /** 预计耗尽时间（秒），未在消耗时为 null */
+  exhaustsAt: number | null;
+  resetAt: number | null;
+  /** 是否会在重置前耗尽 */
+  exhaustsBeforeReset: boolean;
+  /** 最新样本时间（秒） */
+  updatedAt: number;
+}
+
+/**
+ * 查询账号的配额历史（按时间先后）
+ */
+export async function getQuotaHistory(
+  platform: string,
+  accountId: string,
+  meter?: string,
+  since?: number,
+): Promise<QuotaPoint[]> {
+  return invoke<QuotaPoint[]>('get_quota_history', {
+    platform,
+    accountId,
+    meter: meter ?? null,
+    since: since ?? null,
+  });
+}
+
+/**
+ * 计算各账号配额项的预计耗尽时间（不传平台时计算全部平台）
+ */
+export async function getQuotaForecasts(platform?: string): Promise<QuotaForecast[]> {
+  return invoke<QuotaForecast[]>('get_quota_forecasts', { platform: platform ?? null });
+}
+
+/** 即将到来的一次配额重置 */
+export interface UpcomingReset {
+  platform: string;
+  accountId: string;
+  /** 账号邮箱或登录名 */
+  account: string;
+  /** 重置时间（秒） */
+  resetAt: number;
+  meters: {
+    name: string;
+    remainingPercent: number;
+  }[];
+}
+
+/**
+ * 列出各账号即将到来的配额重置（不传平台时列出全部平台）
+ */
+export async function getUpcomingQuotaResets(platform?: string): Promise<UpcomingReset[]> {
+  return invoke<UpcomingReset[]>('get_upcoming_quota_resets', { platform: platform ?? null });
+}
+
+/**
+ * 导出全部账号即将到来的配额重置为 iCalendar (.ics) 文件，返回事件数
+ */
+export async function exportQuotaResetCalendar(targetPath: string): Promise<number> {
+  return invoke<number>('export_quota_reset_calendar', { targetPath });
+}
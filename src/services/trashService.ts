@@ -0,0 +1,39 @@
+/**
+ * 账号回收站服务
+ * 与后端 trash 模块交互
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+/** 回收站条目 */
+export interface TrashEntry {
+  platform: string;
+  id: string;
+  /** 删除时记录的邮箱或登录名 */
+  label: string | null;
+  /** 删除时间（秒） */
+  deletedAt: number;
+  /** 到期清理时间（秒），永久保留时为 null */
+  expiresAt: number | null;
+}
+
+/**
+ * 列出回收站中的账号（不传平台时列出全部平台）
+ */
+export async function listAccountTrash(platform?: string): Promise<TrashEntry[]> {
+  return invoke<TrashEntry[]>('list_account_trash', { platform: platform ?? null });
+}
+
+/**
+ * 从回收站恢复账号，返回恢复的条数
+ */
+export async function restoreTrashedAccounts(platform: string, accountIds: string[]): Promise<number> {
+  return invoke<number>('restore_trashed_accounts', { platform, accountIds });
+}
+
+/**
+ * 彻底删除回收站中的账号，返回删除的条数
+ */
+export async function purgeAccountTrash(platform: string, accountIds: string[]): Promise<number> {
+  return invoke<number>('purge_account_trash', { platform, accountIds });
+}
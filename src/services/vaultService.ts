@@ -0,0 +1,62 @@
+/**
+ * 凭据加密服务
+ * 与后端 vault 模块交互
+ */
+
+import { invoke } from '@tauri-apps/api/core';
+
+/** 密钥来源 */
+export type VaultKeyProvider = 'passphrase' | 'secret_service' | 'key_file';
+
+/** 凭据库状态 */
+export interface VaultStatus {
+  enabled: boolean;
+  locked: boolean;
+  provider: VaultKeyProvider | null;
+  key_file_path: string | null;
+  /** 启用前留下的明文账号数据副本 */
+  plaintext_copies: string[];
+}
+
+/**
+ * 获取凭据库状态
+ */
+export async function getVaultStatus(): Promise<VaultStatus> {
+  return invoke<VaultStatus>('get_vault_status');
+}
+
+/**
+ * 启用凭据库并加密已有账号文件
+ */
+export async function enableVault(
+  provider: VaultKeyProvider,
+  passphrase?: string,
+  keyFilePath?: string,
+): Promise<VaultStatus> {
+  return invoke<VaultStatus>('enable_vault', {
+    provider,
+    passphrase: passphrase || null,
+    keyFilePath: keyFilePath || null,
+  });
+}
+
+/**
+ * 解锁凭据库（口令模式需传入口令）
+ */
+export async function unlockVault(passphrase?: string): Promise<VaultStatus> {
+  return invoke<VaultStatus>('unlock_vault', { passphrase: passphrase || null });
+}
+
+/**
+ * 锁定凭据库
+ */
+export async function lockVault(): Promise<VaultStatus> {
+  return invoke<VaultStatus>('lock_vault');
+}
+
+/**
+ * 停用凭据库并解密全部账号文件
+ */
+export async function disableVault(): Promise<VaultStatus> {
+  return invoke<VaultStatus>('disable_vault');
+}
@@ -1,5 +1,6 @@
 import { invoke } from '@tauri-apps/api/core';
 import { WindsurfAccount } from '../types/windsurf';
+import { ImportOutcome } from './interchangeService';
 
 export interface WindsurfOAuthLoginStartResponse {
   loginId: string;
@@ -27,7 +28,7 @@ export async function deleteWindsurfAccounts(accountIds: string[]): Promise<void
 }
 
 /** 从 JSON 字符串导入账号 */
-export async function importWindsurfFromJson(jsonContent: string): Promise<WindsurfAccount[]> {
+export async function importWindsurfFromJson(jsonContent: string): Promise<ImportOutcome<WindsurfAccount>> {
   return await invoke('import_windsurf_from_json', { jsonContent });
 }
 
@@ -1,4 +1,5 @@
 import { create } from 'zustand';
+import { ImportOutcome } from '../services/interchangeService';
 
 type ProviderUsage = {
   inlineSuggestionsUsedPercent: number | null;
@@ -23,7 +24,7 @@ type ProviderService<TAccount> = {
   injectAccount: (accountId: string) => Promise<unknown>;
   refreshToken: (accountId: string) => Promise<unknown>;
   refreshAllTokens: () => Promise<unknown>;
-  importFromJson: (jsonContent: string) => Promise<TAccount[]>;
+  importFromJson: (jsonContent: string) => Promise<ImportOutcome<TAccount>>;
   exportAccounts: (accountIds: string[]) => Promise<string>;
   updateAccountTags: (accountId: string, tags: string[]) => Promise<TAccount>;
 };
@@ -43,7 +44,7 @@ export interface ProviderAccountStoreState<TAccount> {
   deleteAccounts: (accountIds: string[]) => Promise<void>;
   refreshToken: (accountId: string) => Promise<void>;
   refreshAllTokens: () => Promise<void>;
-  importFromJson: (jsonContent: string) => Promise<TAccount[]>;
+  importFromJson: (jsonContent: string) => Promise<ImportOutcome<TAccount>>;
   exportAccounts: (accountIds: string[]) => Promise<string>;
   updateAccountTags: (accountId: string, tags: string[]) => Promise<TAccount>;
 }
@@ -148,9 +149,9 @@ export function createProviderAccountStore<TAccount extends ProviderAccountAugme
     },
 
     importFromJson: async (jsonContent: string) => {
-      const accounts = await service.importFromJson(jsonContent);
+      const outcome = await service.importFromJson(jsonContent);
       await get().fetchAccounts();
-      return accounts;
+      return outcome;
     },
 
     exportAccounts: async (accountIds: string[]) => {
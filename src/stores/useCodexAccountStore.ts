@@ -1,6 +1,7 @@
 import { create } from 'zustand';
 import { CodexAccount, CodexQuota } from '../types/codex';
 import * as codexService from '../services/codexService';
+import { ImportOutcome } from '../services/interchangeService';
 
 const CODEX_ACCOUNTS_CACHE_KEY = 'agtools.codex.accounts.cache';
 const CODEX_CURRENT_ACCOUNT_CACHE_KEY = 'agtools.codex.accounts.current';
@@ -61,7 +62,7 @@ interface CodexAccountState {
   refreshQuota: (accountId: string) => Promise<CodexQuota>;
   refreshAllQuotas: () => Promise<number>;
   importFromLocal: () => Promise<CodexAccount>;
-  importFromJson: (jsonContent: string) => Promise<CodexAccount[]>;
+  importFromJson: (jsonContent: string) => Promise<ImportOutcome<CodexAccount>>;
   updateAccountTags: (accountId: string, tags: string[]) => Promise<CodexAccount>;
 }
 
@@ -130,9 +131,9 @@ export const useCodexAccountStore = create<CodexAccountState>((set, get) => ({
   },
   
   importFromJson: async (jsonContent: string) => {
-    const accounts = await codexService.importCodexFromJson(jsonContent);
+    const outcome = await codexService.importCodexFromJson(jsonContent);
     await get().fetchAccounts();
-    return accounts;
+    return outcome;
   },
 
   updateAccountTags: async (accountId: string, tags: string[]) => {